@@ -0,0 +1,52 @@
+//! Authority checks shared by the programs' privileged instructions
+//!
+//! A program authority is either a raw key or a Squads v4 multisig vault PDA.
+//! Both sign the same way, so `has_one = authority` still does the key check;
+//! this crate proves the multisig path and lets a program insist on it for
+//! fee-withdrawal and treasury-change instructions.
+//! Functions return `bool` and programs map `false` onto their own errors with `require!`.
+
+use anchor_lang::prelude::*;
+
+// ===================
+// Constants
+// ===================
+
+/// Squads v4 multisig program
+pub const SQUADS_PROGRAM_ID: Pubkey = pubkey!("SQDS4ep65T869zMMBKyuUq6aD6EgTu8psMjkvj52pCf");
+
+/// Vault index the programs expect to be their authority (Squads' default vault)
+pub const DEFAULT_VAULT_INDEX: u8 = 0;
+
+// ===================
+// Squads Vaults
+// ===================
+
+/// Vault PDA `vault_index` of a Squads v4 `multisig` account
+pub fn squads_vault_address(multisig: &Pubkey, vault_index: u8) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"multisig", multisig.as_ref(), b"vault", &[vault_index]],
+        &SQUADS_PROGRAM_ID,
+    )
+    .0
+}
+
+/// True if `authority` is the default vault of `multisig` and `multisig` is a live Squads account
+pub fn is_squads_vault(authority: &Pubkey, multisig: &AccountInfo) -> bool {
+    *multisig.owner == SQUADS_PROGRAM_ID
+        && squads_vault_address(multisig.key, DEFAULT_VAULT_INDEX) == *authority
+}
+
+// ===================
+// Guards
+// ===================
+
+/// Check the path an already key-checked authority used for a privileged instruction
+/// `multisig` is the Squads account when the authority claims to be its vault;
+/// with `require_multisig` set, only a verified vault passes
+pub fn admin_path_ok(authority: &Pubkey, multisig: Option<&AccountInfo>, require_multisig: bool) -> bool {
+    match multisig {
+        Some(multisig) => is_squads_vault(authority, multisig),
+        None => !require_multisig,
+    }
+}
@@ -0,0 +1,44 @@
+use admin_guard::*;
+use anchor_lang::prelude::*;
+
+fn with_account<R>(key: Pubkey, owner: Pubkey, f: impl FnOnce(&AccountInfo) -> R) -> R {
+    let mut lamports = 1_000_000;
+    let mut data = vec![0u8; 8];
+    let info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+    f(&info)
+}
+
+#[test]
+fn raw_key_passes_only_without_multisig_requirement() {
+    let authority = Pubkey::new_unique();
+    assert!(admin_path_ok(&authority, None, false));
+    assert!(!admin_path_ok(&authority, None, true));
+}
+
+#[test]
+fn squads_vault_passes_multisig_requirement() {
+    let multisig = Pubkey::new_unique();
+    let vault = squads_vault_address(&multisig, DEFAULT_VAULT_INDEX);
+    with_account(multisig, SQUADS_PROGRAM_ID, |info| {
+        assert!(is_squads_vault(&vault, info));
+        assert!(admin_path_ok(&vault, Some(info), true));
+    });
+}
+
+#[test]
+fn rejects_vault_of_another_multisig() {
+    let multisig = Pubkey::new_unique();
+    let other_vault = squads_vault_address(&Pubkey::new_unique(), DEFAULT_VAULT_INDEX);
+    with_account(multisig, SQUADS_PROGRAM_ID, |info| {
+        assert!(!admin_path_ok(&other_vault, Some(info), false));
+    });
+}
+
+#[test]
+fn rejects_multisig_not_owned_by_squads() {
+    let multisig = Pubkey::new_unique();
+    let vault = squads_vault_address(&multisig, DEFAULT_VAULT_INDEX);
+    with_account(multisig, Pubkey::new_unique(), |info| {
+        assert!(!admin_path_ok(&vault, Some(info), true));
+    });
+}
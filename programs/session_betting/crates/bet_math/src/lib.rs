@@ -0,0 +1,100 @@
+//! Checked fee and payout math shared by the betting programs
+//!
+//! Every function returns `None` instead of wrapping, truncating or panicking.
+//! Programs map `None` onto their own `MathOverflow` error with `ok_or`.
+
+// ===================
+// Constants
+// ===================
+
+/// Basis points denominator (100% = 10,000 bps)
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+// ===================
+// Fees
+// ===================
+
+/// `amount * bps / BPS_DENOMINATOR`, rounded down
+/// Using u128 for intermediate calculation so large amounts never overflow
+#[inline]
+pub fn bps_of(amount: u64, bps: u64) -> Option<u64> {
+    mul_div(amount, bps, BPS_DENOMINATOR)
+}
+
+/// Split `amount` into `(net, fee)` for a fee of `fee_bps`
+/// Rounding favours the payer: the fee is rounded down, so net + fee == amount
+#[inline]
+pub fn split_fee(amount: u64, fee_bps: u64) -> Option<(u64, u64)> {
+    if fee_bps > BPS_DENOMINATOR {
+        return None;
+    }
+    let fee = bps_of(amount, fee_bps)?;
+    Some((amount.checked_sub(fee)?, fee))
+}
+
+// ===================
+// Proportional Shares
+// ===================
+
+/// `a * b / c`, rounded down
+/// Returns None on a zero denominator or when the result does not fit in u64
+#[inline]
+pub fn mul_div(a: u64, b: u64, c: u64) -> Option<u64> {
+    let result = (a as u128).checked_mul(b as u128)?.checked_div(c as u128)?;
+    u64::try_from(result).ok()
+}
+
+/// Parimutuel gross payout: stake back plus a pro-rata share of the losing pool
+/// An empty winning pool returns the stake (nothing to share against)
+#[inline]
+pub fn parimutuel_winnings(stake: u64, winning_pool: u64, losing_pool: u64) -> Option<u64> {
+    if winning_pool == 0 {
+        return Some(stake);
+    }
+    let share = mul_div(stake, losing_pool, winning_pool)?;
+    stake.checked_add(share)
+}
+
+/// Apply a bps multiplier `times` times, rounding down after each step
+/// e.g. parlay payout = stake * (multiplier_bps / BPS_DENOMINATOR) ^ legs
+#[inline]
+pub fn compound_bps(amount: u64, multiplier_bps: u64, times: usize) -> Option<u64> {
+    let mut result = amount as u128;
+    for _ in 0..times {
+        result = result
+            .checked_mul(multiplier_bps as u128)?
+            .checked_div(BPS_DENOMINATOR as u128)?;
+    }
+    u64::try_from(result).ok()
+}
+
+// ===================
+// Price Changes
+// ===================
+
+/// Signed change from `start` to `end` in basis points of `start`, rounded toward zero
+/// Returns None when `start` is zero
+#[inline]
+pub fn change_bps(start: u64, end: u64) -> Option<i64> {
+    let delta = (end as i128).checked_sub(start as i128)?;
+    let bps = delta
+        .checked_mul(BPS_DENOMINATOR as i128)?
+        .checked_div(start as i128)?;
+    i64::try_from(bps).ok()
+}
+
+/// Absolute change from `start` to `end` in basis points of `start`, rounded down
+/// Returns None when `start` is zero; the u128 result cannot overflow
+#[inline]
+pub fn abs_change_bps(start: u64, end: u64) -> Option<u128> {
+    (start.abs_diff(end) as u128)
+        .checked_mul(BPS_DENOMINATOR as u128)?
+        .checked_div(start as u128)
+}
+
+/// Whether `round_id` (counted from 0) is the n-th, 2n-th, ... round of a market
+/// Round 0 is the first round, so it only qualifies when `every_n` is 1; `every_n` 0 never does
+#[inline]
+pub fn is_every_nth_round(round_id: u64, every_n: u64) -> bool {
+    every_n > 0 && round_id % every_n == every_n - 1
+}
@@ -0,0 +1,119 @@
+use bet_math::*;
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn bps_of_never_exceeds_amount(amount in any::<u64>(), bps in 0..=BPS_DENOMINATOR) {
+        let fee = bps_of(amount, bps).unwrap();
+        prop_assert!(fee <= amount);
+    }
+
+    #[test]
+    fn bps_of_matches_wide_arithmetic(amount in any::<u64>(), bps in any::<u64>()) {
+        let expected = amount as u128 * bps as u128 / BPS_DENOMINATOR as u128;
+        prop_assert_eq!(bps_of(amount, bps), u64::try_from(expected).ok());
+    }
+
+    #[test]
+    fn split_fee_conserves_amount(amount in any::<u64>(), fee_bps in 0..=BPS_DENOMINATOR) {
+        let (net, fee) = split_fee(amount, fee_bps).unwrap();
+        prop_assert_eq!(net as u128 + fee as u128, amount as u128);
+    }
+
+    #[test]
+    fn split_fee_rejects_fee_above_100_percent(
+        amount in any::<u64>(),
+        fee_bps in (BPS_DENOMINATOR + 1)..=u64::MAX,
+    ) {
+        prop_assert_eq!(split_fee(amount, fee_bps), None);
+    }
+
+    #[test]
+    fn mul_div_rounds_down(a in any::<u64>(), b in any::<u64>(), c in 1..=u64::MAX) {
+        let exact = a as u128 * b as u128;
+        match mul_div(a, b, c) {
+            Some(q) => {
+                prop_assert!(q as u128 * c as u128 <= exact);
+                prop_assert!((q as u128 + 1) * c as u128 > exact);
+            }
+            None => prop_assert!(exact / c as u128 > u64::MAX as u128),
+        }
+    }
+
+    #[test]
+    fn mul_div_rejects_zero_denominator(a in any::<u64>(), b in any::<u64>()) {
+        prop_assert_eq!(mul_div(a, b, 0), None);
+    }
+
+    #[test]
+    fn parimutuel_winners_never_overdraw_the_pools(
+        stakes in prop::collection::vec(1..=u32::MAX as u64, 1..20),
+        losing_pool in 0..=u32::MAX as u64 * 20,
+    ) {
+        let winning_pool: u64 = stakes.iter().sum();
+        let mut paid: u128 = 0;
+        for stake in &stakes {
+            let winnings = parimutuel_winnings(*stake, winning_pool, losing_pool).unwrap();
+            prop_assert!(winnings >= *stake);
+            paid += winnings as u128;
+        }
+        prop_assert!(paid <= winning_pool as u128 + losing_pool as u128);
+    }
+
+    #[test]
+    fn parimutuel_refunds_stake_on_empty_winning_pool(stake in any::<u64>(), losing_pool in any::<u64>()) {
+        prop_assert_eq!(parimutuel_winnings(stake, 0, losing_pool), Some(stake));
+    }
+
+    #[test]
+    fn parimutuel_reports_overflow(stake in (u64::MAX / 2)..=u64::MAX) {
+        prop_assert_eq!(parimutuel_winnings(stake, stake, u64::MAX), None);
+    }
+
+    #[test]
+    fn compound_bps_is_monotonic_in_steps(amount in any::<u32>(), times in 0usize..8) {
+        let fewer = compound_bps(amount as u64, 19_000, times).unwrap();
+        let more = compound_bps(amount as u64, 19_000, times + 1).unwrap();
+        prop_assert!(more >= fewer);
+    }
+
+    #[test]
+    fn compound_bps_identity(amount in any::<u64>(), times in 0usize..16) {
+        prop_assert_eq!(compound_bps(amount, BPS_DENOMINATOR, times), Some(amount));
+    }
+
+    #[test]
+    fn change_bps_matches_wide_arithmetic(start in 1..=u64::MAX, end in any::<u64>()) {
+        let expected = (end as i128 - start as i128) * 10_000 / start as i128;
+        prop_assert_eq!(change_bps(start, end), i64::try_from(expected).ok());
+    }
+
+    #[test]
+    fn abs_change_bps_matches_signed(start in 1..=u64::MAX, end in any::<u64>()) {
+        if let Some(signed) = change_bps(start, end) {
+            prop_assert_eq!(abs_change_bps(start, end).unwrap(), signed.unsigned_abs() as u128);
+        }
+    }
+
+    #[test]
+    fn every_nth_round_hits_once_per_window(start in 0..u64::MAX / 2, every_n in 1..1_000u64) {
+        let hits = (start..start + every_n).filter(|&id| is_every_nth_round(id, every_n)).count();
+        prop_assert_eq!(hits, 1);
+        prop_assert!(is_every_nth_round(every_n - 1, every_n));
+    }
+
+    #[test]
+    fn change_bps_rejects_zero_start(end in any::<u64>()) {
+        prop_assert_eq!(change_bps(0, end), None);
+        prop_assert_eq!(abs_change_bps(0, end), None);
+    }
+}
+
+#[test]
+fn every_nth_round_skips_round_zero() {
+    assert!(!is_every_nth_round(0, 100));
+    assert!(!is_every_nth_round(0, 0));
+    assert!(!is_every_nth_round(100, 100));
+    assert!(is_every_nth_round(99, 100));
+    assert!(is_every_nth_round(0, 1));
+}
@@ -0,0 +1,88 @@
+//! Reader for Chainlink OCR2 data feed (store transmissions) accounts
+//!
+//! A feed account is a packed header followed by a ring buffer of transmissions.
+//! The latest answer sits just behind `live_cursor`. Fields are read at fixed
+//! offsets instead of pulling in the Chainlink SDK.
+//! Functions return `Option` and programs map `None` onto their own errors.
+
+use anchor_lang::prelude::*;
+
+// ===================
+// Constants
+// ===================
+
+/// Chainlink OCR2 store program, owner of every feed account
+pub const CHAINLINK_STORE_PROGRAM_ID: Pubkey = pubkey!("HEvSKofvBgfaexv23kMabbYqxasxU3mQ4ibBMEmJWHny");
+
+/// Byte offsets into the account data (8-byte discriminator included)
+pub const DECIMALS_OFFSET: usize = 8 + 130;
+pub const LIVE_LENGTH_OFFSET: usize = 8 + 140;
+pub const LIVE_CURSOR_OFFSET: usize = 8 + 144;
+/// Transmissions start after the fixed 192-byte header
+pub const TRANSMISSIONS_OFFSET: usize = 8 + 192;
+
+/// Size of one transmission: slot u64, timestamp u32, pad u32, answer i128, two pad u64
+pub const TRANSMISSION_SIZE: usize = 48;
+/// Offsets within a transmission
+pub const SLOT_OFFSET: usize = 0;
+pub const TIMESTAMP_OFFSET: usize = 8;
+pub const ANSWER_OFFSET: usize = 16;
+
+// ===================
+// Reading
+// ===================
+
+/// Latest round of a feed: answer, its decimals, and the slot and unix time it was reported
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct LatestRound {
+    pub answer: i128,
+    pub decimals: u8,
+    pub slot: u64,
+    pub timestamp: i64,
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(data.get(offset..offset + 4)?.try_into().ok()?))
+}
+
+/// Latest round from raw feed data; None if the data is short or no round was reported
+pub fn latest_round(data: &[u8]) -> Option<LatestRound> {
+    let decimals = *data.get(DECIMALS_OFFSET)?;
+    let live_length = read_u32(data, LIVE_LENGTH_OFFSET)? as usize;
+    let live_cursor = read_u32(data, LIVE_CURSOR_OFFSET)? as usize;
+    if live_length == 0 {
+        return None;
+    }
+
+    // live_cursor is the next slot to write; the latest round is the one before it
+    let index = (live_cursor + live_length - 1) % live_length;
+    let start = TRANSMISSIONS_OFFSET + index * TRANSMISSION_SIZE;
+    let transmission = data.get(start..start + TRANSMISSION_SIZE)?;
+
+    let timestamp = read_u32(transmission, TIMESTAMP_OFFSET)?;
+    if timestamp == 0 {
+        return None;
+    }
+    let slot = u64::from_le_bytes(transmission.get(SLOT_OFFSET..SLOT_OFFSET + 8)?.try_into().ok()?);
+    let answer = i128::from_le_bytes(transmission.get(ANSWER_OFFSET..ANSWER_OFFSET + 16)?.try_into().ok()?);
+    Some(LatestRound { answer, decimals, slot, timestamp: timestamp as i64 })
+}
+
+/// Latest round of a store-owned feed account
+pub fn read_feed(account: &AccountInfo) -> Option<LatestRound> {
+    if *account.owner != CHAINLINK_STORE_PROGRAM_ID {
+        return None;
+    }
+    latest_round(&account.try_borrow_data().ok()?)
+}
+
+/// Positive answer reported no more than `max_age` seconds before `now`
+pub fn answer_no_older_than(account: &AccountInfo, now: i64, max_age: u64) -> Option<LatestRound> {
+    let round = read_feed(account)?;
+
+    let fresh = now.checked_sub(round.timestamp)? <= i64::try_from(max_age).ok()?;
+    if !fresh || round.answer <= 0 {
+        return None;
+    }
+    Some(round)
+}
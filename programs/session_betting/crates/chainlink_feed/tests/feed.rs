@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use chainlink_feed::*;
+
+const NOW: i64 = 1_700_000_000;
+const LIVE_LENGTH: usize = 4;
+
+/// Feed with `answers` written oldest-first into the ring buffer
+fn feed(answers: &[(i128, i64)]) -> Vec<u8> {
+    let mut data = vec![0u8; TRANSMISSIONS_OFFSET + LIVE_LENGTH * TRANSMISSION_SIZE];
+    data[DECIMALS_OFFSET] = 8;
+    data[LIVE_LENGTH_OFFSET..LIVE_LENGTH_OFFSET + 4].copy_from_slice(&(LIVE_LENGTH as u32).to_le_bytes());
+    for (i, (answer, timestamp)) in answers.iter().enumerate() {
+        let start = TRANSMISSIONS_OFFSET + (i % LIVE_LENGTH) * TRANSMISSION_SIZE;
+        data[start + SLOT_OFFSET..start + SLOT_OFFSET + 8].copy_from_slice(&(i as u64 + 1).to_le_bytes());
+        data[start + TIMESTAMP_OFFSET..start + TIMESTAMP_OFFSET + 4].copy_from_slice(&(*timestamp as u32).to_le_bytes());
+        data[start + ANSWER_OFFSET..start + ANSWER_OFFSET + 16].copy_from_slice(&answer.to_le_bytes());
+    }
+    let cursor = (answers.len() % LIVE_LENGTH) as u32;
+    data[LIVE_CURSOR_OFFSET..LIVE_CURSOR_OFFSET + 4].copy_from_slice(&cursor.to_le_bytes());
+    data
+}
+
+fn with_account<R>(owner: Pubkey, mut data: Vec<u8>, f: impl FnOnce(&AccountInfo) -> R) -> R {
+    let key = Pubkey::new_unique();
+    let mut lamports = 1_000_000;
+    let info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+    f(&info)
+}
+
+#[test]
+fn reads_latest_answer_across_ring_wraparound() {
+    let answers: Vec<(i128, i64)> = (1..=6).map(|i| (i * 100, NOW - 60 + i as i64)).collect();
+    let round = latest_round(&feed(&answers)).unwrap();
+    assert_eq!(round.answer, 600);
+    assert_eq!(round.decimals, 8);
+    assert_eq!(round.slot, 6);
+    assert_eq!(round.timestamp, NOW - 54);
+}
+
+#[test]
+fn rejects_stale_or_non_positive_answers() {
+    with_account(CHAINLINK_STORE_PROGRAM_ID, feed(&[(100, NOW - 61)]), |info| {
+        assert!(answer_no_older_than(info, NOW, 60).is_none());
+    });
+    with_account(CHAINLINK_STORE_PROGRAM_ID, feed(&[(0, NOW)]), |info| {
+        assert!(answer_no_older_than(info, NOW, 60).is_none());
+    });
+    with_account(CHAINLINK_STORE_PROGRAM_ID, feed(&[(100, NOW - 5)]), |info| {
+        assert_eq!(answer_no_older_than(info, NOW, 60).map(|r| r.answer), Some(100));
+    });
+}
+
+#[test]
+fn rejects_accounts_not_owned_by_store() {
+    with_account(Pubkey::new_unique(), feed(&[(100, NOW)]), |info| {
+        assert!(read_feed(info).is_none());
+    });
+}
+
+#[test]
+fn rejects_empty_feeds() {
+    assert!(latest_round(&feed(&[])).is_none());
+    assert!(latest_round(&[0u8; 16]).is_none());
+}
@@ -0,0 +1,214 @@
+//! One reader for the Pyth, Switchboard and Chainlink feeds used by the programs
+//!
+//! `OracleReader` picks the source (Pyth push, Pyth pull, Switchboard On-Demand or
+//! Chainlink) and applies the same owner, feed-id, staleness and sign checks to each,
+//! so the programs stop repeating them. Readings keep the source's own exponent;
+//! `OraclePrice::price_in` rescales to the fixed decimals a program stores.
+//! Functions return `OracleError` and programs map it onto their own errors.
+
+use anchor_lang::prelude::*;
+use pyth_sdk_solana::load_price_feed_from_account_info;
+use pyth_sdk_solana::state::{load_price_account, SolanaPriceAccount};
+
+// ===================
+// Types
+// ===================
+
+/// Where a feed account comes from
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OracleReader {
+    /// Legacy Pyth price account, keyed by its 32-byte feed ID
+    PythPush,
+    /// Pyth receiver `PriceUpdateV2` account, keyed by its 32-byte feed ID
+    PythPull,
+    /// Switchboard On-Demand pull feed, keyed by the feed account address
+    Switchboard,
+    /// Chainlink OCR2 store feed, keyed by the feed account address
+    Chainlink,
+}
+
+/// Why a feed account could not be read
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OracleError {
+    /// Wrong owner, discriminator or layout, or no price published yet
+    InvalidAccount,
+    /// A valid account for some other feed
+    FeedMismatch,
+    /// Published more than the allowed age ago
+    Stale,
+    /// Zero or negative price
+    NonPositive,
+    /// The source publishes no EMA price
+    EmaUnsupported,
+}
+
+/// A reading as published: the price is `price * 10^expo`, and so is `conf`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct OraclePrice {
+    pub price: i128,
+    /// Confidence interval (0 for sources that publish none)
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+    /// Slot the price was published or posted in
+    pub publish_slot: u64,
+}
+
+// ===================
+// Scaling
+// ===================
+
+/// Rescale `value * 10^expo` to a fixed-point number with `decimals`, rounding down
+/// None on overflow
+pub fn rescale(value: i128, expo: i32, decimals: u32) -> Option<i128> {
+    // value * 10^(expo + decimals)
+    let shift = i64::from(expo).checked_add(i64::from(decimals))?;
+    let factor = 10i128.checked_pow(u32::try_from(shift.unsigned_abs()).ok()?)?;
+    if shift >= 0 {
+        value.checked_mul(factor)
+    } else {
+        value.checked_div(factor)
+    }
+}
+
+impl OraclePrice {
+    /// Whether the price was published no more than `max_age` seconds before `now`
+    pub fn is_fresh(&self, now: i64, max_age: u64) -> bool {
+        match (now.checked_sub(self.publish_time), i64::try_from(max_age)) {
+            (Some(age), Ok(max_age)) => age <= max_age,
+            _ => false,
+        }
+    }
+
+    /// Positive price as a fixed-point number with `decimals`; None if it is not positive
+    /// after rescaling or does not fit
+    pub fn price_in(&self, decimals: u32) -> Option<u64> {
+        let price = u64::try_from(rescale(self.price, self.expo, decimals)?).ok()?;
+        (price > 0).then_some(price)
+    }
+
+    /// Confidence interval as a fixed-point number with `decimals`
+    pub fn conf_in(&self, decimals: u32) -> Option<u64> {
+        u64::try_from(rescale(i128::from(self.conf), self.expo, decimals)?).ok()
+    }
+}
+
+// ===================
+// Reading
+// ===================
+
+impl OracleReader {
+    /// Latest reading from `account` for `feed_id`, with no staleness or sign check
+    /// Switchboard and Chainlink feeds are keyed by their account address;
+    /// `use_ema` reads the Pyth EMA price and confidence instead of the aggregate
+    pub fn read(self, account: &AccountInfo, feed_id: &[u8; 32], use_ema: bool) -> std::result::Result<OraclePrice, OracleError> {
+        match self {
+            OracleReader::PythPush => read_pyth_push(account, feed_id, use_ema),
+            OracleReader::PythPull => read_pyth_pull(account, feed_id, use_ema),
+            OracleReader::Switchboard => {
+                require_feed_account(account, feed_id, use_ema)?;
+                if *account.owner != switchboard_pull::SWITCHBOARD_ON_DEMAND_PROGRAM_ID {
+                    return Err(OracleError::InvalidAccount);
+                }
+                let data = account.try_borrow_data().map_err(|_| OracleError::InvalidAccount)?;
+                let (value, updated_at) = switchboard_pull::read_result(&data).ok_or(OracleError::InvalidAccount)?;
+                let slot_bytes = data
+                    .get(switchboard_pull::RESULT_SLOT_OFFSET..switchboard_pull::RESULT_SLOT_OFFSET + 8)
+                    .ok_or(OracleError::InvalidAccount)?;
+                Ok(OraclePrice {
+                    price: value,
+                    conf: 0,
+                    expo: -(switchboard_pull::SWITCHBOARD_DECIMALS as i32),
+                    publish_time: updated_at,
+                    publish_slot: u64::from_le_bytes(slot_bytes.try_into().map_err(|_| OracleError::InvalidAccount)?),
+                })
+            }
+            OracleReader::Chainlink => {
+                require_feed_account(account, feed_id, use_ema)?;
+                let round = chainlink_feed::read_feed(account).ok_or(OracleError::InvalidAccount)?;
+                Ok(OraclePrice {
+                    price: round.answer,
+                    conf: 0,
+                    expo: -i32::from(round.decimals),
+                    publish_time: round.timestamp,
+                    publish_slot: round.slot,
+                })
+            }
+        }
+    }
+
+    /// Positive reading published no more than `max_age` seconds before `now`
+    pub fn read_no_older_than(
+        self,
+        account: &AccountInfo,
+        feed_id: &[u8; 32],
+        now: i64,
+        max_age: u64,
+        use_ema: bool,
+    ) -> std::result::Result<OraclePrice, OracleError> {
+        let reading = self.read(account, feed_id, use_ema)?;
+        if !reading.is_fresh(now, max_age) {
+            return Err(OracleError::Stale);
+        }
+        if reading.price <= 0 {
+            return Err(OracleError::NonPositive);
+        }
+        Ok(reading)
+    }
+}
+
+/// Feeds keyed by account address must be the account passed, and publish no EMA
+fn require_feed_account(account: &AccountInfo, feed_id: &[u8; 32], use_ema: bool) -> std::result::Result<(), OracleError> {
+    if use_ema {
+        return Err(OracleError::EmaUnsupported);
+    }
+    if account.key.to_bytes() != *feed_id {
+        return Err(OracleError::FeedMismatch);
+    }
+    Ok(())
+}
+
+fn read_pyth_push(account: &AccountInfo, feed_id: &[u8; 32], use_ema: bool) -> std::result::Result<OraclePrice, OracleError> {
+    let price_feed = load_price_feed_from_account_info(account).map_err(|_| OracleError::InvalidAccount)?;
+    if price_feed.id.to_bytes() != *feed_id {
+        return Err(OracleError::FeedMismatch);
+    }
+    let price = if use_ema {
+        price_feed.get_ema_price_unchecked()
+    } else {
+        price_feed.get_price_unchecked()
+    };
+
+    let data = account.try_borrow_data().map_err(|_| OracleError::InvalidAccount)?;
+    let price_account: &SolanaPriceAccount = load_price_account(&data).map_err(|_| OracleError::InvalidAccount)?;
+    Ok(OraclePrice {
+        price: i128::from(price.price),
+        conf: price.conf,
+        expo: price.expo,
+        publish_time: price.publish_time,
+        publish_slot: price_account.agg.pub_slot,
+    })
+}
+
+fn read_pyth_pull(account: &AccountInfo, feed_id: &[u8; 32], use_ema: bool) -> std::result::Result<OraclePrice, OracleError> {
+    let update = pyth_pull::decode_price_update(&account.try_borrow_data().map_err(|_| OracleError::InvalidAccount)?)
+        .filter(|_| *account.owner == pyth_pull::PYTH_RECEIVER_PROGRAM_ID)
+        .filter(|update| update.verification_level == pyth_pull::VerificationLevel::Full)
+        .ok_or(OracleError::InvalidAccount)?;
+    let message = update.price_message;
+    if message.feed_id != *feed_id {
+        return Err(OracleError::FeedMismatch);
+    }
+    let (price, conf) = if use_ema {
+        (message.ema_price, message.ema_conf)
+    } else {
+        (message.price, message.conf)
+    };
+    Ok(OraclePrice {
+        price: i128::from(price),
+        conf,
+        expo: message.exponent,
+        publish_time: message.publish_time,
+        publish_slot: update.posted_slot,
+    })
+}
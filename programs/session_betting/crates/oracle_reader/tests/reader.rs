@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use oracle_reader::*;
+
+const FEED: [u8; 32] = [7u8; 32];
+const NOW: i64 = 1_700_000_000;
+const SWITCHBOARD_ONE: i128 = 1_000_000_000_000_000_000;
+
+fn pyth_update(price: i64, exponent: i32, publish_time: i64) -> Vec<u8> {
+    let body = pyth_pull::PriceUpdateV2 {
+        write_authority: Pubkey::new_unique(),
+        verification_level: pyth_pull::VerificationLevel::Full,
+        price_message: pyth_pull::PriceFeedMessage {
+            feed_id: FEED,
+            price,
+            conf: 20,
+            exponent,
+            publish_time,
+            prev_publish_time: publish_time - 1,
+            ema_price: price - 1,
+            ema_conf: 30,
+        },
+        posted_slot: 9,
+    };
+    let mut data = pyth_pull::PRICE_UPDATE_V2_DISCRIMINATOR.to_vec();
+    body.serialize(&mut data).unwrap();
+    data
+}
+
+fn switchboard_feed(value: i128, updated_at: i64) -> Vec<u8> {
+    use switchboard_pull::*;
+    let mut data = vec![0u8; RESULT_SLOT_OFFSET + 64];
+    data[..8].copy_from_slice(&PULL_FEED_DISCRIMINATOR);
+    data[LAST_UPDATE_TIMESTAMP_OFFSET..LAST_UPDATE_TIMESTAMP_OFFSET + 8].copy_from_slice(&updated_at.to_le_bytes());
+    data[RESULT_VALUE_OFFSET..RESULT_VALUE_OFFSET + 16].copy_from_slice(&value.to_le_bytes());
+    data[RESULT_SLOT_OFFSET..RESULT_SLOT_OFFSET + 8].copy_from_slice(&42u64.to_le_bytes());
+    data
+}
+
+fn with_account<R>(key: Pubkey, owner: Pubkey, mut data: Vec<u8>, f: impl FnOnce(&AccountInfo) -> R) -> R {
+    let mut lamports = 1_000_000;
+    let info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+    f(&info)
+}
+
+#[test]
+fn reads_pyth_pull_price_and_ema() {
+    let data = pyth_update(50_000, -8, NOW - 5);
+    with_account(Pubkey::new_unique(), pyth_pull::PYTH_RECEIVER_PROGRAM_ID, data, |info| {
+        let spot = OracleReader::PythPull.read_no_older_than(info, &FEED, NOW, 60, false).unwrap();
+        assert_eq!((spot.price, spot.conf, spot.expo, spot.publish_slot), (50_000, 20, -8, 9));
+        let ema = OracleReader::PythPull.read_no_older_than(info, &FEED, NOW, 60, true).unwrap();
+        assert_eq!((ema.price, ema.conf), (49_999, 30));
+        assert_eq!(OracleReader::PythPull.read(info, &[8u8; 32], false), Err(OracleError::FeedMismatch));
+    });
+}
+
+#[test]
+fn rejects_stale_non_positive_or_foreign_readings() {
+    with_account(Pubkey::new_unique(), pyth_pull::PYTH_RECEIVER_PROGRAM_ID, pyth_update(1, -8, NOW - 61), |info| {
+        assert_eq!(OracleReader::PythPull.read_no_older_than(info, &FEED, NOW, 60, false), Err(OracleError::Stale));
+        // Staleness alone is readable, e.g. to prove a feed has halted
+        assert!(!OracleReader::PythPull.read(info, &FEED, false).unwrap().is_fresh(NOW, 60));
+    });
+    with_account(Pubkey::new_unique(), pyth_pull::PYTH_RECEIVER_PROGRAM_ID, pyth_update(0, -8, NOW), |info| {
+        assert_eq!(OracleReader::PythPull.read_no_older_than(info, &FEED, NOW, 60, false), Err(OracleError::NonPositive));
+    });
+    with_account(Pubkey::new_unique(), Pubkey::new_unique(), pyth_update(1, -8, NOW), |info| {
+        assert_eq!(OracleReader::PythPull.read(info, &FEED, false), Err(OracleError::InvalidAccount));
+    });
+}
+
+#[test]
+fn switchboard_feeds_are_keyed_by_account() {
+    let key = Pubkey::new_unique();
+    let data = switchboard_feed(50_000 * SWITCHBOARD_ONE, NOW - 10);
+    with_account(key, switchboard_pull::SWITCHBOARD_ON_DEMAND_PROGRAM_ID, data, |info| {
+        let reading = OracleReader::Switchboard.read_no_older_than(info, &key.to_bytes(), NOW, 60, false).unwrap();
+        assert_eq!((reading.expo, reading.conf, reading.publish_slot), (-18, 0, 42));
+        assert_eq!(reading.price_in(8), Some(50_000 * 100_000_000));
+        assert_eq!(OracleReader::Switchboard.read(info, &FEED, false), Err(OracleError::FeedMismatch));
+        assert_eq!(OracleReader::Switchboard.read(info, &key.to_bytes(), true), Err(OracleError::EmaUnsupported));
+    });
+}
+
+#[test]
+fn rescales_between_exponents() {
+    assert_eq!(rescale(123_456, -5, 8), Some(123_456_000));
+    assert_eq!(rescale(123_456_789, -10, 8), Some(1_234_567));
+    assert_eq!(rescale(5, 2, 0), Some(500));
+    assert_eq!(rescale(i128::MAX, 0, 8), None);
+    assert_eq!(rescale(1, i32::MIN, 0), None);
+}
+
+#[test]
+fn pyth_and_switchboard_readings_meet_in_the_same_decimals() {
+    // $50,000.12345 from a Pyth feed with exponent -5 and from a Switchboard feed (18 decimals)
+    let pyth = pyth_update(5_000_012_345, -5, NOW);
+    let pyth_price = with_account(Pubkey::new_unique(), pyth_pull::PYTH_RECEIVER_PROGRAM_ID, pyth, |info| {
+        OracleReader::PythPull.read_no_older_than(info, &FEED, NOW, 60, false).unwrap()
+    });
+
+    let key = Pubkey::new_unique();
+    let switchboard = switchboard_feed(5_000_012_345 * SWITCHBOARD_ONE / 100_000, NOW);
+    let switchboard_price = with_account(key, switchboard_pull::SWITCHBOARD_ON_DEMAND_PROGRAM_ID, switchboard, |info| {
+        OracleReader::Switchboard.read_no_older_than(info, &key.to_bytes(), NOW, 60, false).unwrap()
+    });
+
+    assert_ne!(pyth_price.price, switchboard_price.price);
+    assert_eq!(pyth_price.price_in(8), Some(5_000_012_345_000));
+    assert_eq!(pyth_price.price_in(8), switchboard_price.price_in(8));
+    // conf scales with the price it belongs to
+    assert_eq!(pyth_price.conf_in(8), Some(20_000));
+}
@@ -0,0 +1,96 @@
+//! Decoder for Pyth pull-oracle `PriceUpdateV2` accounts
+//!
+//! Prices are posted by the Pyth Solana receiver program after it verifies the
+//! Wormhole-signed update. This crate reads those accounts without pulling in the
+//! receiver SDK, so the programs stay on a single Anchor version.
+//! Functions return `Option` and programs map `None` onto their own errors.
+
+use anchor_lang::prelude::*;
+
+// ===================
+// Constants
+// ===================
+
+/// Pyth Solana receiver program (owner of every PriceUpdateV2 account)
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+/// Anchor discriminator of the receiver's `PriceUpdateV2` account
+pub const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+
+// ===================
+// Account Layout
+// ===================
+
+/// How many Wormhole guardian signatures the receiver checked
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VerificationLevel {
+    Partial { num_signatures: u8 },
+    Full,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PriceFeedMessage {
+    pub feed_id: [u8; 32],
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+    pub prev_publish_time: i64,
+    pub ema_price: i64,
+    pub ema_conf: u64,
+}
+
+/// Body of a `PriceUpdateV2` account (after the 8-byte discriminator)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PriceUpdateV2 {
+    pub write_authority: Pubkey,
+    pub verification_level: VerificationLevel,
+    pub price_message: PriceFeedMessage,
+    pub posted_slot: u64,
+}
+
+// ===================
+// Decoding
+// ===================
+
+/// Decode raw `PriceUpdateV2` account data; None if the discriminator or layout is wrong
+pub fn decode_price_update(data: &[u8]) -> Option<PriceUpdateV2> {
+    let body = data.strip_prefix(&PRICE_UPDATE_V2_DISCRIMINATOR)?;
+    PriceUpdateV2::deserialize(&mut &body[..]).ok()
+}
+
+/// Fully verified update for `feed_id` from a receiver-owned account
+pub fn verified_update(account: &AccountInfo, feed_id: &[u8; 32]) -> Option<PriceUpdateV2> {
+    if *account.owner != PYTH_RECEIVER_PROGRAM_ID {
+        return None;
+    }
+    let update = decode_price_update(&account.try_borrow_data().ok()?)?;
+    if update.verification_level != VerificationLevel::Full || update.price_message.feed_id != *feed_id {
+        return None;
+    }
+    Some(update)
+}
+
+/// Fully verified message for `feed_id` from a receiver-owned account
+pub fn verified_message(account: &AccountInfo, feed_id: &[u8; 32]) -> Option<PriceFeedMessage> {
+    verified_update(account, feed_id).map(|update| update.price_message)
+}
+
+/// Fully verified update for `feed_id` with a positive price published no more than `max_age` seconds before `now`
+/// Use this over price_no_older_than when the confidence interval or posted slot matters
+pub fn update_no_older_than(account: &AccountInfo, feed_id: &[u8; 32], now: i64, max_age: u64) -> Option<PriceUpdateV2> {
+    let update = verified_update(account, feed_id)?;
+    let message = &update.price_message;
+
+    let fresh = now.checked_sub(message.publish_time)? <= i64::try_from(max_age).ok()?;
+    if !fresh || message.price <= 0 {
+        return None;
+    }
+    Some(update)
+}
+
+/// Fully verified, positive price for `feed_id` published no more than `max_age` seconds before `now`
+/// `account` must be owned by the Pyth receiver program
+pub fn price_no_older_than(account: &AccountInfo, feed_id: &[u8; 32], now: i64, max_age: u64) -> Option<u64> {
+    update_no_older_than(account, feed_id, now, max_age).map(|update| update.price_message.price as u64)
+}
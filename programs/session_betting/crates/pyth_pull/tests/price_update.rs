@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use pyth_pull::*;
+
+const FEED: [u8; 32] = [7u8; 32];
+const NOW: i64 = 1_700_000_000;
+
+fn update(level: VerificationLevel, price: i64, publish_time: i64) -> Vec<u8> {
+    let body = PriceUpdateV2 {
+        write_authority: Pubkey::new_unique(),
+        verification_level: level,
+        price_message: PriceFeedMessage {
+            feed_id: FEED,
+            price,
+            conf: 10,
+            exponent: -8,
+            publish_time,
+            prev_publish_time: publish_time - 1,
+            ema_price: price,
+            ema_conf: 10,
+        },
+        posted_slot: 1,
+    };
+    let mut data = PRICE_UPDATE_V2_DISCRIMINATOR.to_vec();
+    body.serialize(&mut data).unwrap();
+    data
+}
+
+fn with_account<R>(owner: Pubkey, mut data: Vec<u8>, f: impl FnOnce(&AccountInfo) -> R) -> R {
+    let key = Pubkey::new_unique();
+    let mut lamports = 1_000_000;
+    let info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+    f(&info)
+}
+
+#[test]
+fn reads_fresh_fully_verified_price() {
+    let data = update(VerificationLevel::Full, 50_000, NOW - 5);
+    with_account(PYTH_RECEIVER_PROGRAM_ID, data, |info| {
+        assert_eq!(price_no_older_than(info, &FEED, NOW, 60), Some(50_000));
+        let update = update_no_older_than(info, &FEED, NOW, 60).unwrap();
+        assert_eq!((update.price_message.conf, update.posted_slot), (10, 1));
+        assert_eq!(price_no_older_than(info, &[8u8; 32], NOW, 60), None);
+    });
+}
+
+#[test]
+fn rejects_stale_or_partially_verified_updates() {
+    let stale = update(VerificationLevel::Full, 50_000, NOW - 61);
+    with_account(PYTH_RECEIVER_PROGRAM_ID, stale, |info| {
+        assert_eq!(price_no_older_than(info, &FEED, NOW, 60), None);
+    });
+
+    let partial = update(VerificationLevel::Partial { num_signatures: 5 }, 50_000, NOW);
+    with_account(PYTH_RECEIVER_PROGRAM_ID, partial, |info| {
+        assert_eq!(price_no_older_than(info, &FEED, NOW, 60), None);
+    });
+}
+
+#[test]
+fn rejects_accounts_not_owned_by_receiver() {
+    let data = update(VerificationLevel::Full, 50_000, NOW);
+    with_account(Pubkey::new_unique(), data, |info| {
+        assert_eq!(price_no_older_than(info, &FEED, NOW, 60), None);
+    });
+}
+
+#[test]
+fn rejects_wrong_discriminator() {
+    let mut data = update(VerificationLevel::Full, 50_000, NOW);
+    data[0] ^= 1;
+    assert!(decode_price_update(&data).is_none());
+}
@@ -0,0 +1,65 @@
+//! Reader for Switchboard On-Demand pull feed accounts
+//!
+//! `PullFeedAccountData` is a zero-copy (`repr(C)`) account, so the fields the
+//! programs need are read at fixed offsets instead of pulling in the Switchboard SDK.
+//! Results are fixed-point with 18 decimals.
+//! Functions return `Option` and programs map `None` onto their own errors.
+
+use anchor_lang::prelude::*;
+
+// ===================
+// Constants
+// ===================
+
+/// Switchboard On-Demand program (mainnet), owner of every pull feed account
+pub const SWITCHBOARD_ON_DEMAND_PROGRAM_ID: Pubkey = pubkey!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv");
+
+/// Anchor discriminator of `PullFeedAccountData`
+pub const PULL_FEED_DISCRIMINATOR: [u8; 8] = [196, 27, 108, 196, 10, 215, 219, 40];
+
+/// Decimals of every Switchboard result value
+pub const SWITCHBOARD_DECIMALS: u32 = 18;
+
+/// Byte offsets into the account data (discriminator included)
+/// 32 submissions of 64 bytes, then authority/queue/feed_hash and config fields
+pub const LAST_UPDATE_TIMESTAMP_OFFSET: usize = 8 + 2208;
+/// Start of `result: CurrentResult`; `value: i128` is its first field
+pub const RESULT_VALUE_OFFSET: usize = 8 + 2256;
+/// `result.slot`, zero until the feed has produced a result
+pub const RESULT_SLOT_OFFSET: usize = RESULT_VALUE_OFFSET + 104;
+
+// ===================
+// Reading
+// ===================
+
+/// Current result value (18 decimals) and last update time from raw pull feed data
+/// None if the discriminator is wrong, the data is short, or no result exists yet
+pub fn read_result(data: &[u8]) -> Option<(i128, i64)> {
+    if data.get(..8)? != PULL_FEED_DISCRIMINATOR {
+        return None;
+    }
+    let slot = u64::from_le_bytes(data.get(RESULT_SLOT_OFFSET..RESULT_SLOT_OFFSET + 8)?.try_into().ok()?);
+    if slot == 0 {
+        return None;
+    }
+    let value = i128::from_le_bytes(data.get(RESULT_VALUE_OFFSET..RESULT_VALUE_OFFSET + 16)?.try_into().ok()?);
+    let updated_at = i64::from_le_bytes(
+        data.get(LAST_UPDATE_TIMESTAMP_OFFSET..LAST_UPDATE_TIMESTAMP_OFFSET + 8)?.try_into().ok()?,
+    );
+    Some((value, updated_at))
+}
+
+/// Positive result (18 decimals) updated no more than `max_age` seconds before `now`
+/// `account` must be owned by the Switchboard On-Demand program
+pub fn value_no_older_than(account: &AccountInfo, now: i64, max_age: u64) -> Option<i128> {
+    if *account.owner != SWITCHBOARD_ON_DEMAND_PROGRAM_ID {
+        return None;
+    }
+    let (value, updated_at) = read_result(&account.try_borrow_data().ok()?)?;
+
+    let fresh = now.checked_sub(updated_at)? <= i64::try_from(max_age).ok()?;
+    if !fresh || value <= 0 {
+        return None;
+    }
+    Some(value)
+}
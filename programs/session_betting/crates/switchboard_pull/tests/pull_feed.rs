@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use switchboard_pull::*;
+
+const NOW: i64 = 1_700_000_000;
+const ONE: i128 = 1_000_000_000_000_000_000;
+
+fn feed(value: i128, updated_at: i64, slot: u64) -> Vec<u8> {
+    let mut data = vec![0u8; RESULT_SLOT_OFFSET + 64];
+    data[..8].copy_from_slice(&PULL_FEED_DISCRIMINATOR);
+    data[LAST_UPDATE_TIMESTAMP_OFFSET..LAST_UPDATE_TIMESTAMP_OFFSET + 8].copy_from_slice(&updated_at.to_le_bytes());
+    data[RESULT_VALUE_OFFSET..RESULT_VALUE_OFFSET + 16].copy_from_slice(&value.to_le_bytes());
+    data[RESULT_SLOT_OFFSET..RESULT_SLOT_OFFSET + 8].copy_from_slice(&slot.to_le_bytes());
+    data
+}
+
+fn with_account<R>(owner: Pubkey, mut data: Vec<u8>, f: impl FnOnce(&AccountInfo) -> R) -> R {
+    let key = Pubkey::new_unique();
+    let mut lamports = 1_000_000;
+    let info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+    f(&info)
+}
+
+#[test]
+fn reads_fresh_positive_result() {
+    let data = feed(50_000 * ONE, NOW - 10, 42);
+    with_account(SWITCHBOARD_ON_DEMAND_PROGRAM_ID, data, |info| {
+        assert_eq!(value_no_older_than(info, NOW, 60), Some(50_000 * ONE));
+    });
+}
+
+#[test]
+fn rejects_stale_or_empty_results() {
+    with_account(SWITCHBOARD_ON_DEMAND_PROGRAM_ID, feed(ONE, NOW - 61, 42), |info| {
+        assert_eq!(value_no_older_than(info, NOW, 60), None);
+    });
+    assert!(read_result(&feed(ONE, NOW, 0)).is_none());
+}
+
+#[test]
+fn rejects_accounts_not_owned_by_switchboard() {
+    with_account(Pubkey::new_unique(), feed(ONE, NOW, 42), |info| {
+        assert_eq!(value_no_older_than(info, NOW, 60), None);
+    });
+}
+
+#[test]
+fn rejects_wrong_discriminator_and_short_data() {
+    let mut data = feed(ONE, NOW, 42);
+    data[0] ^= 1;
+    assert!(read_result(&data).is_none());
+    assert!(read_result(&PULL_FEED_DISCRIMINATOR).is_none());
+}
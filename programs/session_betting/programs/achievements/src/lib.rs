@@ -0,0 +1,483 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{create_account, CreateAccount};
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id, AssociatedToken};
+use anchor_spl::token_2022::spl_token_2022::extension::ExtensionType;
+use anchor_spl::token_2022::spl_token_2022::instruction::AuthorityType;
+use anchor_spl::token_2022::spl_token_2022::state::Mint;
+use anchor_spl::token_2022::{self, InitializeMint2, MintTo, SetAuthority, Token2022};
+use anchor_spl::token_interface::spl_token_metadata_interface::state::TokenMetadata;
+use anchor_spl::token_interface::{
+    metadata_pointer_initialize, non_transferable_mint_initialize, token_metadata_initialize,
+    MetadataPointerInitialize, NonTransferableMintInitialize, TokenMetadataInitialize,
+};
+
+declare_id!("7aCJSWev77x5NuuAq9y26dpqT6Lmh64KM4q24ymr7h6w");
+
+// ===================
+// Constants
+// ===================
+
+/// Maximum number of program PDAs allowed to award achievements
+pub const MAX_CREDITERS: usize = 8;
+
+/// Metadata bounds (mirrors common wallet display limits)
+pub const MAX_NAME_LEN: usize = 32;
+pub const MAX_SYMBOL_LEN: usize = 10;
+pub const MAX_URI_LEN: usize = 200;
+
+// ===================
+// Program
+// ===================
+
+#[program]
+pub mod achievements {
+    use super::*;
+
+    /// Initialize the achievements registry
+    pub fn initialize_achievements(ctx: Context<InitializeAchievements>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.authority = ctx.accounts.authority.key();
+        config.crediters = Vec::new();
+        config.bump = ctx.bumps.config;
+        Ok(())
+    }
+
+    /// Replace the set of PDAs allowed to award achievements
+    /// AUTHORITY ONLY - each game program signs award_achievement with its `achievements_crediter` PDA
+    pub fn set_crediters(ctx: Context<SetCrediters>, crediters: Vec<Pubkey>) -> Result<()> {
+        require!(crediters.len() <= MAX_CREDITERS, AchievementsError::TooManyCrediters);
+
+        ctx.accounts.config.crediters = crediters;
+        Ok(())
+    }
+
+    /// Define a new achievement and the metadata its badges carry
+    /// AUTHORITY ONLY
+    pub fn define_achievement(
+        ctx: Context<DefineAchievement>,
+        achievement_id: u32,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        validate_metadata(&name, &symbol, &uri)?;
+
+        let achievement = &mut ctx.accounts.achievement;
+        achievement.achievement_id = achievement_id;
+        achievement.name = name;
+        achievement.symbol = symbol;
+        achievement.uri = uri;
+        achievement.active = true;
+        achievement.awarded_count = 0;
+        achievement.bump = ctx.bumps.achievement;
+        Ok(())
+    }
+
+    /// Update an achievement's metadata or retire it
+    /// AUTHORITY ONLY - badges already minted keep the metadata they were minted with
+    pub fn update_achievement(
+        ctx: Context<UpdateAchievement>,
+        name: String,
+        symbol: String,
+        uri: String,
+        active: bool,
+    ) -> Result<()> {
+        validate_metadata(&name, &symbol, &uri)?;
+
+        let achievement = &mut ctx.accounts.achievement;
+        achievement.name = name;
+        achievement.symbol = symbol;
+        achievement.uri = uri;
+        achievement.active = active;
+        Ok(())
+    }
+
+    /// Mint a soulbound badge for an achievement to a player
+    /// CPI ONLY - crediter must be an allowlisted program PDA
+    /// Each (achievement, player) pair can only be awarded once
+    pub fn award_achievement(
+        ctx: Context<AwardAchievement>,
+        player: Pubkey,
+        achievement_id: u32,
+    ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        let achievement = &ctx.accounts.achievement;
+
+        // SECURITY: Only allowlisted game programs can award badges
+        require!(
+            config.crediters.contains(&ctx.accounts.crediter.key()),
+            AchievementsError::UnauthorizedCrediter
+        );
+        require!(achievement.active, AchievementsError::AchievementInactive);
+
+        let config_key = config.key();
+        let mint_key = ctx.accounts.mint.key();
+        let id_bytes = achievement_id.to_le_bytes();
+        let config_seeds: &[&[u8]] = &[b"achievements_config", &[config.bump]];
+        let mint_seeds: &[&[u8]] = &[b"badge_mint", &id_bytes, player.as_ref(), &[ctx.bumps.mint]];
+
+        // Mint is created at its base size; the token program reallocs for metadata,
+        // so lamports must cover the final size up front
+        let mint_space = ExtensionType::try_calculate_account_len::<Mint>(&[
+            ExtensionType::NonTransferable,
+            ExtensionType::MetadataPointer,
+        ])?;
+        let metadata_space = TokenMetadata {
+            name: achievement.name.clone(),
+            symbol: achievement.symbol.clone(),
+            uri: achievement.uri.clone(),
+            ..Default::default()
+        }
+        .tlv_size_of()?;
+        let lamports = Rent::get()?.minimum_balance(
+            mint_space
+                .checked_add(metadata_space)
+                .ok_or(AchievementsError::MathOverflow)?,
+        );
+
+        create_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                CreateAccount {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: ctx.accounts.mint.to_account_info(),
+                },
+                &[mint_seeds],
+            ),
+            lamports,
+            mint_space as u64,
+            &ctx.accounts.token_program.key(),
+        )?;
+
+        // SECURITY: NonTransferable makes the badge soulbound
+        non_transferable_mint_initialize(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            NonTransferableMintInitialize {
+                token_program_id: ctx.accounts.token_program.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+            },
+        ))?;
+        metadata_pointer_initialize(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                MetadataPointerInitialize {
+                    token_program_id: ctx.accounts.token_program.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                },
+            ),
+            Some(config_key),
+            Some(mint_key),
+        )?;
+        token_2022::initialize_mint2(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                InitializeMint2 {
+                    mint: ctx.accounts.mint.to_account_info(),
+                },
+            ),
+            0,
+            &config_key,
+            None,
+        )?;
+        token_metadata_initialize(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TokenMetadataInitialize {
+                    program_id: ctx.accounts.token_program.to_account_info(),
+                    metadata: ctx.accounts.mint.to_account_info(),
+                    update_authority: ctx.accounts.config.to_account_info(),
+                    mint_authority: ctx.accounts.config.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                },
+                &[config_seeds],
+            ),
+            achievement.name.clone(),
+            achievement.symbol.clone(),
+            achievement.uri.clone(),
+        )?;
+
+        associated_token::create(CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: ctx.accounts.payer.to_account_info(),
+                associated_token: ctx.accounts.player_token_account.to_account_info(),
+                authority: ctx.accounts.recipient.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+        ))?;
+        token_2022::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.player_token_account.to_account_info(),
+                    authority: ctx.accounts.config.to_account_info(),
+                },
+                &[config_seeds],
+            ),
+            1,
+        )?;
+
+        // SECURITY: Drop the mint authority so supply is fixed at one
+        token_2022::set_authority(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                SetAuthority {
+                    current_authority: ctx.accounts.config.to_account_info(),
+                    account_or_mint: ctx.accounts.mint.to_account_info(),
+                },
+                &[config_seeds],
+            ),
+            AuthorityType::MintTokens,
+            None,
+        )?;
+
+        let clock = Clock::get()?;
+        let player_achievement = &mut ctx.accounts.player_achievement;
+        player_achievement.player = player;
+        player_achievement.achievement_id = achievement_id;
+        player_achievement.mint = mint_key;
+        player_achievement.awarded_at = clock.unix_timestamp;
+        player_achievement.bump = ctx.bumps.player_achievement;
+
+        let achievement = &mut ctx.accounts.achievement;
+        achievement.awarded_count = achievement.awarded_count
+            .checked_add(1)
+            .ok_or(AchievementsError::MathOverflow)?;
+
+        emit_cpi!(AchievementAwarded {
+            player,
+            achievement_id,
+            mint: mint_key,
+            crediter: ctx.accounts.crediter.key(),
+        });
+        Ok(())
+    }
+}
+
+// ===================
+// Helper Functions
+// ===================
+
+fn validate_metadata(name: &str, symbol: &str, uri: &str) -> Result<()> {
+    require!(
+        !name.is_empty() && name.len() <= MAX_NAME_LEN,
+        AchievementsError::InvalidName
+    );
+    require!(symbol.len() <= MAX_SYMBOL_LEN, AchievementsError::InvalidSymbol);
+    require!(uri.len() <= MAX_URI_LEN, AchievementsError::InvalidUri);
+    Ok(())
+}
+
+// ===================
+// Account Structs
+// ===================
+
+#[derive(Accounts)]
+pub struct InitializeAchievements<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AchievementsConfig::INIT_SPACE,
+        seeds = [b"achievements_config"],
+        bump
+    )]
+    pub config: Account<'info, AchievementsConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetCrediters<'info> {
+    #[account(
+        mut,
+        seeds = [b"achievements_config"],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, AchievementsConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(achievement_id: u32)]
+pub struct DefineAchievement<'info> {
+    #[account(
+        seeds = [b"achievements_config"],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, AchievementsConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Achievement::INIT_SPACE,
+        seeds = [b"achievement", achievement_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub achievement: Account<'info, Achievement>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateAchievement<'info> {
+    #[account(
+        seeds = [b"achievements_config"],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, AchievementsConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"achievement", achievement.achievement_id.to_le_bytes().as_ref()],
+        bump = achievement.bump
+    )]
+    pub achievement: Account<'info, Achievement>,
+
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(player: Pubkey, achievement_id: u32)]
+pub struct AwardAchievement<'info> {
+    #[account(
+        seeds = [b"achievements_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, AchievementsConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"achievement", achievement_id.to_le_bytes().as_ref()],
+        bump = achievement.bump
+    )]
+    pub achievement: Account<'info, Achievement>,
+
+    /// SECURITY: init fails if this player already holds the achievement
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PlayerAchievement::INIT_SPACE,
+        seeds = [b"player_achievement", achievement_id.to_le_bytes().as_ref(), player.as_ref()],
+        bump
+    )]
+    pub player_achievement: Account<'info, PlayerAchievement>,
+
+    /// CHECK: Badge mint PDA - created and initialized with Token-2022 extensions in the handler
+    #[account(
+        mut,
+        seeds = [b"badge_mint", achievement_id.to_le_bytes().as_ref(), player.as_ref()],
+        bump
+    )]
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: Player's associated token account for the badge - created in the handler
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(&player, &mint.key(), &token_program.key())
+    )]
+    pub player_token_account: UncheckedAccount<'info>,
+
+    /// CHECK: Badge recipient (the `player` argument) - only used as the token account owner
+    #[account(address = player)]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// Calling program's `achievements_crediter` PDA, signed via invoke_signed
+    pub crediter: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+// ===================
+// State
+// ===================
+
+#[account]
+#[derive(InitSpace)]
+pub struct AchievementsConfig {
+    pub authority: Pubkey,
+    /// Program PDAs allowed to call award_achievement
+    #[max_len(MAX_CREDITERS)]
+    pub crediters: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Achievement {
+    pub achievement_id: u32,
+    #[max_len(MAX_NAME_LEN)]
+    pub name: String,
+    #[max_len(MAX_SYMBOL_LEN)]
+    pub symbol: String,
+    #[max_len(MAX_URI_LEN)]
+    pub uri: String,
+    /// Retired achievements can no longer be awarded
+    pub active: bool,
+    pub awarded_count: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct PlayerAchievement {
+    pub player: Pubkey,
+    pub achievement_id: u32,
+    /// Soulbound badge mint held by the player
+    pub mint: Pubkey,
+    pub awarded_at: i64,
+    pub bump: u8,
+}
+
+// ===================
+// Events
+// ===================
+
+/// Emitted when a badge is minted to a player
+#[event]
+pub struct AchievementAwarded {
+    pub player: Pubkey,
+    pub achievement_id: u32,
+    pub mint: Pubkey,
+    pub crediter: Pubkey,
+}
+
+// ===================
+// Errors
+// ===================
+
+#[error_code]
+pub enum AchievementsError {
+    #[msg("Too many crediters")]
+    TooManyCrediters,
+    #[msg("Unauthorized crediter")]
+    UnauthorizedCrediter,
+    #[msg("Achievement inactive")]
+    AchievementInactive,
+    #[msg("Bad name")]
+    InvalidName,
+    #[msg("Bad symbol")]
+    InvalidSymbol,
+    #[msg("Bad URI")]
+    InvalidUri,
+    #[msg("Overflow")]
+    MathOverflow,
+}
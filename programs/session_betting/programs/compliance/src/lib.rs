@@ -0,0 +1,281 @@
+use anchor_lang::prelude::*;
+
+declare_id!("6k12aMUGNxCajMiyh4RDnuTGP93TGGQmhDoUzuddVZD3");
+
+// ===================
+// Constants
+// ===================
+
+/// Longest self-exclusion a wallet can request in one call: 5 years
+pub const MAX_SELF_EXCLUSION_SECONDS: i64 = 5 * 365 * 24 * 60 * 60;
+
+// ===================
+// Program
+// ===================
+
+#[program]
+pub mod compliance {
+    use super::*;
+
+    /// Initialize the shared compliance policy
+    pub fn initialize_compliance(ctx: Context<InitializeCompliance>, mode: EligibilityMode) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.authority = ctx.accounts.authority.key();
+        config.mode = mode;
+        config.bump = ctx.bumps.config;
+        Ok(())
+    }
+
+    /// Switch the policy applied to every betting entrypoint
+    /// AUTHORITY ONLY
+    pub fn set_mode(ctx: Context<SetMode>, mode: EligibilityMode) -> Result<()> {
+        ctx.accounts.config.mode = mode;
+
+        emit_cpi!(ModeUpdated { mode });
+        Ok(())
+    }
+
+    /// Allowlist or blocklist a wallet across all products
+    /// AUTHORITY ONLY - never affects withdrawals, which do not consult this program
+    pub fn set_user_status(ctx: Context<SetUserStatus>, user: Pubkey, status: EligibilityStatus) -> Result<()> {
+        let record = &mut ctx.accounts.user_compliance;
+        record.user = user;
+        record.status = status;
+        record.bump = ctx.bumps.user_compliance;
+
+        emit_cpi!(UserStatusUpdated { user, status });
+        Ok(())
+    }
+
+    /// Exclude the signing wallet from betting until `until`
+    /// REQUIRES wallet signature - exclusions can be extended but never shortened
+    pub fn self_exclude(ctx: Context<SelfExclude>, until: i64) -> Result<()> {
+        let clock = Clock::get()?;
+        let record = &mut ctx.accounts.user_compliance;
+
+        let max_until = clock.unix_timestamp
+            .checked_add(MAX_SELF_EXCLUSION_SECONDS)
+            .ok_or(ComplianceError::MathOverflow)?;
+        require!(
+            until > clock.unix_timestamp && until <= max_until,
+            ComplianceError::InvalidExclusion
+        );
+        // SECURITY: A compromised key must not be able to lift an exclusion early
+        require!(until > record.self_excluded_until, ComplianceError::InvalidExclusion);
+
+        record.user = ctx.accounts.user.key();
+        record.self_excluded_until = until;
+        record.bump = ctx.bumps.user_compliance;
+
+        emit_cpi!(SelfExcluded {
+            user: record.user,
+            until,
+        });
+        Ok(())
+    }
+
+    /// Fail unless the wallet may place a new bet (user is bound through the record's seeds)
+    /// Permissionless - betting programs CPI this from bet entrypoints only
+    pub fn assert_eligible(ctx: Context<AssertEligible>, _user: Pubkey) -> Result<()> {
+        // Wallets without a record are Unset and not self-excluded
+        let info = &ctx.accounts.user_compliance;
+        let (status, self_excluded_until) = if info.data_is_empty() {
+            (EligibilityStatus::Unset, 0)
+        } else {
+            require_keys_eq!(*info.owner, crate::ID, ComplianceError::NotEligible);
+            let record = UserCompliance::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+            (record.status, record.self_excluded_until)
+        };
+
+        let clock = Clock::get()?;
+        require!(
+            clock.unix_timestamp >= self_excluded_until,
+            ComplianceError::SelfExcluded
+        );
+
+        let eligible = match ctx.accounts.config.mode {
+            EligibilityMode::Open => true,
+            EligibilityMode::Allowlist => status == EligibilityStatus::Allowed,
+            EligibilityMode::Blocklist => status != EligibilityStatus::Blocked,
+        };
+        require!(eligible, ComplianceError::NotEligible);
+        Ok(())
+    }
+}
+
+// ===================
+// Account Structs
+// ===================
+
+#[derive(Accounts)]
+pub struct InitializeCompliance<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ComplianceConfig::INIT_SPACE,
+        seeds = [b"compliance_config"],
+        bump
+    )]
+    pub config: Account<'info, ComplianceConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetMode<'info> {
+    #[account(
+        mut,
+        seeds = [b"compliance_config"],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, ComplianceConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct SetUserStatus<'info> {
+    #[account(
+        seeds = [b"compliance_config"],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, ComplianceConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + UserCompliance::INIT_SPACE,
+        seeds = [b"user_compliance", user.as_ref()],
+        bump
+    )]
+    pub user_compliance: Account<'info, UserCompliance>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SelfExclude<'info> {
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserCompliance::INIT_SPACE,
+        seeds = [b"user_compliance", user.key().as_ref()],
+        bump
+    )]
+    pub user_compliance: Account<'info, UserCompliance>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct AssertEligible<'info> {
+    #[account(
+        seeds = [b"compliance_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ComplianceConfig>,
+
+    /// CHECK: User's compliance record - may not exist yet, so it is read in the handler
+    /// SECURITY: Seeds pin it to `user` so a blocked wallet can't pass someone else's record
+    #[account(
+        seeds = [b"user_compliance", user.as_ref()],
+        bump
+    )]
+    pub user_compliance: UncheckedAccount<'info>,
+}
+
+// ===================
+// State
+// ===================
+
+#[account]
+#[derive(InitSpace)]
+pub struct ComplianceConfig {
+    pub authority: Pubkey,
+    pub mode: EligibilityMode,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct UserCompliance {
+    pub user: Pubkey,
+    pub status: EligibilityStatus,
+    /// Unix timestamp before which the wallet cannot bet (0 = not excluded)
+    pub self_excluded_until: i64,
+    pub bump: u8,
+}
+
+// ===================
+// Enums
+// ===================
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum EligibilityMode {
+    Open,
+    Allowlist,
+    Blocklist,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum EligibilityStatus {
+    Unset,
+    Allowed,
+    Blocked,
+}
+
+// ===================
+// Events
+// ===================
+
+/// Emitted when the compliance mode changes
+#[event]
+pub struct ModeUpdated {
+    pub mode: EligibilityMode,
+}
+
+/// Emitted when the authority sets a wallet's status
+#[event]
+pub struct UserStatusUpdated {
+    pub user: Pubkey,
+    pub status: EligibilityStatus,
+}
+
+/// Emitted when a wallet excludes itself
+#[event]
+pub struct SelfExcluded {
+    pub user: Pubkey,
+    pub until: i64,
+}
+
+// ===================
+// Errors
+// ===================
+
+#[error_code]
+pub enum ComplianceError {
+    #[msg("Not eligible")]
+    NotEligible,
+    #[msg("Self-excluded")]
+    SelfExcluded,
+    #[msg("Bad exclusion")]
+    InvalidExclusion,
+    #[msg("Overflow")]
+    MathOverflow,
+}
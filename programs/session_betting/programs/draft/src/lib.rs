@@ -0,0 +1,1120 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use compliance::cpi::accounts::AssertEligible;
+use compliance::program::Compliance;
+use insurance::cpi::accounts::CoverShortfall;
+use insurance::program::Insurance;
+use oracle_reader::{OracleError, OracleReader};
+use session_betting::cpi::accounts::CreditWinnings;
+use session_betting::program::SessionBetting;
+use session_betting::GameType;
+
+declare_id!("5GjZMLfRDS4RrxwSroe8WgaEpD7oiomLbGBxBW9Fr6BR");
+
+// ===================
+// Constants
+// ===================
+
+/// Minimum entry fee: 0.01 SOL (10,000,000 lamports)
+pub const MIN_ENTRY_FEE: u64 = 10_000_000;
+
+/// Platform fee: 5% (500 basis points) of the prize pool
+pub const PLATFORM_FEE_BPS: u64 = 500;
+
+/// Basis points denominator
+pub use bet_math::BPS_DENOMINATOR;
+
+/// Maximum number of assets a contest can offer
+pub const MAX_CONTEST_ASSETS: usize = 8;
+
+/// Maximum number of assets a roster can hold
+pub const MAX_ROSTER_SIZE: usize = 5;
+
+/// Contests with fewer entries than this are cancelled and refunded at start
+pub const MIN_CONTEST_ENTRIES: u32 = 2;
+
+/// Maximum age of Pyth price data (in seconds)
+pub const MAX_PRICE_AGE_SECONDS: u64 = 60;
+
+/// Window after end_time during which entries can be scored (in seconds)
+pub const SCORING_WINDOW_SECONDS: i64 = 60 * 60;
+
+/// Registry deprecation bits for this program's entrypoints (see registry::ProgramEntry)
+pub const ENTRYPOINT_ENTER_CONTEST: u64 = 1 << 0;
+
+// ===================
+// Program
+// ===================
+
+#[program]
+pub mod draft {
+    use super::*;
+
+    /// Initialize the draft program config
+    pub fn initialize_draft(ctx: Context<InitializeDraft>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.authority = ctx.accounts.authority.key();
+        config.contest_count = 0;
+        config.require_multisig = false;
+        config.bump = ctx.bumps.config;
+        Ok(())
+    }
+
+    /// Require the Squads multisig path for fee withdrawals
+    /// AUTHORITY ONLY - once enabled, only the multisig can change it again
+    pub fn set_require_multisig(ctx: Context<SetRequireMultisig>, require_multisig: bool) -> Result<()> {
+        // SECURITY: Treasury paths must come from the Squads vault when required
+        require!(
+            admin_guard::admin_path_ok(
+                &ctx.accounts.authority.key(),
+                ctx.accounts.admin_multisig.as_deref(),
+                ctx.accounts.config.require_multisig,
+            ),
+            DraftError::MultisigRequired
+        );
+
+        ctx.accounts.config.require_multisig = require_multisig;
+        Ok(())
+    }
+
+    /// Create a new contest over a fixed list of Pyth assets
+    /// AUTHORITY ONLY
+    pub fn create_contest(
+        ctx: Context<CreateContest>,
+        entry_fee: u64,
+        roster_size: u8,
+        max_entries: u32,
+        start_time: i64,
+        end_time: i64,
+        feed_ids: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+
+        // SECURITY: Validate contest parameters
+        require!(entry_fee >= MIN_ENTRY_FEE, DraftError::EntryFeeTooSmall);
+        require!(
+            roster_size > 0 && (roster_size as usize) <= MAX_ROSTER_SIZE,
+            DraftError::InvalidRosterSize
+        );
+        require!(
+            feed_ids.len() >= roster_size as usize && feed_ids.len() <= MAX_CONTEST_ASSETS,
+            DraftError::InvalidAssetList
+        );
+        require!(max_entries >= MIN_CONTEST_ENTRIES, DraftError::InvalidMaxEntries);
+        require!(
+            start_time > clock.unix_timestamp && end_time > start_time,
+            DraftError::InvalidContestWindow
+        );
+
+        // SECURITY: Duplicate assets would let a roster double-count one feed
+        for (i, feed_id) in feed_ids.iter().enumerate() {
+            require!(
+                !feed_ids[..i].contains(feed_id),
+                DraftError::InvalidAssetList
+            );
+        }
+
+        let config = &mut ctx.accounts.config;
+        let contest = &mut ctx.accounts.contest;
+        let asset_count = feed_ids.len();
+
+        contest.contest_id = config.contest_count;
+        contest.entry_fee = entry_fee;
+        contest.roster_size = roster_size;
+        contest.max_entries = max_entries;
+        contest.entry_count = 0;
+        contest.start_time = start_time;
+        contest.end_time = end_time;
+        contest.feed_ids = feed_ids;
+        contest.start_prices = vec![0; asset_count];
+        contest.end_prices = vec![0; asset_count];
+        contest.prize_pool = 0;
+        contest.fee = 0;
+        contest.best_score = i64::MIN;
+        contest.winner_count = 0;
+        contest.prizes_settled = 0;
+        contest.fee_withdrawn = false;
+        contest.status = ContestStatus::Open;
+        contest.bump = ctx.bumps.contest;
+
+        config.contest_count = config.contest_count
+            .checked_add(1)
+            .ok_or(DraftError::MathOverflow)?;
+
+        emit_cpi!(ContestCreated {
+            contest_id: contest.contest_id,
+            entry_fee,
+            roster_size,
+            start_time,
+            end_time,
+        });
+        Ok(())
+    }
+
+    /// Enter a contest with a roster of asset indices
+    /// REQUIRES wallet signature - entry fee is paid into the contest escrow
+    pub fn enter_contest(ctx: Context<EnterContest>, picks: Vec<u8>) -> Result<()> {
+        let contest = &mut ctx.accounts.contest;
+        let clock = Clock::get()?;
+
+        // SECURITY: Entries close when the contest starts
+        require!(contest.status == ContestStatus::Open, DraftError::ContestNotOpen);
+        require!(clock.unix_timestamp < contest.start_time, DraftError::ContestStarted);
+        require!(contest.entry_count < contest.max_entries, DraftError::ContestFull);
+
+        // SECURITY: Roster must be the right size with unique, valid picks
+        require!(
+            picks.len() == contest.roster_size as usize,
+            DraftError::InvalidRoster
+        );
+        for (i, pick) in picks.iter().enumerate() {
+            require!(
+                (*pick as usize) < contest.feed_ids.len() && !picks[..i].contains(pick),
+                DraftError::InvalidRoster
+            );
+        }
+
+        // SECURITY: Shared compliance policy (bet paths only)
+        let cpi_context = CpiContext::new(
+            ctx.accounts.compliance_program.to_account_info(),
+            AssertEligible {
+                config: ctx.accounts.compliance_config.to_account_info(),
+                user_compliance: ctx.accounts.user_compliance.to_account_info(),
+            },
+        );
+        compliance::cpi::assert_eligible(cpi_context, ctx.accounts.player.key())?;
+
+        // Refuse entrypoints deprecated in the program registry
+        registry::require_not_deprecated(&ctx.accounts.program_entry, ENTRYPOINT_ENTER_CONTEST)?;
+
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.player.to_account_info(),
+                to: ctx.accounts.escrow.to_account_info(),
+            },
+        );
+        transfer(cpi_context, contest.entry_fee)?;
+
+        contest.entry_count = contest.entry_count
+            .checked_add(1)
+            .ok_or(DraftError::MathOverflow)?;
+        contest.prize_pool = contest.prize_pool
+            .checked_add(contest.entry_fee)
+            .ok_or(DraftError::MathOverflow)?;
+
+        let entry = &mut ctx.accounts.entry;
+        entry.contest_id = contest.contest_id;
+        entry.player = ctx.accounts.player.key();
+        entry.picks = picks;
+        entry.score = 0;
+        entry.scored = false;
+        entry.settled = false;
+        entry.bump = ctx.bumps.entry;
+
+        emit_cpi!(ContestEntered {
+            contest_id: contest.contest_id,
+            player: entry.player,
+            picks: entry.picks.clone(),
+        });
+        Ok(())
+    }
+
+    /// Record start prices and lock rosters
+    /// Permissionless - anyone can call after start_time
+    /// Pyth price accounts are passed as remaining_accounts in feed_ids order
+    pub fn start_contest<'info>(ctx: Context<'_, '_, 'info, 'info, StartContest<'info>>) -> Result<()> {
+        let contest = &mut ctx.accounts.contest;
+        let clock = Clock::get()?;
+
+        require!(contest.status == ContestStatus::Open, DraftError::ContestNotOpen);
+        require!(clock.unix_timestamp >= contest.start_time, DraftError::TooEarly);
+
+        // Not enough players for a contest - refund everyone
+        if contest.entry_count < MIN_CONTEST_ENTRIES {
+            contest.status = ContestStatus::Cancelled;
+            emit_cpi!(ContestCancelled { contest_id: contest.contest_id });
+            return Ok(());
+        }
+
+        contest.start_prices = read_prices(&contest.feed_ids, ctx.remaining_accounts, clock.unix_timestamp)?;
+        contest.status = ContestStatus::Live;
+        Ok(())
+    }
+
+    /// Record end prices and open the scoring window
+    /// Permissionless - anyone can call after end_time
+    /// Pyth price accounts are passed as remaining_accounts in feed_ids order
+    pub fn end_contest<'info>(ctx: Context<'_, '_, 'info, 'info, EndContest<'info>>) -> Result<()> {
+        let contest = &mut ctx.accounts.contest;
+        let clock = Clock::get()?;
+
+        require!(contest.status == ContestStatus::Live, DraftError::ContestNotLive);
+        require!(clock.unix_timestamp >= contest.end_time, DraftError::TooEarly);
+
+        contest.end_prices = read_prices(&contest.feed_ids, ctx.remaining_accounts, clock.unix_timestamp)?;
+        contest.fee = bet_math::bps_of(contest.prize_pool, PLATFORM_FEE_BPS)
+            .ok_or(DraftError::MathOverflow)?;
+        contest.status = ContestStatus::Scoring;
+        Ok(())
+    }
+
+    /// Score an entry from recorded start/end prices
+    /// Permissionless - anyone can call during the scoring window
+    pub fn score_entry(ctx: Context<ScoreEntry>) -> Result<()> {
+        let contest = &mut ctx.accounts.contest;
+        let entry = &mut ctx.accounts.entry;
+        let clock = Clock::get()?;
+
+        require!(contest.status == ContestStatus::Scoring, DraftError::ContestNotScoring);
+        require!(
+            clock.unix_timestamp < scoring_deadline(contest)?,
+            DraftError::ScoringClosed
+        );
+        require!(!entry.scored, DraftError::AlreadyScored);
+
+        let score = calculate_score(contest, &entry.picks)?;
+        entry.score = score;
+        entry.scored = true;
+
+        // Track the leaderboard top - ties split the prize
+        if score > contest.best_score {
+            contest.best_score = score;
+            contest.winner_count = 1;
+        } else if score == contest.best_score {
+            contest.winner_count = contest.winner_count
+                .checked_add(1)
+                .ok_or(DraftError::MathOverflow)?;
+        }
+
+        emit_cpi!(EntryScored {
+            contest_id: contest.contest_id,
+            player: entry.player,
+            score,
+        });
+        Ok(())
+    }
+
+    /// Pay a winning entry's prize into the player's session_betting balance
+    /// AUTHORITY ONLY - moves the prize to session_betting's Draft game vault and CPIs credit_winnings
+    pub fn settle_entry(ctx: Context<SettleEntry>) -> Result<()> {
+        let contest = &ctx.accounts.contest;
+        let entry = &mut ctx.accounts.entry;
+        let clock = Clock::get()?;
+
+        require!(contest.status == ContestStatus::Scoring, DraftError::ContestNotScoring);
+
+        // SECURITY: Winners are final only after the scoring window closes
+        require!(
+            clock.unix_timestamp >= scoring_deadline(contest)?,
+            DraftError::TooEarly
+        );
+        require!(!entry.settled, DraftError::AlreadySettled);
+        require!(
+            entry.scored && entry.score == contest.best_score,
+            DraftError::NotAWinner
+        );
+
+        let prize = contest.prize_pool
+            .checked_sub(contest.fee)
+            .ok_or(DraftError::MathOverflow)?
+            .checked_div(contest.winner_count as u64)
+            .ok_or(DraftError::MathOverflow)?;
+
+        entry.settled = true;
+        let contest = &mut ctx.accounts.contest;
+        contest.prizes_settled = contest.prizes_settled
+            .checked_add(1)
+            .ok_or(DraftError::MathOverflow)?;
+
+        // Rounding or a bug must never leave a winner unpaid
+        cover_escrow_shortfall(
+            &ctx.accounts.insurance,
+            &ctx.bumps.insurance,
+            ctx.accounts.escrow.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            prize,
+        )?;
+
+        // Move the prize from contest escrow into the Draft game vault
+        let contest_id_bytes = contest.contest_id.to_le_bytes();
+        let bump = ctx.bumps.escrow;
+        let seeds: &[&[u8]] = &[b"contest_escrow", contest_id_bytes.as_ref(), &[bump]];
+        let signer_seeds = &[seeds];
+
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.escrow.to_account_info(),
+                to: ctx.accounts.game_vault.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(cpi_context, prize)?;
+
+        // Credit the player's balance from the game vault
+        let mut game_id = [0u8; 32];
+        game_id[..8].copy_from_slice(&contest_id_bytes);
+
+        let cpi_context = CpiContext::new(
+            ctx.accounts.session_betting_program.to_account_info(),
+            CreditWinnings {
+                game_state: ctx.accounts.game_state.to_account_info(),
+                authority: ctx.accounts.authority.to_account_info(),
+                owner: ctx.accounts.player.to_account_info(),
+                user_balance: ctx.accounts.user_balance.to_account_info(),
+                user_vault: ctx.accounts.user_vault.to_account_info(),
+                game_vault: ctx.accounts.game_vault.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                event_authority: ctx.accounts.session_betting_event_authority.to_account_info(),
+                program: ctx.accounts.session_betting_program.to_account_info(),
+            },
+        );
+        session_betting::cpi::credit_winnings(cpi_context, prize, GameType::Draft, game_id)?;
+
+        emit_cpi!(PrizeSettled {
+            contest_id: contest.contest_id,
+            player: entry.player,
+            amount: prize,
+        });
+        Ok(())
+    }
+
+    /// Cancel a contest before it starts
+    /// AUTHORITY ONLY
+    pub fn cancel_contest(ctx: Context<CancelContest>) -> Result<()> {
+        let contest = &mut ctx.accounts.contest;
+
+        require!(contest.status == ContestStatus::Open, DraftError::ContestNotOpen);
+
+        contest.status = ContestStatus::Cancelled;
+        emit_cpi!(ContestCancelled { contest_id: contest.contest_id });
+        Ok(())
+    }
+
+    /// Refund an entry fee from a cancelled contest to the player's wallet
+    /// Permissionless - funds can only go to the entry's player
+    pub fn refund_entry(ctx: Context<RefundEntry>) -> Result<()> {
+        let contest = &ctx.accounts.contest;
+        let entry = &mut ctx.accounts.entry;
+
+        require!(contest.status == ContestStatus::Cancelled, DraftError::ContestNotCancelled);
+        require!(!entry.settled, DraftError::AlreadySettled);
+
+        entry.settled = true;
+
+        let contest_id_bytes = contest.contest_id.to_le_bytes();
+        let bump = ctx.bumps.escrow;
+        let seeds: &[&[u8]] = &[b"contest_escrow", contest_id_bytes.as_ref(), &[bump]];
+        let signer_seeds = &[seeds];
+
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.escrow.to_account_info(),
+                to: ctx.accounts.player.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(cpi_context, contest.entry_fee)?;
+        Ok(())
+    }
+
+    /// Withdraw a contest's platform fee once every winner has been paid
+    /// AUTHORITY ONLY - sweeps the escrow, including rounding dust from prize splits
+    pub fn withdraw_contest_fee(ctx: Context<WithdrawContestFee>) -> Result<()> {
+        // SECURITY: Treasury paths must come from the Squads vault when required
+        require!(
+            admin_guard::admin_path_ok(
+                &ctx.accounts.authority.key(),
+                ctx.accounts.admin_multisig.as_deref(),
+                ctx.accounts.config.require_multisig,
+            ),
+            DraftError::MultisigRequired
+        );
+
+        let contest = &mut ctx.accounts.contest;
+        let clock = Clock::get()?;
+
+        require!(contest.status == ContestStatus::Scoring, DraftError::ContestNotScoring);
+        require!(
+            clock.unix_timestamp >= scoring_deadline(contest)?,
+            DraftError::TooEarly
+        );
+        require!(!contest.fee_withdrawn, DraftError::FeeAlreadyWithdrawn);
+
+        // SECURITY: Fee comes last so the escrow always covers outstanding prizes
+        require!(
+            contest.winner_count > 0 && contest.prizes_settled == contest.winner_count,
+            DraftError::PrizesOutstanding
+        );
+
+        contest.fee_withdrawn = true;
+        let amount = ctx.accounts.escrow.lamports();
+
+        let contest_id_bytes = contest.contest_id.to_le_bytes();
+        let bump = ctx.bumps.escrow;
+        let seeds: &[&[u8]] = &[b"contest_escrow", contest_id_bytes.as_ref(), &[bump]];
+        let signer_seeds = &[seeds];
+
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.escrow.to_account_info(),
+                to: ctx.accounts.authority.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(cpi_context, amount)?;
+        Ok(())
+    }
+}
+
+// ===================
+// Helper Functions
+// ===================
+
+/// Top up `escrow` from the insurance fund so it can pay `amount`
+/// No-op while the escrow already holds enough; fails if it is short and no insurance accounts were supplied
+fn cover_escrow_shortfall<'info>(
+    accounts: &InsuranceTopUpAccounts<'info>,
+    bumps: &InsuranceTopUpAccountsBumps,
+    escrow: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let shortfall = amount.saturating_sub(escrow.lamports());
+    if shortfall == 0 {
+        return Ok(());
+    }
+
+    let (
+        Some(crediter),
+        Some(config),
+        Some(insurance_vault),
+        Some(event_authority),
+        Some(insurance_program),
+    ) = (
+        &accounts.insurance_crediter,
+        &accounts.insurance_config,
+        &accounts.insurance_vault,
+        &accounts.insurance_event_authority,
+        &accounts.insurance_program,
+    )
+    else {
+        return err!(DraftError::EscrowShortfall);
+    };
+
+    let crediter_bump = bumps.insurance_crediter.ok_or(DraftError::Unauthorized)?;
+    let seeds: &[&[u8]] = &[b"insurance_crediter", &[crediter_bump]];
+    let signer_seeds = &[seeds];
+
+    let cpi_context = CpiContext::new_with_signer(
+        insurance_program.to_account_info(),
+        CoverShortfall {
+            config: config.to_account_info(),
+            insurance_vault: insurance_vault.to_account_info(),
+            escrow,
+            crediter: crediter.to_account_info(),
+            system_program,
+            event_authority: event_authority.to_account_info(),
+            program: insurance_program.to_account_info(),
+        },
+        signer_seeds,
+    );
+    insurance::cpi::cover_shortfall(cpi_context, shortfall)
+}
+
+/// Read one validated Pyth price per contest asset
+/// SECURITY: Each account must match its feed id and pass the staleness check
+fn read_prices<'info>(
+    feed_ids: &[[u8; 32]],
+    price_accounts: &'info [AccountInfo<'info>],
+    current_time: i64,
+) -> Result<Vec<u64>> {
+    require!(
+        price_accounts.len() == feed_ids.len(),
+        DraftError::PriceAccountMismatch
+    );
+
+    let mut prices = Vec::with_capacity(feed_ids.len());
+    for (feed_id, price_account) in feed_ids.iter().zip(price_accounts.iter()) {
+        let reading = OracleReader::PythPush
+            .read_no_older_than(price_account, feed_id, current_time, MAX_PRICE_AGE_SECONDS, false)
+            .map_err(oracle_error)?;
+        prices.push(u64::try_from(reading.price).map_err(|_| DraftError::MathOverflow)?);
+    }
+    Ok(prices)
+}
+
+/// Map a shared oracle reader error onto this program's errors
+fn oracle_error(err: OracleError) -> Error {
+    match err {
+        OracleError::FeedMismatch => DraftError::PriceFeedMismatch,
+        OracleError::Stale => DraftError::PriceTooStale,
+        OracleError::NonPositive => DraftError::InvalidPrice,
+        OracleError::InvalidAccount | OracleError::EmaUnsupported => DraftError::InvalidPriceFeed,
+    }
+    .into()
+}
+
+/// Roster score: sum of each picked asset's price change in basis points
+fn calculate_score(contest: &Contest, picks: &[u8]) -> Result<i64> {
+    let mut score: i64 = 0;
+    for pick in picks {
+        let change_bps = bet_math::change_bps(
+            contest.start_prices[*pick as usize],
+            contest.end_prices[*pick as usize],
+        )
+        .ok_or(DraftError::MathOverflow)?;
+
+        score = score
+            .checked_add(change_bps)
+            .ok_or(DraftError::MathOverflow)?;
+    }
+    Ok(score)
+}
+
+/// End of the window during which entries can be scored
+fn scoring_deadline(contest: &Contest) -> Result<i64> {
+    Ok(contest.end_time
+        .checked_add(SCORING_WINDOW_SECONDS)
+        .ok_or(DraftError::MathOverflow)?)
+}
+
+// ===================
+// Account Structs
+// ===================
+
+#[derive(Accounts)]
+pub struct InitializeDraft<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + DraftConfig::INIT_SPACE,
+        seeds = [b"draft_config"],
+        bump
+    )]
+    pub config: Account<'info, DraftConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetRequireMultisig<'info> {
+    #[account(
+        mut,
+        seeds = [b"draft_config"],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, DraftConfig>,
+
+    pub authority: Signer<'info>,
+
+    /// CHECK: Squads multisig whose default vault is the authority (omit for a raw-key authority)
+    /// Verified by admin_guard; required when config.require_multisig is set
+    pub admin_multisig: Option<UncheckedAccount<'info>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateContest<'info> {
+    #[account(
+        mut,
+        seeds = [b"draft_config"],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, DraftConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Contest::INIT_SPACE,
+        seeds = [b"contest", config.contest_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub contest: Account<'info, Contest>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct EnterContest<'info> {
+    #[account(
+        mut,
+        seeds = [b"contest", contest.contest_id.to_le_bytes().as_ref()],
+        bump = contest.bump
+    )]
+    pub contest: Account<'info, Contest>,
+
+    #[account(
+        init,
+        payer = player,
+        space = 8 + ContestEntry::INIT_SPACE,
+        seeds = [b"entry", contest.contest_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub entry: Account<'info, ContestEntry>,
+
+    /// Contest escrow PDA holding entry fees - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"contest_escrow", contest.contest_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub escrow: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Shared compliance config, validated by the compliance program
+    pub compliance_config: UncheckedAccount<'info>,
+
+    /// CHECK: Player's compliance record (may be uninitialized), validated by the compliance program
+    pub user_compliance: UncheckedAccount<'info>,
+
+    pub compliance_program: Program<'info, Compliance>,
+
+    /// CHECK: This program's registry entry (may be unregistered), read by registry::require_not_deprecated
+    #[account(
+        seeds = [b"program_entry", crate::ID.as_ref()],
+        bump,
+        seeds::program = registry::ID
+    )]
+    pub program_entry: UncheckedAccount<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct StartContest<'info> {
+    #[account(
+        mut,
+        seeds = [b"contest", contest.contest_id.to_le_bytes().as_ref()],
+        bump = contest.bump
+    )]
+    pub contest: Account<'info, Contest>,
+
+    /// Anyone can start a contest (permissionless)
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EndContest<'info> {
+    #[account(
+        mut,
+        seeds = [b"contest", contest.contest_id.to_le_bytes().as_ref()],
+        bump = contest.bump
+    )]
+    pub contest: Account<'info, Contest>,
+
+    /// Anyone can end a contest (permissionless)
+    pub caller: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ScoreEntry<'info> {
+    #[account(
+        mut,
+        seeds = [b"contest", contest.contest_id.to_le_bytes().as_ref()],
+        bump = contest.bump
+    )]
+    pub contest: Account<'info, Contest>,
+
+    #[account(
+        mut,
+        seeds = [b"entry", contest.contest_id.to_le_bytes().as_ref(), entry.player.as_ref()],
+        bump = entry.bump
+    )]
+    pub entry: Account<'info, ContestEntry>,
+
+    /// Anyone can score an entry (permissionless)
+    pub caller: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SettleEntry<'info> {
+    #[account(
+        seeds = [b"draft_config"],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, DraftConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"contest", contest.contest_id.to_le_bytes().as_ref()],
+        bump = contest.bump
+    )]
+    pub contest: Account<'info, Contest>,
+
+    #[account(
+        mut,
+        seeds = [b"entry", contest.contest_id.to_le_bytes().as_ref(), entry.player.as_ref()],
+        bump = entry.bump,
+        has_one = player
+    )]
+    pub entry: Account<'info, ContestEntry>,
+
+    /// Contest escrow PDA holding entry fees - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"contest_escrow", contest.contest_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub escrow: SystemAccount<'info>,
+
+    /// Must also be the session_betting authority - credit_winnings checks this
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Entry's player wallet, verified by has_one on entry
+    pub player: AccountInfo<'info>,
+
+    /// CHECK: session_betting game state, validated by the credit_winnings CPI
+    pub game_state: AccountInfo<'info>,
+
+    /// CHECK: Player's session_betting balance, validated by the credit_winnings CPI
+    #[account(mut)]
+    pub user_balance: AccountInfo<'info>,
+
+    /// CHECK: Player's session_betting vault, validated by the credit_winnings CPI
+    #[account(mut)]
+    pub user_vault: AccountInfo<'info>,
+
+    /// session_betting's Draft game vault - prizes pass through it on the way to user vaults
+    #[account(
+        mut,
+        seeds = [b"global_vault", [GameType::Draft as u8].as_ref()],
+        bump,
+        seeds::program = session_betting_program.key()
+    )]
+    pub game_vault: SystemAccount<'info>,
+
+    /// CHECK: session_betting's event-CPI authority PDA
+    #[account(
+        seeds = [b"__event_authority"],
+        seeds::program = session_betting::ID,
+        bump
+    )]
+    pub session_betting_event_authority: UncheckedAccount<'info>,
+
+    pub session_betting_program: Program<'info, SessionBetting>,
+
+    pub system_program: Program<'info, System>,
+
+    pub insurance: InsuranceTopUpAccounts<'info>,
+}
+
+/// Optional accounts that let a claim draw an escrow shortfall from the insurance fund
+#[derive(Accounts)]
+pub struct InsuranceTopUpAccounts<'info> {
+    /// CHECK: Program signer for insurance cover_shortfall (optional, with the accounts below)
+    #[account(
+        seeds = [b"insurance_crediter"],
+        bump
+    )]
+    pub insurance_crediter: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Insurance config, validated by the insurance program
+    #[account(mut)]
+    pub insurance_config: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Insurance vault, validated by the insurance program
+    #[account(mut)]
+    pub insurance_vault: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Insurance's event-CPI authority PDA
+    #[account(
+        seeds = [b"__event_authority"],
+        seeds::program = insurance::ID,
+        bump
+    )]
+    pub insurance_event_authority: Option<UncheckedAccount<'info>>,
+
+    pub insurance_program: Option<Program<'info, Insurance>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelContest<'info> {
+    #[account(
+        seeds = [b"draft_config"],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, DraftConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"contest", contest.contest_id.to_le_bytes().as_ref()],
+        bump = contest.bump
+    )]
+    pub contest: Account<'info, Contest>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RefundEntry<'info> {
+    #[account(
+        seeds = [b"contest", contest.contest_id.to_le_bytes().as_ref()],
+        bump = contest.bump
+    )]
+    pub contest: Account<'info, Contest>,
+
+    #[account(
+        mut,
+        seeds = [b"entry", contest.contest_id.to_le_bytes().as_ref(), entry.player.as_ref()],
+        bump = entry.bump,
+        has_one = player
+    )]
+    pub entry: Account<'info, ContestEntry>,
+
+    /// Contest escrow PDA holding entry fees - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"contest_escrow", contest.contest_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub escrow: SystemAccount<'info>,
+
+    /// Entry's player wallet - receives the refund
+    #[account(mut)]
+    pub player: SystemAccount<'info>,
+
+    /// Anyone can process a refund (permissionless)
+    pub caller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawContestFee<'info> {
+    #[account(
+        seeds = [b"draft_config"],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, DraftConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"contest", contest.contest_id.to_le_bytes().as_ref()],
+        bump = contest.bump
+    )]
+    pub contest: Account<'info, Contest>,
+
+    /// Contest escrow PDA holding entry fees - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"contest_escrow", contest.contest_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub escrow: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Squads multisig whose default vault is the authority (omit for a raw-key authority)
+    /// Verified by admin_guard; required when config.require_multisig is set
+    pub admin_multisig: Option<UncheckedAccount<'info>>,
+}
+
+// ===================
+// State
+// ===================
+
+#[account]
+#[derive(InitSpace)]
+pub struct DraftConfig {
+    pub authority: Pubkey,
+    pub contest_count: u64,
+    /// Fee withdrawals must be signed by the Squads multisig vault
+    pub require_multisig: bool,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Contest {
+    pub contest_id: u64,
+    pub entry_fee: u64,
+    /// Number of assets each roster must pick
+    pub roster_size: u8,
+    pub max_entries: u32,
+    pub entry_count: u32,
+    /// Rosters lock and start prices are recorded at this time
+    pub start_time: i64,
+    pub end_time: i64,
+    /// Pyth feed ids of the assets players can draft
+    #[max_len(MAX_CONTEST_ASSETS)]
+    pub feed_ids: Vec<[u8; 32]>,
+    #[max_len(MAX_CONTEST_ASSETS)]
+    pub start_prices: Vec<u64>,
+    #[max_len(MAX_CONTEST_ASSETS)]
+    pub end_prices: Vec<u64>,
+    /// Sum of entry fees held in escrow
+    pub prize_pool: u64,
+    /// Platform fee taken from the prize pool (set at end_contest)
+    pub fee: u64,
+    /// Highest score recorded during the scoring window
+    pub best_score: i64,
+    /// Number of scored entries tied at best_score
+    pub winner_count: u32,
+    /// Number of winners paid so far
+    pub prizes_settled: u32,
+    pub fee_withdrawn: bool,
+    pub status: ContestStatus,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ContestEntry {
+    pub contest_id: u64,
+    pub player: Pubkey,
+    /// Indices into the contest's feed_ids
+    #[max_len(MAX_ROSTER_SIZE)]
+    pub picks: Vec<u8>,
+    /// Sum of picked assets' price changes in basis points
+    pub score: i64,
+    pub scored: bool,
+    /// Prize paid or entry fee refunded
+    pub settled: bool,
+    pub bump: u8,
+}
+
+// ===================
+// Enums
+// ===================
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ContestStatus {
+    Open,
+    Live,
+    Scoring,
+    Cancelled,
+}
+
+// ===================
+// Events
+// ===================
+
+/// Emitted when a contest is created
+#[event]
+pub struct ContestCreated {
+    pub contest_id: u64,
+    pub entry_fee: u64,
+    pub roster_size: u8,
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+/// Emitted when a player enters a contest
+#[event]
+pub struct ContestEntered {
+    pub contest_id: u64,
+    pub player: Pubkey,
+    pub picks: Vec<u8>,
+}
+
+/// Emitted when an entry is scored
+#[event]
+pub struct EntryScored {
+    pub contest_id: u64,
+    pub player: Pubkey,
+    pub score: i64,
+}
+
+/// Emitted when a prize is credited to a winner's balance
+#[event]
+pub struct PrizeSettled {
+    pub contest_id: u64,
+    pub player: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when a contest is cancelled and entries become refundable
+#[event]
+pub struct ContestCancelled {
+    pub contest_id: u64,
+}
+
+// ===================
+// Errors
+// ===================
+
+#[error_code]
+pub enum DraftError {
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Fee too small")]
+    EntryFeeTooSmall,
+    #[msg("Bad roster size")]
+    InvalidRosterSize,
+    #[msg("Bad asset list")]
+    InvalidAssetList,
+    #[msg("Bad max entries")]
+    InvalidMaxEntries,
+    #[msg("Bad contest window")]
+    InvalidContestWindow,
+    #[msg("Contest not open")]
+    ContestNotOpen,
+    #[msg("Contest not live")]
+    ContestNotLive,
+    #[msg("Contest not scoring")]
+    ContestNotScoring,
+    #[msg("Contest not cancelled")]
+    ContestNotCancelled,
+    #[msg("Contest started")]
+    ContestStarted,
+    #[msg("Contest full")]
+    ContestFull,
+    #[msg("Bad roster")]
+    InvalidRoster,
+    #[msg("Too early")]
+    TooEarly,
+    #[msg("Scoring closed")]
+    ScoringClosed,
+    #[msg("Already scored")]
+    AlreadyScored,
+    #[msg("Already settled")]
+    AlreadySettled,
+    #[msg("Not a winner")]
+    NotAWinner,
+    #[msg("Fee withdrawn")]
+    FeeAlreadyWithdrawn,
+    #[msg("Prizes outstanding")]
+    PrizesOutstanding,
+    #[msg("Price accounts mismatch")]
+    PriceAccountMismatch,
+    #[msg("Invalid price")]
+    InvalidPrice,
+    #[msg("Bad feed")]
+    InvalidPriceFeed,
+    #[msg("Feed mismatch")]
+    PriceFeedMismatch,
+    #[msg("Price stale")]
+    PriceTooStale,
+    #[msg("Overflow")]
+    MathOverflow,
+    #[msg("Multisig required")]
+    MultisigRequired,
+    #[msg("Escrow short")]
+    EscrowShortfall,
+}
@@ -0,0 +1,555 @@
+use anchor_lang::prelude::*;
+use session_betting::cpi::accounts::{
+    AcceptAuthorityTransfer, ProposeAuthorityTransfer, SetCircuitBreaker, SetEligibilityMode,
+    SetMinPoolForSettlement, SetPaused, SetPoolCaps,
+};
+use session_betting::program::SessionBetting;
+use session_betting::EligibilityMode;
+
+declare_id!("GuAVWm2TT9qqbirfXCjM5JHGvXP6PKRfofyvYZNeFQ6x");
+
+// ===================
+// Constants
+// ===================
+
+/// Maximum council size
+pub const MAX_COUNCIL_MEMBERS: usize = 7;
+
+/// Minimum delay between approval and execution (in seconds)
+pub const MIN_TIMELOCK_SECONDS: i64 = 60 * 60;
+
+/// Maximum delay between approval and execution (in seconds)
+pub const MAX_TIMELOCK_SECONDS: i64 = 14 * 24 * 60 * 60;
+
+// ===================
+// Program
+// ===================
+
+#[program]
+pub mod governance {
+    use super::*;
+
+    /// Initialize the council
+    /// To take over parameter control, session_betting's authority proposes the
+    /// governance PDA as new authority and accept_session_betting_authority is called
+    pub fn initialize_governance(
+        ctx: Context<InitializeGovernance>,
+        members: Vec<Pubkey>,
+        threshold: u8,
+        timelock_seconds: i64,
+    ) -> Result<()> {
+        validate_council(&members, threshold)?;
+        validate_timelock(timelock_seconds)?;
+
+        let governance = &mut ctx.accounts.governance;
+        governance.members = members;
+        governance.threshold = threshold;
+        governance.timelock_seconds = timelock_seconds;
+        governance.proposal_count = 0;
+        governance.bump = ctx.bumps.governance;
+        Ok(())
+    }
+
+    /// Propose a whitelisted action
+    /// COUNCIL ONLY - the proposer's approval is recorded immediately
+    pub fn create_proposal(ctx: Context<CreateProposal>, action: GovernanceAction) -> Result<()> {
+        let governance = &mut ctx.accounts.governance;
+        let proposer = ctx.accounts.proposer.key();
+        let clock = Clock::get()?;
+
+        require!(governance.members.contains(&proposer), GovernanceError::NotCouncilMember);
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.proposal_id = governance.proposal_count;
+        proposal.proposer = proposer;
+        proposal.action = action;
+        proposal.approvals = vec![proposer];
+        proposal.approved_at = 0;
+        proposal.executed = false;
+        proposal.cancelled = false;
+        proposal.bump = ctx.bumps.proposal;
+
+        if approval_count(governance, proposal) >= governance.threshold as usize {
+            proposal.approved_at = clock.unix_timestamp;
+        }
+
+        governance.proposal_count = governance.proposal_count
+            .checked_add(1)
+            .ok_or(GovernanceError::MathOverflow)?;
+
+        emit_cpi!(ProposalCreated {
+            proposal_id: proposal.proposal_id,
+            proposer,
+            action,
+        });
+        Ok(())
+    }
+
+    /// Approve a proposal - the timelock starts once the threshold is reached
+    /// COUNCIL ONLY
+    pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+        let governance = &ctx.accounts.governance;
+        let proposal = &mut ctx.accounts.proposal;
+        let member = ctx.accounts.member.key();
+        let clock = Clock::get()?;
+
+        require!(governance.members.contains(&member), GovernanceError::NotCouncilMember);
+        require!(
+            !proposal.executed && !proposal.cancelled,
+            GovernanceError::ProposalClosed
+        );
+        require!(!proposal.approvals.contains(&member), GovernanceError::AlreadyApproved);
+
+        proposal.approvals.push(member);
+
+        if proposal.approved_at == 0
+            && approval_count(governance, proposal) >= governance.threshold as usize
+        {
+            proposal.approved_at = clock.unix_timestamp;
+        }
+
+        emit_cpi!(ProposalApproved {
+            proposal_id: proposal.proposal_id,
+            member,
+            approvals: proposal.approvals.len() as u8,
+        });
+        Ok(())
+    }
+
+    /// Cancel a proposal before execution
+    /// PROPOSER ONLY
+    pub fn cancel_proposal(ctx: Context<CancelProposal>) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+
+        require!(
+            !proposal.executed && !proposal.cancelled,
+            GovernanceError::ProposalClosed
+        );
+
+        proposal.cancelled = true;
+        Ok(())
+    }
+
+    /// Execute an approved proposal once its timelock has elapsed
+    /// Permissionless - session_betting actions are CPIs signed by the governance PDA
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+        let clock = Clock::get()?;
+        let governance = &ctx.accounts.governance;
+        let proposal = &ctx.accounts.proposal;
+
+        require!(
+            !proposal.executed && !proposal.cancelled,
+            GovernanceError::ProposalClosed
+        );
+
+        // SECURITY: Re-count against the current council - removed members no longer count
+        require!(
+            proposal.approved_at > 0
+                && approval_count(governance, proposal) >= governance.threshold as usize,
+            GovernanceError::NotApproved
+        );
+
+        let eta = proposal.approved_at
+            .checked_add(governance.timelock_seconds)
+            .ok_or(GovernanceError::MathOverflow)?;
+        require!(clock.unix_timestamp >= eta, GovernanceError::TimelockActive);
+
+        let action = proposal.action;
+        let bump = governance.bump;
+        let seeds: &[&[u8]] = &[b"governance", &[bump]];
+        let signer_seeds = &[seeds];
+
+        let program = ctx.accounts.session_betting_program.to_account_info();
+        let game_state = ctx.accounts.game_state.to_account_info();
+        let authority = ctx.accounts.governance.to_account_info();
+        let event_authority = ctx.accounts.session_betting_event_authority.to_account_info();
+
+        match action {
+            GovernanceAction::SetPaused { paused } => {
+                let cpi_context = CpiContext::new_with_signer(
+                    program.clone(),
+                    SetPaused { game_state, authority, event_authority, program },
+                    signer_seeds,
+                );
+                session_betting::cpi::set_paused(cpi_context, paused)?;
+            }
+            GovernanceAction::SetPoolCaps { max_total_pool, max_side_pool, max_player_stake } => {
+                let cpi_context = CpiContext::new_with_signer(
+                    program.clone(),
+                    SetPoolCaps { game_state, authority, event_authority, program },
+                    signer_seeds,
+                );
+                session_betting::cpi::set_pool_caps(
+                    cpi_context,
+                    max_total_pool,
+                    max_side_pool,
+                    max_player_stake,
+                )?;
+            }
+            GovernanceAction::SetCircuitBreaker { max_price_move_bps } => {
+                let cpi_context = CpiContext::new_with_signer(
+                    program,
+                    SetCircuitBreaker { game_state, authority },
+                    signer_seeds,
+                );
+                session_betting::cpi::set_circuit_breaker(cpi_context, max_price_move_bps)?;
+            }
+            GovernanceAction::SetMinPoolForSettlement { min_pool } => {
+                let cpi_context = CpiContext::new_with_signer(
+                    program,
+                    SetMinPoolForSettlement { game_state, authority },
+                    signer_seeds,
+                );
+                session_betting::cpi::set_min_pool_for_settlement(cpi_context, min_pool)?;
+            }
+            GovernanceAction::SetEligibilityMode { mode } => {
+                let cpi_context = CpiContext::new_with_signer(
+                    program,
+                    SetEligibilityMode { game_state, authority },
+                    signer_seeds,
+                );
+                session_betting::cpi::set_eligibility_mode(cpi_context, mode)?;
+            }
+            GovernanceAction::ProposeAuthorityTransfer { new_authority } => {
+                let cpi_context = CpiContext::new_with_signer(
+                    program.clone(),
+                    ProposeAuthorityTransfer {
+                        game_state,
+                        authority,
+                        admin_multisig: None,
+                        event_authority,
+                        program,
+                    },
+                    signer_seeds,
+                );
+                session_betting::cpi::propose_authority_transfer(cpi_context, new_authority)?;
+            }
+            GovernanceAction::AddMember { member } => {
+                let governance = &mut ctx.accounts.governance;
+                require!(!governance.members.contains(&member), GovernanceError::InvalidCouncil);
+                governance.members.push(member);
+                validate_council(&governance.members, governance.threshold)?;
+            }
+            GovernanceAction::RemoveMember { member } => {
+                let governance = &mut ctx.accounts.governance;
+                governance.members.retain(|m| *m != member);
+                validate_council(&governance.members, governance.threshold)?;
+            }
+            GovernanceAction::SetThreshold { threshold } => {
+                let governance = &mut ctx.accounts.governance;
+                validate_council(&governance.members, threshold)?;
+                governance.threshold = threshold;
+            }
+            GovernanceAction::SetTimelock { timelock_seconds } => {
+                validate_timelock(timelock_seconds)?;
+                ctx.accounts.governance.timelock_seconds = timelock_seconds;
+            }
+        }
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.executed = true;
+
+        emit_cpi!(ProposalExecuted {
+            proposal_id: proposal.proposal_id,
+            action,
+        });
+        Ok(())
+    }
+
+    /// Accept a pending session_betting authority transfer to the governance PDA
+    /// Permissionless - only succeeds if session_betting proposed this PDA
+    pub fn accept_session_betting_authority(ctx: Context<AcceptSessionBettingAuthority>) -> Result<()> {
+        let bump = ctx.accounts.governance.bump;
+        let seeds: &[&[u8]] = &[b"governance", &[bump]];
+        let signer_seeds = &[seeds];
+
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.session_betting_program.to_account_info(),
+            AcceptAuthorityTransfer {
+                game_state: ctx.accounts.game_state.to_account_info(),
+                new_authority: ctx.accounts.governance.to_account_info(),
+                event_authority: ctx.accounts.session_betting_event_authority.to_account_info(),
+                program: ctx.accounts.session_betting_program.to_account_info(),
+            },
+            signer_seeds,
+        );
+        session_betting::cpi::accept_authority_transfer(cpi_context)
+    }
+}
+
+// ===================
+// Helper Functions
+// ===================
+
+fn validate_council(members: &[Pubkey], threshold: u8) -> Result<()> {
+    require!(
+        !members.is_empty() && members.len() <= MAX_COUNCIL_MEMBERS,
+        GovernanceError::InvalidCouncil
+    );
+    for (i, member) in members.iter().enumerate() {
+        require!(!members[..i].contains(member), GovernanceError::InvalidCouncil);
+    }
+    require!(
+        threshold > 0 && threshold as usize <= members.len(),
+        GovernanceError::InvalidThreshold
+    );
+    Ok(())
+}
+
+fn validate_timelock(timelock_seconds: i64) -> Result<()> {
+    require!(
+        (MIN_TIMELOCK_SECONDS..=MAX_TIMELOCK_SECONDS).contains(&timelock_seconds),
+        GovernanceError::InvalidTimelock
+    );
+    Ok(())
+}
+
+/// Approvals from current council members
+fn approval_count(governance: &Governance, proposal: &Proposal) -> usize {
+    proposal.approvals
+        .iter()
+        .filter(|a| governance.members.contains(a))
+        .count()
+}
+
+// ===================
+// Account Structs
+// ===================
+
+#[derive(Accounts)]
+pub struct InitializeGovernance<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Governance::INIT_SPACE,
+        seeds = [b"governance"],
+        bump
+    )]
+    pub governance: Account<'info, Governance>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance.bump
+    )]
+    pub governance: Account<'info, Governance>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + Proposal::INIT_SPACE,
+        seeds = [b"proposal", governance.proposal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    #[account(
+        seeds = [b"governance"],
+        bump = governance.bump
+    )]
+    pub governance: Account<'info, Governance>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub member: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelProposal<'info> {
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        has_one = proposer
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub proposer: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance.bump
+    )]
+    pub governance: Account<'info, Governance>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// CHECK: session_betting game state, validated by the session_betting CPI
+    #[account(mut)]
+    pub game_state: AccountInfo<'info>,
+
+    /// CHECK: session_betting's event-CPI authority PDA
+    #[account(
+        seeds = [b"__event_authority"],
+        seeds::program = session_betting::ID,
+        bump
+    )]
+    pub session_betting_event_authority: UncheckedAccount<'info>,
+
+    pub session_betting_program: Program<'info, SessionBetting>,
+
+    /// Anyone can execute an approved proposal (permissionless)
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptSessionBettingAuthority<'info> {
+    #[account(
+        seeds = [b"governance"],
+        bump = governance.bump
+    )]
+    pub governance: Account<'info, Governance>,
+
+    /// CHECK: session_betting game state, validated by the session_betting CPI
+    #[account(mut)]
+    pub game_state: AccountInfo<'info>,
+
+    /// CHECK: session_betting's event-CPI authority PDA
+    #[account(
+        seeds = [b"__event_authority"],
+        seeds::program = session_betting::ID,
+        bump
+    )]
+    pub session_betting_event_authority: UncheckedAccount<'info>,
+
+    pub session_betting_program: Program<'info, SessionBetting>,
+
+    pub caller: Signer<'info>,
+}
+
+// ===================
+// State
+// ===================
+
+#[account]
+#[derive(InitSpace)]
+pub struct Governance {
+    #[max_len(MAX_COUNCIL_MEMBERS)]
+    pub members: Vec<Pubkey>,
+    /// Approvals required before the timelock starts
+    pub threshold: u8,
+    /// Delay between reaching the threshold and execution
+    pub timelock_seconds: i64,
+    pub proposal_count: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Proposal {
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub action: GovernanceAction,
+    #[max_len(MAX_COUNCIL_MEMBERS)]
+    pub approvals: Vec<Pubkey>,
+    /// When the threshold was reached (0 = not yet approved)
+    pub approved_at: i64,
+    pub executed: bool,
+    pub cancelled: bool,
+    pub bump: u8,
+}
+
+// ===================
+// Enums
+// ===================
+
+/// Whitelisted actions - anything else stays with the session_betting operator keys
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum GovernanceAction {
+    SetPaused { paused: bool },
+    SetPoolCaps { max_total_pool: u64, max_side_pool: u64, max_player_stake: u64 },
+    SetCircuitBreaker { max_price_move_bps: u64 },
+    SetMinPoolForSettlement { min_pool: u64 },
+    SetEligibilityMode { mode: EligibilityMode },
+    ProposeAuthorityTransfer { new_authority: Pubkey },
+    AddMember { member: Pubkey },
+    RemoveMember { member: Pubkey },
+    SetThreshold { threshold: u8 },
+    SetTimelock { timelock_seconds: i64 },
+}
+
+// ===================
+// Events
+// ===================
+
+/// Emitted when a council member creates a proposal
+#[event]
+pub struct ProposalCreated {
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub action: GovernanceAction,
+}
+
+/// Emitted when a council member approves a proposal
+#[event]
+pub struct ProposalApproved {
+    pub proposal_id: u64,
+    pub member: Pubkey,
+    pub approvals: u8,
+}
+
+/// Emitted when a proposal is executed
+#[event]
+pub struct ProposalExecuted {
+    pub proposal_id: u64,
+    pub action: GovernanceAction,
+}
+
+// ===================
+// Errors
+// ===================
+
+#[error_code]
+pub enum GovernanceError {
+    #[msg("Bad council")]
+    InvalidCouncil,
+    #[msg("Bad threshold")]
+    InvalidThreshold,
+    #[msg("Bad timelock")]
+    InvalidTimelock,
+    #[msg("Not council member")]
+    NotCouncilMember,
+    #[msg("Already approved")]
+    AlreadyApproved,
+    #[msg("Proposal closed")]
+    ProposalClosed,
+    #[msg("Not approved")]
+    NotApproved,
+    #[msg("Timelock active")]
+    TimelockActive,
+    #[msg("Overflow")]
+    MathOverflow,
+}
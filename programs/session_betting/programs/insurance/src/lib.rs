@@ -0,0 +1,364 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+declare_id!("HsmEMxpUkFDajAkq8fwXpHvX76ezch8aBc5cpkYY8QrV");
+
+// ===================
+// Constants
+// ===================
+
+/// Maximum number of program PDAs allowed to contribute or request top-ups
+pub const MAX_CREDITERS: usize = 8;
+
+/// Contribution cap: 20% (2000 basis points) of each fee
+pub const MAX_CONTRIBUTION_BPS: u64 = 2_000;
+
+// ===================
+// Program
+// ===================
+
+#[program]
+pub mod insurance {
+    use super::*;
+
+    /// Initialize the insurance fund
+    /// Seeds the insurance vault with its rent-exempt minimum so payouts never strand it
+    pub fn initialize_insurance(ctx: Context<InitializeInsurance>, contribution_bps: u64) -> Result<()> {
+        require!(contribution_bps <= MAX_CONTRIBUTION_BPS, InsuranceError::InvalidContribution);
+
+        let rent = Rent::get()?.minimum_balance(0);
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.authority.to_account_info(),
+                to: ctx.accounts.insurance_vault.to_account_info(),
+            },
+        );
+        transfer(cpi_context, rent)?;
+
+        let config = &mut ctx.accounts.config;
+        config.authority = ctx.accounts.authority.key();
+        config.crediters = Vec::new();
+        config.contribution_bps = contribution_bps;
+        config.total_contributed = 0;
+        config.total_replenished = 0;
+        config.total_paid_out = 0;
+        config.bump = ctx.bumps.config;
+        Ok(())
+    }
+
+    /// Replace the set of PDAs allowed to contribute fees and request top-ups
+    /// AUTHORITY ONLY - each game program signs with its `insurance_crediter` PDA
+    pub fn set_crediters(ctx: Context<SetInsuranceConfig>, crediters: Vec<Pubkey>) -> Result<()> {
+        require!(crediters.len() <= MAX_CREDITERS, InsuranceError::TooManyCrediters);
+
+        ctx.accounts.config.crediters = crediters;
+        Ok(())
+    }
+
+    /// Set the share of each platform fee routed into the fund
+    /// AUTHORITY ONLY
+    pub fn set_contribution_bps(ctx: Context<SetInsuranceConfig>, contribution_bps: u64) -> Result<()> {
+        require!(contribution_bps <= MAX_CONTRIBUTION_BPS, InsuranceError::InvalidContribution);
+
+        ctx.accounts.config.contribution_bps = contribution_bps;
+        Ok(())
+    }
+
+    /// Move the fund's share of a platform fee into the insurance vault
+    /// CPI ONLY - crediter must be an allowlisted program PDA and fee_source its signed fee vault
+    /// Returns the lamports taken so the caller can reduce its own fee accounting
+    pub fn contribute(ctx: Context<Contribute>, fee: u64) -> Result<u64> {
+        let config = &mut ctx.accounts.config;
+
+        // SECURITY: Only allowlisted game programs can contribute
+        require!(
+            config.crediters.contains(&ctx.accounts.crediter.key()),
+            InsuranceError::UnauthorizedCrediter
+        );
+
+        let amount = bet_math::bps_of(fee, config.contribution_bps)
+            .ok_or(InsuranceError::MathOverflow)?;
+        if amount == 0 {
+            return Ok(0);
+        }
+
+        config.total_contributed = config.total_contributed
+            .checked_add(amount)
+            .ok_or(InsuranceError::MathOverflow)?;
+
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.fee_source.to_account_info(),
+                to: ctx.accounts.insurance_vault.to_account_info(),
+            },
+        );
+        transfer(cpi_context, amount)?;
+
+        emit_cpi!(InsuranceContributed {
+            crediter: ctx.accounts.crediter.key(),
+            amount,
+        });
+        Ok(amount)
+    }
+
+    /// Top up a game escrow that is short of a claim it must pay
+    /// CPI ONLY - crediter must be an allowlisted program PDA; the caller sizes the shortfall
+    pub fn cover_shortfall(ctx: Context<CoverShortfall>, shortfall: u64) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        // SECURITY: Only allowlisted game programs can draw on the fund
+        require!(
+            config.crediters.contains(&ctx.accounts.crediter.key()),
+            InsuranceError::UnauthorizedCrediter
+        );
+        require!(shortfall > 0, InsuranceError::ZeroShortfall);
+
+        // SECURITY: Never dip into the vault's rent-exempt minimum
+        let spendable = ctx.accounts.insurance_vault.lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(0));
+        require!(spendable >= shortfall, InsuranceError::InsufficientFund);
+
+        config.total_paid_out = config.total_paid_out
+            .checked_add(shortfall)
+            .ok_or(InsuranceError::MathOverflow)?;
+
+        let seeds: &[&[u8]] = &[b"insurance_vault", &[ctx.bumps.insurance_vault]];
+        let signer_seeds = &[seeds];
+
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.insurance_vault.to_account_info(),
+                to: ctx.accounts.escrow.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(cpi_context, shortfall)?;
+
+        emit_cpi!(ShortfallCovered {
+            crediter: ctx.accounts.crediter.key(),
+            escrow: ctx.accounts.escrow.key(),
+            amount: shortfall,
+        });
+        Ok(())
+    }
+
+    /// Refill the fund from the authority wallet
+    /// AUTHORITY ONLY
+    pub fn replenish(ctx: Context<Replenish>, amount: u64) -> Result<()> {
+        require!(amount > 0, InsuranceError::ZeroShortfall);
+
+        let config = &mut ctx.accounts.config;
+        config.total_replenished = config.total_replenished
+            .checked_add(amount)
+            .ok_or(InsuranceError::MathOverflow)?;
+
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.authority.to_account_info(),
+                to: ctx.accounts.insurance_vault.to_account_info(),
+            },
+        );
+        transfer(cpi_context, amount)?;
+
+        emit_cpi!(InsuranceReplenished {
+            authority: ctx.accounts.authority.key(),
+            amount,
+        });
+        Ok(())
+    }
+}
+
+// ===================
+// Account Structs
+// ===================
+
+#[derive(Accounts)]
+pub struct InitializeInsurance<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + InsuranceConfig::INIT_SPACE,
+        seeds = [b"insurance_config"],
+        bump
+    )]
+    pub config: Account<'info, InsuranceConfig>,
+
+    /// Insurance vault PDA holding the fund - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetInsuranceConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"insurance_config"],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, InsuranceConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Contribute<'info> {
+    #[account(
+        mut,
+        seeds = [b"insurance_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, InsuranceConfig>,
+
+    /// Insurance vault PDA holding the fund - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: SystemAccount<'info>,
+
+    /// Calling program's fee vault, signed via invoke_signed
+    #[account(mut)]
+    pub fee_source: Signer<'info>,
+
+    /// Calling program's `insurance_crediter` PDA, signed via invoke_signed
+    pub crediter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CoverShortfall<'info> {
+    #[account(
+        mut,
+        seeds = [b"insurance_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, InsuranceConfig>,
+
+    /// Insurance vault PDA holding the fund - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: SystemAccount<'info>,
+
+    /// CHECK: Calling program's escrow receiving the top-up (chosen by the allowlisted caller)
+    #[account(mut)]
+    pub escrow: UncheckedAccount<'info>,
+
+    /// Calling program's `insurance_crediter` PDA, signed via invoke_signed
+    pub crediter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Replenish<'info> {
+    #[account(
+        mut,
+        seeds = [b"insurance_config"],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, InsuranceConfig>,
+
+    /// Insurance vault PDA holding the fund - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// ===================
+// State
+// ===================
+
+#[account]
+#[derive(InitSpace)]
+pub struct InsuranceConfig {
+    pub authority: Pubkey,
+    /// Program PDAs allowed to call contribute and cover_shortfall
+    #[max_len(MAX_CREDITERS)]
+    pub crediters: Vec<Pubkey>,
+    /// Share of each platform fee routed into the fund
+    pub contribution_bps: u64,
+    /// Lifetime lamports received from fees
+    pub total_contributed: u64,
+    /// Lifetime lamports received from the authority
+    pub total_replenished: u64,
+    /// Lifetime lamports paid to short escrows
+    pub total_paid_out: u64,
+    pub bump: u8,
+}
+
+// ===================
+// Events
+// ===================
+
+/// Emitted when a game program routes a fee share into the fund
+#[event]
+pub struct InsuranceContributed {
+    pub crediter: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when the fund tops up a short escrow
+#[event]
+pub struct ShortfallCovered {
+    pub crediter: Pubkey,
+    pub escrow: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when the authority refills the fund
+#[event]
+pub struct InsuranceReplenished {
+    pub authority: Pubkey,
+    pub amount: u64,
+}
+
+// ===================
+// Errors
+// ===================
+
+#[error_code]
+pub enum InsuranceError {
+    #[msg("Bad contribution")]
+    InvalidContribution,
+    #[msg("Too many crediters")]
+    TooManyCrediters,
+    #[msg("Unauthorized crediter")]
+    UnauthorizedCrediter,
+    #[msg("Zero amount")]
+    ZeroShortfall,
+    #[msg("Insufficient fund")]
+    InsufficientFund,
+    #[msg("Overflow")]
+    MathOverflow,
+}
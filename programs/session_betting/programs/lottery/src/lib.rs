@@ -0,0 +1,643 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use switchboard_on_demand::RandomnessAccountData;
+
+declare_id!("Dr6Jm8AMdBWqoVhkrvRAP7mSvmZ5k1d7hVot1W2aVzEA");
+
+// ===================
+// Constants
+// ===================
+
+/// Maximum number of program PDAs allowed to credit tickets
+pub const MAX_CREDITERS: usize = 4;
+
+/// Minimum lamports wagered per ticket: 0.01 SOL
+pub const MIN_LAMPORTS_PER_TICKET: u64 = 10_000_000;
+
+// ===================
+// Program
+// ===================
+
+#[program]
+pub mod lottery {
+    use super::*;
+
+    /// Initialize the lottery config and open draw 0
+    /// Seeds the prize vault with its rent-exempt minimum so payouts never strand it
+    pub fn initialize_lottery(ctx: Context<InitializeLottery>, lamports_per_ticket: u64) -> Result<()> {
+        require!(
+            lamports_per_ticket >= MIN_LAMPORTS_PER_TICKET,
+            LotteryError::InvalidTicketPrice
+        );
+
+        let rent = Rent::get()?.minimum_balance(0);
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.authority.to_account_info(),
+                to: ctx.accounts.prize_vault.to_account_info(),
+            },
+        );
+        transfer(cpi_context, rent)?;
+
+        let config = &mut ctx.accounts.config;
+        config.authority = ctx.accounts.authority.key();
+        config.lamports_per_ticket = lamports_per_ticket;
+        config.crediters = Vec::new();
+        config.current_draw = 0;
+        config.bump = ctx.bumps.config;
+
+        init_draw(&mut ctx.accounts.draw, 0, ctx.bumps.draw);
+        Ok(())
+    }
+
+    /// Replace the set of PDAs allowed to credit tickets
+    /// AUTHORITY ONLY - each game program signs credit_tickets with its `lottery_crediter` PDA
+    pub fn set_crediters(ctx: Context<SetCrediters>, crediters: Vec<Pubkey>) -> Result<()> {
+        require!(crediters.len() <= MAX_CREDITERS, LotteryError::TooManyCrediters);
+
+        ctx.accounts.config.crediters = crediters;
+        Ok(())
+    }
+
+    /// Credit tickets for lamports a player wagered in a game program
+    /// CPI ONLY - crediter must be an allowlisted program PDA
+    pub fn credit_tickets(ctx: Context<CreditTickets>, player: Pubkey, wagered: u64) -> Result<()> {
+        let config = &ctx.accounts.config;
+
+        // SECURITY: Only allowlisted game programs can mint tickets
+        require!(
+            config.crediters.contains(&ctx.accounts.crediter.key()),
+            LotteryError::UnauthorizedCrediter
+        );
+
+        let player_tickets = &mut ctx.accounts.player_tickets;
+        player_tickets.player = player;
+        player_tickets.bump = ctx.bumps.player_tickets;
+
+        // Carry sub-ticket remainders so small bets still add up
+        let total = player_tickets.pending_lamports
+            .checked_add(wagered)
+            .ok_or(LotteryError::MathOverflow)?;
+        let earned = total / config.lamports_per_ticket;
+        player_tickets.pending_lamports = total % config.lamports_per_ticket;
+        player_tickets.tickets = player_tickets.tickets
+            .checked_add(earned)
+            .ok_or(LotteryError::MathOverflow)?;
+
+        if earned > 0 {
+            emit_cpi!(TicketsCredited {
+                player,
+                tickets: earned,
+                crediter: ctx.accounts.crediter.key(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Add lamports to an open draw's prize
+    /// Anyone can fund (fee slices are routed here by the treasury)
+    pub fn fund_draw(ctx: Context<FundDraw>, amount: u64) -> Result<()> {
+        let draw = &mut ctx.accounts.draw;
+
+        require!(draw.status == DrawStatus::Open, LotteryError::DrawNotOpen);
+        require!(amount > 0, LotteryError::AmountTooSmall);
+
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.funder.to_account_info(),
+                to: ctx.accounts.prize_vault.to_account_info(),
+            },
+        );
+        transfer(cpi_context, amount)?;
+
+        draw.prize = draw.prize
+            .checked_add(amount)
+            .ok_or(LotteryError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Enter the current draw with all earned tickets
+    /// REQUIRES wallet signature - tickets become a contiguous range in the draw
+    pub fn enter_draw(ctx: Context<EnterDraw>) -> Result<()> {
+        let draw = &mut ctx.accounts.draw;
+        let player_tickets = &mut ctx.accounts.player_tickets;
+
+        require!(draw.status == DrawStatus::Open, LotteryError::DrawNotOpen);
+        require!(player_tickets.tickets > 0, LotteryError::NoTickets);
+
+        let count = player_tickets.tickets;
+        player_tickets.tickets = 0;
+
+        let entry = &mut ctx.accounts.entry;
+        entry.draw_id = draw.draw_id;
+        entry.player = player_tickets.player;
+        entry.start = draw.total_tickets;
+        entry.count = count;
+        entry.bump = ctx.bumps.entry;
+
+        draw.total_tickets = draw.total_tickets
+            .checked_add(count)
+            .ok_or(LotteryError::MathOverflow)?;
+
+        emit_cpi!(DrawEntered {
+            draw_id: draw.draw_id,
+            player: entry.player,
+            start: entry.start,
+            count,
+        });
+        Ok(())
+    }
+
+    /// Close entries and commit to a Switchboard randomness account
+    /// AUTHORITY ONLY - randomness must have been committed in the previous slot
+    pub fn commit_draw(ctx: Context<CommitDraw>) -> Result<()> {
+        let draw = &mut ctx.accounts.draw;
+        let clock = Clock::get()?;
+
+        require!(draw.status == DrawStatus::Open, LotteryError::DrawNotOpen);
+        require!(draw.total_tickets > 0, LotteryError::NoTickets);
+
+        let randomness = RandomnessAccountData::parse(ctx.accounts.randomness_account.data.borrow())
+            .map_err(|_| LotteryError::InvalidRandomnessAccount)?;
+
+        // SECURITY: Randomness must be freshly committed and not yet revealed
+        require!(
+            randomness.seed_slot == clock.slot.saturating_sub(1),
+            LotteryError::RandomnessExpired
+        );
+
+        draw.randomness_account = ctx.accounts.randomness_account.key();
+        draw.commit_slot = randomness.seed_slot;
+        draw.status = DrawStatus::Committed;
+        Ok(())
+    }
+
+    /// Reveal the committed randomness, pick the winning ticket and open the next draw
+    /// Permissionless - anyone can call once the randomness is revealed
+    pub fn settle_draw(ctx: Context<SettleDraw>) -> Result<()> {
+        let draw = &mut ctx.accounts.draw;
+        let clock = Clock::get()?;
+
+        require!(draw.status == DrawStatus::Committed, LotteryError::DrawNotCommitted);
+
+        let randomness = RandomnessAccountData::parse(ctx.accounts.randomness_account.data.borrow())
+            .map_err(|_| LotteryError::InvalidRandomnessAccount)?;
+
+        // SECURITY: Must be the same commitment made at commit_draw
+        require!(
+            randomness.seed_slot == draw.commit_slot,
+            LotteryError::RandomnessExpired
+        );
+
+        let value = randomness.get_value(&clock)
+            .map_err(|_| LotteryError::RandomnessNotResolved)?;
+
+        let mut seed = [0u8; 8];
+        seed.copy_from_slice(&value[..8]);
+        draw.winning_ticket = u64::from_le_bytes(seed) % draw.total_tickets;
+        draw.status = DrawStatus::Drawn;
+
+        let config = &mut ctx.accounts.config;
+        config.current_draw = config.current_draw
+            .checked_add(1)
+            .ok_or(LotteryError::MathOverflow)?;
+        init_draw(&mut ctx.accounts.next_draw, config.current_draw, ctx.bumps.next_draw);
+
+        emit_cpi!(DrawSettled {
+            draw_id: draw.draw_id,
+            winning_ticket: draw.winning_ticket,
+            total_tickets: draw.total_tickets,
+            prize: draw.prize,
+        });
+        Ok(())
+    }
+
+    /// Pay a drawn prize to the entry holding the winning ticket
+    /// Permissionless - funds can only go to the winning entry's player
+    pub fn claim_prize(ctx: Context<ClaimPrize>) -> Result<()> {
+        let draw = &mut ctx.accounts.draw;
+        let entry = &ctx.accounts.entry;
+
+        require!(draw.status == DrawStatus::Drawn, LotteryError::DrawNotDrawn);
+        require!(!draw.claimed, LotteryError::AlreadyClaimed);
+
+        let end = entry.start
+            .checked_add(entry.count)
+            .ok_or(LotteryError::MathOverflow)?;
+        require!(
+            draw.winning_ticket >= entry.start && draw.winning_ticket < end,
+            LotteryError::NotWinningEntry
+        );
+
+        // SECURITY: Mark claimed BEFORE transfer
+        draw.claimed = true;
+
+        let seeds: &[&[u8]] = &[b"prize_vault", &[ctx.bumps.prize_vault]];
+        let signer_seeds = &[seeds];
+
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.prize_vault.to_account_info(),
+                to: ctx.accounts.player.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(cpi_context, draw.prize)?;
+
+        emit_cpi!(PrizeClaimed {
+            draw_id: draw.draw_id,
+            player: entry.player,
+            amount: draw.prize,
+        });
+        Ok(())
+    }
+}
+
+// ===================
+// Helper Functions
+// ===================
+
+fn init_draw(draw: &mut Draw, draw_id: u64, bump: u8) {
+    draw.draw_id = draw_id;
+    draw.prize = 0;
+    draw.total_tickets = 0;
+    draw.randomness_account = Pubkey::default();
+    draw.commit_slot = 0;
+    draw.winning_ticket = 0;
+    draw.status = DrawStatus::Open;
+    draw.claimed = false;
+    draw.bump = bump;
+}
+
+/// Randomness accounts must be owned by the Switchboard on-demand program
+fn switchboard_program_id() -> Pubkey {
+    <RandomnessAccountData as switchboard_on_demand::Owner>::owner()
+}
+
+// ===================
+// Account Structs
+// ===================
+
+#[derive(Accounts)]
+pub struct InitializeLottery<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + LotteryConfig::INIT_SPACE,
+        seeds = [b"lottery_config"],
+        bump
+    )]
+    pub config: Account<'info, LotteryConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Draw::INIT_SPACE,
+        seeds = [b"draw", 0u64.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub draw: Account<'info, Draw>,
+
+    /// Prize vault PDA shared by all draws - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"prize_vault"],
+        bump
+    )]
+    pub prize_vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetCrediters<'info> {
+    #[account(
+        mut,
+        seeds = [b"lottery_config"],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, LotteryConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(player: Pubkey)]
+pub struct CreditTickets<'info> {
+    #[account(
+        seeds = [b"lottery_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, LotteryConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerTickets::INIT_SPACE,
+        seeds = [b"player_tickets", player.as_ref()],
+        bump
+    )]
+    pub player_tickets: Account<'info, PlayerTickets>,
+
+    /// Calling program's `lottery_crediter` PDA, signed via invoke_signed
+    pub crediter: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundDraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"draw", draw.draw_id.to_le_bytes().as_ref()],
+        bump = draw.bump
+    )]
+    pub draw: Account<'info, Draw>,
+
+    /// Prize vault PDA shared by all draws - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"prize_vault"],
+        bump
+    )]
+    pub prize_vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct EnterDraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"draw", draw.draw_id.to_le_bytes().as_ref()],
+        bump = draw.bump
+    )]
+    pub draw: Account<'info, Draw>,
+
+    #[account(
+        mut,
+        seeds = [b"player_tickets", player.key().as_ref()],
+        bump = player_tickets.bump
+    )]
+    pub player_tickets: Account<'info, PlayerTickets>,
+
+    #[account(
+        init,
+        payer = player,
+        space = 8 + DrawEntry::INIT_SPACE,
+        seeds = [b"draw_entry", draw.draw_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub entry: Account<'info, DrawEntry>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CommitDraw<'info> {
+    #[account(
+        seeds = [b"lottery_config"],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, LotteryConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"draw", config.current_draw.to_le_bytes().as_ref()],
+        bump = draw.bump
+    )]
+    pub draw: Account<'info, Draw>,
+
+    /// CHECK: Parsed as Switchboard RandomnessAccountData, owner checked here
+    #[account(owner = switchboard_program_id() @ LotteryError::InvalidRandomnessAccount)]
+    pub randomness_account: AccountInfo<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SettleDraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"lottery_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, LotteryConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"draw", config.current_draw.to_le_bytes().as_ref()],
+        bump = draw.bump
+    )]
+    pub draw: Account<'info, Draw>,
+
+    #[account(
+        init,
+        payer = caller,
+        space = 8 + Draw::INIT_SPACE,
+        seeds = [b"draw", (config.current_draw + 1).to_le_bytes().as_ref()],
+        bump
+    )]
+    pub next_draw: Account<'info, Draw>,
+
+    /// CHECK: Must be the randomness account committed in commit_draw
+    #[account(address = draw.randomness_account @ LotteryError::InvalidRandomnessAccount)]
+    pub randomness_account: AccountInfo<'info>,
+
+    /// Anyone can settle a draw (permissionless) - pays rent for the next draw
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimPrize<'info> {
+    #[account(
+        mut,
+        seeds = [b"draw", draw.draw_id.to_le_bytes().as_ref()],
+        bump = draw.bump
+    )]
+    pub draw: Account<'info, Draw>,
+
+    #[account(
+        seeds = [b"draw_entry", draw.draw_id.to_le_bytes().as_ref(), entry.player.as_ref()],
+        bump = entry.bump,
+        has_one = player
+    )]
+    pub entry: Account<'info, DrawEntry>,
+
+    /// Prize vault PDA shared by all draws - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"prize_vault"],
+        bump
+    )]
+    pub prize_vault: SystemAccount<'info>,
+
+    /// Winning entry's player wallet - receives the prize
+    #[account(mut)]
+    pub player: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// ===================
+// State
+// ===================
+
+#[account]
+#[derive(InitSpace)]
+pub struct LotteryConfig {
+    pub authority: Pubkey,
+    /// Lamports wagered per ticket earned
+    pub lamports_per_ticket: u64,
+    /// Program PDAs allowed to call credit_tickets
+    #[max_len(MAX_CREDITERS)]
+    pub crediters: Vec<Pubkey>,
+    pub current_draw: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct PlayerTickets {
+    pub player: Pubkey,
+    /// Earned tickets not yet entered into a draw
+    pub tickets: u64,
+    /// Wagered lamports short of a full ticket
+    pub pending_lamports: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Draw {
+    pub draw_id: u64,
+    pub prize: u64,
+    pub total_tickets: u64,
+    /// Switchboard randomness account committed for this draw
+    pub randomness_account: Pubkey,
+    pub commit_slot: u64,
+    pub winning_ticket: u64,
+    pub status: DrawStatus,
+    pub claimed: bool,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct DrawEntry {
+    pub draw_id: u64,
+    pub player: Pubkey,
+    /// First ticket number held by this entry
+    pub start: u64,
+    pub count: u64,
+    pub bump: u8,
+}
+
+// ===================
+// Enums
+// ===================
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum DrawStatus {
+    Open,
+    Committed,
+    Drawn,
+}
+
+// ===================
+// Events
+// ===================
+
+/// Emitted when a game program credits tickets to a player
+#[event]
+pub struct TicketsCredited {
+    pub player: Pubkey,
+    pub tickets: u64,
+    pub crediter: Pubkey,
+}
+
+/// Emitted when a player enters a draw
+#[event]
+pub struct DrawEntered {
+    pub draw_id: u64,
+    pub player: Pubkey,
+    pub start: u64,
+    pub count: u64,
+}
+
+/// Emitted when a draw's winning ticket is revealed
+#[event]
+pub struct DrawSettled {
+    pub draw_id: u64,
+    pub winning_ticket: u64,
+    pub total_tickets: u64,
+    pub prize: u64,
+}
+
+/// Emitted when a draw's prize is paid
+#[event]
+pub struct PrizeClaimed {
+    pub draw_id: u64,
+    pub player: Pubkey,
+    pub amount: u64,
+}
+
+// ===================
+// Errors
+// ===================
+
+#[error_code]
+pub enum LotteryError {
+    #[msg("Bad ticket price")]
+    InvalidTicketPrice,
+    #[msg("Too many crediters")]
+    TooManyCrediters,
+    #[msg("Unauthorized crediter")]
+    UnauthorizedCrediter,
+    #[msg("Amount too small")]
+    AmountTooSmall,
+    #[msg("No tickets")]
+    NoTickets,
+    #[msg("Draw not open")]
+    DrawNotOpen,
+    #[msg("Draw not committed")]
+    DrawNotCommitted,
+    #[msg("Draw not drawn")]
+    DrawNotDrawn,
+    #[msg("Bad randomness account")]
+    InvalidRandomnessAccount,
+    #[msg("Randomness expired")]
+    RandomnessExpired,
+    #[msg("Randomness not resolved")]
+    RandomnessNotResolved,
+    #[msg("Not winning entry")]
+    NotWinningEntry,
+    #[msg("Already claimed")]
+    AlreadyClaimed,
+    #[msg("Overflow")]
+    MathOverflow,
+}
@@ -0,0 +1,383 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+declare_id!("F7bR87f8oQcTTK7njWQBvcAkQpB2iw1hySreeVbNAgtY");
+
+// ===================
+// Constants
+// ===================
+
+/// Maximum number of program PDAs allowed to accrue referral fees
+pub const MAX_CREDITERS: usize = 4;
+
+/// Referrer share cap: 20% (2000 basis points) of the platform fee
+pub const MAX_REFERRAL_FEE_BPS: u64 = 2_000;
+
+// ===================
+// Program
+// ===================
+
+#[program]
+pub mod referral {
+    use super::*;
+
+    /// Initialize the referral registry
+    /// Seeds the referral vault with its rent-exempt minimum so claims never strand it
+    pub fn initialize_referrals(ctx: Context<InitializeReferrals>, referral_fee_bps: u64) -> Result<()> {
+        require!(referral_fee_bps <= MAX_REFERRAL_FEE_BPS, ReferralError::InvalidReferralFee);
+
+        let rent = Rent::get()?.minimum_balance(0);
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.authority.to_account_info(),
+                to: ctx.accounts.referral_vault.to_account_info(),
+            },
+        );
+        transfer(cpi_context, rent)?;
+
+        let config = &mut ctx.accounts.config;
+        config.authority = ctx.accounts.authority.key();
+        config.crediters = Vec::new();
+        config.referral_fee_bps = referral_fee_bps;
+        config.bump = ctx.bumps.config;
+        Ok(())
+    }
+
+    /// Replace the set of PDAs allowed to accrue referral fees
+    /// AUTHORITY ONLY - each game program signs accrue_referral_fee with its `referral_crediter` PDA
+    pub fn set_crediters(ctx: Context<SetReferralConfig>, crediters: Vec<Pubkey>) -> Result<()> {
+        require!(crediters.len() <= MAX_CREDITERS, ReferralError::TooManyCrediters);
+
+        ctx.accounts.config.crediters = crediters;
+        Ok(())
+    }
+
+    /// Set the share of each platform fee routed to the bettor's referrer
+    /// AUTHORITY ONLY
+    pub fn set_referral_fee_bps(ctx: Context<SetReferralConfig>, referral_fee_bps: u64) -> Result<()> {
+        require!(referral_fee_bps <= MAX_REFERRAL_FEE_BPS, ReferralError::InvalidReferralFee);
+
+        ctx.accounts.config.referral_fee_bps = referral_fee_bps;
+        Ok(())
+    }
+
+    /// Link the signing wallet to a referrer for every product
+    /// REQUIRES wallet signature - can only be set once per wallet
+    pub fn register_referrer(ctx: Context<RegisterReferrer>, referrer: Pubkey) -> Result<()> {
+        let user = ctx.accounts.user.key();
+
+        // SECURITY: No self-referral
+        require!(referrer != user, ReferralError::SelfReferral);
+
+        let referral = &mut ctx.accounts.referral;
+        referral.user = user;
+        referral.referrer = referrer;
+        referral.registered_at = Clock::get()?.unix_timestamp;
+        referral.bump = ctx.bumps.referral;
+
+        let earnings = &mut ctx.accounts.referrer_earnings;
+        earnings.referrer = referrer;
+        earnings.referral_count = earnings.referral_count
+            .checked_add(1)
+            .ok_or(ReferralError::MathOverflow)?;
+        earnings.bump = ctx.bumps.referrer_earnings;
+
+        emit_cpi!(ReferrerRegistered { user, referrer });
+        Ok(())
+    }
+
+    /// Move the referrer's share of a platform fee into the referral vault
+    /// CPI ONLY - crediter must be an allowlisted program PDA and fee_source its signed fee vault
+    /// Returns the lamports taken so the caller can reduce its own fee accounting
+    pub fn accrue_referral_fee(ctx: Context<AccrueReferralFee>, user: Pubkey, fee: u64) -> Result<u64> {
+        let config = &ctx.accounts.config;
+
+        // SECURITY: Only allowlisted game programs can route fees
+        require!(
+            config.crediters.contains(&ctx.accounts.crediter.key()),
+            ReferralError::UnauthorizedCrediter
+        );
+
+        let share = bet_math::bps_of(fee, config.referral_fee_bps)
+            .ok_or(ReferralError::MathOverflow)?;
+        if share == 0 {
+            return Ok(0);
+        }
+
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.fee_source.to_account_info(),
+                to: ctx.accounts.referral_vault.to_account_info(),
+            },
+        );
+        transfer(cpi_context, share)?;
+
+        let earnings = &mut ctx.accounts.referrer_earnings;
+        earnings.accrued = earnings.accrued
+            .checked_add(share)
+            .ok_or(ReferralError::MathOverflow)?;
+
+        emit_cpi!(ReferralFeeAccrued {
+            user,
+            referrer: earnings.referrer,
+            amount: share,
+            crediter: ctx.accounts.crediter.key(),
+        });
+        Ok(share)
+    }
+
+    /// Withdraw all unclaimed referral earnings to the referrer wallet
+    /// REQUIRES wallet signature
+    pub fn claim_referral_earnings(ctx: Context<ClaimReferralEarnings>) -> Result<()> {
+        let earnings = &mut ctx.accounts.referrer_earnings;
+
+        let amount = earnings.accrued
+            .checked_sub(earnings.claimed)
+            .ok_or(ReferralError::MathOverflow)?;
+        require!(amount > 0, ReferralError::NothingToClaim);
+
+        // Update claimed BEFORE transfer (reentrancy protection)
+        earnings.claimed = earnings.accrued;
+
+        let seeds: &[&[u8]] = &[b"referral_vault", &[ctx.bumps.referral_vault]];
+        let signer_seeds = &[seeds];
+
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.referral_vault.to_account_info(),
+                to: ctx.accounts.referrer.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(cpi_context, amount)?;
+
+        emit_cpi!(ReferralEarningsClaimed {
+            referrer: earnings.referrer,
+            amount,
+        });
+        Ok(())
+    }
+}
+
+// ===================
+// Account Structs
+// ===================
+
+#[derive(Accounts)]
+pub struct InitializeReferrals<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ReferralConfig::INIT_SPACE,
+        seeds = [b"referral_config"],
+        bump
+    )]
+    pub config: Account<'info, ReferralConfig>,
+
+    /// Referral vault PDA holding unclaimed earnings - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"referral_vault"],
+        bump
+    )]
+    pub referral_vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetReferralConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"referral_config"],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, ReferralConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(referrer: Pubkey)]
+pub struct RegisterReferrer<'info> {
+    /// SECURITY: init fails if the wallet already has a referrer
+    #[account(
+        init,
+        payer = user,
+        space = 8 + Referral::INIT_SPACE,
+        seeds = [b"referral", user.key().as_ref()],
+        bump
+    )]
+    pub referral: Account<'info, Referral>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + ReferrerEarnings::INIT_SPACE,
+        seeds = [b"referrer_earnings", referrer.as_ref()],
+        bump
+    )]
+    pub referrer_earnings: Account<'info, ReferrerEarnings>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct AccrueReferralFee<'info> {
+    #[account(
+        seeds = [b"referral_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReferralConfig>,
+
+    /// SECURITY: Seeded by the user argument so callers can't route fees to another wallet's referrer
+    #[account(
+        seeds = [b"referral", user.as_ref()],
+        bump = referral.bump
+    )]
+    pub referral: Account<'info, Referral>,
+
+    #[account(
+        mut,
+        seeds = [b"referrer_earnings", referral.referrer.as_ref()],
+        bump = referrer_earnings.bump
+    )]
+    pub referrer_earnings: Account<'info, ReferrerEarnings>,
+
+    /// Referral vault PDA holding unclaimed earnings - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"referral_vault"],
+        bump
+    )]
+    pub referral_vault: SystemAccount<'info>,
+
+    /// Calling program's fee vault, signed via invoke_signed
+    #[account(mut)]
+    pub fee_source: Signer<'info>,
+
+    /// Calling program's `referral_crediter` PDA, signed via invoke_signed
+    pub crediter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimReferralEarnings<'info> {
+    #[account(
+        mut,
+        seeds = [b"referrer_earnings", referrer.key().as_ref()],
+        bump = referrer_earnings.bump
+    )]
+    pub referrer_earnings: Account<'info, ReferrerEarnings>,
+
+    /// Referral vault PDA holding unclaimed earnings - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"referral_vault"],
+        bump
+    )]
+    pub referral_vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// ===================
+// State
+// ===================
+
+#[account]
+#[derive(InitSpace)]
+pub struct ReferralConfig {
+    pub authority: Pubkey,
+    /// Program PDAs allowed to call accrue_referral_fee
+    #[max_len(MAX_CREDITERS)]
+    pub crediters: Vec<Pubkey>,
+    /// Share of each platform fee paid to the referrer
+    pub referral_fee_bps: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Referral {
+    pub user: Pubkey,
+    pub referrer: Pubkey,
+    pub registered_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ReferrerEarnings {
+    pub referrer: Pubkey,
+    pub referral_count: u64,
+    /// Lifetime lamports accrued across all products
+    pub accrued: u64,
+    pub claimed: u64,
+    pub bump: u8,
+}
+
+// ===================
+// Events
+// ===================
+
+/// Emitted when a wallet links to a referrer
+#[event]
+pub struct ReferrerRegistered {
+    pub user: Pubkey,
+    pub referrer: Pubkey,
+}
+
+/// Emitted when a game program routes a fee share to a referrer
+#[event]
+pub struct ReferralFeeAccrued {
+    pub user: Pubkey,
+    pub referrer: Pubkey,
+    pub amount: u64,
+    pub crediter: Pubkey,
+}
+
+/// Emitted when a referrer withdraws earnings
+#[event]
+pub struct ReferralEarningsClaimed {
+    pub referrer: Pubkey,
+    pub amount: u64,
+}
+
+// ===================
+// Errors
+// ===================
+
+#[error_code]
+pub enum ReferralError {
+    #[msg("Bad referral fee")]
+    InvalidReferralFee,
+    #[msg("Too many crediters")]
+    TooManyCrediters,
+    #[msg("Unauthorized crediter")]
+    UnauthorizedCrediter,
+    #[msg("Cannot refer yourself")]
+    SelfReferral,
+    #[msg("Nothing to claim")]
+    NothingToClaim,
+    #[msg("Overflow")]
+    MathOverflow,
+}
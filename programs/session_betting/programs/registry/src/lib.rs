@@ -0,0 +1,247 @@
+use anchor_lang::prelude::*;
+
+declare_id!("3jsJZEKw1q4z698EmWLL3xn2Tbd4GKmmXBS8LJZQwzVF");
+
+// ===================
+// Program
+// ===================
+
+#[program]
+pub mod registry {
+    use super::*;
+
+    /// Initialize the program registry
+    pub fn initialize_registry(ctx: Context<InitializeRegistry>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.authority = ctx.accounts.authority.key();
+        config.bump = ctx.bumps.config;
+        Ok(())
+    }
+
+    /// Start tracking a deployed game program
+    /// AUTHORITY ONLY
+    pub fn register_program(
+        ctx: Context<RegisterProgram>,
+        program_id: Pubkey,
+        version: ProgramVersion,
+    ) -> Result<()> {
+        let entry = &mut ctx.accounts.program_entry;
+        entry.program_id = program_id;
+        entry.version = version;
+        entry.migration = MigrationStatus::Stable;
+        entry.deprecated_entrypoints = 0;
+        entry.updated_at = Clock::get()?.unix_timestamp;
+        entry.bump = ctx.bumps.program_entry;
+
+        emit_cpi!(ProgramVersionUpdated {
+            program_id,
+            version,
+            migration: entry.migration,
+        });
+        Ok(())
+    }
+
+    /// Record a new deployment and where its migration stands
+    /// AUTHORITY ONLY - versions can only move forward
+    pub fn set_version(
+        ctx: Context<UpdateProgramEntry>,
+        version: ProgramVersion,
+        migration: MigrationStatus,
+    ) -> Result<()> {
+        let entry = &mut ctx.accounts.program_entry;
+
+        // SECURITY: Clients pick instruction sets by version, so never roll it back
+        require!(version >= entry.version, RegistryError::VersionRollback);
+
+        entry.version = version;
+        entry.migration = migration;
+        entry.updated_at = Clock::get()?.unix_timestamp;
+
+        emit_cpi!(ProgramVersionUpdated {
+            program_id: entry.program_id,
+            version,
+            migration,
+        });
+        Ok(())
+    }
+
+    /// Replace the bitmask of entrypoints the program must refuse
+    /// AUTHORITY ONLY - bit meanings are defined by each game program
+    pub fn set_deprecated_entrypoints(ctx: Context<UpdateProgramEntry>, deprecated_entrypoints: u64) -> Result<()> {
+        let entry = &mut ctx.accounts.program_entry;
+        entry.deprecated_entrypoints = deprecated_entrypoints;
+        entry.updated_at = Clock::get()?.unix_timestamp;
+
+        emit_cpi!(EntrypointsDeprecated {
+            program_id: entry.program_id,
+            deprecated_entrypoints,
+        });
+        Ok(())
+    }
+}
+
+// ===================
+// Helper Functions
+// ===================
+
+/// Fail if `entrypoint` is flagged deprecated in the caller's registry entry
+/// Programs pass their `[b"program_entry", program_id]` PDA (seeds::program = registry::ID);
+/// an entry that has not been registered yet deprecates nothing
+pub fn require_not_deprecated(program_entry: &AccountInfo, entrypoint: u64) -> Result<()> {
+    if program_entry.data_is_empty() {
+        return Ok(());
+    }
+    require_keys_eq!(*program_entry.owner, crate::ID, RegistryError::InvalidProgramEntry);
+
+    let entry = ProgramEntry::try_deserialize(&mut &program_entry.try_borrow_data()?[..])?;
+    require!(
+        entry.deprecated_entrypoints & entrypoint == 0,
+        RegistryError::EntrypointDeprecated
+    );
+    Ok(())
+}
+
+// ===================
+// Account Structs
+// ===================
+
+#[derive(Accounts)]
+pub struct InitializeRegistry<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + RegistryConfig::INIT_SPACE,
+        seeds = [b"registry_config"],
+        bump
+    )]
+    pub config: Account<'info, RegistryConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(program_id: Pubkey)]
+pub struct RegisterProgram<'info> {
+    #[account(
+        seeds = [b"registry_config"],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, RegistryConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ProgramEntry::INIT_SPACE,
+        seeds = [b"program_entry", program_id.as_ref()],
+        bump
+    )]
+    pub program_entry: Account<'info, ProgramEntry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateProgramEntry<'info> {
+    #[account(
+        seeds = [b"registry_config"],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, RegistryConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"program_entry", program_entry.program_id.as_ref()],
+        bump = program_entry.bump
+    )]
+    pub program_entry: Account<'info, ProgramEntry>,
+
+    pub authority: Signer<'info>,
+}
+
+// ===================
+// State
+// ===================
+
+#[account]
+#[derive(InitSpace)]
+pub struct RegistryConfig {
+    pub authority: Pubkey,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ProgramEntry {
+    pub program_id: Pubkey,
+    /// Currently deployed version - clients and the keeper pick instruction sets from this
+    pub version: ProgramVersion,
+    pub migration: MigrationStatus,
+    /// Bitmask of entrypoints the program refuses (bit meanings are program-defined)
+    pub deprecated_entrypoints: u64,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, InitSpace)]
+pub struct ProgramVersion {
+    pub major: u16,
+    pub minor: u16,
+    pub patch: u16,
+}
+
+// ===================
+// Enums
+// ===================
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum MigrationStatus {
+    /// No migration running - all live instructions are safe to use
+    Stable,
+    /// Accounts are being migrated - clients should hold off on new positions
+    Migrating,
+    /// Migration finished - clients should switch to the new instruction set
+    Migrated,
+}
+
+// ===================
+// Events
+// ===================
+
+/// Emitted when a program's version or migration status changes
+#[event]
+pub struct ProgramVersionUpdated {
+    pub program_id: Pubkey,
+    pub version: ProgramVersion,
+    pub migration: MigrationStatus,
+}
+
+/// Emitted when a program's deprecated entrypoints change
+#[event]
+pub struct EntrypointsDeprecated {
+    pub program_id: Pubkey,
+    pub deprecated_entrypoints: u64,
+}
+
+// ===================
+// Errors
+// ===================
+
+#[error_code]
+pub enum RegistryError {
+    #[msg("Version rollback")]
+    VersionRollback,
+    #[msg("Entrypoint deprecated")]
+    EntrypointDeprecated,
+    #[msg("Bad program entry")]
+    InvalidProgramEntry,
+}
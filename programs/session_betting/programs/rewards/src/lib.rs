@@ -0,0 +1,509 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use anchor_lang::system_program::{transfer, Transfer};
+
+declare_id!("2ecwMU6W4Gtuhzk4rRcmhF8GmTv5RFzVWDAzG6TMQQXp");
+
+// ===================
+// Constants
+// ===================
+
+/// Maximum number of program PDAs allowed to credit points
+pub const MAX_CREDITERS: usize = 4;
+
+/// Bounds for epoch length (in seconds)
+pub const MIN_EPOCH_DURATION_SECONDS: i64 = 60 * 60;
+pub const MAX_EPOCH_DURATION_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+// ===================
+// Program
+// ===================
+
+#[program]
+pub mod rewards {
+    use super::*;
+
+    /// Initialize the rewards ledger and open epoch 0
+    /// Seeds the rewards vault with its rent-exempt minimum so claims never strand it
+    pub fn initialize_rewards(
+        ctx: Context<InitializeRewards>,
+        points_per_sol: u64,
+        epoch_duration: i64,
+    ) -> Result<()> {
+        require!(points_per_sol > 0, RewardsError::InvalidPointsRate);
+        require!(
+            (MIN_EPOCH_DURATION_SECONDS..=MAX_EPOCH_DURATION_SECONDS).contains(&epoch_duration),
+            RewardsError::InvalidEpochDuration
+        );
+
+        let rent = Rent::get()?.minimum_balance(0);
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.authority.to_account_info(),
+                to: ctx.accounts.rewards_vault.to_account_info(),
+            },
+        );
+        transfer(cpi_context, rent)?;
+
+        let clock = Clock::get()?;
+        let config = &mut ctx.accounts.config;
+        config.authority = ctx.accounts.authority.key();
+        config.crediters = Vec::new();
+        config.points_per_sol = points_per_sol;
+        config.current_epoch = 0;
+        config.epoch_start = clock.unix_timestamp;
+        config.epoch_duration = epoch_duration;
+        config.bump = ctx.bumps.config;
+
+        init_epoch(&mut ctx.accounts.epoch, 0, ctx.bumps.epoch);
+        Ok(())
+    }
+
+    /// Replace the set of PDAs allowed to credit points
+    /// AUTHORITY ONLY - each game program signs credit_points with its `rewards_crediter` PDA
+    pub fn set_crediters(ctx: Context<SetCrediters>, crediters: Vec<Pubkey>) -> Result<()> {
+        require!(crediters.len() <= MAX_CREDITERS, RewardsError::TooManyCrediters);
+
+        ctx.accounts.config.crediters = crediters;
+        Ok(())
+    }
+
+    /// Accrue points for lamports a user wagered in a game program
+    /// CPI ONLY - crediter must be an allowlisted program PDA
+    pub fn credit_points(ctx: Context<CreditPoints>, user: Pubkey, wagered: u64) -> Result<()> {
+        let config = &ctx.accounts.config;
+
+        // SECURITY: Only allowlisted game programs can mint points
+        require!(
+            config.crediters.contains(&ctx.accounts.crediter.key()),
+            RewardsError::UnauthorizedCrediter
+        );
+
+        let points = bet_math::mul_div(wagered, config.points_per_sol, LAMPORTS_PER_SOL)
+            .ok_or(RewardsError::MathOverflow)?;
+
+        let epoch = &mut ctx.accounts.epoch;
+        epoch.total_points = epoch.total_points
+            .checked_add(points)
+            .ok_or(RewardsError::MathOverflow)?;
+
+        let user_points = &mut ctx.accounts.user_points;
+        user_points.user = user;
+        user_points.total_points = user_points.total_points
+            .checked_add(points)
+            .ok_or(RewardsError::MathOverflow)?;
+        user_points.bump = ctx.bumps.user_points;
+
+        let user_epoch_points = &mut ctx.accounts.user_epoch_points;
+        user_epoch_points.epoch = epoch.epoch;
+        user_epoch_points.user = user;
+        user_epoch_points.points = user_epoch_points.points
+            .checked_add(points)
+            .ok_or(RewardsError::MathOverflow)?;
+        user_epoch_points.bump = ctx.bumps.user_epoch_points;
+
+        emit_cpi!(PointsCredited {
+            user,
+            epoch: epoch.epoch,
+            points,
+            crediter: ctx.accounts.crediter.key(),
+        });
+        Ok(())
+    }
+
+    /// Add lamports to the current epoch's reward pool
+    /// Anyone can fund - rewards are fixed once the epoch is snapshotted
+    pub fn fund_epoch(ctx: Context<FundEpoch>, amount: u64) -> Result<()> {
+        require!(amount > 0, RewardsError::AmountTooSmall);
+
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.funder.to_account_info(),
+                to: ctx.accounts.rewards_vault.to_account_info(),
+            },
+        );
+        transfer(cpi_context, amount)?;
+
+        let epoch = &mut ctx.accounts.epoch;
+        epoch.reward_pool = epoch.reward_pool
+            .checked_add(amount)
+            .ok_or(RewardsError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Snapshot the current epoch and open the next one
+    /// Permissionless - anyone can call once the epoch has elapsed
+    pub fn advance_epoch(ctx: Context<AdvanceEpoch>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let epoch = &mut ctx.accounts.epoch;
+        let clock = Clock::get()?;
+
+        let epoch_end = config.epoch_start
+            .checked_add(config.epoch_duration)
+            .ok_or(RewardsError::MathOverflow)?;
+        require!(clock.unix_timestamp >= epoch_end, RewardsError::EpochNotOver);
+
+        epoch.finalized = true;
+
+        emit_cpi!(EpochSnapshotted {
+            epoch: epoch.epoch,
+            total_points: epoch.total_points,
+            reward_pool: epoch.reward_pool,
+        });
+
+        config.current_epoch = config.current_epoch
+            .checked_add(1)
+            .ok_or(RewardsError::MathOverflow)?;
+        config.epoch_start = clock.unix_timestamp;
+        init_epoch(&mut ctx.accounts.next_epoch, config.current_epoch, ctx.bumps.next_epoch);
+        Ok(())
+    }
+
+    /// Claim a pro-rata share of a snapshotted epoch's reward pool
+    /// Permissionless - funds and rent go to the user; the epoch record is closed
+    pub fn claim_epoch_rewards(ctx: Context<ClaimEpochRewards>) -> Result<()> {
+        let epoch = &mut ctx.accounts.epoch;
+        let user_epoch_points = &ctx.accounts.user_epoch_points;
+
+        require!(epoch.finalized, RewardsError::EpochNotFinalized);
+
+        let amount = bet_math::mul_div(user_epoch_points.points, epoch.reward_pool, epoch.total_points)
+            .ok_or(RewardsError::MathOverflow)?;
+
+        epoch.claimed = epoch.claimed
+            .checked_add(amount)
+            .ok_or(RewardsError::MathOverflow)?;
+
+        if amount > 0 {
+            let seeds: &[&[u8]] = &[b"rewards_vault", &[ctx.bumps.rewards_vault]];
+            let signer_seeds = &[seeds];
+
+            let cpi_context = CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.rewards_vault.to_account_info(),
+                    to: ctx.accounts.user.to_account_info(),
+                },
+                signer_seeds,
+            );
+            transfer(cpi_context, amount)?;
+        }
+
+        emit_cpi!(EpochRewardsClaimed {
+            user: user_epoch_points.user,
+            epoch: epoch.epoch,
+            points: user_epoch_points.points,
+            amount,
+        });
+        Ok(())
+    }
+}
+
+// ===================
+// Helper Functions
+// ===================
+
+fn init_epoch(epoch: &mut Epoch, id: u64, bump: u8) {
+    epoch.epoch = id;
+    epoch.total_points = 0;
+    epoch.reward_pool = 0;
+    epoch.claimed = 0;
+    epoch.finalized = false;
+    epoch.bump = bump;
+}
+
+// ===================
+// Account Structs
+// ===================
+
+#[derive(Accounts)]
+pub struct InitializeRewards<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + RewardsConfig::INIT_SPACE,
+        seeds = [b"rewards_config"],
+        bump
+    )]
+    pub config: Account<'info, RewardsConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Epoch::INIT_SPACE,
+        seeds = [b"epoch", 0u64.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub epoch: Account<'info, Epoch>,
+
+    /// Rewards vault PDA funding epoch claims - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"rewards_vault"],
+        bump
+    )]
+    pub rewards_vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetCrediters<'info> {
+    #[account(
+        mut,
+        seeds = [b"rewards_config"],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, RewardsConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct CreditPoints<'info> {
+    #[account(
+        seeds = [b"rewards_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, RewardsConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"epoch", config.current_epoch.to_le_bytes().as_ref()],
+        bump = epoch.bump
+    )]
+    pub epoch: Account<'info, Epoch>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + UserPoints::INIT_SPACE,
+        seeds = [b"user_points", user.as_ref()],
+        bump
+    )]
+    pub user_points: Account<'info, UserPoints>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + UserEpochPoints::INIT_SPACE,
+        seeds = [b"user_epoch", config.current_epoch.to_le_bytes().as_ref(), user.as_ref()],
+        bump
+    )]
+    pub user_epoch_points: Account<'info, UserEpochPoints>,
+
+    /// Calling program's `rewards_crediter` PDA, signed via invoke_signed
+    pub crediter: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundEpoch<'info> {
+    #[account(
+        seeds = [b"rewards_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, RewardsConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"epoch", config.current_epoch.to_le_bytes().as_ref()],
+        bump = epoch.bump
+    )]
+    pub epoch: Account<'info, Epoch>,
+
+    /// Rewards vault PDA funding epoch claims - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"rewards_vault"],
+        bump
+    )]
+    pub rewards_vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AdvanceEpoch<'info> {
+    #[account(
+        mut,
+        seeds = [b"rewards_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, RewardsConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"epoch", config.current_epoch.to_le_bytes().as_ref()],
+        bump = epoch.bump
+    )]
+    pub epoch: Account<'info, Epoch>,
+
+    #[account(
+        init,
+        payer = caller,
+        space = 8 + Epoch::INIT_SPACE,
+        seeds = [b"epoch", (config.current_epoch + 1).to_le_bytes().as_ref()],
+        bump
+    )]
+    pub next_epoch: Account<'info, Epoch>,
+
+    /// Anyone can advance the epoch (permissionless) - pays rent for the next epoch
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimEpochRewards<'info> {
+    #[account(
+        mut,
+        seeds = [b"epoch", epoch.epoch.to_le_bytes().as_ref()],
+        bump = epoch.bump
+    )]
+    pub epoch: Account<'info, Epoch>,
+
+    #[account(
+        mut,
+        seeds = [b"user_epoch", epoch.epoch.to_le_bytes().as_ref(), user.key().as_ref()],
+        bump = user_epoch_points.bump,
+        close = user
+    )]
+    pub user_epoch_points: Account<'info, UserEpochPoints>,
+
+    /// Rewards vault PDA funding epoch claims - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"rewards_vault"],
+        bump
+    )]
+    pub rewards_vault: SystemAccount<'info>,
+
+    /// User wallet - receives the reward and the record's rent
+    #[account(mut)]
+    pub user: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// ===================
+// State
+// ===================
+
+#[account]
+#[derive(InitSpace)]
+pub struct RewardsConfig {
+    pub authority: Pubkey,
+    /// Program PDAs allowed to call credit_points
+    #[max_len(MAX_CREDITERS)]
+    pub crediters: Vec<Pubkey>,
+    /// Points accrued per SOL wagered
+    pub points_per_sol: u64,
+    pub current_epoch: u64,
+    pub epoch_start: i64,
+    pub epoch_duration: i64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Epoch {
+    pub epoch: u64,
+    pub total_points: u64,
+    /// Lamports split pro-rata by points once finalized
+    pub reward_pool: u64,
+    pub claimed: u64,
+    /// Snapshot taken - points and reward pool are final
+    pub finalized: bool,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct UserPoints {
+    pub user: Pubkey,
+    /// Lifetime points across all epochs
+    pub total_points: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct UserEpochPoints {
+    pub epoch: u64,
+    pub user: Pubkey,
+    pub points: u64,
+    pub bump: u8,
+}
+
+// ===================
+// Events
+// ===================
+
+/// Emitted when a game program credits points to a user
+#[event]
+pub struct PointsCredited {
+    pub user: Pubkey,
+    pub epoch: u64,
+    pub points: u64,
+    pub crediter: Pubkey,
+}
+
+/// Emitted when an epoch is snapshotted
+#[event]
+pub struct EpochSnapshotted {
+    pub epoch: u64,
+    pub total_points: u64,
+    pub reward_pool: u64,
+}
+
+/// Emitted when a user claims an epoch's rewards
+#[event]
+pub struct EpochRewardsClaimed {
+    pub user: Pubkey,
+    pub epoch: u64,
+    pub points: u64,
+    pub amount: u64,
+}
+
+// ===================
+// Errors
+// ===================
+
+#[error_code]
+pub enum RewardsError {
+    #[msg("Bad points rate")]
+    InvalidPointsRate,
+    #[msg("Bad epoch duration")]
+    InvalidEpochDuration,
+    #[msg("Too many crediters")]
+    TooManyCrediters,
+    #[msg("Unauthorized crediter")]
+    UnauthorizedCrediter,
+    #[msg("Amount too small")]
+    AmountTooSmall,
+    #[msg("Epoch not over")]
+    EpochNotOver,
+    #[msg("Epoch not finalized")]
+    EpochNotFinalized,
+    #[msg("Overflow")]
+    MathOverflow,
+}
@@ -1,6 +1,18 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
 use anchor_lang::system_program::{transfer, Transfer};
-use pyth_sdk_solana::load_price_feed_from_account_info;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked};
+use compliance::cpi::accounts::AssertEligible;
+use compliance::program::Compliance;
+use insurance::cpi::accounts::Contribute;
+use insurance::program::Insurance;
+use lottery::cpi::accounts::CreditTickets;
+use lottery::program::Lottery;
+use oracle_reader::{OracleError, OracleReader};
+use referral::cpi::accounts::{AccrueReferralFee, RegisterReferrer};
+use referral::program::Referral;
+use rewards::cpi::accounts::CreditPoints;
+use rewards::program::Rewards;
 
 declare_id!("4EMMUfMMx61ynFq53fi8nsXBdDRcB1KuDuAmjsYMAKAA");
 
@@ -8,40 +20,181 @@ declare_id!("4EMMUfMMx61ynFq53fi8nsXBdDRcB1KuDuAmjsYMAKAA");
 // Constants
 // ===================
 
-/// Minimum bet amount: 0.01 SOL (10,000,000 lamports)
+/// Default minimum bet amount: 0.01 SOL (10,000,000 lamports), tunable via set_bet_limits
 pub const MIN_BET: u64 = 10_000_000;
 
-/// Maximum bet amount: 100 SOL (100,000,000,000 lamports)
+/// Default maximum bet amount: 100 SOL (100,000,000,000 lamports), tunable via set_bet_limits
 pub const MAX_BET: u64 = 100_000_000_000;
 
-/// Platform fee: 5% (500 basis points)
+/// Default platform fee: 5% (500 basis points), tunable via set_fee_bps
 pub const PLATFORM_FEE_BPS: u64 = 500;
 
+/// Platform fee cap: 10% (1000 basis points)
+pub const MAX_FEE_BPS: u64 = 1_000;
+
+/// Current GameState layout version; bump whenever a field is carved out of GameState.reserved
+/// v0 is the original layout (authority..bump, no version byte), decoded by migrate_game_state;
+/// v7 keeps that prefix, puts version right after bump, then the remaining fixed-size fields,
+/// the zeroed reserve and last the variable-length fields
+pub const GAME_STATE_VERSION: u8 = 7;
+
+/// Size of the original GameState (authority..bump); a v0 account is exactly 8 + this
+pub const GAME_STATE_V0_SPACE: usize = 32 + 33 + 32 + 8 + 8 + 8 + 1 + 1;
+
+/// Bytes kept zeroed at the end of GameState's fixed-size fields for fields added later
+pub const GAME_STATE_RESERVED_BYTES: usize = 128;
+
+/// Current UserBalance layout version; bump with every field appended to UserBalance
+/// v0 is the original layout (owner..bump, no version byte), decoded by migrate_user_balance;
+/// every field is fixed-size, so appended fields land in the zeroed tail grow_account adds
+pub const USER_BALANCE_VERSION: u8 = 1;
+
+/// Size of the original UserBalance (owner..bump); a v0 account is exactly 8 + this
+pub const USER_BALANCE_V0_SPACE: usize = 32 + 8 + 8 + 8 + 8 + 1;
+
+/// Layout version stamped as the first field of every other account at creation
+/// Give an account type its own constant the first time its layout changes
+pub const ACCOUNT_VERSION: u8 = 1;
+
+/// Settled rounds kept in each market's RoundHistory ring buffer
+pub const ROUND_HISTORY_LEN: usize = 50;
+
+/// Maximum recipients in the fee split applied by withdraw_fees
+pub const MAX_FEE_SPLIT_RECIPIENTS: usize = 4;
+
+/// Maximum game programs allowed to debit and credit user balances over CPI
+pub const MAX_GAME_PROGRAMS: usize = 8;
+
+/// Number of GameType variants, sizing GameState.game_type_stats
+pub const GAME_TYPE_COUNT: usize = 4;
+
+/// Longest delay the admin timelock can be set to: 30 days
+pub const MAX_ADMIN_TIMELOCK_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+/// Default cancellation fee: 1% (100 basis points), tunable via set_cancel_fee_bps (<= MAX_FEE_BPS)
+pub const CANCEL_FEE_BPS: u64 = 100;
+
+/// Default win-streak bonus: +1% (100 basis points) of winnings per consecutive prior win
+pub const STREAK_BONUS_BPS_PER_WIN: u64 = 100;
+
+/// Win-streak bonus cap: 10% (1000 basis points) of winnings, and never more than the fee
+pub const MAX_STREAK_BONUS_BPS: u64 = 1_000;
+
+/// Default early-bird bonus: up to +1% (100 basis points) of winnings for a bet placed at round start
+pub const EARLY_BIRD_BONUS_BPS: u64 = 100;
+
+/// Early-bird bonus cap: 10% (1000 basis points) of winnings, and never more than the fee
+pub const MAX_EARLY_BIRD_BONUS_BPS: u64 = 1_000;
+
+/// Default jackpot contribution: 10% (1000 basis points) of each claim fee
+pub const JACKPOT_CONTRIBUTION_BPS: u64 = 1_000;
+
+/// Jackpot contribution cap: 50% (5000 basis points) of each claim fee
+pub const MAX_JACKPOT_CONTRIBUTION_BPS: u64 = 5_000;
+
+/// Default jackpot trigger: every 100th round of a market pays out the jackpot
+pub const JACKPOT_EVERY_N_ROUNDS: u64 = 100;
+
+/// Cooldown before a player's own betting limits can be loosened: 24 hours
+pub const LIMIT_LOOSEN_COOLDOWN_SECONDS: i64 = 24 * 60 * 60;
+
+/// Length of the personal daily volume window: 1 UTC day
+pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
 /// Basis points denominator
-pub const BPS_DENOMINATOR: u64 = 10_000;
+pub use bet_math::BPS_DENOMINATOR;
 
-/// Round duration: 30 seconds
+/// Default shortest round, and a new market's round duration: 30 seconds
 pub const ROUND_DURATION_SECONDS: i64 = 30;
 
-/// Lock buffer: 5 seconds before round end
+/// Default longest round: 5 minutes
+pub const DEFAULT_MAX_ROUND_DURATION_SECONDS: i64 = 5 * 60;
+
+/// Hard ceiling for the admin-set round duration bounds: 1 hour
+pub const MAX_ROUND_DURATION_SECONDS: i64 = 60 * 60;
+
+/// A new market's lock buffer: betting closes 5 seconds before round end
 pub const LOCK_BUFFER_SECONDS: i64 = 5;
 
 /// Fallback lock delay: 60 seconds after lock_time, anyone can lock the round
 pub const FALLBACK_LOCK_DELAY_SECONDS: i64 = 60;
 
+/// Force-settle delay: 5 minutes after round end, anyone can settle a stuck locked round
+/// Shorter than CANCEL_ROUND_DELAY_SECONDS so the recorded end price is used before refunds
+pub const FORCE_SETTLE_DELAY_SECONDS: i64 = 5 * 60;
+
+/// Cancel delay: 10 minutes after round end, anyone can cancel an unsettled round
+pub const CANCEL_ROUND_DELAY_SECONDS: i64 = 10 * 60;
+
+/// Emergency refund delay: after 7 days of continuous pause, anyone can refund open positions
+pub const EMERGENCY_REFUND_DELAY_SECONDS: i64 = 7 * 24 * 60 * 60;
+
 /// Maximum session validity: 7 days
 pub const MAX_SESSION_DURATION_SECONDS: i64 = 7 * 24 * 60 * 60;
 
+/// Maximum live session keys per user tracked in their SessionRegistry
+pub const MAX_SESSIONS_PER_USER: usize = 8;
+
 /// Maximum price staleness: 60 seconds
 pub const MAX_PRICE_AGE_SECONDS: u64 = 60;
 
+/// Default Pyth confidence limit: 1% (100 basis points) of price
+pub const DEFAULT_MAX_CONF_BPS: u64 = 100;
+
+/// Retry window for wide-confidence prints: 30 seconds after lock_time
+/// Locks are rejected inside it; after it the round locks anyway and settles as a Draw
+pub const CONF_RETRY_WINDOW_SECONDS: i64 = 30;
+
+/// Minimum spacing between TWAP samples: 5 seconds
+pub const TWAP_SAMPLE_INTERVAL_SECONDS: i64 = 5;
+
+/// Decimals of round prices - every oracle reading is rescaled to this by its own exponent
+pub const PRICE_DECIMALS: u32 = 8;
+
 /// Grace period for claiming winnings before round can be closed: 1 hour
 /// After this period, authority can close the round and reclaim rent
 /// Unclaimed winnings are forfeited to the protocol
 pub const CLAIM_GRACE_PERIOD_SECONDS: i64 = 60 * 60;
 
+/// Default cap on session-signed bets per minute per session key
+pub const DEFAULT_SESSION_BETS_PER_MINUTE: u64 = 30;
+
+/// Default bounty for closing a stale round: 0.0005 SOL, tunable via set_close_round_bounty
+pub const DEFAULT_CLOSE_ROUND_BOUNTY: u64 = 500_000;
+
+/// Minimum number of legs in a parlay
+pub const MIN_PARLAY_LEGS: usize = 2;
+
+/// Maximum number of legs in a parlay
+pub const MAX_PARLAY_LEGS: usize = 5;
+
+/// How long after placement a parlay with a missing leg round can be refunded: 1 day
+/// Leg rounds go missing when close_round reclaims them or when they never start
+pub const PARLAY_EXPIRY_SECONDS: i64 = 24 * 60 * 60;
+
+/// Parlay payout multiplier per winning leg: 1.9x (19,000 basis points)
+/// The 0.1x shortfall from even odds is the house edge on the parlay vault
+pub const PARLAY_LEG_MULTIPLIER_BPS: u64 = 19_000;
+
+/// Best payout multiplier a fixed-odds market may quote: 2x the stake (even odds on a two-sided
+/// round, i.e. no house edge), so the house vault never quotes better than a fair coin
+pub const MAX_FIXED_ODDS_BPS: u64 = 20_000;
+
+/// Entrypoint bits for this program - registry deprecation flags (see registry::ProgramEntry)
+/// and the instructions a session key is scoped to (SessionToken::allowed_instructions)
+pub const ENTRYPOINT_PLACE_BET: u64 = 1 << 0;
+pub const ENTRYPOINT_CLAIM_WINNINGS: u64 = 1 << 1;
+pub const ENTRYPOINT_COMMIT_BET: u64 = 1 << 2;
+pub const ENTRYPOINT_EXECUTE_COPY_BET: u64 = 1 << 3;
+pub const ENTRYPOINT_PLACE_PARLAY: u64 = 1 << 4;
+pub const ENTRYPOINT_INCREASE_BET: u64 = 1 << 5;
+pub const ENTRYPOINT_CANCEL_BET: u64 = 1 << 6;
+pub const ENTRYPOINT_REVEAL_BET: u64 = 1 << 7;
+pub const ENTRYPOINT_PLACE_TOKEN_BET: u64 = 1 << 8;
+pub const ENTRYPOINT_PLACE_HOUSE_BET: u64 = 1 << 9;
+
 /// Price feed ID for SOL/USD (Pyth)
-/// Can be updated via set_price_feed instruction
+/// Other assets get their own market via create_market
 pub const DEFAULT_PRICE_FEED_ID: [u8; 32] = [
     0xe6, 0x2d, 0xf6, 0xc8, 0xb4, 0xa8, 0x5f, 0xe1,
     0xa6, 0x7d, 0xb4, 0x4d, 0xc1, 0x2d, 0xe5, 0xdb,
@@ -62,68 +215,216 @@ pub mod session_betting {
     // =====================
 
     /// Initialize the global game state (called once on deployment)
-    /// Only the deployer becomes the authority
+    /// Only the deployer becomes the authority; also opens the first market for price_feed_id
     pub fn initialize_game(ctx: Context<InitializeGame>, price_feed_id: [u8; 32]) -> Result<()> {
-        let game_state = &mut ctx.accounts.game_state;
-        game_state.authority = ctx.accounts.authority.key();
-        game_state.pending_authority = None;
-        game_state.price_feed_id = price_feed_id;
-        game_state.current_round = 0;
-        game_state.total_volume = 0;
-        game_state.total_fees_collected = 0;
-        game_state.is_paused = false;
-        game_state.bump = ctx.bumps.game_state;
+        let market = &mut ctx.accounts.market;
+        market.feed_id = price_feed_id;
+        market.price_source = PriceSource::Pyth;
+        market.current_round = 0;
+        market.last_round_started_at = 0;
+        market.switchboard_feed = Pubkey::default();
+        market.is_active = true;
+        market.bump = ctx.bumps.market;
+        market.round_duration = ROUND_DURATION_SECONDS;
+        market.lock_buffer = LOCK_BUFFER_SECONDS;
+        market.fixed_odds_bps = 0;
+        market.max_round_exposure = 0;
+        market.version = ACCOUNT_VERSION;
+
+        init_jackpot(&mut ctx.accounts.jackpot, ctx.bumps.jackpot);
+        ctx.accounts.game_state.set_inner(new_game_state(
+            ctx.accounts.authority.key(),
+            price_feed_id,
+            ctx.bumps.game_state,
+        ));
         Ok(())
     }
 
-    /// Start a new betting round with the current price
-    /// Authority only - backend reads price from oracle and submits
-    pub fn start_round(ctx: Context<StartRound>, start_price: u64) -> Result<()> {
-        let game_state = &mut ctx.accounts.game_state;
-        let round = &mut ctx.accounts.round;
-        let pool = &mut ctx.accounts.pool;
+    /// Open another market (e.g. ETH/USD, SOL/USD) whose rounds run alongside the others
+    /// AUTHORITY ONLY - markets are keyed by feed ID, so each feed gets exactly one
+    /// Pyth markets use the 32-byte Pyth feed ID; Chainlink markets use the feed account address
+    /// While the admin timelock is on the market starts inactive until an ActivateMarket change executes
+    pub fn create_market(ctx: Context<CreateMarket>, feed_id: [u8; 32], price_source: PriceSource) -> Result<()> {
+        let timelocked = ctx.accounts.game_state.admin_timelock_seconds > 0;
+        let market = &mut ctx.accounts.market;
+        market.feed_id = feed_id;
+        market.price_source = price_source;
+        market.current_round = 0;
+        market.last_round_started_at = 0;
+        market.switchboard_feed = Pubkey::default();
+        // SECURITY: Users get the timelock to vet a new feed before it takes bets
+        market.is_active = !timelocked;
+        market.bump = ctx.bumps.market;
+        market.round_duration = ROUND_DURATION_SECONDS;
+        market.lock_buffer = LOCK_BUFFER_SECONDS;
+        market.fixed_odds_bps = 0;
+        market.max_round_exposure = 0;
+        market.version = ACCOUNT_VERSION;
+        Ok(())
+    }
+
+    /// Stop or resume new rounds on a market (in-flight rounds still lock and settle)
+    /// AUTHORITY ONLY - stopping is immediate; resuming goes through propose_admin_change
+    /// while the admin timelock is on
+    pub fn set_market_active(ctx: Context<SetMarketActive>, active: bool) -> Result<()> {
+        if active {
+            require_no_timelock(&ctx.accounts.game_state)?;
+        }
+        ctx.accounts.market.is_active = active;
+        Ok(())
+    }
+
+    /// Set the Switchboard pull feed used when the market's Pyth feed goes stale
+    /// AUTHORITY ONLY - Pubkey::default() disables the secondary source
+    /// Only while the admin timelock is off - otherwise use propose_admin_change
+    pub fn set_switchboard_feed(ctx: Context<SetSwitchboardFeed>, switchboard_feed: Pubkey) -> Result<()> {
+        require_no_timelock(&ctx.accounts.game_state)?;
+
+        ctx.accounts.market.switchboard_feed = switchboard_feed;
+        Ok(())
+    }
+
+    /// Set how long the market's rounds run and how early before the end betting closes
+    /// (e.g. 30s rounds on SOL, 5m rounds on BTC) - AUTHORITY ONLY
+    /// Rounds already open keep their times; round_duration must also sit within the
+    /// round duration bounds when a round starts
+    /// Only while the admin timelock is off - otherwise use propose_admin_change
+    pub fn set_market_cadence(ctx: Context<SetMarketCadence>, round_duration: i64, lock_buffer: i64) -> Result<()> {
+        require_no_timelock(&ctx.accounts.game_state)?;
+        validate_market_cadence(round_duration, lock_buffer)?;
+
+        let market = &mut ctx.accounts.market;
+        market.round_duration = round_duration;
+        market.lock_buffer = lock_buffer;
+        Ok(())
+    }
+
+    /// Switch a market between pari-mutuel pools and fixed odds quoted against its house vault
+    /// AUTHORITY ONLY - fixed_odds_bps 0 returns the market to pari-mutuel; max_round_exposure caps
+    /// what the house can lose on one round (0 = only its free liquidity)
+    /// Applies to rounds opened afterwards; open rounds keep the odds they started with
+    /// Only while the admin timelock is off - otherwise use propose_admin_change
+    pub fn set_market_fixed_odds(
+        ctx: Context<SetMarketFixedOdds>,
+        fixed_odds_bps: u64,
+        max_round_exposure: u64,
+    ) -> Result<()> {
+        require_no_timelock(&ctx.accounts.game_state)?;
+        validate_fixed_odds(fixed_odds_bps)?;
+
+        let market = &mut ctx.accounts.market;
+        market.fixed_odds_bps = fixed_odds_bps;
+        market.max_round_exposure = max_round_exposure;
+        Ok(())
+    }
+
+    /// Create the market's RoundHistory ring buffer, appended to by settle_round
+    /// AUTHORITY ONLY
+    pub fn initialize_round_history(ctx: Context<InitializeRoundHistory>) -> Result<()> {
+        let mut history = ctx.accounts.round_history.load_init()?;
+        history.market = ctx.accounts.market.key();
+        history.bump = ctx.bumps.round_history;
+        history.version = ACCOUNT_VERSION;
+        Ok(())
+    }
+
+    /// Start a new betting round on a market at the current oracle price
+    /// Authority or operator - the start price is read from the market's feed in the same
+    /// instruction, so a leaked key cannot pick it
+    /// The round runs for the market's round_duration, which must sit within the admin-set
+    /// round duration bounds; settlement_mode picks how the round's end price is derived
+    /// (spot, EMA or TWAP)
+    pub fn start_round(ctx: Context<StartRound>, settlement_mode: SettlementMode) -> Result<()> {
+        let game_state = &ctx.accounts.game_state;
+        let market = &mut ctx.accounts.market;
 
         // SECURITY: Game not paused
         require!(!game_state.is_paused, SessionBettingError::GamePaused);
 
-        // SECURITY: Valid price
-        require!(start_price > 0, SessionBettingError::InvalidPrice);
+        // SECURITY: Retired markets take no new rounds
+        require!(market.is_active, SessionBettingError::MarketInactive);
 
-        let clock = Clock::get()?;
-        let round_id = game_state.current_round;
+        // SECURITY: Market cadence within configured bounds
+        require!(
+            market.round_duration >= game_state.min_round_duration
+                && market.round_duration <= game_state.max_round_duration,
+            SessionBettingError::InvalidRoundDuration
+        );
+
+        // SECURITY: Fresh, positive, tight price from the market's price source
+        let start = read_start_price(
+            game_state,
+            market,
+            &ctx.accounts.price_feed,
+            settlement_mode,
+            Clock::get()?.unix_timestamp,
+        )?;
+
+        open_round(
+            market,
+            &mut ctx.accounts.round,
+            &mut ctx.accounts.pool,
+            (ctx.bumps.round, ctx.bumps.pool),
+            &start,
+            settlement_mode,
+        )
+    }
+
+    /// Start the market's next round at the current Pyth price (EMA price for EMA rounds)
+    /// Permissionless - no trusted price input; at most one new round per market round_duration
+    pub fn start_round_oracle(ctx: Context<StartRoundOracle>, settlement_mode: SettlementMode) -> Result<()> {
+        let game_state = &ctx.accounts.game_state;
+        let market = &mut ctx.accounts.market;
+
+        // SECURITY: Game not paused
+        require!(!game_state.is_paused, SessionBettingError::GamePaused);
+
+        // SECURITY: Retired markets take no new rounds
+        require!(market.is_active, SessionBettingError::MarketInactive);
 
-        // Initialize round
-        round.round_id = round_id;
-        round.start_time = clock.unix_timestamp;
-        round.lock_time = clock.unix_timestamp + ROUND_DURATION_SECONDS - LOCK_BUFFER_SECONDS;
-        round.end_time = clock.unix_timestamp + ROUND_DURATION_SECONDS;
-        // Fallback allows permissionless locking after authority timeout
-        round.lock_time_fallback = round.lock_time + FALLBACK_LOCK_DELAY_SECONDS;
-        round.start_price = start_price;
-        round.end_price = 0;
-        round.status = RoundStatus::Open;
-        round.winner = WinnerSide::None;
-        round.bump = ctx.bumps.round;
+        // SECURITY: Market cadence within configured bounds
+        require!(
+            market.round_duration >= game_state.min_round_duration
+                && market.round_duration <= game_state.max_round_duration,
+            SessionBettingError::InvalidRoundDuration
+        );
 
-        // Initialize pool
-        pool.round_id = round_id;
-        pool.up_pool = 0;
-        pool.down_pool = 0;
-        pool.total_pool = 0;
-        pool.bump = ctx.bumps.pool;
+        let clock = Clock::get()?;
 
-        // Increment round counter
-        game_state.current_round = game_state.current_round.checked_add(1)
+        // SECURITY: Rate-limit permissionless round creation per market
+        let next_start = market.last_round_started_at
+            .checked_add(market.round_duration)
             .ok_or(SessionBettingError::MathOverflow)?;
-        Ok(())
+        require!(
+            clock.unix_timestamp >= next_start,
+            SessionBettingError::TooEarlyToStart
+        );
+
+        // SECURITY: Fresh, positive, tight price from the market's price source
+        let start = read_start_price(
+            game_state,
+            market,
+            &ctx.accounts.price_feed,
+            settlement_mode,
+            clock.unix_timestamp,
+        )?;
+
+        open_round(
+            market,
+            &mut ctx.accounts.round,
+            &mut ctx.accounts.pool,
+            (ctx.bumps.round, ctx.bumps.pool),
+            &start,
+            settlement_mode,
+        )
     }
 
-    /// Lock the round with price from Pyth oracle - AUTHORITY ONLY
+    /// Lock the round with price from Pyth oracle - AUTHORITY OR OPERATOR ONLY
     /// Uses Pyth oracle for tamper-proof price data
     pub fn lock_round(ctx: Context<LockRound>) -> Result<()> {
         let round = &mut ctx.accounts.round;
         let price_account = &ctx.accounts.price_feed;
-        let game_state = &ctx.accounts.game_state;
+        let market = &ctx.accounts.market;
 
         // SECURITY: Round must be open
         require!(round.status == RoundStatus::Open, SessionBettingError::RoundNotOpen);
@@ -136,28 +437,25 @@ pub mod session_betting {
             SessionBettingError::TooEarlyToLock
         );
 
-        // SECURITY: Load and validate price from Pyth oracle
-        let price_feed = load_price_feed_from_account_info(price_account)
-            .map_err(|_| SessionBettingError::InvalidPriceFeed)?;
-
-        // SECURITY: Verify price feed ID matches configured feed
-        require!(
-            price_feed.id.to_bytes() == game_state.price_feed_id,
-            SessionBettingError::PriceFeedMismatch
-        );
-
-        // SECURITY: Get price with staleness check
-        let current_time = clock.unix_timestamp;
-        let price = price_feed.get_price_no_older_than(current_time, MAX_PRICE_AGE_SECONDS)
-            .ok_or(SessionBettingError::PriceTooStale)?;
-
-        // SECURITY: Price must be positive
-        require!(price.price > 0, SessionBettingError::InvalidPrice);
+        // SECURITY: Fresh, positive price from the market's price source
+        let reading = read_market_price(
+            ctx.accounts.game_state.oracle_mode,
+            market,
+            price_account,
+            clock.unix_timestamp,
+            round.settlement_mode == SettlementMode::Ema,
+        )?;
 
-        // Convert price to u64 (price is i64 in Pyth)
-        let end_price = price.price as u64;
+        // SECURITY: Wide-confidence prints are retried, then refunded
+        let wide_confidence = check_lock_confidence(
+            &ctx.accounts.game_state,
+            round,
+            &reading,
+            clock.unix_timestamp,
+        )?;
 
-        round.end_price = end_price;
+        round.end_price = settlement_price(round, reading.price)?;
+        round.wide_confidence = wide_confidence;
         round.status = RoundStatus::Locked;
         Ok(())
     }
@@ -167,7 +465,7 @@ pub mod session_betting {
     /// This prevents rounds from getting stuck if authority goes offline
     /// SECURITY: Uses Pyth oracle for tamper-proof pricing (no arbitrary price input)
     pub fn lock_round_fallback(ctx: Context<LockRoundFallback>) -> Result<()> {
-        let game_state = &ctx.accounts.game_state;
+        let market = &ctx.accounts.market;
         let round = &mut ctx.accounts.round;
         let price_account = &ctx.accounts.price_feed;
 
@@ -182,33 +480,144 @@ pub mod session_betting {
             SessionBettingError::TooEarlyForFallback
         );
 
-        // SECURITY: Load and validate price from Pyth oracle
-        let price_feed = load_price_feed_from_account_info(price_account)
-            .map_err(|_| SessionBettingError::InvalidPriceFeed)?;
+        // SECURITY: Fresh, positive price from the market's price source
+        let reading = read_market_price(
+            ctx.accounts.game_state.oracle_mode,
+            market,
+            price_account,
+            clock.unix_timestamp,
+            round.settlement_mode == SettlementMode::Ema,
+        )?;
+
+        // SECURITY: Wide-confidence prints are retried, then refunded
+        let wide_confidence = check_lock_confidence(
+            &ctx.accounts.game_state,
+            round,
+            &reading,
+            clock.unix_timestamp,
+        )?;
+
+        round.end_price = settlement_price(round, reading.price)?;
+        round.wide_confidence = wide_confidence;
+        round.status = RoundStatus::Locked;
+        Ok(())
+    }
+
+    /// Lock the round from the market's Switchboard feed while its primary feed is stale
+    /// Permissionless after lock_time - caller must show the primary feed is past MAX_PRICE_AGE_SECONDS
+    /// In Pull mode any old PriceUpdateV2 looks stale, so only the authority or operator may use this path
+    pub fn lock_round_switchboard(ctx: Context<LockRoundSwitchboard>) -> Result<()> {
+        let game_state = &ctx.accounts.game_state;
+        let market = &ctx.accounts.market;
+        let round = &mut ctx.accounts.round;
+
+        // SECURITY: Round must be open
+        require!(round.status == RoundStatus::Open, SessionBettingError::RoundNotOpen);
+
+        let clock = Clock::get()?;
 
-        // SECURITY: Verify price feed ID matches configured feed
+        // SECURITY: Must be after lock_time
         require!(
-            price_feed.id.to_bytes() == game_state.price_feed_id,
-            SessionBettingError::PriceFeedMismatch
+            clock.unix_timestamp >= round.lock_time,
+            SessionBettingError::TooEarlyToLock
         );
 
-        // SECURITY: Get price with staleness check
-        // This prevents price manipulation even in permissionless fallback
-        let current_time = clock.unix_timestamp;
-        let price = price_feed.get_price_no_older_than(current_time, MAX_PRICE_AGE_SECONDS)
-            .ok_or(SessionBettingError::PriceTooStale)?;
+        // SECURITY: Market must have a secondary source configured
+        require!(
+            market.switchboard_feed != Pubkey::default(),
+            SessionBettingError::SwitchboardNotConfigured
+        );
 
-        // SECURITY: Price must be positive
-        require!(price.price > 0, SessionBettingError::InvalidPrice);
+        // SECURITY: Pull-mode staleness cannot be proven by a single account
+        if game_state.oracle_mode == OracleMode::Pull {
+            require!(
+                is_round_operator(game_state, &ctx.accounts.caller.key()),
+                SessionBettingError::Unauthorized
+            );
+        }
 
-        // Convert price to u64 (price is i64 in Pyth)
-        let end_price = price.price as u64;
+        // SECURITY: Only fall back while the primary feed is actually stale
+        require!(
+            primary_is_stale(
+                game_state.oracle_mode,
+                market,
+                &ctx.accounts.price_feed,
+                clock.unix_timestamp,
+            )?,
+            SessionBettingError::PythNotStale
+        );
 
-        round.end_price = end_price;
+        // SECURITY: Fresh, positive Switchboard result, rescaled to round price decimals
+        // Switchboard has no EMA, so EMA rounds settle on its spot value here
+        let lock_price = OracleReader::Switchboard
+            .read_no_older_than(
+                &ctx.accounts.switchboard_feed,
+                &market.switchboard_feed.to_bytes(),
+                clock.unix_timestamp,
+                MAX_PRICE_AGE_SECONDS,
+                false,
+            )
+            .map_err(oracle_error)?
+            .price_in(PRICE_DECIMALS)
+            .ok_or(SessionBettingError::InvalidPrice)?;
+
+        round.end_price = settlement_price(round, lock_price)?;
         round.status = RoundStatus::Locked;
         Ok(())
     }
 
+    /// Record a price sample for a TWAP round
+    /// Permissionless crank - at most one sample per TWAP_SAMPLE_INTERVAL_SECONDS, until lock_time
+    /// At lock the end price is the average of every sample plus the lock reading
+    pub fn record_price_sample(ctx: Context<RecordPriceSample>) -> Result<()> {
+        let game_state = &ctx.accounts.game_state;
+        let market = &ctx.accounts.market;
+        let round = &mut ctx.accounts.round;
+
+        // SECURITY: Round must be open
+        require!(round.status == RoundStatus::Open, SessionBettingError::RoundNotOpen);
+        require!(
+            round.settlement_mode == SettlementMode::Twap,
+            SessionBettingError::NotTwapRound
+        );
+
+        let clock = Clock::get()?;
+
+        // SECURITY: Samples only count while the round is running
+        require!(clock.unix_timestamp < round.lock_time, SessionBettingError::RoundLocked);
+
+        // SECURITY: Space samples out so no caller can stack the average
+        let next_sample = round.last_sample_at
+            .checked_add(TWAP_SAMPLE_INTERVAL_SECONDS)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        require!(
+            clock.unix_timestamp >= next_sample,
+            SessionBettingError::TooEarlyToSample
+        );
+
+        // SECURITY: Fresh, positive, tight price from the market's price source
+        let sample = read_market_price(
+            game_state.oracle_mode,
+            market,
+            &ctx.accounts.price_feed,
+            clock.unix_timestamp,
+            false,
+        )?;
+        require!(
+            !conf_too_wide(&sample, game_state.max_conf_bps),
+            SessionBettingError::PriceConfidenceTooWide
+        );
+
+        round.price_sample_sum = round.price_sample_sum
+            .checked_add(sample.price as u128)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        round.price_sample_count = round.price_sample_count
+            .checked_add(1)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        round.last_sample_at = clock.unix_timestamp;
+        Ok(())
+    }
+
     /// Settle the round - determines winner side
     /// Permissionless - anyone can call after round is locked
     pub fn settle_round(ctx: Context<SettleRound>) -> Result<()> {
@@ -216,42 +625,341 @@ pub mod session_betting {
         let round = &mut ctx.accounts.round;
         let pool = &ctx.accounts.pool;
 
-        // SECURITY: Round must be locked
-        require!(round.status == RoundStatus::Locked, SessionBettingError::RoundNotLocked);
+        let clock = Clock::get()?;
+        require_settleable(round, clock.unix_timestamp)?;
+
+        let (move_bps, breaker_tripped) =
+            apply_settlement(game_state, round, pool, Some(&mut ctx.accounts.jackpot))?;
+
+        if let Some(history) = &ctx.accounts.round_history {
+            record_round_history(&mut *history.load_mut()?, round, pool, clock.unix_timestamp);
+        }
+
+        // Emit audit events
+        if breaker_tripped {
+            emit_cpi!(CircuitBreakerTripped {
+                round_id: round.round_id,
+                start_price: round.start_price,
+                end_price: round.end_price,
+                move_bps,
+                max_price_move_bps: game_state.max_price_move_bps,
+            });
+        }
+        if round.jackpot_payout > 0 {
+            emit_cpi!(JackpotTriggered {
+                market: round.market,
+                round_id: round.round_id,
+                amount: round.jackpot_payout,
+                winner: round.winner,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+        emit_cpi!(round_settled_event(round, pool, clock.unix_timestamp));
+
+        Ok(())
+    }
+
+    /// Settle the market's current round and open the next one in a single crank
+    /// AUTHORITY OR OPERATOR - removes the dead time between settle_round and start_round;
+    /// the next round starts at a fresh oracle price, like start_round
+    pub fn advance_round(ctx: Context<AdvanceRound>, settlement_mode: SettlementMode) -> Result<()> {
+        let game_state = &mut ctx.accounts.game_state;
+        let market = &mut ctx.accounts.market;
+        let round = &mut ctx.accounts.round;
+        let pool = &ctx.accounts.pool;
+
+        // SECURITY: Only the market's latest round can be advanced
+        require!(
+            round.round_id.checked_add(1) == Some(market.current_round),
+            SessionBettingError::RoundNotLatest
+        );
 
         let clock = Clock::get()?;
+        require_settleable(round, clock.unix_timestamp)?;
+
+        let (move_bps, breaker_tripped) =
+            apply_settlement(game_state, round, pool, Some(&mut ctx.accounts.jackpot))?;
+
+        if let Some(history) = &ctx.accounts.round_history {
+            record_round_history(&mut *history.load_mut()?, round, pool, clock.unix_timestamp);
+        }
+
+        // Emit audit events
+        if breaker_tripped {
+            emit_cpi!(CircuitBreakerTripped {
+                round_id: round.round_id,
+                start_price: round.start_price,
+                end_price: round.end_price,
+                move_bps,
+                max_price_move_bps: game_state.max_price_move_bps,
+            });
+        }
+        if round.jackpot_payout > 0 {
+            emit_cpi!(JackpotTriggered {
+                market: round.market,
+                round_id: round.round_id,
+                amount: round.jackpot_payout,
+                winner: round.winner,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+        emit_cpi!(round_settled_event(round, pool, clock.unix_timestamp));
 
-        // SECURITY: Must be after end_time
+        // SECURITY: Same checks as start_round for the next round
+        require!(!game_state.is_paused, SessionBettingError::GamePaused);
+        require!(market.is_active, SessionBettingError::MarketInactive);
         require!(
-            clock.unix_timestamp >= round.end_time,
-            SessionBettingError::TooEarlyToSettle
+            market.round_duration >= game_state.min_round_duration
+                && market.round_duration <= game_state.max_round_duration,
+            SessionBettingError::InvalidRoundDuration
         );
 
-        // Determine winner
-        let winner = if round.end_price > round.start_price {
-            WinnerSide::Up
-        } else if round.end_price < round.start_price {
-            WinnerSide::Down
-        } else {
-            WinnerSide::Draw
-        };
+        // SECURITY: Fresh, positive, tight price from the market's price source
+        let start = read_start_price(
+            game_state,
+            market,
+            &ctx.accounts.price_feed,
+            settlement_mode,
+            clock.unix_timestamp,
+        )?;
 
-        round.winner = winner;
-        round.status = RoundStatus::Settled;
+        open_round(
+            market,
+            &mut ctx.accounts.next_round,
+            &mut ctx.accounts.next_pool,
+            (ctx.bumps.next_round, ctx.bumps.next_pool),
+            &start,
+            settlement_mode,
+        )
+    }
 
-        // Update stats
-        game_state.total_volume = game_state.total_volume
-            .checked_add(pool.total_pool)
-            .ok_or(SessionBettingError::MathOverflow)?;
+    /// Settle a round and push its payouts straight into its bettors' balances
+    /// AUTHORITY OR OPERATOR - the crank runs this once the round has ended so small winners
+    /// are paid without having to claim before close_round forfeits their funds
+    /// A locked round is settled first, like settle_round; later batches credit the settled round
+    /// (position, user_balance, user_stats, referral, referrer_earnings) groups are passed via
+    /// remaining_accounts; batch across transactions for large rounds. referral is the owner's
+    /// referral PDA whether or not it exists (referrer_earnings is ignored when it doesn't).
+    /// Positions already claimed are skipped, and owners without a UserStats account must claim themselves
+    pub fn settle_and_credit<'info>(ctx: Context<'_, '_, 'info, 'info, SettleAndCredit<'info>>) -> Result<()> {
+        let game_state = &mut ctx.accounts.game_state;
+        let round = &mut ctx.accounts.round;
+        let pool = &ctx.accounts.pool;
+        let clock = Clock::get()?;
 
-        // Emit audit event
-        emit!(RoundSettled {
+        if round.status == RoundStatus::Locked {
+            require_settleable(round, clock.unix_timestamp)?;
+
+            let (move_bps, breaker_tripped) =
+                apply_settlement(game_state, round, pool, Some(&mut ctx.accounts.jackpot))?;
+
+            if let Some(history) = &ctx.accounts.round_history {
+                record_round_history(&mut *history.load_mut()?, round, pool, clock.unix_timestamp);
+            }
+
+            // Emit audit events
+            if breaker_tripped {
+                emit_cpi!(CircuitBreakerTripped {
+                    round_id: round.round_id,
+                    start_price: round.start_price,
+                    end_price: round.end_price,
+                    move_bps,
+                    max_price_move_bps: game_state.max_price_move_bps,
+                });
+            }
+            if round.jackpot_payout > 0 {
+                emit_cpi!(JackpotTriggered {
+                    market: round.market,
+                    round_id: round.round_id,
+                    amount: round.jackpot_payout,
+                    winner: round.winner,
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+            emit_cpi!(round_settled_event(round, pool, clock.unix_timestamp));
+        }
+
+        // SECURITY: Round must be settled or cancelled
+        require!(round_is_final(round), SessionBettingError::RoundNotSettled);
+
+        // SECURITY: Accounts come in (position, user_balance, user_stats, referral, referrer_earnings) groups
+        require!(
+            ctx.remaining_accounts.len().checked_rem(5) == Some(0),
+            SessionBettingError::CreditAccountMismatch
+        );
+
+        let round_id_bytes = round.round_id.to_le_bytes();
+        let mut credited: u32 = 0;
+        let mut total_paid: u64 = 0;
+
+        for accounts in ctx.remaining_accounts.chunks(5) {
+            let mut position: Account<PlayerPosition> = Account::try_from(&accounts[0])?;
+            let mut user_balance: Account<UserBalance> = Account::try_from(&accounts[1])?;
+            let mut user_stats: Account<UserStats> = Account::try_from(&accounts[2])?;
+
+            // SECURITY: Position must belong to this round (market and round_id are in its seeds)
+            let position_pda = Pubkey::create_program_address(
+                &[
+                    b"position",
+                    round.market.as_ref(),
+                    round_id_bytes.as_ref(),
+                    position.bettor.as_ref(),
+                    &[position.index],
+                    &[position.bump],
+                ],
+                &crate::ID,
+            ).map_err(|_| SessionBettingError::CreditAccountMismatch)?;
+            require_keys_eq!(position.key(), position_pda, SessionBettingError::CreditAccountMismatch);
+
+            // SECURITY: Balance must be the canonical PDA of its owner
+            let balance_pda = Pubkey::create_program_address(
+                &[b"balance", user_balance.owner.as_ref(), &[user_balance.bump]],
+                &crate::ID,
+            ).map_err(|_| SessionBettingError::CreditAccountMismatch)?;
+            require_keys_eq!(user_balance.key(), balance_pda, SessionBettingError::CreditAccountMismatch);
+
+            // SECURITY: Stats must be the owner's canonical PDA
+            let stats_pda = Pubkey::create_program_address(
+                &[b"user_stats", user_balance.owner.as_ref(), &[user_stats.bump]],
+                &crate::ID,
+            ).map_err(|_| SessionBettingError::CreditAccountMismatch)?;
+            require_keys_eq!(user_stats.key(), stats_pda, SessionBettingError::CreditAccountMismatch);
+
+            // SECURITY: Referral must be the owner's PDA, so an existing referral can't be
+            // swapped for an empty account to skip the referrer's share
+            let (referral_pda, _) = Pubkey::find_program_address(
+                &[b"referral", user_balance.owner.as_ref()],
+                &referral::ID,
+            );
+            require_keys_eq!(accounts[3].key(), referral_pda, SessionBettingError::CreditAccountMismatch);
+
+            if position.claimed {
+                continue;
+            }
+
+            // process_claim checks position.player == user_balance.owner
+            let claim = process_claim(
+                game_state,
+                round,
+                pool,
+                &mut position,
+                &mut user_balance,
+                &mut user_stats,
+                &mut ctx.accounts.jackpot,
+            )?;
+            position.exit(&crate::ID)?;
+            user_balance.exit(&crate::ID)?;
+            user_stats.exit(&crate::ID)?;
+
+            // Route the fee exactly as claim_winnings does
+            if !accounts[3].data_is_empty() {
+                route_referral_fee(
+                    &ctx.accounts.referral_fees,
+                    &ctx.bumps.referral_fees,
+                    (&accounts[3], &accounts[4]),
+                    game_state,
+                    user_balance.owner,
+                    claim.fee,
+                )?;
+            }
+            contribute_insurance_fee(
+                &ctx.accounts.insurance_fees,
+                &ctx.bumps.insurance_fees,
+                game_state,
+                claim.fee,
+            )?;
+
+            credited = credited
+                .checked_add(1)
+                .ok_or(SessionBettingError::MathOverflow)?;
+            total_paid = total_paid
+                .checked_add(claim.payout)
+                .ok_or(SessionBettingError::MathOverflow)?;
+        }
+
+        emit_cpi!(RoundCredited {
+            market: round.market,
             round_id: round.round_id,
-            start_price: round.start_price,
-            end_price: round.end_price,
-            winner,
-            up_pool: pool.up_pool,
-            down_pool: pool.down_pool,
+            positions: credited,
+            total_paid,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Settle a locked round that settle_round cannot process (e.g. a missing jackpot or
+    /// history account) from its recorded end price; works while paused
+    /// AUTHORITY OR OPERATOR once the round is settleable, then permissionless after
+    /// FORCE_SETTLE_DELAY_SECONDS. Skips the jackpot and round history
+    pub fn force_settle(ctx: Context<ForceSettle>) -> Result<()> {
+        let game_state = &mut ctx.accounts.game_state;
+        let round = &mut ctx.accounts.round;
+        let pool = &ctx.accounts.pool;
+
+        let clock = Clock::get()?;
+        require_settleable(round, clock.unix_timestamp)?;
+
+        // SECURITY: Other callers must wait out the delay (gives settle_round and the operator priority)
+        if !is_round_operator(game_state, &ctx.accounts.caller.key()) {
+            let force_time = round.end_time
+                .checked_add(FORCE_SETTLE_DELAY_SECONDS)
+                .ok_or(SessionBettingError::MathOverflow)?;
+            require!(
+                clock.unix_timestamp >= force_time,
+                SessionBettingError::TooEarlyToSettle
+            );
+        }
+
+        apply_settlement(game_state, round, pool, None)?;
+
+        // Emit audit events
+        emit_cpi!(round_settled_event(round, pool, clock.unix_timestamp));
+        emit_cpi!(RoundForceSettled {
+            market: round.market,
+            round_id: round.round_id,
+            settled_by: ctx.accounts.caller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Cancel a round that can no longer settle (e.g. the oracle is down) and refund every stake
+    /// AUTHORITY OR OPERATOR until CANCEL_ROUND_DELAY_SECONDS after round end, then permissionless
+    /// Once an end price is recorded the outcome is known, so only the delayed path may cancel
+    /// Position holders claim full refunds through the usual claim instructions
+    pub fn cancel_round(ctx: Context<CancelRound>) -> Result<()> {
+        let round = &mut ctx.accounts.round;
+
+        // SECURITY: Only rounds that have not settled yet
+        require!(!round_is_final(round), SessionBettingError::RoundAlreadySettled);
+
+        let clock = Clock::get()?;
+
+        // SECURITY: Privileged callers skip the delay only while the outcome is unknown, so a
+        // round cannot be voided after its end price shows which side won
+        let early_cancel = is_round_operator(&ctx.accounts.game_state, &ctx.accounts.caller.key())
+            && round.end_price == 0;
+
+        // SECURITY: Other callers must wait out the delay (gives authority and operator priority window)
+        if !early_cancel {
+            let cancel_time = round.end_time
+                .checked_add(CANCEL_ROUND_DELAY_SECONDS)
+                .ok_or(SessionBettingError::MathOverflow)?;
+            require!(
+                clock.unix_timestamp >= cancel_time,
+                SessionBettingError::CancelTooEarly
+            );
+        }
+
+        round.winner = WinnerSide::Draw;
+        round.status = RoundStatus::Cancelled;
+
+        emit_cpi!(RoundCancelled {
+            market: round.market,
+            round_id: round.round_id,
+            cancelled_by: ctx.accounts.caller.key(),
             timestamp: clock.unix_timestamp,
         });
 
@@ -259,16 +967,14 @@ pub mod session_betting {
     }
 
     /// Close a settled round and reclaim rent
-    /// AUTHORITY ONLY - can only be called after grace period
+    /// Permissionless - can only be called after grace period; the caller earns
+    /// close_round_bounty out of the round's rent and the rest returns to the authority
     /// Any unclaimed winnings are forfeited to the protocol
     pub fn close_round(ctx: Context<CloseRound>) -> Result<()> {
         let round = &ctx.accounts.round;
 
-        // SECURITY: Round must be settled
-        require!(
-            round.status == RoundStatus::Settled,
-            SessionBettingError::RoundNotSettled
-        );
+        // SECURITY: Round must be settled or cancelled
+        require!(round_is_final(round), SessionBettingError::RoundNotSettled);
 
         let clock = Clock::get()?;
 
@@ -282,20 +988,41 @@ pub mod session_betting {
             SessionBettingError::GracePeriodNotOver
         );
 
+        // SECURITY: A fixed-odds round hands its reserve back to the house vault first
+        // (release_house_reserve), or the LPs' liquidity would stay locked forever
+        require!(
+            ctx.accounts.pool.house_reserved == 0,
+            SessionBettingError::HouseReserveOutstanding
+        );
+
+        // Pay the cranker out of the round's rent; never more than the account holds
+        let round_info = ctx.accounts.round.to_account_info();
+        let bounty = ctx.accounts.game_state.close_round_bounty.min(round_info.lamports());
+        if bounty > 0 {
+            round_info.sub_lamports(bounty)?;
+            ctx.accounts.caller.add_lamports(bounty)?;
+        }
+
         // Accounts are closed via the close constraint in CloseRound
-        // Rent is returned to authority
+        // Remaining rent is returned to authority
         Ok(())
     }
 
     /// Pause/unpause the game (emergency only)
     pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
         let game_state = &mut ctx.accounts.game_state;
+        let clock = Clock::get()?;
 
+        // Re-pausing keeps the original timestamp so the emergency delay stays continuous
+        if !paused {
+            game_state.paused_at = 0;
+        } else if !game_state.is_paused {
+            game_state.paused_at = clock.unix_timestamp;
+        }
         game_state.is_paused = paused;
 
         // Emit audit event
-        let clock = Clock::get()?;
-        emit!(GamePaused {
+        emit_cpi!(GamePaused {
             authority: ctx.accounts.authority.key(),
             paused,
             timestamp: clock.unix_timestamp,
@@ -304,12 +1031,454 @@ pub mod session_betting {
         Ok(())
     }
 
-    /// Update the Pyth price feed ID (authority only)
-    pub fn set_price_feed(ctx: Context<SetPriceFeed>, price_feed_id: [u8; 32]) -> Result<()> {
+    /// Configure per-round pool caps (authority only)
+    /// A value of 0 disables the corresponding cap
+    /// Only while the admin timelock is off - otherwise use propose_admin_change
+    pub fn set_pool_caps(
+        ctx: Context<SetPoolCaps>,
+        max_total_pool: u64,
+        max_side_pool: u64,
+        max_player_stake: u64,
+    ) -> Result<()> {
         let game_state = &mut ctx.accounts.game_state;
+        require_no_timelock(game_state)?;
 
-        game_state.price_feed_id = price_feed_id;
-        Ok(())
+        // SECURITY: A side cap can never exceed the total cap
+        if max_total_pool > 0 {
+            require!(max_side_pool <= max_total_pool, SessionBettingError::InvalidPoolCaps);
+        }
+
+        game_state.max_total_pool = max_total_pool;
+        game_state.max_side_pool = max_side_pool;
+        game_state.max_player_stake = max_player_stake;
+
+        emit_cpi!(PoolCapsUpdated {
+            max_total_pool,
+            max_side_pool,
+            max_player_stake,
+        });
+        Ok(())
+    }
+
+    /// Configure the extreme-move circuit breaker (authority only)
+    /// Rounds whose price moved more than max_price_move_bps settle as a Draw
+    /// A value of 0 disables the circuit breaker; only while the admin timelock is off
+    pub fn set_circuit_breaker(ctx: Context<SetCircuitBreaker>, max_price_move_bps: u64) -> Result<()> {
+        let game_state = &mut ctx.accounts.game_state;
+        require_no_timelock(game_state)?;
+
+        game_state.max_price_move_bps = max_price_move_bps;
+        Ok(())
+    }
+
+    /// Set the widest Pyth confidence interval, in bps of price, that rounds start or lock on (authority only)
+    /// A value of 0 disables the check; only while the admin timelock is off
+    pub fn set_max_conf_bps(ctx: Context<SetMaxConfBps>, max_conf_bps: u64) -> Result<()> {
+        let game_state = &mut ctx.accounts.game_state;
+        require_no_timelock(game_state)?;
+
+        game_state.max_conf_bps = max_conf_bps;
+        Ok(())
+    }
+
+    /// Set the platform fee taken from winnings (authority only)
+    /// Capped at MAX_FEE_BPS; applies to every claim made after the change
+    /// Only while the admin timelock is off - otherwise use propose_admin_change
+    pub fn set_fee_bps(ctx: Context<SetFeeBps>, fee_bps: u64) -> Result<()> {
+        require_no_timelock(&ctx.accounts.game_state)?;
+        require!(fee_bps <= MAX_FEE_BPS, SessionBettingError::InvalidFee);
+
+        ctx.accounts.game_state.fee_bps = fee_bps;
+        Ok(())
+    }
+
+    /// Set the fee kept when a bet is cancelled before lock (authority only)
+    /// Capped at MAX_FEE_BPS; only while the admin timelock is off
+    pub fn set_cancel_fee_bps(ctx: Context<SetCancelFeeBps>, cancel_fee_bps: u64) -> Result<()> {
+        require_no_timelock(&ctx.accounts.game_state)?;
+        require!(cancel_fee_bps <= MAX_FEE_BPS, SessionBettingError::InvalidFee);
+
+        ctx.accounts.game_state.cancel_fee_bps = cancel_fee_bps;
+        Ok(())
+    }
+
+    /// Set the keeper key allowed to run the round lifecycle (authority only)
+    /// The operator can start and lock rounds, and cancel them while no end price is recorded,
+    /// but never touches fees or balances; Pubkey::default() removes it
+    /// Removal is immediate; a new operator goes through propose_admin_change while the timelock is on
+    pub fn set_operator(ctx: Context<SetOperator>, operator: Pubkey) -> Result<()> {
+        if operator != Pubkey::default() {
+            require_no_timelock(&ctx.accounts.game_state)?;
+        }
+        ctx.accounts.game_state.operator = operator;
+        Ok(())
+    }
+
+    /// Allow a game program to call debit_for_game / credit_for_game (authority only)
+    /// Goes through propose_admin_change while the admin timelock is on
+    pub fn add_game_program(ctx: Context<AddGameProgram>, program_id: Pubkey) -> Result<()> {
+        require_no_timelock(&ctx.accounts.game_state)?;
+        add_game_program_entry(&mut ctx.accounts.game_state, program_id)
+    }
+
+    /// Revoke a game program's access to user balances (authority only)
+    /// Takes effect immediately, even while the admin timelock is on
+    pub fn remove_game_program(ctx: Context<RemoveGameProgram>, program_id: Pubkey) -> Result<()> {
+        let game_programs = &mut ctx.accounts.game_state.game_programs;
+        let index = game_programs.iter()
+            .position(|p| *p == program_id)
+            .ok_or(SessionBettingError::UnauthorizedGameProgram)?;
+        game_programs.remove(index);
+        Ok(())
+    }
+
+    /// Set the lamports paid to whoever closes a stale round (authority only)
+    /// Paid out of the round's rent, so values above it simply pay the whole rent
+    pub fn set_close_round_bounty(ctx: Context<SetCloseRoundBounty>, close_round_bounty: u64) -> Result<()> {
+        ctx.accounts.game_state.close_round_bounty = close_round_bounty;
+        Ok(())
+    }
+
+    /// Set how many bets a session key may sign per minute (authority only, 0 = unlimited)
+    /// Only while the admin timelock is off - otherwise use propose_admin_change
+    pub fn set_session_rate_limit(ctx: Context<SetSessionRateLimit>, session_bets_per_minute: u64) -> Result<()> {
+        require_no_timelock(&ctx.accounts.game_state)?;
+        ctx.accounts.game_state.session_bets_per_minute = session_bets_per_minute;
+        Ok(())
+    }
+
+    /// Set the win-streak bonus schedule (authority only)
+    /// Bonus = min(streak * per_win_bps, max_bps) of winnings, paid out of the claim fee
+    /// Only while the admin timelock is off - otherwise use propose_admin_change
+    pub fn set_streak_bonus(
+        ctx: Context<SetStreakBonus>,
+        streak_bonus_bps_per_win: u64,
+        max_streak_bonus_bps: u64,
+    ) -> Result<()> {
+        require_no_timelock(&ctx.accounts.game_state)?;
+        require!(
+            max_streak_bonus_bps <= MAX_STREAK_BONUS_BPS
+                && streak_bonus_bps_per_win <= max_streak_bonus_bps,
+            SessionBettingError::InvalidStreakBonus
+        );
+
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.streak_bonus_bps_per_win = streak_bonus_bps_per_win;
+        game_state.max_streak_bonus_bps = max_streak_bonus_bps;
+        Ok(())
+    }
+
+    /// Configure how the progressive jackpot is funded and when it pays out (authority only)
+    /// A round wins the jackpot when its price moves at least trigger_move_bps or its
+    /// round is every trigger_every_n_rounds-th of its market (either trigger 0 = disabled)
+    /// Only while the admin timelock is off - otherwise use propose_admin_change
+    pub fn set_jackpot_config(
+        ctx: Context<SetJackpotConfig>,
+        contribution_bps: u64,
+        trigger_move_bps: u64,
+        trigger_every_n_rounds: u64,
+    ) -> Result<()> {
+        require_no_timelock(&ctx.accounts.game_state)?;
+        require!(
+            contribution_bps <= MAX_JACKPOT_CONTRIBUTION_BPS,
+            SessionBettingError::InvalidJackpotConfig
+        );
+
+        let jackpot = &mut ctx.accounts.jackpot;
+        jackpot.contribution_bps = contribution_bps;
+        jackpot.trigger_move_bps = trigger_move_bps;
+        jackpot.trigger_every_n_rounds = trigger_every_n_rounds;
+        Ok(())
+    }
+
+    /// Set the early-bird bonus for bets placed at round start (authority only)
+    /// Decays linearly to 0 at lock; paid out of the claim fee
+    /// Only while the admin timelock is off - otherwise use propose_admin_change
+    pub fn set_early_bird_bonus(ctx: Context<SetEarlyBirdBonus>, max_early_bird_bonus_bps: u64) -> Result<()> {
+        require_no_timelock(&ctx.accounts.game_state)?;
+        require!(
+            max_early_bird_bonus_bps <= MAX_EARLY_BIRD_BONUS_BPS,
+            SessionBettingError::InvalidEarlyBirdBonus
+        );
+
+        ctx.accounts.game_state.max_early_bird_bonus_bps = max_early_bird_bonus_bps;
+        Ok(())
+    }
+
+    /// Set the per-bet stake limits (authority only)
+    /// min_bet also acts as the minimum deposit, so limits can follow the SOL price
+    /// Only while the admin timelock is off - otherwise use propose_admin_change
+    pub fn set_bet_limits(ctx: Context<SetBetLimits>, min_bet: u64, max_bet: u64) -> Result<()> {
+        require_no_timelock(&ctx.accounts.game_state)?;
+        require!(
+            min_bet > 0 && min_bet <= max_bet,
+            SessionBettingError::InvalidBetLimits
+        );
+
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.min_bet = min_bet;
+        game_state.max_bet = max_bet;
+        Ok(())
+    }
+
+    /// Set the range of durations start_round accepts (authority only)
+    /// Rounds must outlast the lock buffer and stay under MAX_ROUND_DURATION_SECONDS
+    /// Only while the admin timelock is off - otherwise use propose_admin_change
+    pub fn set_round_duration_bounds(
+        ctx: Context<SetRoundDurationBounds>,
+        min_duration: i64,
+        max_duration: i64,
+    ) -> Result<()> {
+        require_no_timelock(&ctx.accounts.game_state)?;
+        require!(
+            min_duration > LOCK_BUFFER_SECONDS
+                && min_duration <= max_duration
+                && max_duration <= MAX_ROUND_DURATION_SECONDS,
+            SessionBettingError::InvalidRoundDuration
+        );
+
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.min_round_duration = min_duration;
+        game_state.max_round_duration = max_duration;
+        Ok(())
+    }
+
+    /// Configure the minimum total pool for a round to pay out (authority only)
+    /// Rounds with a smaller pool settle as a Draw and everyone is refunded
+    /// Only while the admin timelock is off - otherwise use propose_admin_change
+    pub fn set_min_pool_for_settlement(ctx: Context<SetMinPoolForSettlement>, min_pool: u64) -> Result<()> {
+        let game_state = &mut ctx.accounts.game_state;
+        require_no_timelock(game_state)?;
+
+        game_state.min_pool_for_settlement = min_pool;
+        Ok(())
+    }
+
+    /// Require the Squads multisig path for fee withdrawals and authority changes
+    /// AUTHORITY ONLY - once enabled, only the multisig can change it again
+    pub fn set_require_multisig(ctx: Context<SetRequireMultisig>, require_multisig: bool) -> Result<()> {
+        // SECURITY: Treasury paths must come from the Squads vault when required
+        require!(
+            admin_guard::admin_path_ok(
+                &ctx.accounts.authority.key(),
+                ctx.accounts.admin_multisig.as_deref(),
+                ctx.accounts.game_state.require_multisig,
+            ),
+            SessionBettingError::MultisigRequired
+        );
+
+        ctx.accounts.game_state.require_multisig = require_multisig;
+        Ok(())
+    }
+
+    /// Choose how lock and oracle-start paths read Pyth (authority only)
+    /// Push: legacy price accounts; Pull: PriceUpdateV2 accounts posted by the Pyth receiver
+    /// Only while the admin timelock is off - otherwise use propose_admin_change
+    pub fn set_oracle_mode(ctx: Context<SetOracleMode>, mode: OracleMode) -> Result<()> {
+        require_no_timelock(&ctx.accounts.game_state)?;
+        ctx.accounts.game_state.oracle_mode = mode;
+        Ok(())
+    }
+
+    /// Set the compliance eligibility mode (authority only)
+    /// Open: everyone may bet; Allowlist: only Allowed users; Blocklist: everyone except Blocked users
+    /// Only betting entrypoints consult this - claims and withdrawals are never gated
+    pub fn set_eligibility_mode(ctx: Context<SetEligibilityMode>, mode: EligibilityMode) -> Result<()> {
+        let game_state = &mut ctx.accounts.game_state;
+
+        game_state.eligibility_mode = mode;
+        Ok(())
+    }
+
+    /// Record a user's eligibility in the compliance registry (authority only)
+    pub fn set_user_eligibility(ctx: Context<SetUserEligibility>, status: EligibilityStatus) -> Result<()> {
+        let user_balance = &mut ctx.accounts.user_balance;
+
+        user_balance.eligibility = status;
+
+        emit_cpi!(EligibilityUpdated {
+            user: user_balance.owner,
+            status,
+        });
+        Ok(())
+    }
+
+    /// Cap a single user's position size below the global max_bet (authority only, 0 = no override)
+    /// Pair with a Blocked eligibility to freeze an account; withdrawals are never gated
+    pub fn set_user_max_bet(ctx: Context<SetUserMaxBet>, max_bet_override: u64) -> Result<()> {
+        let user_balance = &mut ctx.accounts.user_balance;
+
+        user_balance.max_bet_override = max_bet_override;
+
+        emit_cpi!(UserMaxBetUpdated {
+            user: user_balance.owner,
+            max_bet_override,
+        });
+        Ok(())
+    }
+
+    /// Cap a single user's token position size, in token_mint base units (authority only, 0 = no override)
+    /// The token counterpart of set_user_max_bet; eligibility is shared with the SOL path
+    pub fn set_user_token_max_bet(ctx: Context<SetUserTokenMaxBet>, max_bet_override: u64) -> Result<()> {
+        let token_balance = &mut ctx.accounts.token_balance;
+
+        token_balance.max_bet_override = max_bet_override;
+
+        emit_cpi!(UserTokenMaxBetUpdated {
+            user: token_balance.owner,
+            mint: token_balance.mint,
+            max_bet_override,
+        });
+        Ok(())
+    }
+
+    // =====================
+    // Timelocked Admin Instructions
+    // =====================
+
+    /// Raise the delay between proposing and executing fee, limit and price feed changes
+    /// AUTHORITY ONLY - lowering it must itself go through propose_admin_change
+    pub fn set_admin_timelock(ctx: Context<SetAdminTimelock>, seconds: i64) -> Result<()> {
+        let game_state = &mut ctx.accounts.game_state;
+
+        // SECURITY: A hot key may lengthen the delay instantly, never shorten it
+        require!(
+            seconds >= game_state.admin_timelock_seconds && seconds <= MAX_ADMIN_TIMELOCK_SECONDS,
+            SessionBettingError::InvalidTimelock
+        );
+
+        game_state.admin_timelock_seconds = seconds;
+        Ok(())
+    }
+
+    /// Step 1: Queue a sensitive admin change, executable after admin_timelock_seconds
+    /// AUTHORITY ONLY - replaces any change already queued
+    pub fn propose_admin_change(ctx: Context<ProposeAdminChange>, change: AdminChange) -> Result<()> {
+        let game_state = &mut ctx.accounts.game_state;
+
+        validate_admin_change(&change)?;
+
+        let execute_after = Clock::get()?.unix_timestamp
+            .checked_add(game_state.admin_timelock_seconds)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        game_state.pending_admin_change = Some(change);
+        game_state.pending_admin_change_at = execute_after;
+
+        emit_cpi!(AdminChangeProposed {
+            change,
+            execute_after,
+        });
+        Ok(())
+    }
+
+    /// Step 2: Apply the queued admin change once its delay has passed
+    /// AUTHORITY ONLY - switchboard feed and market activation changes must pass the target
+    /// market; jackpot config changes must pass the jackpot
+    pub fn execute_admin_change(ctx: Context<ExecuteAdminChange>) -> Result<()> {
+        let game_state = &mut ctx.accounts.game_state;
+
+        // SECURITY: Must have a queued change
+        let change = game_state.pending_admin_change
+            .ok_or(SessionBettingError::NoPendingAdminChange)?;
+
+        // SECURITY: Users get the full delay to react before it applies
+        require!(
+            Clock::get()?.unix_timestamp >= game_state.pending_admin_change_at,
+            SessionBettingError::TimelockNotElapsed
+        );
+
+        match change {
+            AdminChange::FeeBps { fee_bps } => game_state.fee_bps = fee_bps,
+            AdminChange::CancelFeeBps { cancel_fee_bps } => game_state.cancel_fee_bps = cancel_fee_bps,
+            AdminChange::BetLimits { min_bet, max_bet } => {
+                game_state.min_bet = min_bet;
+                game_state.max_bet = max_bet;
+            }
+            AdminChange::SwitchboardFeed { market, switchboard_feed } => {
+                let target = ctx.accounts.market.as_mut()
+                    .ok_or(SessionBettingError::WrongMarket)?;
+                require_keys_eq!(target.key(), market, SessionBettingError::WrongMarket);
+                target.switchboard_feed = switchboard_feed;
+            }
+            AdminChange::MarketCadence { market, round_duration, lock_buffer } => {
+                let target = ctx.accounts.market.as_mut()
+                    .ok_or(SessionBettingError::WrongMarket)?;
+                require_keys_eq!(target.key(), market, SessionBettingError::WrongMarket);
+                target.round_duration = round_duration;
+                target.lock_buffer = lock_buffer;
+            }
+            AdminChange::MarketFixedOdds { market, fixed_odds_bps, max_round_exposure } => {
+                let target = ctx.accounts.market.as_mut()
+                    .ok_or(SessionBettingError::WrongMarket)?;
+                require_keys_eq!(target.key(), market, SessionBettingError::WrongMarket);
+                target.fixed_odds_bps = fixed_odds_bps;
+                target.max_round_exposure = max_round_exposure;
+            }
+            AdminChange::Timelock { seconds } => game_state.admin_timelock_seconds = seconds,
+            AdminChange::AddGameProgram { program_id } => add_game_program_entry(game_state, program_id)?,
+            AdminChange::ActivateMarket { market } => {
+                let target = ctx.accounts.market.as_mut()
+                    .ok_or(SessionBettingError::WrongMarket)?;
+                require_keys_eq!(target.key(), market, SessionBettingError::WrongMarket);
+                target.is_active = true;
+            }
+            AdminChange::PoolCaps { max_total_pool, max_side_pool, max_player_stake } => {
+                game_state.max_total_pool = max_total_pool;
+                game_state.max_side_pool = max_side_pool;
+                game_state.max_player_stake = max_player_stake;
+            }
+            AdminChange::TokenBetLimits { limits } => game_state.token_limits = limits,
+            AdminChange::CircuitBreaker { max_price_move_bps } => {
+                game_state.max_price_move_bps = max_price_move_bps;
+            }
+            AdminChange::MaxConfBps { max_conf_bps } => game_state.max_conf_bps = max_conf_bps,
+            AdminChange::MinPoolForSettlement { min_pool } => game_state.min_pool_for_settlement = min_pool,
+            AdminChange::OracleMode { mode } => game_state.oracle_mode = mode,
+            AdminChange::RoundDurationBounds { min_duration, max_duration } => {
+                game_state.min_round_duration = min_duration;
+                game_state.max_round_duration = max_duration;
+            }
+            AdminChange::Operator { operator } => game_state.operator = operator,
+            AdminChange::SessionRateLimit { session_bets_per_minute } => {
+                game_state.session_bets_per_minute = session_bets_per_minute;
+            }
+            AdminChange::StreakBonus { streak_bonus_bps_per_win, max_streak_bonus_bps } => {
+                game_state.streak_bonus_bps_per_win = streak_bonus_bps_per_win;
+                game_state.max_streak_bonus_bps = max_streak_bonus_bps;
+            }
+            AdminChange::EarlyBirdBonus { max_early_bird_bonus_bps } => {
+                game_state.max_early_bird_bonus_bps = max_early_bird_bonus_bps;
+            }
+            AdminChange::JackpotConfig { contribution_bps, trigger_move_bps, trigger_every_n_rounds } => {
+                let jackpot = ctx.accounts.jackpot.as_mut()
+                    .ok_or(SessionBettingError::InvalidJackpotConfig)?;
+                jackpot.contribution_bps = contribution_bps;
+                jackpot.trigger_move_bps = trigger_move_bps;
+                jackpot.trigger_every_n_rounds = trigger_every_n_rounds;
+            }
+        }
+
+        game_state.pending_admin_change = None;
+        game_state.pending_admin_change_at = 0;
+
+        emit_cpi!(AdminChangeExecuted { change });
+        Ok(())
+    }
+
+    /// Drop the queued admin change
+    /// AUTHORITY ONLY
+    pub fn cancel_admin_change(ctx: Context<CancelAdminChange>) -> Result<()> {
+        let game_state = &mut ctx.accounts.game_state;
+
+        // SECURITY: Must have a queued change to cancel
+        require!(
+            game_state.pending_admin_change.is_some(),
+            SessionBettingError::NoPendingAdminChange
+        );
+
+        game_state.pending_admin_change = None;
+        game_state.pending_admin_change_at = 0;
+        Ok(())
     }
 
     // =====================
@@ -319,6 +1488,16 @@ pub mod session_betting {
     /// Step 1: Current authority proposes a new authority
     /// SECURITY: Two-step transfer prevents accidental lockout
     pub fn propose_authority_transfer(ctx: Context<ProposeAuthorityTransfer>, new_authority: Pubkey) -> Result<()> {
+        // SECURITY: Treasury paths must come from the Squads vault when required
+        require!(
+            admin_guard::admin_path_ok(
+                &ctx.accounts.authority.key(),
+                ctx.accounts.admin_multisig.as_deref(),
+                ctx.accounts.game_state.require_multisig,
+            ),
+            SessionBettingError::MultisigRequired
+        );
+
         let game_state = &mut ctx.accounts.game_state;
 
         // SECURITY: New authority cannot be zero address
@@ -329,7 +1508,7 @@ pub mod session_betting {
 
         game_state.pending_authority = Some(new_authority);
 
-        emit!(AuthorityTransferProposed {
+        emit_cpi!(AuthorityTransferProposed {
             current_authority: game_state.authority,
             pending_authority: new_authority,
         });
@@ -355,7 +1534,7 @@ pub mod session_betting {
         game_state.authority = pending;
         game_state.pending_authority = None;
 
-        emit!(AuthorityTransferred {
+        emit_cpi!(AuthorityTransferred {
             old_authority,
             new_authority: pending,
         });
@@ -383,9 +1562,24 @@ pub mod session_betting {
 
     /// Withdraw collected platform fees to authority wallet
     /// SECURITY: Authority only, tracks withdrawal amount
-    pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+    /// With a fee split configured, the amount is divided among its recipients instead,
+    /// passed in split order via remaining_accounts
+    pub fn withdraw_fees<'info>(ctx: Context<'_, '_, 'info, 'info, WithdrawFees<'info>>, amount: u64) -> Result<()> {
+        // SECURITY: Treasury paths must come from the Squads vault when required
+        require!(
+            admin_guard::admin_path_ok(
+                &ctx.accounts.authority.key(),
+                ctx.accounts.admin_multisig.as_deref(),
+                ctx.accounts.game_state.require_multisig,
+            ),
+            SessionBettingError::MultisigRequired
+        );
+
         let game_state = &mut ctx.accounts.game_state;
 
+        // SECURITY: While a stream is configured, fees only leave through it
+        require!(game_state.fee_stream_rate == 0, SessionBettingError::FeeStreamActive);
+
         // SECURITY: Amount must be positive
         require!(amount > 0, SessionBettingError::AmountTooSmall);
 
@@ -406,22 +1600,57 @@ pub mod session_betting {
             .checked_sub(amount)
             .ok_or(SessionBettingError::MathOverflow)?;
 
-        // Transfer from global vault to authority
+        // SECURITY: Fees never come out of wagers reserved for players
+        require_solvent(game_state, ctx.accounts.global_vault.lamports(), amount)?;
+
+        // Transfer from global vault to authority, or across the fee split
         let bump = ctx.bumps.global_vault;
         let seeds: &[&[u8]] = &[b"global_vault", &[bump]];
         let signer_seeds = &[seeds];
 
-        let cpi_context = CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.global_vault.to_account_info(),
-                to: ctx.accounts.authority.to_account_info(),
-            },
-            signer_seeds,
-        );
-        transfer(cpi_context, amount)?;
+        let split = game_state.fee_split.clone();
+        let mut payouts: Vec<(AccountInfo<'info>, u64)> = Vec::with_capacity(split.len().max(1));
+        if split.is_empty() {
+            payouts.push((ctx.accounts.authority.to_account_info(), amount));
+        } else {
+            let mut remaining = amount;
+            for (i, entry) in split.iter().enumerate() {
+                let recipient = ctx.remaining_accounts
+                    .get(i)
+                    .ok_or(SessionBettingError::FeeSplitMismatch)?;
+
+                // SECURITY: Fees only go to the recipients the authority configured
+                require_keys_eq!(recipient.key(), entry.recipient, SessionBettingError::FeeSplitMismatch);
+
+                // The last recipient takes the rounding dust
+                let share = if i + 1 == split.len() {
+                    remaining
+                } else {
+                    bet_math::bps_of(amount, entry.bps).ok_or(SessionBettingError::MathOverflow)?
+                };
+                remaining = remaining
+                    .checked_sub(share)
+                    .ok_or(SessionBettingError::MathOverflow)?;
+                payouts.push((recipient.clone(), share));
+            }
+        }
+
+        for (recipient, share) in payouts {
+            if share == 0 {
+                continue;
+            }
+            let cpi_context = CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.global_vault.to_account_info(),
+                    to: recipient,
+                },
+                signer_seeds,
+            );
+            transfer(cpi_context, share)?;
+        }
 
-        emit!(FeesWithdrawn {
+        emit_cpi!(FeesWithdrawn {
             authority: ctx.accounts.authority.key(),
             amount,
             remaining_fees: game_state.total_fees_collected,
@@ -429,103 +1658,233 @@ pub mod session_betting {
         Ok(())
     }
 
-    // =====================
-    // Game Settlement Instructions (Authority Only)
-    // =====================
+    /// Route withdraw_fees across up to MAX_FEE_SPLIT_RECIPIENTS wallets (e.g. treasury/insurance/buyback)
+    /// AUTHORITY ONLY - shares must total 100%; an empty split pays the authority as before
+    pub fn set_fee_split(ctx: Context<SetFeeSplit>, fee_split: Vec<FeeSplitEntry>) -> Result<()> {
+        // SECURITY: Treasury paths must come from the Squads vault when required
+        require!(
+            admin_guard::admin_path_ok(
+                &ctx.accounts.authority.key(),
+                ctx.accounts.admin_multisig.as_deref(),
+                ctx.accounts.game_state.require_multisig,
+            ),
+            SessionBettingError::MultisigRequired
+        );
 
-    /// Transfer lamports from user's vault to global vault
-    /// Used when user loses a game - their entry fee/bet goes to the pool
-    /// AUTHORITY ONLY - backend calls this during settlement
-    pub fn transfer_to_global_vault(ctx: Context<TransferToGlobalVault>, amount: u64, game_type: GameType) -> Result<()> {
-        // SECURITY: User must have sufficient balance
-        let user_balance = &mut ctx.accounts.user_balance;
         require!(
-            user_balance.balance >= amount,
-            SessionBettingError::InsufficientBalance
+            fee_split.len() <= MAX_FEE_SPLIT_RECIPIENTS,
+            SessionBettingError::InvalidFeeSplit
+        );
+        if !fee_split.is_empty() {
+            let mut total_bps: u64 = 0;
+            for entry in &fee_split {
+                // SECURITY: No zero-address recipients or empty shares
+                require!(
+                    entry.recipient != Pubkey::default() && entry.bps > 0,
+                    SessionBettingError::InvalidFeeSplit
+                );
+                total_bps = total_bps
+                    .checked_add(entry.bps)
+                    .ok_or(SessionBettingError::MathOverflow)?;
+            }
+            require!(total_bps == BPS_DENOMINATOR, SessionBettingError::InvalidFeeSplit);
+        }
+
+        ctx.accounts.game_state.fee_split = fee_split;
+        Ok(())
+    }
+
+    /// Stream collected fees to a treasury at a fixed rate instead of lump-sum withdrawals
+    /// AUTHORITY ONLY - a rate of 0 stops the stream and re-enables withdraw_fees
+    pub fn configure_fee_stream(ctx: Context<ConfigureFeeStream>, recipient: Pubkey, rate_per_second: u64) -> Result<()> {
+        // SECURITY: Treasury paths must come from the Squads vault when required
+        require!(
+            admin_guard::admin_path_ok(
+                &ctx.accounts.authority.key(),
+                ctx.accounts.admin_multisig.as_deref(),
+                ctx.accounts.game_state.require_multisig,
+            ),
+            SessionBettingError::MultisigRequired
         );
 
-        // Update balance BEFORE transfer (reentrancy protection)
-        user_balance.balance = user_balance.balance
+        // SECURITY: Streamed fees can't be sent to the zero address
+        require!(
+            rate_per_second == 0 || recipient != Pubkey::default(),
+            SessionBettingError::InvalidAuthority
+        );
+
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.fee_stream_recipient = recipient;
+        game_state.fee_stream_rate = rate_per_second;
+        game_state.fee_stream_last_at = Clock::get()?.unix_timestamp;
+
+        emit_cpi!(FeeStreamConfigured {
+            recipient,
+            rate_per_second,
+        });
+        Ok(())
+    }
+
+    /// Pay the fees accrued by the stream since the last crank to the treasury
+    /// Permissionless - funds can only go to the configured recipient
+    /// Accrual while no fees are available is dropped, so the stream never bursts later
+    pub fn crank_fee_stream(ctx: Context<CrankFeeStream>) -> Result<()> {
+        let game_state = &mut ctx.accounts.game_state;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(game_state.fee_stream_rate > 0, SessionBettingError::FeeStreamInactive);
+
+        let elapsed = now.saturating_sub(game_state.fee_stream_last_at) as u64;
+        let accrued = game_state.fee_stream_rate.saturating_mul(elapsed);
+        // SECURITY: Streamed fees never come out of wagers reserved for players
+        let spendable = ctx.accounts.global_vault.lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(0))
+            .saturating_sub(game_state.reserved_payouts);
+        let amount = accrued
+            .min(game_state.total_fees_collected)
+            .min(spendable);
+
+        game_state.fee_stream_last_at = now;
+        if amount == 0 {
+            return Ok(());
+        }
+
+        // Update fees collected BEFORE transfer (reentrancy protection)
+        game_state.total_fees_collected = game_state.total_fees_collected
             .checked_sub(amount)
             .ok_or(SessionBettingError::MathOverflow)?;
 
-        // Transfer from user's vault to global vault
-        let owner_key = ctx.accounts.owner.key();
-        let seeds = &[
-            b"vault",
-            owner_key.as_ref(),
-            &[ctx.bumps.user_vault],
-        ];
-        let signer_seeds = &[&seeds[..]];
+        let bump = ctx.bumps.global_vault;
+        let seeds: &[&[u8]] = &[b"global_vault", &[bump]];
+        let signer_seeds = &[seeds];
 
         let cpi_context = CpiContext::new_with_signer(
             ctx.accounts.system_program.to_account_info(),
             Transfer {
-                from: ctx.accounts.user_vault.to_account_info(),
-                to: ctx.accounts.global_vault.to_account_info(),
+                from: ctx.accounts.global_vault.to_account_info(),
+                to: ctx.accounts.recipient.to_account_info(),
             },
             signer_seeds,
         );
         transfer(cpi_context, amount)?;
 
-        // Emit audit event
-        let clock = Clock::get()?;
-        emit!(FundsLocked {
-            user: ctx.accounts.owner.key(),
+        emit_cpi!(FeesStreamed {
+            recipient: ctx.accounts.recipient.key(),
             amount,
-            game_mode: game_type,
-            timestamp: clock.unix_timestamp,
+            remaining_fees: game_state.total_fees_collected,
         });
-
         Ok(())
     }
 
-    /// Credit winnings to user's balance and transfer lamports from global vault
-    /// AUTHORITY ONLY - backend calls this to pay out winners
-    pub fn credit_winnings(
-        ctx: Context<CreditWinnings>,
-        amount: u64,
-        game_type: GameType,
-        game_id: [u8; 32],
+    // =====================
+    // Game Settlement Instructions (Authority Only)
+    // =====================
+
+    /// Transfer lamports from user's vault to global vault
+    /// Used when user loses a game - their entry fee/bet goes to the pool
+    /// AUTHORITY ONLY - backend calls this during settlement
+    pub fn transfer_to_global_vault(ctx: Context<TransferToGlobalVault>, amount: u64, game_type: GameType) -> Result<()> {
+        lock_wager(
+            &mut ctx.accounts.game_state,
+            &mut ctx.accounts.user_balance,
+            &ctx.accounts.user_vault,
+            &ctx.accounts.game_vault,
+            &ctx.accounts.system_program,
+            game_type,
+            ctx.bumps.user_vault,
+            amount,
+        )?;
+        record_game_type_stats(&mut ctx.accounts.game_state, game_type, amount, 0, 0)?;
+
+        // Emit audit event
+        let clock = Clock::get()?;
+        emit_cpi!(FundsLocked {
+            user: ctx.accounts.owner.key(),
+            amount,
+            game_mode: game_type,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Credit winnings to user's balance and transfer lamports from global vault
+    /// AUTHORITY ONLY - backend calls this to pay out winners
+    pub fn credit_winnings(
+        ctx: Context<CreditWinnings>,
+        amount: u64,
+        game_type: GameType,
+        game_id: [u8; 32],
     ) -> Result<()> {
-        // SECURITY: Amount must be positive
-        require!(amount > 0, SessionBettingError::AmountTooSmall);
+        pay_winnings(
+            &mut ctx.accounts.game_state,
+            &mut ctx.accounts.user_balance,
+            &ctx.accounts.user_vault,
+            &ctx.accounts.game_vault,
+            &ctx.accounts.system_program,
+            game_type,
+            ctx.bumps.game_vault,
+            amount,
+        )?;
+        record_game_type_stats(&mut ctx.accounts.game_state, game_type, 0, 0, amount)?;
 
-        // SECURITY: Global vault must have sufficient balance to pay out
-        require!(
-            ctx.accounts.global_vault.lamports() >= amount,
-            SessionBettingError::InsufficientVaultBalance
-        );
+        emit_cpi!(WinningsCredited {
+            user: ctx.accounts.owner.key(),
+            amount,
+            game_type,
+            game_id,
+        });
+        Ok(())
+    }
 
-        let user_balance = &mut ctx.accounts.user_balance;
+    /// Lock a user's wager in the global vault on behalf of an allowlisted game program
+    /// CPI ONLY - signed by the game program's `game_signer` PDA; same accounting as transfer_to_global_vault
+    pub fn debit_for_game(ctx: Context<DebitForGame>, amount: u64, game_type: GameType) -> Result<()> {
+        lock_wager(
+            &mut ctx.accounts.game_state,
+            &mut ctx.accounts.user_balance,
+            &ctx.accounts.user_vault,
+            &ctx.accounts.game_vault,
+            &ctx.accounts.system_program,
+            game_type,
+            ctx.bumps.user_vault,
+            amount,
+        )?;
+        record_game_type_stats(&mut ctx.accounts.game_state, game_type, amount, 0, 0)?;
 
-        // Credit to user balance
-        user_balance.balance = user_balance.balance
-            .checked_add(amount)
-            .ok_or(SessionBettingError::MathOverflow)?;
-        user_balance.total_winnings = user_balance.total_winnings
-            .checked_add(amount)
-            .ok_or(SessionBettingError::MathOverflow)?;
+        // Emit audit event
+        let clock = Clock::get()?;
+        emit_cpi!(FundsLocked {
+            user: ctx.accounts.owner.key(),
+            amount,
+            game_mode: game_type,
+            timestamp: clock.unix_timestamp,
+        });
 
-        // Transfer from global vault to user's vault
-        let bump = ctx.bumps.global_vault;
-        let seeds: &[&[u8]] = &[
-            b"global_vault",
-            &[bump],
-        ];
-        let signer_seeds = &[seeds];
+        Ok(())
+    }
 
-        let cpi_context = CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.global_vault.to_account_info(),
-                to: ctx.accounts.user_vault.to_account_info(),
-            },
-            signer_seeds,
-        );
-        transfer(cpi_context, amount)?;
+    /// Pay winnings into a user's balance on behalf of an allowlisted game program
+    /// CPI ONLY - signed by the game program's `game_signer` PDA; same accounting as credit_winnings
+    pub fn credit_for_game(
+        ctx: Context<CreditForGame>,
+        amount: u64,
+        game_type: GameType,
+        game_id: [u8; 32],
+    ) -> Result<()> {
+        pay_winnings(
+            &mut ctx.accounts.game_state,
+            &mut ctx.accounts.user_balance,
+            &ctx.accounts.user_vault,
+            &ctx.accounts.game_vault,
+            &ctx.accounts.system_program,
+            game_type,
+            ctx.bumps.game_vault,
+            amount,
+        )?;
+        record_game_type_stats(&mut ctx.accounts.game_state, game_type, 0, 0, amount)?;
 
-        emit!(WinningsCredited {
+        emit_cpi!(WinningsCredited {
             user: ctx.accounts.owner.key(),
             amount,
             game_type,
@@ -548,13 +1907,193 @@ pub mod session_betting {
         Ok(())
     }
 
+    /// Fund one game type's vault (authority deposits funds for that game's payouts)
+    /// AUTHORITY ONLY - the first deposit must cover the vault's rent-exempt minimum
+    pub fn fund_game_vault(ctx: Context<FundGameVault>, game_type: GameType, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.authority.to_account_info(),
+                to: ctx.accounts.game_vault.to_account_info(),
+            },
+        );
+        transfer(cpi_context, amount)?;
+
+        emit_cpi!(GameVaultFunded {
+            game_type,
+            amount,
+            from_global_vault: false,
+        });
+        Ok(())
+    }
+
+    /// Move wagers locked in the shared global vault before game vaults existed into their game vault
+    /// AUTHORITY ONLY - reserved_payouts shrinks and the game vault's reservation grows by `amount`
+    pub fn move_reserved_to_game_vault(
+        ctx: Context<MoveReservedToGameVault>,
+        game_type: GameType,
+        amount: u64,
+    ) -> Result<()> {
+        let game_state = &mut ctx.accounts.game_state;
+
+        // SECURITY: Only wagers actually reserved in the global vault can move
+        game_state.reserved_payouts = game_state.reserved_payouts
+            .checked_sub(amount)
+            .ok_or(SessionBettingError::InsufficientVaultBalance)?;
+        let reserved = &mut game_state.game_vault_reserved[game_type as usize];
+        *reserved = reserved
+            .checked_add(amount)
+            .ok_or(SessionBettingError::MathOverflow)?;
+
+        // SECURITY: Fees and the remaining reservations stay covered
+        require_solvent(game_state, ctx.accounts.global_vault.lamports(), amount)?;
+
+        let bump = ctx.bumps.global_vault;
+        let seeds: &[&[u8]] = &[b"global_vault", &[bump]];
+        let signer_seeds = &[seeds];
+
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.global_vault.to_account_info(),
+                to: ctx.accounts.game_vault.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(cpi_context, amount)?;
+
+        emit_cpi!(GameVaultFunded {
+            game_type,
+            amount,
+            from_global_vault: true,
+        });
+        Ok(())
+    }
+
+    /// Check the global vault covers everything it owes: uncollected fees plus reserved wagers
+    /// Permissionless - fails with VaultInsolvent otherwise; returns the surplus via return data
+    pub fn assert_solvency(ctx: Context<AssertSolvency>) -> Result<u64> {
+        let lamports = ctx.accounts.global_vault.lamports();
+
+        require_solvent(&ctx.accounts.game_state, lamports, 0)?;
+        Ok(lamports.saturating_sub(global_vault_obligations(&ctx.accounts.game_state)?))
+    }
+
+    // =====================
+    // Account Migration Instructions
+    // =====================
+
+    /// Grow GameState to the current layout and stamp GAME_STATE_VERSION
+    /// AUTHORITY ONLY - fields added since the account was created were carved from the
+    /// zeroed reserve, so they read as zero; the account is rewritten with no stale tail
+    /// A v0 account keeps its original fields and gets launch defaults for the rest plus the
+    /// jackpot; its feed then needs a market from create_market
+    pub fn migrate_game_state(ctx: Context<MigrateGameState>) -> Result<()> {
+        let info = ctx.accounts.game_state.to_account_info();
+        let is_v0 = info.data_len() == 8 + GAME_STATE_V0_SPACE;
+        grow_account(
+            &info,
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+            8 + GameState::INIT_SPACE,
+        )?;
+
+        let (mut game_state, from_version) = if is_v0 {
+            let v0: GameStateV0 = read_v0_account(&info, GameState::DISCRIMINATOR)?;
+            let mut game_state = new_game_state(v0.authority, v0.price_feed_id, v0.bump);
+            game_state.pending_authority = v0.pending_authority;
+            game_state.current_round = v0.current_round;
+            game_state.total_volume = v0.total_volume;
+            game_state.total_fees_collected = v0.total_fees_collected;
+            game_state.is_paused = v0.is_paused;
+            // SECURITY: Everything in the vault beyond its rent and the fees is v0 wagers owed
+            // to players, so fee withdrawals can never reach it
+            game_state.reserved_payouts = ctx.accounts.global_vault.lamports()
+                .saturating_sub(Rent::get()?.minimum_balance(0))
+                .saturating_sub(v0.total_fees_collected);
+            (game_state, 0)
+        } else {
+            let game_state = GameState::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+            let from_version = game_state.version;
+            (game_state, from_version)
+        };
+        require_keys_eq!(
+            game_state.authority,
+            ctx.accounts.authority.key(),
+            SessionBettingError::Unauthorized
+        );
+        game_state.version = GAME_STATE_VERSION;
+        write_account(&info, &game_state)?;
+
+        if ctx.accounts.jackpot.version == 0 {
+            init_jackpot(&mut ctx.accounts.jackpot, ctx.bumps.jackpot);
+        }
+
+        emit_cpi!(AccountMigrated {
+            account: info.key(),
+            from_version,
+            to_version: GAME_STATE_VERSION,
+        });
+        Ok(())
+    }
+
+    /// Grow a UserBalance to the current layout and stamp USER_BALANCE_VERSION
+    /// Permissionless - the payer covers the extra rent; balances are never touched
+    pub fn migrate_user_balance(ctx: Context<MigrateUserBalance>) -> Result<()> {
+        let info = ctx.accounts.user_balance.to_account_info();
+        let is_v0 = info.data_len() == 8 + USER_BALANCE_V0_SPACE;
+        grow_account(
+            &info,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            8 + UserBalance::INIT_SPACE,
+        )?;
+
+        let mut user_balance = if is_v0 {
+            let v0: UserBalanceV0 = read_v0_account(&info, UserBalance::DISCRIMINATOR)?;
+            UserBalance {
+                owner: v0.owner,
+                balance: v0.balance,
+                total_deposited: v0.total_deposited,
+                total_withdrawn: v0.total_withdrawn,
+                total_winnings: v0.total_winnings,
+                bump: v0.bump,
+                version: 0,
+                copy_opt_out: false,
+                eligibility: EligibilityStatus::Unset,
+                max_bet_override: 0,
+                personal_limits: PersonalLimits::default(),
+            }
+        } else {
+            UserBalance::try_deserialize(&mut &info.try_borrow_data()?[..])?
+        };
+        let from_version = user_balance.version;
+        user_balance.version = USER_BALANCE_VERSION;
+        write_account(&info, &user_balance)?;
+
+        emit_cpi!(AccountMigrated {
+            account: info.key(),
+            from_version,
+            to_version: USER_BALANCE_VERSION,
+        });
+        Ok(())
+    }
+
     // =====================
     // Session Key Instructions
     // =====================
 
     /// Create a session token that authorizes a temporary key to act on behalf of the user
     /// REQUIRES wallet signature to create the session
-    pub fn create_session(ctx: Context<CreateSession>, valid_until: i64) -> Result<()> {
+    /// The key may only call the ENTRYPOINT_* bits in allowed_instructions, staking at most
+    /// max_bet_per_tx per call and spend_limit lamports in total (0 = no cap)
+    pub fn create_session(
+        ctx: Context<CreateSession>,
+        valid_until: i64,
+        max_bet_per_tx: u64,
+        allowed_instructions: u64,
+        spend_limit: u64,
+    ) -> Result<()> {
         let session = &mut ctx.accounts.session_token;
         let clock = Clock::get()?;
 
@@ -570,13 +2109,78 @@ pub mod session_betting {
         session.authority = ctx.accounts.authority.key();
         session.session_signer = ctx.accounts.session_signer.key();
         session.valid_until = valid_until;
+        session.max_bet_per_tx = max_bet_per_tx;
+        session.allowed_instructions = allowed_instructions;
+        session.spend_limit = spend_limit;
+        session.spent = 0;
+        session.token_spend_limit = 0;
+        session.token_spent = 0;
         session.bump = ctx.bumps.session_token;
+        session.version = ACCOUNT_VERSION;
+
+        let registry = &mut ctx.accounts.session_registry;
+        if registry.owner == Pubkey::default() {
+            registry.owner = ctx.accounts.authority.key();
+            registry.bump = ctx.bumps.session_registry;
+            registry.version = ACCOUNT_VERSION;
+        }
+        require!(
+            registry.signers.len() < MAX_SESSIONS_PER_USER,
+            SessionBettingError::TooManySessions
+        );
+        registry.signers.push(session.session_signer);
+        session.generation = registry.generation;
+        Ok(())
+    }
+
+    /// Let a session key stake up to token_spend_limit of game_state.token_mint in total
+    /// REQUIRES wallet signature - NEVER session key
+    /// Sessions start at 0, which keeps the key off the token path entirely
+    pub fn set_session_token_limit(ctx: Context<SetSessionTokenLimit>, token_spend_limit: u64) -> Result<()> {
+        ctx.accounts.session_token.token_spend_limit = token_spend_limit;
         Ok(())
     }
 
     /// Revoke a session token (wallet signature required)
-    pub fn revoke_session(_ctx: Context<RevokeSession>) -> Result<()> {
+    /// Pass the session registry to free the signer's slot in it
+    pub fn revoke_session(ctx: Context<RevokeSession>) -> Result<()> {
         // The account will be closed and rent returned to authority
+        if let Some(registry) = &mut ctx.accounts.session_registry {
+            let signer = ctx.accounts.session_token.session_signer;
+            registry.signers.retain(|key| *key != signer);
+        }
+        Ok(())
+    }
+
+    /// Invalidate every session key the user has issued in one call (wallet signature required)
+    /// Bumps the registry generation so older SessionTokens fail verification; their
+    /// accounts can still be closed with revoke_session to reclaim rent
+    pub fn revoke_all_sessions(ctx: Context<RevokeAllSessions>) -> Result<()> {
+        let registry = &mut ctx.accounts.session_registry;
+
+        registry.generation = registry.generation
+            .checked_add(1)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        registry.signers.clear();
+        Ok(())
+    }
+
+    /// Move a session's expiry without recreating it (wallet signature required)
+    /// Scope, spend limit and spent carry over; works on expired sessions too
+    pub fn extend_session(ctx: Context<ExtendSession>, new_valid_until: i64) -> Result<()> {
+        let session = &mut ctx.accounts.session_token;
+        let clock = Clock::get()?;
+
+        // SECURITY: Same duration bound as create_session, measured from now
+        let duration = new_valid_until.checked_sub(clock.unix_timestamp)
+            .ok_or(SessionBettingError::InvalidSessionDuration)?;
+        require!(duration > 0, SessionBettingError::InvalidSessionDuration);
+        require!(
+            duration <= MAX_SESSION_DURATION_SECONDS,
+            SessionBettingError::SessionTooLong
+        );
+
+        session.valid_until = new_valid_until;
         Ok(())
     }
 
@@ -591,121 +2195,344 @@ pub mod session_betting {
         require!(!ctx.accounts.game_state.is_paused, SessionBettingError::GamePaused);
 
         // SECURITY: Minimum deposit check
-        require!(amount >= MIN_BET, SessionBettingError::AmountTooSmall);
-
-        // SECURITY: Transfer SOL from user to vault
-        let cpi_context = CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.user.to_account_info(),
-                to: ctx.accounts.vault.to_account_info(),
-            },
-        );
-        transfer(cpi_context, amount)?;
-
-        // Update balance
-        let user_balance = &mut ctx.accounts.user_balance;
-        user_balance.owner = ctx.accounts.user.key();
-        user_balance.balance = user_balance.balance
-            .checked_add(amount)
-            .ok_or(SessionBettingError::MathOverflow)?;
-        user_balance.total_deposited = user_balance.total_deposited
-            .checked_add(amount)
-            .ok_or(SessionBettingError::MathOverflow)?;
-        user_balance.bump = ctx.bumps.user_balance;
+        require!(amount >= ctx.accounts.game_state.min_bet, SessionBettingError::AmountTooSmall);
+
+        credit_deposit(
+            &mut ctx.accounts.user_balance,
+            &ctx.accounts.vault,
+            &ctx.accounts.user,
+            ctx.accounts.user.key(),
+            &ctx.accounts.system_program,
+            ctx.bumps.user_balance,
+            amount,
+        )?;
 
         // Emit audit event
         let clock = Clock::get()?;
-        emit!(FundsDeposited {
+        emit_cpi!(FundsDeposited {
             user: ctx.accounts.user.key(),
             amount,
-            new_balance: user_balance.balance,
+            new_balance: ctx.accounts.user_balance.balance,
             timestamp: clock.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Withdraw SOL from user's balance account
-    /// CRITICAL SECURITY: REQUIRES wallet signature - NEVER session key
-    /// This prevents session key theft from draining funds
-    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
-        let user_balance = &mut ctx.accounts.user_balance;
+    /// First deposit that also links the wallet to a referrer in the referral program
+    /// REQUIRES wallet signature - the referrer then earns a share of this wallet's claim fees
+    pub fn deposit_with_referrer(
+        ctx: Context<DepositWithReferrer>,
+        amount: u64,
+        referrer: Pubkey,
+    ) -> Result<()> {
+        // SECURITY: Game not paused
+        require!(!ctx.accounts.game_state.is_paused, SessionBettingError::GamePaused);
 
-        // SECURITY: Check ownership (wallet must sign, not session)
-        require!(
-            user_balance.owner == ctx.accounts.user.key(),
-            SessionBettingError::NotBalanceOwner
-        );
+        // SECURITY: Minimum deposit check
+        require!(amount >= ctx.accounts.game_state.min_bet, SessionBettingError::AmountTooSmall);
 
-        // SECURITY: Check sufficient balance
+        // SECURITY: Referrers are only attached on a wallet's first deposit
         require!(
-            user_balance.balance >= amount,
-            SessionBettingError::InsufficientBalance
+            ctx.accounts.user_balance.total_deposited == 0,
+            SessionBettingError::NotFirstDeposit
         );
 
-        // SECURITY: Update state BEFORE transfer (reentrancy protection)
-        user_balance.balance = user_balance.balance
-            .checked_sub(amount)
-            .ok_or(SessionBettingError::MathOverflow)?;
-        user_balance.total_withdrawn = user_balance.total_withdrawn
-            .checked_add(amount)
-            .ok_or(SessionBettingError::MathOverflow)?;
-
-        // Transfer from vault to user (PDA signs)
-        let user_key = ctx.accounts.user.key();
-        let seeds = &[
-            b"vault",
-            user_key.as_ref(),
-            &[ctx.bumps.vault],
-        ];
-        let signer_seeds = &[&seeds[..]];
+        credit_deposit(
+            &mut ctx.accounts.user_balance,
+            &ctx.accounts.vault,
+            &ctx.accounts.user,
+            ctx.accounts.user.key(),
+            &ctx.accounts.system_program,
+            ctx.bumps.user_balance,
+            amount,
+        )?;
 
-        let cpi_context = CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.vault.to_account_info(),
-                to: ctx.accounts.user.to_account_info(),
+        // Referral program rejects self-referral and wallets that already have a referrer
+        let cpi_context = CpiContext::new(
+            ctx.accounts.referral_program.to_account_info(),
+            RegisterReferrer {
+                referral: ctx.accounts.referral.to_account_info(),
+                referrer_earnings: ctx.accounts.referrer_earnings.to_account_info(),
+                user: ctx.accounts.user.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                event_authority: ctx.accounts.referral_event_authority.to_account_info(),
+                program: ctx.accounts.referral_program.to_account_info(),
             },
-            signer_seeds,
         );
-        transfer(cpi_context, amount)?;
+        referral::cpi::register_referrer(cpi_context, referrer)?;
 
         // Emit audit event
         let clock = Clock::get()?;
-        emit!(FundsWithdrawn {
+        emit_cpi!(FundsDeposited {
             user: ctx.accounts.user.key(),
             amount,
+            new_balance: ctx.accounts.user_balance.balance,
             timestamp: clock.unix_timestamp,
         });
 
         Ok(())
     }
 
-    // =====================
-    // Betting Instructions (Session Key Enabled)
-    // =====================
-
-    /// Place a bet on UP or DOWN
-    /// Can use session key OR wallet signature
-    pub fn place_bet(ctx: Context<PlaceBet>, side: BetSide, amount: u64) -> Result<()> {
-        let user_balance = &mut ctx.accounts.user_balance;
-        let round = &ctx.accounts.round;
-        let pool = &mut ctx.accounts.pool;
-        let position = &mut ctx.accounts.position;
+    /// Fund another wallet's balance (promo credits, onboarding players without gas)
+    /// Any payer - the balance and vault still belong to `owner`, and only the owner can withdraw
+    pub fn deposit_for(ctx: Context<DepositFor>, owner: Pubkey, amount: u64) -> Result<()> {
+        // SECURITY: Game not paused
+        require!(!ctx.accounts.game_state.is_paused, SessionBettingError::GamePaused);
 
-        // SECURITY: Verify signer authority (session or wallet)
-        verify_session_or_authority(
-            &ctx.accounts.session_token,
-            &ctx.accounts.signer,
-            &user_balance.owner,
+        // SECURITY: Minimum deposit check
+        require!(amount >= ctx.accounts.game_state.min_bet, SessionBettingError::AmountTooSmall);
+
+        // SECURITY: A default owner would strand the deposit with no wallet able to withdraw
+        require!(owner != Pubkey::default(), SessionBettingError::InvalidAddress);
+
+        credit_deposit(
+            &mut ctx.accounts.user_balance,
+            &ctx.accounts.vault,
+            &ctx.accounts.payer,
+            owner,
+            &ctx.accounts.system_program,
+            ctx.bumps.user_balance,
+            amount,
         )?;
 
-        // SECURITY: Game not paused
-        require!(
-            !ctx.accounts.game_state.is_paused,
-            SessionBettingError::GamePaused
-        );
+        // Emit audit event
+        let clock = Clock::get()?;
+        emit_cpi!(FundsDepositedFor {
+            payer: ctx.accounts.payer.key(),
+            owner,
+            amount,
+            new_balance: ctx.accounts.user_balance.balance,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Withdraw SOL from user's balance account
+    /// CRITICAL SECURITY: REQUIRES wallet signature - NEVER session key
+    /// This prevents session key theft from draining funds
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        withdraw_from_vault(
+            &mut ctx.accounts.user_balance,
+            &ctx.accounts.vault,
+            &ctx.accounts.user,
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.system_program,
+            ctx.bumps.vault,
+            amount,
+        )?;
+
+        // Emit audit event
+        let clock = Clock::get()?;
+        emit_cpi!(FundsWithdrawn {
+            user: ctx.accounts.user.key(),
+            amount,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Withdraw the user's entire balance
+    /// CRITICAL SECURITY: REQUIRES wallet signature - NEVER session key
+    pub fn withdraw_all(ctx: Context<Withdraw>) -> Result<()> {
+        let amount = ctx.accounts.user_balance.balance;
+        require!(amount > 0, SessionBettingError::AmountTooSmall);
+
+        withdraw_from_vault(
+            &mut ctx.accounts.user_balance,
+            &ctx.accounts.vault,
+            &ctx.accounts.user,
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.system_program,
+            ctx.bumps.vault,
+            amount,
+        )?;
+
+        // Emit audit event
+        let clock = Clock::get()?;
+        emit_cpi!(FundsWithdrawn {
+            user: ctx.accounts.user.key(),
+            amount,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Fully exit: withdraw any remaining balance and close the balance account
+    /// CRITICAL SECURITY: REQUIRES wallet signature - NEVER session key
+    /// Balance rent is refunded to the owner; the vault disappears once it holds no lamports.
+    /// Unclaimed positions stay claimable after a new deposit recreates the account
+    pub fn close_balance(ctx: Context<CloseBalance>) -> Result<()> {
+        let user_balance = &ctx.accounts.user_balance;
+
+        // SECURITY: Reopening starts from a fresh account, so closing must not shed
+        // an authority block, a per-user cap or the player's own limits
+        require!(
+            user_balance.eligibility != EligibilityStatus::Blocked
+                && user_balance.max_bet_override == 0
+                && !user_balance.personal_limits.is_set(),
+            SessionBettingError::BalanceRestricted
+        );
+
+        let amount = user_balance.balance;
+        if amount > 0 {
+            withdraw_from_vault(
+                &mut ctx.accounts.user_balance,
+                &ctx.accounts.vault,
+                &ctx.accounts.user,
+                &ctx.accounts.user.to_account_info(),
+                &ctx.accounts.system_program,
+                ctx.bumps.vault,
+                amount,
+            )?;
+        }
+
+        // Emit audit event
+        let clock = Clock::get()?;
+        emit_cpi!(BalanceClosed {
+            user: ctx.accounts.user.key(),
+            withdrawn: amount,
+            timestamp: clock.unix_timestamp,
+        });
+
+        // Balance account is closed via the close constraint in CloseBalance
+        Ok(())
+    }
+
+    /// Withdraw SOL from user's balance account to another address (e.g. an exchange deposit)
+    /// CRITICAL SECURITY: REQUIRES wallet signature - NEVER session key
+    pub fn withdraw_to(ctx: Context<WithdrawTo>, destination: Pubkey, amount: u64) -> Result<()> {
+        // SECURITY: Paying the zero address would burn the withdrawal
+        require!(destination != Pubkey::default(), SessionBettingError::InvalidAddress);
+
+        withdraw_from_vault(
+            &mut ctx.accounts.user_balance,
+            &ctx.accounts.vault,
+            &ctx.accounts.user,
+            &ctx.accounts.recipient.to_account_info(),
+            &ctx.accounts.system_program,
+            ctx.bumps.vault,
+            amount,
+        )?;
+
+        // Emit audit event
+        let clock = Clock::get()?;
+        emit_cpi!(FundsWithdrawnTo {
+            user: ctx.accounts.user.key(),
+            destination,
+            amount,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Set responsible-gambling limits on the signer's own betting (0 = no limit)
+    /// REQUIRES wallet signature - NEVER session key
+    /// Tighter limits apply immediately; looser ones only after LIMIT_LOOSEN_COOLDOWN_SECONDS
+    /// Self-exclusion is handled by the shared compliance program's self_exclude
+    pub fn set_personal_limits(
+        ctx: Context<SetPersonalLimits>,
+        max_bet: u64,
+        max_daily_volume: u64,
+    ) -> Result<()> {
+        let user_balance = &mut ctx.accounts.user_balance;
+        let effective_at = queue_personal_limits(
+            &mut user_balance.personal_limits,
+            max_bet,
+            max_daily_volume,
+            Clock::get()?.unix_timestamp,
+        )?;
+
+        emit_cpi!(PersonalLimitsUpdated {
+            user: user_balance.owner,
+            max_bet,
+            max_daily_volume,
+            effective_at,
+        });
+
+        Ok(())
+    }
+
+    /// Set responsible-gambling limits on the signer's own token betting, in token_mint
+    /// base units (0 = no limit)
+    /// REQUIRES wallet signature - NEVER session key
+    /// Same cooldown rules as set_personal_limits; self-exclusion covers both paths
+    pub fn set_token_personal_limits(
+        ctx: Context<SetTokenPersonalLimits>,
+        max_bet: u64,
+        max_daily_volume: u64,
+    ) -> Result<()> {
+        let token_balance = &mut ctx.accounts.token_balance;
+        let effective_at = queue_personal_limits(
+            &mut token_balance.personal_limits,
+            max_bet,
+            max_daily_volume,
+            Clock::get()?.unix_timestamp,
+        )?;
+
+        emit_cpi!(TokenPersonalLimitsUpdated {
+            user: token_balance.owner,
+            mint: token_balance.mint,
+            max_bet,
+            max_daily_volume,
+            effective_at,
+        });
+
+        Ok(())
+    }
+
+    // =====================
+    // Betting Instructions (Session Key Enabled)
+    // =====================
+
+    /// Place a bet on UP or DOWN
+    /// Can use session key OR wallet signature
+    /// position_index picks which of the bettor's positions in this round to open (0 for a single bet)
+    pub fn place_bet(ctx: Context<PlaceBet>, side: BetSide, amount: u64, position_index: u8) -> Result<()> {
+        let user_balance = &mut ctx.accounts.user_balance;
+        let round = &ctx.accounts.round;
+        let pool = &mut ctx.accounts.pool;
+        let position = &mut ctx.accounts.position;
+        let player_round = &mut ctx.accounts.player_round;
+        init_player_round(player_round, user_balance.owner, round.round_id, ctx.bumps.player_round);
+
+        // SECURITY: Verify signer authority (session or wallet)
+        verify_session_or_authority(
+            &ctx.accounts.session_token,
+            &ctx.accounts.session_registry,
+            &ctx.accounts.signer,
+            &user_balance.owner,
+            ENTRYPOINT_PLACE_BET,
+            amount,
+        )?;
+        record_session_spend(
+            &mut ctx.accounts.session_token,
+            &ctx.accounts.signer,
+            amount,
+            ctx.accounts.game_state.session_bets_per_minute,
+        )?;
+
+        // SECURITY: Game not paused
+        require!(
+            !ctx.accounts.game_state.is_paused,
+            SessionBettingError::GamePaused
+        );
+
+        // SECURITY: Compliance eligibility
+        check_eligibility(&ctx.accounts.game_state, user_balance)?;
+        check_compliance(
+            &ctx.accounts.compliance_program,
+            &ctx.accounts.compliance_config,
+            &ctx.accounts.user_compliance,
+            user_balance.owner,
+        )?;
+
+        // Refuse entrypoints deprecated in the program registry
+        registry::require_not_deprecated(&ctx.accounts.program_entry, ENTRYPOINT_PLACE_BET)?;
 
         // SECURITY: Round must be open
         require!(round.status == RoundStatus::Open, SessionBettingError::RoundNotOpen);
@@ -718,8 +2545,15 @@ pub mod session_betting {
         );
 
         // SECURITY: Valid bet amount
-        require!(amount >= MIN_BET, SessionBettingError::AmountTooSmall);
-        require!(amount <= MAX_BET, SessionBettingError::AmountTooLarge);
+        require!(amount >= ctx.accounts.game_state.min_bet, SessionBettingError::AmountTooSmall);
+        require!(amount <= ctx.accounts.game_state.max_bet, SessionBettingError::AmountTooLarge);
+        check_personal_limits(user_balance, amount, amount, clock.unix_timestamp)?;
+
+        // SECURITY: Per-round pool caps, with the player cap on the wallet's whole stake in the round
+        let player_stake = player_round_stake(player_round)?
+            .checked_add(amount)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        check_pool_caps(&ctx.accounts.game_state, pool, side, amount, player_stake)?;
 
         // SECURITY: Sufficient balance
         require!(
@@ -734,31 +2568,101 @@ pub mod session_betting {
 
         // Record position
         position.player = user_balance.owner;
+        position.bettor = user_balance.owner;
         position.round_id = round.round_id;
+        position.index = position_index;
         position.side = side;
         position.amount = amount;
+        position.bet_timestamp = clock.unix_timestamp;
+        position.commitment = None;
+        position.revealed = true;
         position.claimed = false;
+        position.copied = false;
         position.bump = ctx.bumps.position;
+        position.version = ACCOUNT_VERSION;
 
         // Update pool
-        match side {
-            BetSide::Up => {
-                pool.up_pool = pool.up_pool
-                    .checked_add(amount)
-                    .ok_or(SessionBettingError::MathOverflow)?;
-            }
-            BetSide::Down => {
-                pool.down_pool = pool.down_pool
-                    .checked_add(amount)
-                    .ok_or(SessionBettingError::MathOverflow)?;
-            }
+        add_to_pool(pool, player_round, side, amount)?;
+
+        init_user_stats(&mut ctx.accounts.user_stats, user_balance.owner, ctx.bumps.user_stats);
+        record_bet_stats(&mut ctx.accounts.user_stats, amount)?;
+
+        // Earn lottery tickets when the lottery accounts are supplied
+        if let (
+            Some(lottery_program),
+            Some(crediter),
+            Some(lottery_config),
+            Some(player_tickets),
+            Some(event_authority),
+        ) = (
+            &ctx.accounts.lottery_program,
+            &ctx.accounts.lottery_crediter,
+            &ctx.accounts.lottery_config,
+            &ctx.accounts.player_tickets,
+            &ctx.accounts.lottery_event_authority,
+        ) {
+            let bump = ctx.bumps.lottery_crediter.ok_or(SessionBettingError::Unauthorized)?;
+            let seeds: &[&[u8]] = &[b"lottery_crediter", &[bump]];
+            let signer_seeds = &[seeds];
+
+            let cpi_context = CpiContext::new_with_signer(
+                lottery_program.to_account_info(),
+                CreditTickets {
+                    config: lottery_config.to_account_info(),
+                    player_tickets: player_tickets.to_account_info(),
+                    crediter: crediter.to_account_info(),
+                    payer: ctx.accounts.signer.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    event_authority: event_authority.to_account_info(),
+                    program: lottery_program.to_account_info(),
+                },
+                signer_seeds,
+            );
+            lottery::cpi::credit_tickets(cpi_context, user_balance.owner, amount)?;
+        }
+
+        // Accrue reward points when the rewards accounts are supplied
+        if let (
+            Some(rewards_program),
+            Some(crediter),
+            Some(rewards_config),
+            Some(rewards_epoch),
+            Some(user_points),
+            Some(user_epoch_points),
+            Some(event_authority),
+        ) = (
+            &ctx.accounts.rewards_program,
+            &ctx.accounts.rewards_crediter,
+            &ctx.accounts.rewards_config,
+            &ctx.accounts.rewards_epoch,
+            &ctx.accounts.user_points,
+            &ctx.accounts.user_epoch_points,
+            &ctx.accounts.rewards_event_authority,
+        ) {
+            let bump = ctx.bumps.rewards_crediter.ok_or(SessionBettingError::Unauthorized)?;
+            let seeds: &[&[u8]] = &[b"rewards_crediter", &[bump]];
+            let signer_seeds = &[seeds];
+
+            let cpi_context = CpiContext::new_with_signer(
+                rewards_program.to_account_info(),
+                CreditPoints {
+                    config: rewards_config.to_account_info(),
+                    epoch: rewards_epoch.to_account_info(),
+                    user_points: user_points.to_account_info(),
+                    user_epoch_points: user_epoch_points.to_account_info(),
+                    crediter: crediter.to_account_info(),
+                    payer: ctx.accounts.signer.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    event_authority: event_authority.to_account_info(),
+                    program: rewards_program.to_account_info(),
+                },
+                signer_seeds,
+            );
+            rewards::cpi::credit_points(cpi_context, user_balance.owner, amount)?;
         }
-        pool.total_pool = pool.total_pool
-            .checked_add(amount)
-            .ok_or(SessionBettingError::MathOverflow)?;
 
         // Emit audit event
-        emit!(BetPlaced {
+        emit_cpi!(BetPlaced {
             user: user_balance.owner,
             round_id: round.round_id,
             side,
@@ -769,209 +2673,7020 @@ pub mod session_betting {
         Ok(())
     }
 
-    /// Claim winnings after round is settled
-    /// Can use session key OR wallet signature
-    /// Winnings go to user's balance account (not direct wallet)
-    pub fn claim_winnings(ctx: Context<ClaimWinnings>) -> Result<()> {
-        let game_state = &mut ctx.accounts.game_state;
+    /// Add stake to an existing position before lock, on the same side
+    /// Session key enabled; the combined stake must stay within max_bet
+    pub fn increase_bet(ctx: Context<IncreaseBet>, amount: u64) -> Result<()> {
+        let game_state = &ctx.accounts.game_state;
+        let user_balance = &mut ctx.accounts.user_balance;
         let round = &ctx.accounts.round;
-        let pool = &ctx.accounts.pool;
+        let pool = &mut ctx.accounts.pool;
         let position = &mut ctx.accounts.position;
-        let user_balance = &mut ctx.accounts.user_balance;
+        let player_round = &mut ctx.accounts.player_round;
 
         // SECURITY: Verify signer authority (session or wallet)
         verify_session_or_authority(
             &ctx.accounts.session_token,
+            &ctx.accounts.session_registry,
             &ctx.accounts.signer,
             &user_balance.owner,
+            ENTRYPOINT_INCREASE_BET,
+            amount,
+        )?;
+        record_session_spend(
+            &mut ctx.accounts.session_token,
+            &ctx.accounts.signer,
+            amount,
+            game_state.session_bets_per_minute,
+        )?;
+
+        // SECURITY: Game not paused
+        require!(!game_state.is_paused, SessionBettingError::GamePaused);
+
+        // SECURITY: Compliance eligibility
+        check_eligibility(game_state, user_balance)?;
+        check_compliance(
+            &ctx.accounts.compliance_program,
+            &ctx.accounts.compliance_config,
+            &ctx.accounts.user_compliance,
+            user_balance.owner,
         )?;
 
-        // SECURITY: Round must be settled
+        // Refuse entrypoints deprecated in the program registry
+        registry::require_not_deprecated(&ctx.accounts.program_entry, ENTRYPOINT_INCREASE_BET)?;
+
+        // SECURITY: Round must be open
+        require!(round.status == RoundStatus::Open, SessionBettingError::RoundNotOpen);
+
+        // SECURITY: Not past lock time
+        let clock = Clock::get()?;
         require!(
-            round.status == RoundStatus::Settled,
-            SessionBettingError::RoundNotSettled
+            clock.unix_timestamp < round.lock_time,
+            SessionBettingError::RoundLocked
         );
 
-        // SECURITY: Position not already claimed
-        require!(!position.claimed, SessionBettingError::AlreadyClaimed);
+        // SECURITY: Hidden bets have no side to add to until revealed
+        require!(position.revealed, SessionBettingError::NotRevealed);
+
+        // SECURITY: Valid amounts - the combined stake is what max_bet limits
+        require!(amount > 0, SessionBettingError::AmountTooSmall);
+        let new_amount = position.amount
+            .checked_add(amount)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        require!(new_amount <= game_state.max_bet, SessionBettingError::AmountTooLarge);
+        check_personal_limits(user_balance, new_amount, amount, clock.unix_timestamp)?;
+
+        // SECURITY: Per-round pool caps, with the player cap on the wallet's whole stake in the round
+        let player_stake = player_round_stake(player_round)?
+            .checked_add(amount)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        check_pool_caps(game_state, pool, position.side, amount, player_stake)?;
 
-        // SECURITY: Position belongs to user
+        // SECURITY: Sufficient balance
         require!(
-            position.player == user_balance.owner,
-            SessionBettingError::NotPositionOwner
+            user_balance.balance >= amount,
+            SessionBettingError::InsufficientBalance
         );
 
-        // Calculate winnings
-        let winnings = calculate_winnings(
+        // SECURITY: Update balance BEFORE growing the position
+        user_balance.balance = user_balance.balance
+            .checked_sub(amount)
+            .ok_or(SessionBettingError::MathOverflow)?;
+
+        // Blend the entry time so a late top-up can't inherit an early-bird bonus
+        position.bet_timestamp = blended_bet_timestamp(
+            round,
+            position.bet_timestamp,
             position.amount,
-            position.side,
-            round.winner,
-            pool.up_pool,
-            pool.down_pool,
+            clock.unix_timestamp,
+            amount,
         )?;
+        position.amount = new_amount;
+        add_to_pool(pool, player_round, position.side, amount)?;
 
-        // SECURITY: Mark as claimed BEFORE credit (reentrancy protection)
-        position.claimed = true;
+        emit_cpi!(BetIncreased {
+            user: user_balance.owner,
+            round_id: round.round_id,
+            side: position.side,
+            amount,
+            total: new_amount,
+            timestamp: clock.unix_timestamp,
+        });
 
-        if winnings > 0 {
-            // Calculate fee
-            let fee = winnings
-                .checked_mul(PLATFORM_FEE_BPS)
-                .ok_or(SessionBettingError::MathOverflow)?
-                .checked_div(BPS_DENOMINATOR)
-                .ok_or(SessionBettingError::MathOverflow)?;
+        Ok(())
+    }
 
-            let payout = winnings
-                .checked_sub(fee)
-                .ok_or(SessionBettingError::MathOverflow)?;
+    /// Cancel a bet before lock: the stake minus cancel_fee_bps returns to the balance
+    /// Session key enabled - funds only move back into the owner's balance account
+    /// The position closes (rent to its owner), so the bettor can bet the round again
+    /// Positions another player has copied cannot be cancelled
+    pub fn cancel_bet(ctx: Context<CancelBet>) -> Result<()> {
+        let game_state = &mut ctx.accounts.game_state;
+        let user_balance = &mut ctx.accounts.user_balance;
+        let round = &ctx.accounts.round;
+        let pool = &mut ctx.accounts.pool;
+        let position = &ctx.accounts.position;
 
-            // Credit to user balance
-            user_balance.balance = user_balance.balance
-                .checked_add(payout)
-                .ok_or(SessionBettingError::MathOverflow)?;
-            user_balance.total_winnings = user_balance.total_winnings
-                .checked_add(payout)
-                .ok_or(SessionBettingError::MathOverflow)?;
+        // SECURITY: Verify signer authority (session or wallet)
+        verify_session_or_authority(
+            &ctx.accounts.session_token,
+            &ctx.accounts.session_registry,
+            &ctx.accounts.signer,
+            &user_balance.owner,
+            ENTRYPOINT_CANCEL_BET,
+            0,
+        )?;
 
-            // Track fees
-            game_state.total_fees_collected = game_state.total_fees_collected
-                .checked_add(fee)
-                .ok_or(SessionBettingError::MathOverflow)?;
-        } else if round.winner == WinnerSide::Draw {
-            // Refund on draw
-            user_balance.balance = user_balance.balance
-                .checked_add(position.amount)
-                .ok_or(SessionBettingError::MathOverflow)?;
-        }
+        // SECURITY: Round must be open
+        require!(round.status == RoundStatus::Open, SessionBettingError::RoundNotOpen);
 
-        Ok(())
-    }
-}
+        // SECURITY: Not past lock time
+        let clock = Clock::get()?;
+        require!(
+            clock.unix_timestamp < round.lock_time,
+            SessionBettingError::RoundLocked
+        );
 
-// ===================
-// Helper Functions
-// ===================
+        // SECURITY: Hidden bets have not joined a side pool yet
+        require!(position.revealed, SessionBettingError::NotRevealed);
 
-#[inline]
-fn verify_session_or_authority(
-    session_token: &Option<Account<SessionToken>>,
-    signer: &Signer,
-    expected_authority: &Pubkey,
-) -> Result<()> {
-    // If signer is the authority directly, allow
-    if signer.key() == *expected_authority {
-        return Ok(());
-    }
+        // SECURITY: Followers' mirrored stakes must never end up opposite the leader
+        require!(!position.copied, SessionBettingError::PositionCopied);
 
-    // Otherwise, must have valid session token
-    match session_token {
-        Some(session) => {
-            // SECURITY: Session must be for this authority
-            require!(
-                session.authority == *expected_authority,
-                SessionBettingError::SessionAuthorityMismatch
-            );
+        let (refund, fee) = bet_math::split_fee(position.amount, game_state.cancel_fee_bps)
+            .ok_or(SessionBettingError::MathOverflow)?;
 
-            // SECURITY: Signer must be the session signer
-            require!(
-                session.session_signer == signer.key(),
-                SessionBettingError::InvalidSessionSigner
+        remove_from_pool(pool, &mut ctx.accounts.player_round, position.side, position.amount)?;
+
+        user_balance.balance = user_balance.balance
+            .checked_add(refund)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        game_state.total_fees_collected = game_state.total_fees_collected
+            .checked_add(fee)
+            .ok_or(SessionBettingError::MathOverflow)?;
+
+        emit_cpi!(BetCancelled {
+            user: user_balance.owner,
+            round_id: round.round_id,
+            side: position.side,
+            refund,
+            fee,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Escape hatch: refund an unsettled position in full once the game has been paused
+    /// for EMERGENCY_REFUND_DELAY_SECONDS, whatever state its round is stuck in
+    /// Permissionless - funds can only reach position.player's balance, which withdraw
+    /// never gates on the pause
+    pub fn emergency_refund(ctx: Context<EmergencyRefund>) -> Result<()> {
+        let game_state = &ctx.accounts.game_state;
+        let round = &ctx.accounts.round;
+        let pool = &mut ctx.accounts.pool;
+        let position = &ctx.accounts.position;
+        let user_balance = &mut ctx.accounts.user_balance;
+
+        // SECURITY: Only after a long, continuous pause
+        require!(game_state.is_paused, SessionBettingError::NotPausedLongEnough);
+        let clock = Clock::get()?;
+        let refund_time = game_state.paused_at
+            .checked_add(EMERGENCY_REFUND_DELAY_SECONDS)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        require!(
+            clock.unix_timestamp >= refund_time,
+            SessionBettingError::NotPausedLongEnough
+        );
+
+        // SECURITY: Settled and cancelled rounds pay out through the claim instructions
+        require!(!round_is_final(round), SessionBettingError::RoundAlreadySettled);
+
+        // Hidden bets never joined a side pool
+        let player_round = &mut ctx.accounts.player_round;
+        if position.revealed {
+            remove_from_pool(pool, player_round, position.side, position.amount)?;
+        } else {
+            player_round.hidden_stake = player_round.hidden_stake
+                .checked_sub(position.amount)
+                .ok_or(SessionBettingError::MathOverflow)?;
+        }
+
+        user_balance.balance = user_balance.balance
+            .checked_add(position.amount)
+            .ok_or(SessionBettingError::MathOverflow)?;
+
+        emit_cpi!(EmergencyRefunded {
+            user: user_balance.owner,
+            round_id: round.round_id,
+            refund: position.amount,
+            timestamp: clock.unix_timestamp,
+        });
+
+        // Position account is closed via the close constraint in EmergencyRefund
+        Ok(())
+    }
+
+    /// Claim winnings after round is settled
+    /// Permissionless - anyone (e.g. a keeper) can claim on behalf of the position owner
+    /// Winnings always go to the position owner's balance account (not direct wallet)
+    pub fn claim_winnings(ctx: Context<ClaimWinnings>) -> Result<()> {
+        // Refuse entrypoints deprecated in the program registry
+        registry::require_not_deprecated(&ctx.accounts.program_entry, ENTRYPOINT_CLAIM_WINNINGS)?;
+
+        init_user_stats(&mut ctx.accounts.user_stats, ctx.accounts.user_balance.owner, ctx.bumps.user_stats);
+        let claim = process_claim(
+            &mut ctx.accounts.game_state,
+            &ctx.accounts.round,
+            &ctx.accounts.pool,
+            &mut ctx.accounts.position,
+            &mut ctx.accounts.user_balance,
+            &mut ctx.accounts.user_stats,
+            &mut ctx.accounts.jackpot,
+        )?;
+
+        share_referral_fee(
+            &ctx.accounts.referral_fees,
+            &ctx.bumps.referral_fees,
+            &mut ctx.accounts.game_state,
+            ctx.accounts.user_balance.owner,
+            claim.fee,
+        )?;
+        contribute_insurance_fee(
+            &ctx.accounts.insurance_fees,
+            &ctx.bumps.insurance_fees,
+            &mut ctx.accounts.game_state,
+            claim.fee,
+        )
+    }
+
+    /// Claim winnings and close the position account in one transaction
+    /// Permissionless like claim_winnings; position rent is refunded to the position owner
+    pub fn claim_and_close(ctx: Context<ClaimAndClose>) -> Result<()> {
+        // Refuse entrypoints deprecated in the program registry
+        registry::require_not_deprecated(&ctx.accounts.program_entry, ENTRYPOINT_CLAIM_WINNINGS)?;
+
+        // Position account is closed via the close constraint in ClaimAndClose
+        init_user_stats(&mut ctx.accounts.user_stats, ctx.accounts.user_balance.owner, ctx.bumps.user_stats);
+        let fee = process_claim(
+            &mut ctx.accounts.game_state,
+            &ctx.accounts.round,
+            &ctx.accounts.pool,
+            &mut ctx.accounts.position,
+            &mut ctx.accounts.user_balance,
+            &mut ctx.accounts.user_stats,
+            &mut ctx.accounts.jackpot,
+        )?.fee;
+        share_referral_fee(
+            &ctx.accounts.referral_fees,
+            &ctx.bumps.referral_fees,
+            &mut ctx.accounts.game_state,
+            ctx.accounts.user_balance.owner,
+            fee,
+        )?;
+        contribute_insurance_fee(
+            &ctx.accounts.insurance_fees,
+            &ctx.bumps.insurance_fees,
+            &mut ctx.accounts.game_state,
+            fee,
+        )
+    }
+
+    /// Claim a settled position and withdraw from the vault in one transaction
+    /// REQUIRES wallet signature - NEVER session key
+    /// `amount` may include balance held before the claim
+    pub fn claim_and_withdraw(ctx: Context<ClaimAndWithdraw>, amount: u64) -> Result<()> {
+        // Refuse entrypoints deprecated in the program registry
+        registry::require_not_deprecated(&ctx.accounts.program_entry, ENTRYPOINT_CLAIM_WINNINGS)?;
+
+        init_user_stats(&mut ctx.accounts.user_stats, ctx.accounts.user_balance.owner, ctx.bumps.user_stats);
+        let fee = process_claim(
+            &mut ctx.accounts.game_state,
+            &ctx.accounts.round,
+            &ctx.accounts.pool,
+            &mut ctx.accounts.position,
+            &mut ctx.accounts.user_balance,
+            &mut ctx.accounts.user_stats,
+            &mut ctx.accounts.jackpot,
+        )?.fee;
+        share_referral_fee(
+            &ctx.accounts.referral_fees,
+            &ctx.bumps.referral_fees,
+            &mut ctx.accounts.game_state,
+            ctx.accounts.user_balance.owner,
+            fee,
+        )?;
+        contribute_insurance_fee(
+            &ctx.accounts.insurance_fees,
+            &ctx.bumps.insurance_fees,
+            &mut ctx.accounts.game_state,
+            fee,
+        )?;
+
+        withdraw_from_vault(
+            &mut ctx.accounts.user_balance,
+            &ctx.accounts.vault,
+            &ctx.accounts.user,
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.system_program,
+            ctx.bumps.vault,
+            amount,
+        )?;
+
+        let clock = Clock::get()?;
+        emit_cpi!(FundsWithdrawn {
+            user: ctx.accounts.user.key(),
+            amount,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Close a finished position and refund its rent to the position owner
+    /// Permissionless - only once the round is settled and the position has been
+    /// claimed or has nothing to claim (a losing bet, recorded as a loss in the owner's stats)
+    pub fn close_position(ctx: Context<ClosePosition>) -> Result<()> {
+        let round = &ctx.accounts.round;
+        let position = &ctx.accounts.position;
+
+        // SECURITY: Round must be settled or cancelled
+        require!(round_is_final(round), SessionBettingError::RoundNotSettled);
+
+        // SECURITY: Never close a position that still has funds owed to it
+        if !position.claimed {
+            let claim = round_claim(
+                round,
+                position,
+                &ctx.accounts.pool,
+                ctx.accounts.game_state.fee_bps,
+            )?;
+            require!(claim.payout == 0, SessionBettingError::PositionNotClaimed);
+
+            init_user_stats(&mut ctx.accounts.user_stats, position.player, ctx.bumps.user_stats);
+            record_claim_stats(&mut ctx.accounts.user_stats, round, &claim)?;
+        }
+
+        // Position account is closed via the close constraint in ClosePosition
+        Ok(())
+    }
+
+    /// Close a wallet's round record and refund its rent to the wallet
+    /// Permissionless - only once the round is settled or cancelled, when no bet can change it
+    pub fn close_player_round(ctx: Context<ClosePlayerRound>) -> Result<()> {
+        // SECURITY: Round must be settled or cancelled
+        require!(round_is_final(&ctx.accounts.round), SessionBettingError::RoundNotSettled);
+
+        // PlayerRound account is closed via the close constraint in ClosePlayerRound
+        Ok(())
+    }
+
+    /// Preview the exact payout of a position using the same math as claim_winnings
+    /// Read-only - returns a PayoutPreview via return data (simulate the transaction)
+    /// Settled and cancelled rounds use the actual outcome; otherwise assumes the position's side wins
+    /// with the pools as they stand now
+    /// Excludes the fee-funded streak and early-bird bonuses applied at claim time
+    pub fn preview_payout(ctx: Context<PreviewPayout>) -> Result<PayoutPreview> {
+        let round = &ctx.accounts.round;
+        let position = &ctx.accounts.position;
+
+        if round_is_final(round) {
+            return round_claim(round, position, &ctx.accounts.pool, ctx.accounts.game_state.fee_bps);
+        }
+
+        // A one-sided pool settles as a Draw whichever way the price moves
+        let pool = &ctx.accounts.pool;
+        let winner = if pool.up_pool == 0 || pool.down_pool == 0 {
+            WinnerSide::Draw
+        } else {
+            match position.side {
+                BetSide::Up => WinnerSide::Up,
+                BetSide::Down => WinnerSide::Down,
+            }
+        };
+
+        calculate_claim(position, winner, &ctx.accounts.pool, ctx.accounts.game_state.fee_bps)
+    }
+
+    /// Transfer a live position to another player before settlement (e.g. OTC sale)
+    /// REQUIRES wallet signature of the current owner - NEVER session key
+    /// The PDA stays seeded by the original bettor; claims pay the new owner's balance
+    /// The stake moves between the two wallets' round records and counts toward the new
+    /// owner's max_player_stake
+    pub fn transfer_position(ctx: Context<TransferPosition>) -> Result<()> {
+        let round = &ctx.accounts.round;
+        let pool = &mut ctx.accounts.pool;
+        let position = &mut ctx.accounts.position;
+        let new_owner = ctx.accounts.new_owner_balance.owner;
+        let to_round = &mut ctx.accounts.to_player_round;
+        init_player_round(to_round, new_owner, round.round_id, ctx.bumps.to_player_round);
+
+        // SECURITY: Only before settlement
+        require!(!round_is_final(round), SessionBettingError::RoundAlreadySettled);
+
+        // SECURITY: Hidden bets can only be revealed by their bettor
+        require!(position.revealed, SessionBettingError::NotRevealed);
+
+        // SECURITY: Position not already claimed
+        require!(!position.claimed, SessionBettingError::AlreadyClaimed);
+
+        // SECURITY: Cannot transfer to self
+        require!(
+            new_owner != position.player,
+            SessionBettingError::InvalidPositionTransfer
+        );
+
+        // SECURITY: A bought position cannot take the new owner past the per-player cap
+        let new_owner_stake = player_round_stake(to_round)?
+            .checked_add(position.amount)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        let max_player_stake = ctx.accounts.game_state.max_player_stake;
+        require!(
+            max_player_stake == 0 || new_owner_stake <= max_player_stake,
+            SessionBettingError::PlayerStakeCapExceeded
+        );
+
+        debit_player_side(pool, &mut ctx.accounts.from_player_round, position.side, position.amount)?;
+        credit_player_side(pool, to_round, position.side, position.amount)?;
+
+        let previous_owner = position.player;
+        position.player = new_owner;
+
+        emit_cpi!(PositionTransferred {
+            round_id: position.round_id,
+            bettor: position.bettor,
+            from: previous_owner,
+            to: new_owner,
+        });
+        Ok(())
+    }
+
+    // =====================
+    // Commit-Reveal Betting Instructions (Session Key Enabled)
+    // =====================
+
+    /// Commit a hidden bet: stores sha256(side || salt || owner) instead of the side
+    /// The stake is debited now; the side only joins a pool once revealed, which must happen
+    /// before lock - a stake still hidden at lock is forfeited to the round
+    /// Can use session key OR wallet signature; position_index picks which of the bettor's positions to open
+    pub fn commit_bet(ctx: Context<CommitBet>, commitment: [u8; 32], amount: u64, position_index: u8) -> Result<()> {
+        let game_state = &ctx.accounts.game_state;
+        let user_balance = &mut ctx.accounts.user_balance;
+        let round = &ctx.accounts.round;
+        let pool = &mut ctx.accounts.pool;
+        let position = &mut ctx.accounts.position;
+        let player_round = &mut ctx.accounts.player_round;
+        init_player_round(player_round, user_balance.owner, round.round_id, ctx.bumps.player_round);
+
+        // SECURITY: Verify signer authority (session or wallet)
+        verify_session_or_authority(
+            &ctx.accounts.session_token,
+            &ctx.accounts.session_registry,
+            &ctx.accounts.signer,
+            &user_balance.owner,
+            ENTRYPOINT_COMMIT_BET,
+            amount,
+        )?;
+        record_session_spend(
+            &mut ctx.accounts.session_token,
+            &ctx.accounts.signer,
+            amount,
+            game_state.session_bets_per_minute,
+        )?;
+
+        // SECURITY: Game not paused
+        require!(!game_state.is_paused, SessionBettingError::GamePaused);
+
+        // SECURITY: Compliance eligibility
+        check_eligibility(game_state, user_balance)?;
+        check_compliance(
+            &ctx.accounts.compliance_program,
+            &ctx.accounts.compliance_config,
+            &ctx.accounts.user_compliance,
+            user_balance.owner,
+        )?;
+
+        // Refuse entrypoints deprecated in the program registry
+        registry::require_not_deprecated(&ctx.accounts.program_entry, ENTRYPOINT_COMMIT_BET)?;
+
+        // SECURITY: Round must be open
+        require!(round.status == RoundStatus::Open, SessionBettingError::RoundNotOpen);
+
+        // SECURITY: Not past lock time
+        let clock = Clock::get()?;
+        require!(
+            clock.unix_timestamp < round.lock_time,
+            SessionBettingError::RoundLocked
+        );
+
+        // SECURITY: Valid bet amount
+        require!(amount >= game_state.min_bet, SessionBettingError::AmountTooSmall);
+        require!(amount <= game_state.max_bet, SessionBettingError::AmountTooLarge);
+        check_personal_limits(user_balance, amount, amount, clock.unix_timestamp)?;
+
+        // SECURITY: Per-round caps (the side cap cannot apply to a hidden side)
+        if game_state.max_player_stake > 0 {
+            let player_stake = player_round_stake(player_round)?
+                .checked_add(amount)
+                .ok_or(SessionBettingError::MathOverflow)?;
+            require!(
+                player_stake <= game_state.max_player_stake,
+                SessionBettingError::PlayerStakeCapExceeded
+            );
+        }
+        if game_state.max_total_pool > 0 {
+            let new_total = pool.total_pool
+                .checked_add(pool.unrevealed_pool)
+                .and_then(|total| total.checked_add(amount))
+                .ok_or(SessionBettingError::MathOverflow)?;
+            require!(
+                new_total <= game_state.max_total_pool,
+                SessionBettingError::PoolCapExceeded
             );
+        }
+
+        // SECURITY: Sufficient balance
+        require!(
+            user_balance.balance >= amount,
+            SessionBettingError::InsufficientBalance
+        );
+
+        // SECURITY: Update balance BEFORE recording bet (reentrancy protection)
+        user_balance.balance = user_balance.balance
+            .checked_sub(amount)
+            .ok_or(SessionBettingError::MathOverflow)?;
+
+        // Record hidden position (side is a placeholder until revealed)
+        position.player = user_balance.owner;
+        position.bettor = user_balance.owner;
+        position.round_id = round.round_id;
+        position.index = position_index;
+        position.side = BetSide::Up;
+        position.amount = amount;
+        position.bet_timestamp = clock.unix_timestamp;
+        position.commitment = Some(commitment);
+        position.revealed = false;
+        position.claimed = false;
+        position.copied = false;
+        position.bump = ctx.bumps.position;
+        position.version = ACCOUNT_VERSION;
+
+        pool.unrevealed_pool = pool.unrevealed_pool
+            .checked_add(amount)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        player_round.hidden_stake = player_round.hidden_stake
+            .checked_add(amount)
+            .ok_or(SessionBettingError::MathOverflow)?;
+
+        init_user_stats(&mut ctx.accounts.user_stats, user_balance.owner, ctx.bumps.user_stats);
+        record_bet_stats(&mut ctx.accounts.user_stats, amount)?;
+
+        emit_cpi!(BetCommitted {
+            user: user_balance.owner,
+            round_id: round.round_id,
+            amount,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Reveal a committed bet so it joins its side's pool
+    /// Must happen while the round is open, before lock_time - the lock price is unknown
+    /// until then, so a reveal cannot be decided on the outcome
+    /// Can use session key OR wallet signature
+    pub fn reveal_bet(ctx: Context<RevealBet>, side: BetSide, salt: [u8; 32]) -> Result<()> {
+        let round = &ctx.accounts.round;
+        let pool = &mut ctx.accounts.pool;
+        let position = &mut ctx.accounts.position;
+        let owner = ctx.accounts.user_balance.owner;
+
+        // SECURITY: Verify signer authority (session or wallet)
+        verify_session_or_authority(
+            &ctx.accounts.session_token,
+            &ctx.accounts.session_registry,
+            &ctx.accounts.signer,
+            &owner,
+            ENTRYPOINT_REVEAL_BET,
+            0,
+        )?;
+
+        // SECURITY: Must be a committed, unrevealed position
+        let commitment = position.commitment.ok_or(SessionBettingError::NotCommitted)?;
+        require!(!position.revealed, SessionBettingError::AlreadyRevealed);
+
+        // SECURITY: Reveals close before the lock price is read, so a player
+        // cannot wait for the price and only reveal a winning side
+        let clock = Clock::get()?;
+        require!(
+            round.status == RoundStatus::Open && clock.unix_timestamp < round.lock_time,
+            SessionBettingError::RevealWindowClosed
+        );
+
+        // SECURITY: Side and salt must match the commitment
+        require!(
+            bet_commitment(side, &salt, &owner) == commitment,
+            SessionBettingError::InvalidReveal
+        );
+
+        position.side = side;
+        position.revealed = true;
+
+        // Move the stake from the hidden pool into its side
+        let player_round = &mut ctx.accounts.player_round;
+        pool.unrevealed_pool = pool.unrevealed_pool
+            .checked_sub(position.amount)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        player_round.hidden_stake = player_round.hidden_stake
+            .checked_sub(position.amount)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        add_to_pool(pool, player_round, side, position.amount)?;
+
+        emit_cpi!(BetPlaced {
+            user: owner,
+            round_id: round.round_id,
+            side,
+            amount: position.amount,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    // =====================
+    // Copy-Trading Instructions
+    // =====================
+
+    /// Follow a leader: mirror their bets up to max_bet per round
+    /// REQUIRES wallet signature - a session key cannot set up spending on the user's behalf
+    pub fn create_copy_config(ctx: Context<CreateCopyConfig>, leader: Pubkey, max_bet: u64) -> Result<()> {
+        let copy_config = &mut ctx.accounts.copy_config;
+
+        // SECURITY: Cannot copy yourself
+        require!(
+            leader != ctx.accounts.follower.key(),
+            SessionBettingError::InvalidCopyLeader
+        );
+
+        // SECURITY: Cap must allow at least a minimum bet
+        require!(max_bet >= ctx.accounts.game_state.min_bet, SessionBettingError::AmountTooSmall);
+        require!(max_bet <= ctx.accounts.game_state.max_bet, SessionBettingError::AmountTooLarge);
+
+        copy_config.follower = ctx.accounts.follower.key();
+        copy_config.leader = leader;
+        copy_config.max_bet = max_bet;
+        copy_config.bump = ctx.bumps.copy_config;
+        copy_config.version = ACCOUNT_VERSION;
+
+        emit_cpi!(CopyConfigCreated {
+            follower: copy_config.follower,
+            leader,
+            max_bet,
+        });
+        Ok(())
+    }
+
+    /// Stop following a leader (wallet signature required)
+    pub fn close_copy_config(_ctx: Context<CloseCopyConfig>) -> Result<()> {
+        // The account will be closed and rent returned to follower
+        Ok(())
+    }
+
+    /// Opt in or out of being copied by other players (wallet signature required)
+    pub fn set_copyable(ctx: Context<SetCopyable>, copyable: bool) -> Result<()> {
+        let user_balance = &mut ctx.accounts.user_balance;
+
+        user_balance.copy_opt_out = !copyable;
+        Ok(())
+    }
+
+    /// Mirror a leader's bet in the current round for a follower
+    /// Permissionless - anyone (e.g. a keeper) can execute once the leader's position exists
+    /// Stake = min(leader stake, what is left of the follower's max_bet this round), always on
+    /// the leader's side
+    /// Each leader position is mirrored into the follower position with the same index
+    pub fn execute_copy_bet(ctx: Context<ExecuteCopyBet>) -> Result<()> {
+        let copy_config = &ctx.accounts.copy_config;
+        let leader_balance = &ctx.accounts.leader_balance;
+        let leader_position = &mut ctx.accounts.leader_position;
+        let round = &ctx.accounts.round;
+        let pool = &mut ctx.accounts.pool;
+        let follower_balance = &mut ctx.accounts.follower_balance;
+        let position = &mut ctx.accounts.follower_position;
+        let player_round = &mut ctx.accounts.player_round;
+        init_player_round(player_round, follower_balance.owner, round.round_id, ctx.bumps.player_round);
+
+        // SECURITY: Game not paused
+        require!(
+            !ctx.accounts.game_state.is_paused,
+            SessionBettingError::GamePaused
+        );
+
+        // SECURITY: Compliance eligibility of the player whose funds are bet
+        check_eligibility(&ctx.accounts.game_state, follower_balance)?;
+        check_compliance(
+            &ctx.accounts.compliance_program,
+            &ctx.accounts.compliance_config,
+            &ctx.accounts.user_compliance,
+            follower_balance.owner,
+        )?;
+
+        // Refuse entrypoints deprecated in the program registry
+        registry::require_not_deprecated(&ctx.accounts.program_entry, ENTRYPOINT_EXECUTE_COPY_BET)?;
+
+        // SECURITY: Leader has not opted out of being copied
+        require!(!leader_balance.copy_opt_out, SessionBettingError::NotCopyable);
+
+        // SECURITY: Hidden (unrevealed) bets cannot be copied
+        require!(leader_position.revealed, SessionBettingError::NotCopyable);
+
+        // SECURITY: Round must be open
+        require!(round.status == RoundStatus::Open, SessionBettingError::RoundNotOpen);
+
+        // SECURITY: Not past lock time
+        let clock = Clock::get()?;
+        require!(
+            clock.unix_timestamp < round.lock_time,
+            SessionBettingError::RoundLocked
+        );
+
+        // SECURITY: max_bet caps everything copied into this round, across all leader positions
+        let side = leader_position.side;
+        let copy_allowance = copy_config.max_bet.saturating_sub(player_round.copied_stake);
+        let amount = leader_position.amount.min(copy_allowance);
+
+        // SECURITY: Valid bet amount
+        require!(amount >= ctx.accounts.game_state.min_bet, SessionBettingError::AmountTooSmall);
+        check_personal_limits(follower_balance, amount, amount, clock.unix_timestamp)?;
+
+        // SECURITY: Per-round pool caps, with the player cap on the wallet's whole stake in the round
+        let player_stake = player_round_stake(player_round)?
+            .checked_add(amount)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        check_pool_caps(&ctx.accounts.game_state, pool, side, amount, player_stake)?;
+
+        // SECURITY: Sufficient balance
+        require!(
+            follower_balance.balance >= amount,
+            SessionBettingError::InsufficientBalance
+        );
+
+        // SECURITY: Update balance BEFORE recording bet (reentrancy protection)
+        follower_balance.balance = follower_balance.balance
+            .checked_sub(amount)
+            .ok_or(SessionBettingError::MathOverflow)?;
+
+        // Record position
+        position.player = follower_balance.owner;
+        position.bettor = follower_balance.owner;
+        position.round_id = round.round_id;
+        position.index = leader_position.index;
+        position.side = side;
+        position.amount = amount;
+        position.bet_timestamp = clock.unix_timestamp;
+        position.commitment = None;
+        position.revealed = true;
+        position.claimed = false;
+        position.copied = false;
+        position.bump = ctx.bumps.follower_position;
+        position.version = ACCOUNT_VERSION;
+
+        // Update pool
+        add_to_pool(pool, player_round, side, amount)?;
+        player_round.copied_stake = player_round.copied_stake
+            .checked_add(amount)
+            .ok_or(SessionBettingError::MathOverflow)?;
+
+        // SECURITY: The leader can no longer cancel and re-bet against the followers' stakes
+        leader_position.copied = true;
+
+        init_user_stats(&mut ctx.accounts.user_stats, follower_balance.owner, ctx.bumps.user_stats);
+        record_bet_stats(&mut ctx.accounts.user_stats, amount)?;
+
+        emit_cpi!(CopyBetExecuted {
+            leader: copy_config.leader,
+            follower: copy_config.follower,
+            round_id: round.round_id,
+            side,
+            amount,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    // =====================
+    // Parlay Instructions
+    // =====================
+
+    /// Place a parlay on the next N rounds of one market (starting at its next round to be started)
+    /// Can use session key OR wallet signature
+    /// Stake moves from the user's vault into the parlay vault, which pays multiplied odds
+    pub fn place_parlay(ctx: Context<PlaceParlay>, picks: Vec<BetSide>, amount: u64) -> Result<()> {
+        let game_state = &mut ctx.accounts.game_state;
+        let user_balance = &mut ctx.accounts.user_balance;
+        let parlay = &mut ctx.accounts.parlay;
+
+        // SECURITY: Verify signer authority (session or wallet)
+        verify_session_or_authority(
+            &ctx.accounts.session_token,
+            &ctx.accounts.session_registry,
+            &ctx.accounts.signer,
+            &user_balance.owner,
+            ENTRYPOINT_PLACE_PARLAY,
+            amount,
+        )?;
+        record_session_spend(
+            &mut ctx.accounts.session_token,
+            &ctx.accounts.signer,
+            amount,
+            game_state.session_bets_per_minute,
+        )?;
+
+        // SECURITY: Game not paused
+        require!(!game_state.is_paused, SessionBettingError::GamePaused);
+
+        // SECURITY: Compliance eligibility
+        check_eligibility(game_state, user_balance)?;
+        check_compliance(
+            &ctx.accounts.compliance_program,
+            &ctx.accounts.compliance_config,
+            &ctx.accounts.user_compliance,
+            user_balance.owner,
+        )?;
+
+        // Refuse entrypoints deprecated in the program registry
+        registry::require_not_deprecated(&ctx.accounts.program_entry, ENTRYPOINT_PLACE_PARLAY)?;
+
+        // SECURITY: Valid number of legs
+        require!(
+            picks.len() >= MIN_PARLAY_LEGS && picks.len() <= MAX_PARLAY_LEGS,
+            SessionBettingError::InvalidParlayLegs
+        );
+
+        // SECURITY: Valid stake
+        require!(amount >= game_state.min_bet, SessionBettingError::AmountTooSmall);
+        require!(amount <= game_state.max_bet, SessionBettingError::AmountTooLarge);
+        check_personal_limits(user_balance, amount, amount, Clock::get()?.unix_timestamp)?;
+
+        // SECURITY: Sufficient balance
+        require!(
+            user_balance.balance >= amount,
+            SessionBettingError::InsufficientBalance
+        );
+
+        // SECURITY: Parlay vault must cover every outstanding max payout plus this one
+        let max_payout = calculate_parlay_payout(amount, picks.len())?;
+        let new_exposure = game_state.parlay_exposure
+            .checked_add(max_payout)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        let vault_after_stake = ctx.accounts.parlay_vault.lamports()
+            .checked_add(amount)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        require!(
+            vault_after_stake >= new_exposure,
+            SessionBettingError::InsufficientParlayLiquidity
+        );
+
+        // SECURITY: Update state BEFORE transfer (reentrancy protection)
+        user_balance.balance = user_balance.balance
+            .checked_sub(amount)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        game_state.parlay_exposure = new_exposure;
+
+        let start_round = ctx.accounts.market.current_round;
+        parlay.owner = user_balance.owner;
+        parlay.market = ctx.accounts.market.key();
+        parlay.start_round = start_round;
+        parlay.picks = picks;
+        parlay.amount = amount;
+        parlay.max_payout = max_payout;
+        parlay.settled = false;
+        parlay.placed_at = Clock::get()?.unix_timestamp;
+        parlay.bump = ctx.bumps.parlay;
+        parlay.version = ACCOUNT_VERSION;
+
+        // Transfer stake from user's vault to parlay vault (PDA signs)
+        let owner_key = user_balance.owner;
+        let seeds = &[
+            b"vault",
+            owner_key.as_ref(),
+            &[ctx.bumps.user_vault],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_vault.to_account_info(),
+                to: ctx.accounts.parlay_vault.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(cpi_context, amount)?;
+
+        let clock = Clock::get()?;
+        emit_cpi!(ParlayPlaced {
+            user: owner_key,
+            market: parlay.market,
+            start_round,
+            legs: parlay.picks.len() as u8,
+            amount,
+            max_payout,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Settle a parlay once its outcome is known
+    /// Permissionless - payout always goes to the parlay owner's balance
+    /// Leg rounds are passed in order via remaining_accounts
+    /// A lost leg settles the parlay immediately; otherwise every leg must be settled
+    /// Draw legs push (1x) instead of losing
+    /// A leg whose round account is gone can never be read here - see refund_parlay
+    pub fn settle_parlay<'info>(ctx: Context<'_, '_, 'info, 'info, SettleParlay<'info>>) -> Result<()> {
+        let game_state = &mut ctx.accounts.game_state;
+        let parlay = &mut ctx.accounts.parlay;
+
+        // SECURITY: Parlay not already settled
+        require!(!parlay.settled, SessionBettingError::AlreadyClaimed);
+
+        let mut lost = false;
+        let mut winning_legs: usize = 0;
+
+        for (i, pick) in parlay.picks.iter().enumerate() {
+            let leg_info = ctx.remaining_accounts
+                .get(i)
+                .ok_or(SessionBettingError::ParlayLegMismatch)?;
+            let leg: Account<BettingRound> = Account::try_from(leg_info)?;
+
+            // SECURITY: Legs must be the consecutive rounds the parlay was placed on
+            require!(
+                leg.market == parlay.market,
+                SessionBettingError::ParlayLegMismatch
+            );
+            let expected_round = parlay.start_round
+                .checked_add(i as u64)
+                .ok_or(SessionBettingError::MathOverflow)?;
+            require!(
+                leg.round_id == expected_round,
+                SessionBettingError::ParlayLegMismatch
+            );
+            // Cancelled legs settle as Draw and are voided like one
+            require!(round_is_final(&leg), SessionBettingError::RoundNotSettled);
+
+            match (leg.winner, *pick) {
+                (WinnerSide::Up, BetSide::Up) | (WinnerSide::Down, BetSide::Down) => {
+                    winning_legs += 1;
+                }
+                (WinnerSide::Draw, _) => {}
+                _ => {
+                    lost = true;
+                    break;
+                }
+            }
+        }
+
+        let payout = if lost {
+            0
+        } else {
+            calculate_parlay_payout(parlay.amount, winning_legs)?
+        };
+
+        // SECURITY: Mark as settled and release exposure BEFORE transfer (reentrancy protection)
+        parlay.settled = true;
+        game_state.parlay_exposure = game_state.parlay_exposure
+            .checked_sub(parlay.max_payout)
+            .ok_or(SessionBettingError::MathOverflow)?;
+
+        if payout > 0 {
+            require!(
+                ctx.accounts.parlay_vault.lamports() >= payout,
+                SessionBettingError::InsufficientVaultBalance
+            );
+
+            let user_balance = &mut ctx.accounts.user_balance;
+            user_balance.balance = user_balance.balance
+                .checked_add(payout)
+                .ok_or(SessionBettingError::MathOverflow)?;
+            user_balance.total_winnings = user_balance.total_winnings
+                .checked_add(payout)
+                .ok_or(SessionBettingError::MathOverflow)?;
+
+            // Transfer from parlay vault to user's vault
+            let bump = ctx.bumps.parlay_vault;
+            let seeds: &[&[u8]] = &[b"parlay_vault", &[bump]];
+            let signer_seeds = &[seeds];
+
+            let cpi_context = CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.parlay_vault.to_account_info(),
+                    to: ctx.accounts.user_vault.to_account_info(),
+                },
+                signer_seeds,
+            );
+            transfer(cpi_context, payout)?;
+        }
+
+        let clock = Clock::get()?;
+        emit_cpi!(ParlaySettled {
+            user: parlay.owner,
+            start_round: parlay.start_round,
+            won: !lost,
+            payout,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Refund the stake of a parlay that can never settle because a leg round is missing
+    /// (closed by close_round before anyone settled the parlay, or never started)
+    /// Permissionless - only PARLAY_EXPIRY_SECONDS after placement, never while settle_parlay
+    /// could settle it, and never once a remaining leg has lost
+    /// Leg round addresses are passed in order via remaining_accounts, as for settle_parlay
+    pub fn refund_parlay<'info>(ctx: Context<'_, '_, 'info, 'info, RefundParlay<'info>>) -> Result<()> {
+        let game_state = &mut ctx.accounts.game_state;
+        let parlay = &mut ctx.accounts.parlay;
+        let clock = Clock::get()?;
+
+        // SECURITY: Parlay not already settled
+        require!(!parlay.settled, SessionBettingError::AlreadyClaimed);
+
+        // SECURITY: Give keepers the whole expiry window to settle it normally
+        let expires_at = parlay.placed_at
+            .checked_add(PARLAY_EXPIRY_SECONDS)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        require!(clock.unix_timestamp >= expires_at, SessionBettingError::ParlayNotExpired);
+
+        let mut missing_leg = false;
+        for (i, pick) in parlay.picks.iter().enumerate() {
+            let leg_info = ctx.remaining_accounts
+                .get(i)
+                .ok_or(SessionBettingError::ParlayLegMismatch)?;
+
+            // SECURITY: Each leg must be the round PDA itself, so a missing leg cannot be faked
+            let round_id = parlay.start_round
+                .checked_add(i as u64)
+                .ok_or(SessionBettingError::MathOverflow)?;
+            let (expected, _) = Pubkey::find_program_address(
+                &[b"round", parlay.market.as_ref(), round_id.to_le_bytes().as_ref()],
+                &crate::ID,
+            );
+            require_keys_eq!(leg_info.key(), expected, SessionBettingError::ParlayLegMismatch);
+
+            if leg_info.data_is_empty() {
+                missing_leg = true;
+                continue;
+            }
+
+            // SECURITY: Legs still on chain must be final, and none of them may have lost
+            let leg: Account<BettingRound> = Account::try_from(leg_info)?;
+            require!(round_is_final(&leg), SessionBettingError::RoundNotSettled);
+            let lost = matches!(
+                (leg.winner, *pick),
+                (WinnerSide::Up, BetSide::Down) | (WinnerSide::Down, BetSide::Up)
+            );
+            require!(!lost, SessionBettingError::ParlayLegLost);
+        }
+
+        // SECURITY: With every leg on chain, settle_parlay decides the outcome
+        require!(missing_leg, SessionBettingError::ParlaySettleable);
+
+        // SECURITY: Mark as settled and release exposure BEFORE transfer (reentrancy protection)
+        parlay.settled = true;
+        game_state.parlay_exposure = game_state.parlay_exposure
+            .checked_sub(parlay.max_payout)
+            .ok_or(SessionBettingError::MathOverflow)?;
+
+        let refund = parlay.amount;
+        require!(
+            ctx.accounts.parlay_vault.lamports() >= refund,
+            SessionBettingError::InsufficientVaultBalance
+        );
+
+        let user_balance = &mut ctx.accounts.user_balance;
+        user_balance.balance = user_balance.balance
+            .checked_add(refund)
+            .ok_or(SessionBettingError::MathOverflow)?;
+
+        // Transfer the stake from the parlay vault back to the user's vault
+        let bump = ctx.bumps.parlay_vault;
+        let seeds: &[&[u8]] = &[b"parlay_vault", &[bump]];
+        let signer_seeds = &[seeds];
+
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.parlay_vault.to_account_info(),
+                to: ctx.accounts.user_vault.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(cpi_context, refund)?;
+
+        emit_cpi!(ParlayRefunded {
+            user: parlay.owner,
+            start_round: parlay.start_round,
+            amount: refund,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Close a settled or refunded parlay and refund its rent to the parlay owner
+    /// Permissionless - rent can only reach the owner
+    pub fn close_parlay(ctx: Context<CloseParlay>) -> Result<()> {
+        // SECURITY: Never close a parlay that still has a payout or refund owed
+        require!(ctx.accounts.parlay.settled, SessionBettingError::ParlayNotSettled);
+
+        // Parlay account is closed via the close constraint in CloseParlay
+        Ok(())
+    }
+
+    /// Fund the parlay vault (authority provides liquidity for parlay payouts)
+    /// AUTHORITY ONLY
+    pub fn fund_parlay_vault(ctx: Context<FundParlayVault>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.authority.to_account_info(),
+                to: ctx.accounts.parlay_vault.to_account_info(),
+            },
+        );
+        transfer(cpi_context, amount)?;
+        Ok(())
+    }
+
+    /// Withdraw excess parlay liquidity to authority wallet
+    /// SECURITY: Authority only, can never dip below outstanding parlay exposure
+    pub fn withdraw_parlay_vault(ctx: Context<WithdrawParlayVault>, amount: u64) -> Result<()> {
+        // SECURITY: Treasury paths must come from the Squads vault when required
+        require!(
+            admin_guard::admin_path_ok(
+                &ctx.accounts.authority.key(),
+                ctx.accounts.admin_multisig.as_deref(),
+                ctx.accounts.game_state.require_multisig,
+            ),
+            SessionBettingError::MultisigRequired
+        );
+
+        let game_state = &ctx.accounts.game_state;
+
+        // SECURITY: Amount must be positive
+        require!(amount > 0, SessionBettingError::AmountTooSmall);
+
+        // SECURITY: Remaining vault balance must still cover all open parlays
+        let remaining = ctx.accounts.parlay_vault.lamports()
+            .checked_sub(amount)
+            .ok_or(SessionBettingError::InsufficientVaultBalance)?;
+        require!(
+            remaining >= game_state.parlay_exposure,
+            SessionBettingError::InsufficientParlayLiquidity
+        );
+
+        let bump = ctx.bumps.parlay_vault;
+        let seeds: &[&[u8]] = &[b"parlay_vault", &[bump]];
+        let signer_seeds = &[seeds];
+
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.parlay_vault.to_account_info(),
+                to: ctx.accounts.authority.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(cpi_context, amount)?;
+        Ok(())
+    }
+
+    // =====================
+    // Token (USDC) Instructions
+    // =====================
+
+    /// Enable the SPL token path for one mint (e.g. USDC) and create its vault
+    /// AUTHORITY ONLY - can only be set once so token balances never change denomination
+    /// `limits` are the token-denominated counterparts of min_bet, max_bet and the pool caps
+    pub fn set_token_mint(ctx: Context<SetTokenMint>, limits: TokenBetLimits) -> Result<()> {
+        let game_state = &mut ctx.accounts.game_state;
+
+        // SECURITY: Token balances and pools are denominated in this mint forever
+        require!(
+            game_state.token_mint == Pubkey::default(),
+            SessionBettingError::TokenMintAlreadySet
+        );
+        validate_token_bet_limits(&limits)?;
+
+        game_state.token_mint = ctx.accounts.mint.key();
+        game_state.token_fees_collected = 0;
+        game_state.token_limits = limits;
+        Ok(())
+    }
+
+    /// Set the stake limits and pool caps for token bets, in token_mint base units (authority only)
+    /// Only while the admin timelock is off - otherwise use propose_admin_change
+    pub fn set_token_bet_limits(ctx: Context<SetTokenBetLimits>, limits: TokenBetLimits) -> Result<()> {
+        require_no_timelock(&ctx.accounts.game_state)?;
+        validate_token_bet_limits(&limits)?;
+
+        ctx.accounts.game_state.token_limits = limits;
+        Ok(())
+    }
+
+    /// Deposit tokens into the user's token balance
+    /// REQUIRES wallet signature
+    pub fn deposit_token(ctx: Context<DepositToken>, amount: u64) -> Result<()> {
+        // SECURITY: Game not paused
+        require!(!ctx.accounts.game_state.is_paused, SessionBettingError::GamePaused);
+        require!(amount > 0, SessionBettingError::AmountTooSmall);
+
+        let cpi_context = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.token_vault.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        );
+        token::transfer_checked(cpi_context, amount, ctx.accounts.mint.decimals)?;
+
+        let token_balance = &mut ctx.accounts.token_balance;
+        token_balance.owner = ctx.accounts.user.key();
+        token_balance.mint = ctx.accounts.mint.key();
+        token_balance.balance = token_balance.balance
+            .checked_add(amount)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        token_balance.total_deposited = token_balance.total_deposited
+            .checked_add(amount)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        token_balance.bump = ctx.bumps.token_balance;
+        token_balance.version = ACCOUNT_VERSION;
+
+        emit_cpi!(TokenDeposited {
+            user: token_balance.owner,
+            mint: token_balance.mint,
+            amount,
+            new_balance: token_balance.balance,
+        });
+        Ok(())
+    }
+
+    /// Withdraw tokens from the user's token balance
+    /// CRITICAL SECURITY: REQUIRES wallet signature - NEVER session key
+    pub fn withdraw_token(ctx: Context<WithdrawToken>, amount: u64) -> Result<()> {
+        let token_balance = &mut ctx.accounts.token_balance;
+
+        // SECURITY: Check sufficient balance
+        require!(
+            token_balance.balance >= amount,
+            SessionBettingError::InsufficientBalance
+        );
+
+        // SECURITY: Update state BEFORE transfer (reentrancy protection)
+        token_balance.balance = token_balance.balance
+            .checked_sub(amount)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        token_balance.total_withdrawn = token_balance.total_withdrawn
+            .checked_add(amount)
+            .ok_or(SessionBettingError::MathOverflow)?;
+
+        let mint_key = ctx.accounts.mint.key();
+        let seeds: &[&[u8]] = &[b"token_vault", mint_key.as_ref(), &[ctx.bumps.token_vault]];
+        let signer_seeds = &[seeds];
+
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.token_vault.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.token_vault.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer_checked(cpi_context, amount, ctx.accounts.mint.decimals)?;
+
+        emit_cpi!(TokenWithdrawn {
+            user: token_balance.owner,
+            mint: mint_key,
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Place a token-denominated bet on UP or DOWN
+    /// Can use session key OR wallet signature
+    /// Token stakes go into a per-mint pool beside the SOL pool and settle on the same price move
+    pub fn place_token_bet(ctx: Context<PlaceTokenBet>, side: BetSide, amount: u64) -> Result<()> {
+        let token_balance = &mut ctx.accounts.token_balance;
+        let round = &ctx.accounts.round;
+
+        // SECURITY: Verify signer authority (session or wallet)
+        // max_bet_per_tx is in lamports, so token stakes are bounded by token_spend_limit instead
+        verify_session_or_authority(
+            &ctx.accounts.session_token,
+            &ctx.accounts.session_registry,
+            &ctx.accounts.signer,
+            &token_balance.owner,
+            ENTRYPOINT_PLACE_TOKEN_BET,
+            0,
+        )?;
+        record_session_token_spend(
+            &mut ctx.accounts.session_token,
+            &ctx.accounts.signer,
+            amount,
+            ctx.accounts.game_state.session_bets_per_minute,
+        )?;
+
+        // SECURITY: Game not paused
+        require!(
+            !ctx.accounts.game_state.is_paused,
+            SessionBettingError::GamePaused
+        );
+
+        // The bettor's UserBalance carries the authority's eligibility status for every path
+        let user_balance = &mut ctx.accounts.user_balance;
+        if user_balance.owner == Pubkey::default() {
+            user_balance.owner = token_balance.owner;
+            user_balance.bump = ctx.bumps.user_balance;
+            user_balance.version = USER_BALANCE_VERSION;
+        }
+
+        // SECURITY: Compliance eligibility
+        check_eligibility(&ctx.accounts.game_state, user_balance)?;
+        check_compliance(
+            &ctx.accounts.compliance_program,
+            &ctx.accounts.compliance_config,
+            &ctx.accounts.user_compliance,
+            token_balance.owner,
+        )?;
+
+        // Refuse entrypoints deprecated in the program registry
+        registry::require_not_deprecated(&ctx.accounts.program_entry, ENTRYPOINT_PLACE_TOKEN_BET)?;
+
+        // SECURITY: Round must be open and not past lock time
+        require!(round.status == RoundStatus::Open, SessionBettingError::RoundNotOpen);
+        let clock = Clock::get()?;
+        require!(
+            clock.unix_timestamp < round.lock_time,
+            SessionBettingError::RoundLocked
+        );
+
+        // SECURITY: Valid bet amount, in token_mint base units
+        let limits = ctx.accounts.game_state.token_limits;
+        require!(amount > 0 && amount >= limits.min_bet, SessionBettingError::AmountTooSmall);
+        if limits.max_bet > 0 {
+            require!(amount <= limits.max_bet, SessionBettingError::AmountTooLarge);
+        }
+
+        // SECURITY: Authority-set restriction on this account's token stakes
+        if token_balance.max_bet_override > 0 {
+            require!(
+                amount <= token_balance.max_bet_override,
+                SessionBettingError::AmountTooLarge
+            );
+        }
+        enforce_personal_limits(&mut token_balance.personal_limits, amount, amount, clock.unix_timestamp)?;
+
+        // SECURITY: Per-round token pool caps (one token position per wallet, so the
+        // position is the wallet's whole token stake in the round)
+        let pool = &mut ctx.accounts.token_pool;
+        check_token_pool_caps(&limits, pool, side, amount)?;
+
+        require!(
+            token_balance.balance >= amount,
+            SessionBettingError::InsufficientBalance
+        );
+
+        // SECURITY: Deduct balance BEFORE recording the position
+        token_balance.balance = token_balance.balance
+            .checked_sub(amount)
+            .ok_or(SessionBettingError::MathOverflow)?;
+
+        // The first token bet of a round creates its pool
+        if pool.mint == Pubkey::default() {
+            pool.round_id = round.round_id;
+            pool.mint = token_balance.mint;
+            pool.bump = ctx.bumps.token_pool;
+            pool.version = ACCOUNT_VERSION;
+        }
+        match side {
+            BetSide::Up => {
+                pool.up_pool = pool.up_pool
+                    .checked_add(amount)
+                    .ok_or(SessionBettingError::MathOverflow)?;
+            }
+            BetSide::Down => {
+                pool.down_pool = pool.down_pool
+                    .checked_add(amount)
+                    .ok_or(SessionBettingError::MathOverflow)?;
+            }
+        }
+        pool.total_pool = pool.total_pool
+            .checked_add(amount)
+            .ok_or(SessionBettingError::MathOverflow)?;
+
+        let position = &mut ctx.accounts.token_position;
+        position.player = token_balance.owner;
+        position.round_id = round.round_id;
+        position.mint = token_balance.mint;
+        position.side = side;
+        position.amount = amount;
+        position.claimed = false;
+        position.bump = ctx.bumps.token_position;
+        position.version = ACCOUNT_VERSION;
+
+        emit_cpi!(TokenBetPlaced {
+            user: position.player,
+            round_id: round.round_id,
+            mint: position.mint,
+            side,
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Claim token winnings (or a refund on Draw) into the owner's token balance
+    /// Permissionless - funds can only go to the position owner's token balance
+    /// A one-sided token pool refunds every stake, as a one-sided SOL pool settles as a Draw
+    pub fn claim_token_winnings(ctx: Context<ClaimTokenWinnings>) -> Result<()> {
+        let round = &ctx.accounts.round;
+        let pool = &ctx.accounts.token_pool;
+        let position = &mut ctx.accounts.token_position;
+
+        // SECURITY: Round must be settled (cancelled rounds refund as a Draw)
+        require!(round_is_final(round), SessionBettingError::RoundNotSettled);
+
+        // SECURITY: Position not already claimed
+        require!(!position.claimed, SessionBettingError::AlreadyClaimed);
+
+        // The token pool can be one-sided even when the SOL pool was not; with no stake on
+        // the other side there is nobody to pay or be paid, so every stake comes back
+        let winner = if pool.up_pool == 0 || pool.down_pool == 0 {
+            WinnerSide::Draw
+        } else {
+            round.winner
+        };
+
+        let winnings = calculate_winnings(
+            position.amount,
+            position.side,
+            winner,
+            pool.up_pool,
+            pool.down_pool,
+        )?;
+        let (payout, fee) = if winnings > 0 {
+            bet_math::split_fee(winnings, ctx.accounts.game_state.fee_bps)
+                .ok_or(SessionBettingError::MathOverflow)?
+        } else if winner == WinnerSide::Draw {
+            (position.amount, 0)
+        } else {
+            (0, 0)
+        };
+
+        // SECURITY: Mark as claimed BEFORE credit (reentrancy protection)
+        position.claimed = true;
+
+        let token_balance = &mut ctx.accounts.token_balance;
+        token_balance.balance = token_balance.balance
+            .checked_add(payout)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        if winnings > 0 {
+            token_balance.total_winnings = token_balance.total_winnings
+                .checked_add(payout)
+                .ok_or(SessionBettingError::MathOverflow)?;
+        }
+
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.token_fees_collected = game_state.token_fees_collected
+            .checked_add(fee)
+            .ok_or(SessionBettingError::MathOverflow)?;
+
+        emit_cpi!(TokenWinningsClaimed {
+            user: position.player,
+            round_id: position.round_id,
+            mint: position.mint,
+            amount: payout,
+        });
+        Ok(())
+    }
+
+    /// Withdraw collected token fees to the authority's token account
+    /// SECURITY: Authority only, tracks withdrawal amount
+    pub fn withdraw_token_fees(ctx: Context<WithdrawTokenFees>, amount: u64) -> Result<()> {
+        // SECURITY: Treasury paths must come from the Squads vault when required
+        require!(
+            admin_guard::admin_path_ok(
+                &ctx.accounts.authority.key(),
+                ctx.accounts.admin_multisig.as_deref(),
+                ctx.accounts.game_state.require_multisig,
+            ),
+            SessionBettingError::MultisigRequired
+        );
+
+        let game_state = &mut ctx.accounts.game_state;
+
+        require!(amount > 0, SessionBettingError::AmountTooSmall);
+        require!(
+            amount <= game_state.token_fees_collected,
+            SessionBettingError::InsufficientFees
+        );
+
+        // Update fees collected BEFORE transfer (reentrancy protection)
+        game_state.token_fees_collected = game_state.token_fees_collected
+            .checked_sub(amount)
+            .ok_or(SessionBettingError::MathOverflow)?;
+
+        let mint_key = ctx.accounts.mint.key();
+        let seeds: &[&[u8]] = &[b"token_vault", mint_key.as_ref(), &[ctx.bumps.token_vault]];
+        let signer_seeds = &[seeds];
+
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.token_vault.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.authority_token_account.to_account_info(),
+                authority: ctx.accounts.token_vault.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer_checked(cpi_context, amount, ctx.accounts.mint.decimals)?;
+        Ok(())
+    }
+
+    // =====================
+    // Fixed-Odds House Instructions
+    // =====================
+
+    /// Add SOL to a market's house vault, which takes the other side of its fixed-odds bets
+    /// REQUIRES wallet signature - NEVER session key
+    /// Shares are priced as if every open stake were already house money and withdrawals as if
+    /// every open round paid its worst case, so no one can buy in or cash out mid-round at the
+    /// other LPs' expense
+    pub fn deposit_liquidity(ctx: Context<DepositLiquidity>, amount: u64) -> Result<()> {
+        let market = ctx.accounts.market.key();
+        let house = &mut ctx.accounts.house;
+        let lp_position = &mut ctx.accounts.lp_position;
+        init_house(house, market, ctx.bumps.house);
+        init_lp_position(lp_position, ctx.accounts.provider.key(), market, ctx.bumps.lp_position);
+
+        // Vault value before and after the deposit (the first deposit also funds the rent floor)
+        let rent = Rent::get()?.minimum_balance(0);
+        let vault_lamports = ctx.accounts.house_vault.lamports();
+        let value_before = vault_lamports.saturating_sub(rent);
+        let value_after = vault_lamports
+            .checked_add(amount)
+            .ok_or(SessionBettingError::MathOverflow)?
+            .saturating_sub(rent);
+        let value_added = value_after - value_before;
+
+        let shares = if house.total_shares == 0 {
+            value_added
+        } else {
+            bet_math::mul_div(value_added, house.total_shares, value_before)
+                .ok_or(SessionBettingError::HouseLiquidityInsufficient)?
+        };
+        require!(shares > 0, SessionBettingError::AmountTooSmall);
+
+        house.total_shares = house.total_shares
+            .checked_add(shares)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        lp_position.shares = lp_position.shares
+            .checked_add(shares)
+            .ok_or(SessionBettingError::MathOverflow)?;
+
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.provider.to_account_info(),
+                to: ctx.accounts.house_vault.to_account_info(),
+            },
+        );
+        transfer(cpi_context, amount)?;
+
+        emit_cpi!(LiquidityDeposited {
+            market,
+            provider: ctx.accounts.provider.key(),
+            amount,
+            shares,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Burn house shares for their slice of the vault's free liquidity
+    /// REQUIRES wallet signature - NEVER session key
+    /// Liquidity held back for open fixed-odds rounds stays in the vault until they finish
+    pub fn withdraw_liquidity(ctx: Context<WithdrawLiquidity>, shares: u64) -> Result<()> {
+        let house = &mut ctx.accounts.house;
+        let lp_position = &mut ctx.accounts.lp_position;
+
+        require!(shares > 0, SessionBettingError::AmountTooSmall);
+        require!(lp_position.shares >= shares, SessionBettingError::InsufficientBalance);
+
+        let free = house_free_liquidity(house, ctx.accounts.house_vault.lamports())?;
+        let amount = bet_math::mul_div(free, shares, house.total_shares)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        require!(amount > 0, SessionBettingError::HouseLiquidityInsufficient);
+
+        // SECURITY: Burn shares BEFORE transfer (reentrancy protection)
+        lp_position.shares -= shares;
+        house.total_shares = house.total_shares
+            .checked_sub(shares)
+            .ok_or(SessionBettingError::MathOverflow)?;
+
+        let market = ctx.accounts.market.key();
+        let seeds: &[&[u8]] = &[b"house_vault", market.as_ref(), &[ctx.bumps.house_vault]];
+        let signer_seeds = &[seeds];
+
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.house_vault.to_account_info(),
+                to: ctx.accounts.provider.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(cpi_context, amount)?;
+
+        emit_cpi!(LiquidityWithdrawn {
+            market,
+            provider: ctx.accounts.provider.key(),
+            amount,
+            shares,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Bet on a fixed-odds round at the odds it opened with, against the market's house vault
+    /// Session key enabled; the stake moves from the bettor's vault into the house vault and the
+    /// round's worst case must fit the market's exposure cap and the house's free liquidity
+    pub fn place_house_bet(ctx: Context<PlaceHouseBet>, side: BetSide, amount: u64, position_index: u8) -> Result<()> {
+        let user_balance = &mut ctx.accounts.user_balance;
+        let round = &ctx.accounts.round;
+        let pool = &mut ctx.accounts.pool;
+        let house = &mut ctx.accounts.house;
+        let position = &mut ctx.accounts.position;
+        let player_round = &mut ctx.accounts.player_round;
+        init_player_round(player_round, user_balance.owner, round.round_id, ctx.bumps.player_round);
+
+        // SECURITY: Verify signer authority (session or wallet)
+        verify_session_or_authority(
+            &ctx.accounts.session_token,
+            &ctx.accounts.session_registry,
+            &ctx.accounts.signer,
+            &user_balance.owner,
+            ENTRYPOINT_PLACE_HOUSE_BET,
+            amount,
+        )?;
+        record_session_spend(
+            &mut ctx.accounts.session_token,
+            &ctx.accounts.signer,
+            amount,
+            ctx.accounts.game_state.session_bets_per_minute,
+        )?;
+
+        // SECURITY: Game not paused
+        require!(
+            !ctx.accounts.game_state.is_paused,
+            SessionBettingError::GamePaused
+        );
+
+        // SECURITY: Compliance eligibility
+        check_eligibility(&ctx.accounts.game_state, user_balance)?;
+        check_compliance(
+            &ctx.accounts.compliance_program,
+            &ctx.accounts.compliance_config,
+            &ctx.accounts.user_compliance,
+            user_balance.owner,
+        )?;
+
+        // Refuse entrypoints deprecated in the program registry
+        registry::require_not_deprecated(&ctx.accounts.program_entry, ENTRYPOINT_PLACE_HOUSE_BET)?;
+
+        // SECURITY: Round must be open
+        require!(round.status == RoundStatus::Open, SessionBettingError::RoundNotOpen);
+
+        // SECURITY: Not past lock time
+        let clock = Clock::get()?;
+        require!(
+            clock.unix_timestamp < round.lock_time,
+            SessionBettingError::RoundLocked
+        );
+
+        // SECURITY: Valid bet amount
+        require!(amount >= ctx.accounts.game_state.min_bet, SessionBettingError::AmountTooSmall);
+        require!(amount <= ctx.accounts.game_state.max_bet, SessionBettingError::AmountTooLarge);
+        check_personal_limits(user_balance, amount, amount, clock.unix_timestamp)?;
+
+        // SECURITY: Per-round pool caps, with the player cap on the wallet's whole stake in the round
+        let player_stake = player_round_stake(player_round)?
+            .checked_add(amount)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        check_pool_caps(&ctx.accounts.game_state, pool, side, amount, player_stake)?;
+
+        // SECURITY: Sufficient balance
+        require!(
+            user_balance.balance >= amount,
+            SessionBettingError::InsufficientBalance
+        );
+
+        // SECURITY: Update balance BEFORE recording bet (reentrancy protection)
+        user_balance.balance = user_balance.balance
+            .checked_sub(amount)
+            .ok_or(SessionBettingError::MathOverflow)?;
+
+        // Record position
+        position.player = user_balance.owner;
+        position.bettor = user_balance.owner;
+        position.round_id = round.round_id;
+        position.index = position_index;
+        position.side = side;
+        position.amount = amount;
+        position.bet_timestamp = clock.unix_timestamp;
+        position.commitment = None;
+        position.revealed = true;
+        position.claimed = false;
+        position.copied = false;
+        position.bump = ctx.bumps.position;
+        position.version = ACCOUNT_VERSION;
+
+        // Update pool
+        add_to_pool(pool, player_round, side, amount)?;
+
+        // SECURITY: Hold back the round's worst case; what the house could lose on it stays within
+        // the market's exposure cap, and every reserve must be covered by SOL in the vault
+        let reserve = house_round_reserve(pool, round.fixed_odds_bps)?;
+        let exposure = reserve - pool.total_pool;
+        let max_exposure = ctx.accounts.market.max_round_exposure;
+        require!(
+            max_exposure == 0 || exposure <= max_exposure,
+            SessionBettingError::HouseExposureExceeded
+        );
+        house.reserved = house.reserved
+            .checked_sub(pool.house_reserved)
+            .and_then(|reserved| reserved.checked_add(reserve))
+            .ok_or(SessionBettingError::MathOverflow)?;
+        pool.house_reserved = reserve;
+        let vault_lamports = ctx.accounts.house_vault.lamports()
+            .checked_add(amount)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        require!(
+            vault_lamports.saturating_sub(Rent::get()?.minimum_balance(0)) >= house.reserved,
+            SessionBettingError::HouseLiquidityInsufficient
+        );
+
+        init_user_stats(&mut ctx.accounts.user_stats, user_balance.owner, ctx.bumps.user_stats);
+        record_bet_stats(&mut ctx.accounts.user_stats, amount)?;
+
+        // Move the stake from the bettor's vault into the house vault
+        let owner = user_balance.owner;
+        let seeds: &[&[u8]] = &[b"vault", owner.as_ref(), &[ctx.bumps.user_vault]];
+        let signer_seeds = &[seeds];
+
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_vault.to_account_info(),
+                to: ctx.accounts.house_vault.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(cpi_context, amount)?;
+
+        // Emit audit event
+        emit_cpi!(BetPlaced {
+            user: owner,
+            round_id: round.round_id,
+            side,
+            amount,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Claim a fixed-odds position after its round is settled or cancelled
+    /// Permissionless like claim_winnings; the payout moves from the house vault into the
+    /// position owner's vault and balance
+    pub fn claim_house_bet(ctx: Context<ClaimHouseBet>) -> Result<()> {
+        // Refuse entrypoints deprecated in the program registry
+        registry::require_not_deprecated(&ctx.accounts.program_entry, ENTRYPOINT_CLAIM_WINNINGS)?;
+
+        true_up_house_reserve(&mut ctx.accounts.house, &mut ctx.accounts.pool, &ctx.accounts.round)?;
+
+        init_user_stats(&mut ctx.accounts.user_stats, ctx.accounts.user_balance.owner, ctx.bumps.user_stats);
+        // Fixed-odds claims carry no fee, so there is nothing to route to referrers or insurance
+        let claim = process_claim(
+            &mut ctx.accounts.game_state,
+            &ctx.accounts.round,
+            &ctx.accounts.pool,
+            &mut ctx.accounts.position,
+            &mut ctx.accounts.user_balance,
+            &mut ctx.accounts.user_stats,
+            &mut ctx.accounts.jackpot,
+        )?;
+        if claim.payout == 0 {
+            return Ok(());
+        }
+
+        // SECURITY: Pay only out of this round's reserve (released to the LPs after the claim window)
+        release_house_reserve_amount(&mut ctx.accounts.house, &mut ctx.accounts.pool, claim.payout)
+            .map_err(|_| SessionBettingError::ClaimExpired)?;
+
+        let market = ctx.accounts.round.market;
+        let seeds: &[&[u8]] = &[b"house_vault", market.as_ref(), &[ctx.bumps.house_vault]];
+        let signer_seeds = &[seeds];
+
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.house_vault.to_account_info(),
+                to: ctx.accounts.user_vault.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(cpi_context, claim.payout)
+    }
+
+    /// Return the liquidity a finished fixed-odds round no longer needs to its house
+    /// Permissionless - once the round is final its reserve shrinks to what its positions are
+    /// owed; after CLAIM_GRACE_PERIOD_SECONDS unclaimed payouts are forfeited to the LPs, like
+    /// close_round forfeits pari-mutuel winnings. close_round requires this first
+    pub fn release_house_reserve(ctx: Context<ReleaseHouseReserve>) -> Result<()> {
+        let round = &ctx.accounts.round;
+        let pool = &mut ctx.accounts.pool;
+        let house = &mut ctx.accounts.house;
+
+        // SECURITY: Round must be settled or cancelled
+        require!(round_is_final(round), SessionBettingError::RoundNotSettled);
+
+        let mut released = true_up_house_reserve(house, pool, round)?;
+
+        let clock = Clock::get()?;
+        let close_time = round.end_time
+            .checked_add(CLAIM_GRACE_PERIOD_SECONDS)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        if clock.unix_timestamp >= close_time {
+            let forfeited = pool.house_reserved;
+            release_house_reserve_amount(house, pool, forfeited)?;
+            released = released
+                .checked_add(forfeited)
+                .ok_or(SessionBettingError::MathOverflow)?;
+        }
+
+        emit_cpi!(HouseReserveReleased {
+            market: round.market,
+            round_id: round.round_id,
+            amount: released,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+// ===================
+// Helper Functions
+// ===================
+
+#[inline]
+/// Append a settled round to its market's ring buffer, overwriting the oldest entry once full
+fn record_round_history(history: &mut RoundHistory, round: &BettingRound, pool: &BettingPool, now: i64) {
+    let slot = (history.total_recorded % ROUND_HISTORY_LEN as u64) as usize;
+    history.entries[slot] = RoundSummary {
+        round_id: round.round_id,
+        start_price: round.start_price,
+        end_price: round.end_price,
+        up_pool: pool.up_pool,
+        down_pool: pool.down_pool,
+        settled_at: now,
+        winner: round.winner as u8,
+        _padding: [0; 7],
+    };
+    history.total_recorded = history.total_recorded.saturating_add(1);
+}
+
+/// A GameState with every setting at its launch default (initialize_game, and v0 migrations)
+fn new_game_state(authority: Pubkey, price_feed_id: [u8; 32], bump: u8) -> GameState {
+    GameState {
+        authority,
+        pending_authority: None,
+        price_feed_id,
+        current_round: 0,
+        total_volume: 0,
+        total_fees_collected: 0,
+        is_paused: false,
+        bump,
+        version: GAME_STATE_VERSION,
+        operator: Pubkey::default(),
+        reserved_payouts: 0,
+        parlay_exposure: 0,
+        max_total_pool: 0,
+        max_side_pool: 0,
+        max_player_stake: 0,
+        max_price_move_bps: 0,
+        max_conf_bps: DEFAULT_MAX_CONF_BPS,
+        min_pool_for_settlement: 0,
+        fee_bps: PLATFORM_FEE_BPS,
+        cancel_fee_bps: CANCEL_FEE_BPS,
+        close_round_bounty: DEFAULT_CLOSE_ROUND_BOUNTY,
+        session_bets_per_minute: DEFAULT_SESSION_BETS_PER_MINUTE,
+        streak_bonus_bps_per_win: STREAK_BONUS_BPS_PER_WIN,
+        max_streak_bonus_bps: MAX_STREAK_BONUS_BPS,
+        max_early_bird_bonus_bps: EARLY_BIRD_BONUS_BPS,
+        min_bet: MIN_BET,
+        max_bet: MAX_BET,
+        min_round_duration: ROUND_DURATION_SECONDS,
+        max_round_duration: DEFAULT_MAX_ROUND_DURATION_SECONDS,
+        eligibility_mode: EligibilityMode::Open,
+        oracle_mode: OracleMode::Push,
+        require_multisig: false,
+        fee_stream_recipient: Pubkey::default(),
+        fee_stream_rate: 0,
+        fee_stream_last_at: 0,
+        token_mint: Pubkey::default(),
+        token_fees_collected: 0,
+        paused_at: 0,
+        admin_timelock_seconds: 0,
+        pending_admin_change_at: 0,
+        game_type_stats: [GameTypeStats::default(); GAME_TYPE_COUNT],
+        game_vault_reserved: [0; GAME_TYPE_COUNT],
+        token_limits: TokenBetLimits::default(),
+        reserved: [0; GAME_STATE_RESERVED_BYTES],
+        fee_split: Vec::new(),
+        pending_admin_change: None,
+        game_programs: Vec::new(),
+    }
+}
+
+/// Set up the jackpot PDA with its launch defaults
+fn init_jackpot(jackpot: &mut Jackpot, bump: u8) {
+    jackpot.balance = 0;
+    jackpot.contribution_bps = JACKPOT_CONTRIBUTION_BPS;
+    jackpot.trigger_move_bps = 0;
+    jackpot.trigger_every_n_rounds = JACKPOT_EVERY_N_ROUNDS;
+    jackpot.total_contributed = 0;
+    jackpot.total_paid = 0;
+    jackpot.bump = bump;
+    jackpot.version = ACCOUNT_VERSION;
+}
+
+/// Decode an account still in its original (v0) layout, i.e. without a version byte
+fn read_v0_account<T: AnchorDeserialize>(info: &AccountInfo, discriminator: &[u8]) -> Result<T> {
+    let data = info.try_borrow_data()?;
+    require!(
+        data.starts_with(discriminator),
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+    T::deserialize(&mut &data[discriminator.len()..])
+        .map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))
+}
+
+/// Serialize `account` over `info`'s data and zero every byte after it, so bytes left by a
+/// longer earlier serialization (a Vec or Option that has since shrunk) never survive a migration
+fn write_account<T: AccountSerialize>(info: &AccountInfo, account: &T) -> Result<()> {
+    let mut data = info.try_borrow_mut_data()?;
+    let len = data.len();
+    let written = {
+        let mut cursor: &mut [u8] = &mut data[..];
+        account.try_serialize(&mut cursor)?;
+        len - cursor.len()
+    };
+    data[written..].fill(0);
+    Ok(())
+}
+
+/// Realloc a program-owned account up to `new_len`, zero-filling the new tail
+/// and topping up rent from `payer`; accounts already that large are left alone
+fn grow_account<'info>(
+    info: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    new_len: usize,
+) -> Result<()> {
+    // SECURITY: Only ever resize this program's own accounts
+    require_keys_eq!(*info.owner, crate::ID, SessionBettingError::Unauthorized);
+
+    if info.data_len() >= new_len {
+        return Ok(());
+    }
+
+    let rent_due = Rent::get()?.minimum_balance(new_len)
+        .saturating_sub(info.lamports());
+    if rent_due > 0 {
+        let cpi_context = CpiContext::new(
+            system_program.to_account_info(),
+            Transfer {
+                from: payer.to_account_info(),
+                to: info.clone(),
+            },
+        );
+        transfer(cpi_context, rent_due)?;
+    }
+
+    info.resize(new_len)?;
+    Ok(())
+}
+
+/// Lamports the global vault owes: collected-but-unwithdrawn fees plus wagers reserved for payouts
+fn global_vault_obligations(game_state: &GameState) -> Result<u64> {
+    game_state.total_fees_collected
+        .checked_add(game_state.reserved_payouts)
+        .ok_or(SessionBettingError::MathOverflow.into())
+}
+
+/// Fail unless the global vault still covers its obligations after `outflow` lamports leave it
+/// Call after updating the accounting the outflow settles
+fn require_solvent(game_state: &GameState, vault_lamports: u64, outflow: u64) -> Result<()> {
+    let remaining = vault_lamports
+        .checked_sub(outflow)
+        .ok_or(SessionBettingError::InsufficientVaultBalance)?;
+    require!(
+        remaining >= global_vault_obligations(game_state)?,
+        SessionBettingError::VaultInsolvent
+    );
+    Ok(())
+}
+
+/// Fail unless a game vault still covers its reserved wagers and rent after `outflow` lamports leave it
+/// Call after updating the accounting the outflow settles
+fn require_game_vault_solvent(
+    game_state: &GameState,
+    game_type: GameType,
+    vault_lamports: u64,
+    outflow: u64,
+) -> Result<()> {
+    let remaining = vault_lamports
+        .checked_sub(outflow)
+        .ok_or(SessionBettingError::InsufficientVaultBalance)?;
+    let obligations = game_state.game_vault_reserved[game_type as usize]
+        .checked_add(Rent::get()?.minimum_balance(0))
+        .ok_or(SessionBettingError::MathOverflow)?;
+    require!(remaining >= obligations, SessionBettingError::VaultInsolvent);
+    Ok(())
+}
+
+/// Fail while the admin timelock is on - changes must then go through propose_admin_change
+fn require_no_timelock(game_state: &GameState) -> Result<()> {
+    require!(
+        game_state.admin_timelock_seconds == 0,
+        SessionBettingError::TimelockActive
+    );
+    Ok(())
+}
+
+/// A market's rounds must close for betting before they end, and stay under
+/// MAX_ROUND_DURATION_SECONDS
+fn validate_market_cadence(round_duration: i64, lock_buffer: i64) -> Result<()> {
+    require!(
+        lock_buffer > 0
+            && lock_buffer < round_duration
+            && round_duration <= MAX_ROUND_DURATION_SECONDS,
+        SessionBettingError::InvalidRoundDuration
+    );
+    Ok(())
+}
+
+/// Fixed odds must pay more than the stake back and never beat MAX_FIXED_ODDS_BPS (0 = pari-mutuel)
+fn validate_fixed_odds(fixed_odds_bps: u64) -> Result<()> {
+    require!(
+        fixed_odds_bps == 0
+            || (fixed_odds_bps > BPS_DENOMINATOR && fixed_odds_bps <= MAX_FIXED_ODDS_BPS),
+        SessionBettingError::InvalidFixedOdds
+    );
+    Ok(())
+}
+
+/// Bounds a queued admin change must meet, mirroring the direct setters
+fn validate_admin_change(change: &AdminChange) -> Result<()> {
+    match *change {
+        AdminChange::FeeBps { fee_bps } => {
+            require!(fee_bps <= MAX_FEE_BPS, SessionBettingError::InvalidFee);
+        }
+        AdminChange::CancelFeeBps { cancel_fee_bps } => {
+            require!(cancel_fee_bps <= MAX_FEE_BPS, SessionBettingError::InvalidFee);
+        }
+        AdminChange::BetLimits { min_bet, max_bet } => {
+            require!(
+                min_bet > 0 && min_bet <= max_bet,
+                SessionBettingError::InvalidBetLimits
+            );
+        }
+        AdminChange::SwitchboardFeed { .. } => {}
+        AdminChange::MarketCadence { round_duration, lock_buffer, .. } => {
+            validate_market_cadence(round_duration, lock_buffer)?;
+        }
+        AdminChange::MarketFixedOdds { fixed_odds_bps, .. } => validate_fixed_odds(fixed_odds_bps)?,
+        AdminChange::Timelock { seconds } => {
+            require!(
+                (0..=MAX_ADMIN_TIMELOCK_SECONDS).contains(&seconds),
+                SessionBettingError::InvalidTimelock
+            );
+        }
+        AdminChange::AddGameProgram { program_id } => {
+            require!(program_id != Pubkey::default(), SessionBettingError::InvalidAddress);
+        }
+        AdminChange::PoolCaps { max_total_pool, max_side_pool, .. } => {
+            if max_total_pool > 0 {
+                require!(max_side_pool <= max_total_pool, SessionBettingError::InvalidPoolCaps);
+            }
+        }
+        AdminChange::TokenBetLimits { limits } => validate_token_bet_limits(&limits)?,
+        AdminChange::RoundDurationBounds { min_duration, max_duration } => {
+            require!(
+                min_duration > LOCK_BUFFER_SECONDS
+                    && min_duration <= max_duration
+                    && max_duration <= MAX_ROUND_DURATION_SECONDS,
+                SessionBettingError::InvalidRoundDuration
+            );
+        }
+        AdminChange::Operator { operator } => {
+            require!(operator != Pubkey::default(), SessionBettingError::InvalidAddress);
+        }
+        AdminChange::StreakBonus { streak_bonus_bps_per_win, max_streak_bonus_bps } => {
+            require!(
+                max_streak_bonus_bps <= MAX_STREAK_BONUS_BPS
+                    && streak_bonus_bps_per_win <= max_streak_bonus_bps,
+                SessionBettingError::InvalidStreakBonus
+            );
+        }
+        AdminChange::EarlyBirdBonus { max_early_bird_bonus_bps } => {
+            require!(
+                max_early_bird_bonus_bps <= MAX_EARLY_BIRD_BONUS_BPS,
+                SessionBettingError::InvalidEarlyBirdBonus
+            );
+        }
+        AdminChange::JackpotConfig { contribution_bps, .. } => {
+            require!(
+                contribution_bps <= MAX_JACKPOT_CONTRIBUTION_BPS,
+                SessionBettingError::InvalidJackpotConfig
+            );
+        }
+        AdminChange::ActivateMarket { .. }
+        | AdminChange::CircuitBreaker { .. }
+        | AdminChange::MaxConfBps { .. }
+        | AdminChange::MinPoolForSettlement { .. }
+        | AdminChange::OracleMode { .. }
+        | AdminChange::SessionRateLimit { .. } => {}
+    }
+    Ok(())
+}
+
+/// Add to the per-GameType volume, fee and payout counters
+fn record_game_type_stats(
+    game_state: &mut GameState,
+    game_type: GameType,
+    volume: u64,
+    fees: u64,
+    payouts: u64,
+) -> Result<()> {
+    let stats = &mut game_state.game_type_stats[game_type as usize];
+    stats.volume = stats.volume
+        .checked_add(volume)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    stats.fees = stats.fees
+        .checked_add(fees)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    stats.payouts = stats.payouts
+        .checked_add(payouts)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    Ok(())
+}
+
+/// Append a program to the game-program allowlist, ignoring duplicates
+fn add_game_program_entry(game_state: &mut GameState, program_id: Pubkey) -> Result<()> {
+    require!(program_id != Pubkey::default(), SessionBettingError::InvalidAddress);
+    if game_state.game_programs.contains(&program_id) {
+        return Ok(());
+    }
+    require!(
+        game_state.game_programs.len() < MAX_GAME_PROGRAMS,
+        SessionBettingError::TooManyGamePrograms
+    );
+    game_state.game_programs.push(program_id);
+    Ok(())
+}
+
+/// Whether `game_program` is on the allowlist of programs that may move user balances
+fn is_game_program(game_state: &GameState, game_program: &Pubkey) -> bool {
+    game_state.game_programs.contains(game_program)
+}
+
+/// Whether `key` may run the round lifecycle: the authority or the configured operator
+fn is_round_operator(game_state: &GameState, key: &Pubkey) -> bool {
+    *key == game_state.authority
+        || (game_state.operator != Pubkey::default() && *key == game_state.operator)
+}
+
+/// Add a session-signed stake to the session's running total and enforce its spend_limit
+/// and the game's bets-per-minute cap
+/// Call after verify_session_or_authority; wallet-signed stakes are not counted
+/// Token stakes go through record_session_token_spend since spend_limit is in lamports
+fn record_session_spend(
+    session_token: &mut Option<Account<SessionToken>>,
+    signer: &Signer,
+    amount: u64,
+    bets_per_minute: u64,
+) -> Result<()> {
+    let Some(session) = session_token else {
+        return Ok(());
+    };
+    if session.session_signer != signer.key() {
+        return Ok(());
+    }
+    count_session_bet(session, bets_per_minute)?;
+
+    session.spent = session.spent
+        .checked_add(amount)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    if session.spend_limit > 0 {
+        // SECURITY: Bounds what a leaked session key can stake in total
+        require!(
+            session.spent <= session.spend_limit,
+            SessionBettingError::SessionSpendLimitExceeded
+        );
+    }
+    Ok(())
+}
+
+/// Add a session-signed token stake to the session's running token total and enforce its
+/// token_spend_limit (0 = the session key may not bet tokens) and the game's bets-per-minute cap
+/// Call after verify_session_or_authority; wallet-signed stakes are not counted
+fn record_session_token_spend(
+    session_token: &mut Option<Account<SessionToken>>,
+    signer: &Signer,
+    amount: u64,
+    bets_per_minute: u64,
+) -> Result<()> {
+    let Some(session) = session_token else {
+        return Ok(());
+    };
+    if session.session_signer != signer.key() {
+        return Ok(());
+    }
+    count_session_bet(session, bets_per_minute)?;
+
+    session.token_spent = session.token_spent
+        .checked_add(amount)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    // SECURITY: Bounds what a leaked session key can stake from the token balance
+    require!(
+        session.token_spent <= session.token_spend_limit,
+        SessionBettingError::SessionSpendLimitExceeded
+    );
+    Ok(())
+}
+
+/// Count one session-signed bet in the current clock minute, shared by every bet entrypoint
+/// whatever the stake's denomination
+fn count_session_bet(session: &mut SessionToken, bets_per_minute: u64) -> Result<()> {
+    // A new minute starts a new window
+    let now = Clock::get()?.unix_timestamp;
+    if now / 60 != session.last_bet_ts / 60 {
+        session.bets_in_window = 0;
+    }
+    session.last_bet_ts = now;
+    session.bets_in_window = session.bets_in_window
+        .checked_add(1)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    if bets_per_minute > 0 {
+        // SECURITY: Throttles automated abuse from a leaked session key
+        require!(
+            u64::from(session.bets_in_window) <= bets_per_minute,
+            SessionBettingError::SessionRateLimited
+        );
+    }
+    Ok(())
+}
+
+/// `entrypoint` and `amount` are checked against the session's scope; the wallet itself is unscoped
+fn verify_session_or_authority(
+    session_token: &Option<Account<SessionToken>>,
+    session_registry: &Option<Account<SessionRegistry>>,
+    signer: &Signer,
+    expected_authority: &Pubkey,
+    entrypoint: u64,
+    amount: u64,
+) -> Result<()> {
+    // If signer is the authority directly, allow
+    if signer.key() == *expected_authority {
+        return Ok(());
+    }
+
+    // Otherwise, must have valid session token
+    match session_token {
+        Some(session) => {
+            // SECURITY: Session must be for this authority
+            require!(
+                session.authority == *expected_authority,
+                SessionBettingError::SessionAuthorityMismatch
+            );
+
+            // SECURITY: Signer must be the session signer
+            require!(
+                session.session_signer == signer.key(),
+                SessionBettingError::InvalidSessionSigner
+            );
+
+            // SECURITY: Session must not be expired
+            let clock = Clock::get()?;
+            require!(
+                clock.unix_timestamp < session.valid_until,
+                SessionBettingError::SessionExpired
+            );
+
+            // SECURITY: Session must not predate the owner's last revoke_all_sessions
+            let registry = session_registry.as_ref()
+                .ok_or(SessionBettingError::SessionRevoked)?;
+            require!(
+                session.generation == registry.generation,
+                SessionBettingError::SessionRevoked
+            );
+
+            // SECURITY: A session key only reaches what its wallet scoped it to
+            require!(
+                session.allowed_instructions & entrypoint != 0,
+                SessionBettingError::SessionScopeExceeded
+            );
+            if session.max_bet_per_tx > 0 {
+                require!(
+                    amount <= session.max_bet_per_tx,
+                    SessionBettingError::SessionScopeExceeded
+                );
+            }
+
+            Ok(())
+        }
+        None => {
+            // No session and not authority - unauthorized
+            Err(SessionBettingError::Unauthorized.into())
+        }
+    }
+}
+
+/// A validated oracle price in PRICE_DECIMALS
+struct OracleReading {
+    price: u64,
+    /// Confidence interval (0 for sources that publish none)
+    conf: u64,
+    /// Slot the price was published or posted in
+    publish_slot: u64,
+}
+
+/// Reader for the market's feed: Pyth accounts follow the game's oracle mode
+fn market_reader(mode: OracleMode, market: &Market) -> OracleReader {
+    match (market.price_source, mode) {
+        (PriceSource::Pyth, OracleMode::Push) => OracleReader::PythPush,
+        (PriceSource::Pyth, OracleMode::Pull) => OracleReader::PythPull,
+        (PriceSource::Chainlink, _) => OracleReader::Chainlink,
+    }
+}
+
+/// Map a shared oracle reader error onto this program's errors
+fn oracle_error(err: OracleError) -> Error {
+    match err {
+        OracleError::InvalidAccount => SessionBettingError::InvalidPriceFeed,
+        OracleError::FeedMismatch => SessionBettingError::PriceFeedMismatch,
+        OracleError::Stale => SessionBettingError::PriceTooStale,
+        OracleError::NonPositive => SessionBettingError::InvalidPrice,
+        OracleError::EmaUnsupported => SessionBettingError::SettlementModeUnsupported,
+    }
+    .into()
+}
+
+/// Load the market's current reading from its configured source, rejecting stale or
+/// non-positive prices
+/// Pyth push reads a legacy price account, Pyth pull a receiver-posted PriceUpdateV2, and
+/// Chainlink markets are keyed by their feed account (it publishes no confidence interval)
+/// use_ema reads the EMA price and confidence instead of the aggregate (Pyth only)
+fn read_market_price(
+    mode: OracleMode,
+    market: &Market,
+    price_account: &AccountInfo,
+    now: i64,
+    use_ema: bool,
+) -> Result<OracleReading> {
+    // SECURITY: Owned by the source's program, matching feed, fresh and positive
+    let reading = market_reader(mode, market)
+        .read_no_older_than(price_account, &market.feed_id, now, MAX_PRICE_AGE_SECONDS, use_ema)
+        .map_err(oracle_error)?;
+
+    // SECURITY: Every source is rescaled by its own exponent, so a round started on one
+    // source and locked on another (e.g. the Switchboard fallback) compares like with like
+    Ok(OracleReading {
+        price: reading.price_in(PRICE_DECIMALS).ok_or(SessionBettingError::InvalidPrice)?,
+        conf: reading.conf_in(PRICE_DECIMALS).ok_or(SessionBettingError::MathOverflow)?,
+        publish_slot: reading.publish_slot,
+    })
+}
+
+/// Read a round's start price from the market's feed (EMA for EMA rounds)
+/// Wide-confidence prints are rejected outright - the caller can retry
+fn read_start_price(
+    game_state: &GameState,
+    market: &Market,
+    price_account: &AccountInfo,
+    settlement_mode: SettlementMode,
+    now: i64,
+) -> Result<OracleReading> {
+    let start = read_market_price(
+        game_state.oracle_mode,
+        market,
+        price_account,
+        now,
+        settlement_mode == SettlementMode::Ema,
+    )?;
+    require!(
+        !conf_too_wide(&start, game_state.max_conf_bps),
+        SessionBettingError::PriceConfidenceTooWide
+    );
+    Ok(start)
+}
+
+/// End price for the round's settlement mode given the lock reading
+/// TWAP rounds average every recorded sample with the lock reading
+fn settlement_price(round: &BettingRound, lock_price: u64) -> Result<u64> {
+    if round.settlement_mode != SettlementMode::Twap {
+        return Ok(lock_price);
+    }
+
+    let sum = round.price_sample_sum
+        .checked_add(lock_price as u128)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    let count = u128::from(round.price_sample_count) + 1;
+    u64::try_from(sum / count).map_err(|_| SessionBettingError::MathOverflow.into())
+}
+
+/// Whether the reading's conf is wider than max_conf_bps of its price (0 = check disabled)
+fn conf_too_wide(reading: &OracleReading, max_conf_bps: u64) -> bool {
+    if max_conf_bps == 0 {
+        return false;
+    }
+    // Saturate: a ratio too large to represent is too wide for any limit
+    bet_math::mul_div(reading.conf, BPS_DENOMINATOR, reading.price).is_none_or(|conf_bps| conf_bps > max_conf_bps)
+}
+
+/// Returns whether the round is locking on a wide-confidence print
+/// Inside CONF_RETRY_WINDOW_SECONDS after lock_time the lock is rejected so it can be retried;
+/// after that it goes ahead and the round settles as a Draw instead of getting stuck
+fn check_lock_confidence(game_state: &GameState, round: &BettingRound, reading: &OracleReading, now: i64) -> Result<bool> {
+    if !conf_too_wide(reading, game_state.max_conf_bps) {
+        return Ok(false);
+    }
+
+    let retry_deadline = round.lock_time
+        .checked_add(CONF_RETRY_WINDOW_SECONDS)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    require!(now >= retry_deadline, SessionBettingError::PriceConfidenceTooWide);
+    Ok(true)
+}
+
+/// True if the market's primary feed has no price within MAX_PRICE_AGE_SECONDS
+/// The account must still be a valid price account for the market's feed
+fn primary_is_stale(mode: OracleMode, market: &Market, price_account: &AccountInfo, now: i64) -> Result<bool> {
+    let reading = market_reader(mode, market)
+        .read(price_account, &market.feed_id, false)
+        .map_err(oracle_error)?;
+    Ok(!reading.is_fresh(now, MAX_PRICE_AGE_SECONDS))
+}
+
+/// Initialize the market's next round and pool, then advance its round counter
+/// Shared by start_round and start_round_oracle; callers validate price and duration
+/// The round runs on the market's cadence (round_duration, closing lock_buffer before the end)
+fn open_round(
+    market: &mut Account<Market>,
+    round: &mut BettingRound,
+    pool: &mut BettingPool,
+    (round_bump, pool_bump): (u8, u8),
+    start: &OracleReading,
+    settlement_mode: SettlementMode,
+) -> Result<()> {
+    // SECURITY: Chainlink publishes no EMA
+    require!(
+        settlement_mode != SettlementMode::Ema || market.price_source == PriceSource::Pyth,
+        SessionBettingError::SettlementModeUnsupported
+    );
+
+    let round_id = market.current_round;
+    let now = Clock::get()?.unix_timestamp;
+
+    // Initialize round
+    round.market = market.key();
+    round.round_id = round_id;
+    round.start_time = now;
+    round.lock_time = now + market.round_duration - market.lock_buffer;
+    round.end_time = now + market.round_duration;
+    // Fallback allows permissionless locking after authority timeout
+    round.lock_time_fallback = round.lock_time + FALLBACK_LOCK_DELAY_SECONDS;
+    round.start_price = start.price;
+    round.start_publish_slot = start.publish_slot;
+    round.end_price = 0;
+    round.settlement_mode = settlement_mode;
+    round.price_sample_sum = 0;
+    round.price_sample_count = 0;
+    round.last_sample_at = now;
+    round.wide_confidence = false;
+    round.jackpot_payout = 0;
+    round.status = RoundStatus::Open;
+    round.winner = WinnerSide::None;
+    round.bump = round_bump;
+    round.fixed_odds_bps = market.fixed_odds_bps;
+    round.version = ACCOUNT_VERSION;
+
+    // Initialize pool
+    pool.round_id = round_id;
+    pool.up_pool = 0;
+    pool.down_pool = 0;
+    pool.total_pool = 0;
+    pool.up_bettors = 0;
+    pool.down_bettors = 0;
+    pool.unrevealed_pool = 0;
+    pool.bump = pool_bump;
+    pool.house_reserved = 0;
+    pool.house_reserve_settled = false;
+    pool.version = ACCOUNT_VERSION;
+
+    // Increment the market's round counter
+    market.current_round = market.current_round.checked_add(1)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    market.last_round_started_at = now;
+    Ok(())
+}
+
+/// Compute what a claim on this position pays, given a round outcome
+/// Single source of truth for claim_winnings, claim_and_close and preview_payout
+fn calculate_claim(
+    position: &PlayerPosition,
+    winner: WinnerSide,
+    pool: &BettingPool,
+    fee_bps: u64,
+) -> Result<PayoutPreview> {
+    // Unrevealed commit-reveal bets never joined a side and forfeit their stake:
+    // winners share it with the losing pool, and on a Draw the protocol keeps it
+    if !position.revealed {
+        let fee = if winner == WinnerSide::Draw { position.amount } else { 0 };
+        return Ok(PayoutPreview { payout: 0, fee, won: false });
+    }
+
+    // Forfeited hidden stakes are added to the losing side
+    let (up_pool, down_pool) = match winner {
+        WinnerSide::Up => (
+            pool.up_pool,
+            pool.down_pool.checked_add(pool.unrevealed_pool).ok_or(SessionBettingError::MathOverflow)?,
+        ),
+        WinnerSide::Down => (
+            pool.up_pool.checked_add(pool.unrevealed_pool).ok_or(SessionBettingError::MathOverflow)?,
+            pool.down_pool,
+        ),
+        _ => (pool.up_pool, pool.down_pool),
+    };
+
+    // Calculate winnings
+    let winnings = calculate_winnings(
+        position.amount,
+        position.side,
+        winner,
+        up_pool,
+        down_pool,
+    )?;
+
+    if winnings > 0 {
+        // Calculate fee
+        let (payout, fee) = bet_math::split_fee(winnings, fee_bps)
+            .ok_or(SessionBettingError::MathOverflow)?;
+
+        Ok(PayoutPreview { payout, fee, won: true })
+    } else if winner == WinnerSide::Draw {
+        // Refund on draw
+        Ok(PayoutPreview { payout: position.amount, fee: 0, won: false })
+    } else {
+        Ok(PayoutPreview { payout: 0, fee: 0, won: false })
+    }
+}
+
+/// Move a deposit from the payer wallet into the owner's vault and credit their balance
+/// Shared by deposit, deposit_with_referrer and deposit_for; the caller emits the event
+fn credit_deposit<'info>(
+    user_balance: &mut UserBalance,
+    vault: &SystemAccount<'info>,
+    payer: &Signer<'info>,
+    owner: Pubkey,
+    system_program: &Program<'info, System>,
+    balance_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    // SECURITY: Transfer SOL from payer to vault
+    let cpi_context = CpiContext::new(
+        system_program.to_account_info(),
+        Transfer {
+            from: payer.to_account_info(),
+            to: vault.to_account_info(),
+        },
+    );
+    transfer(cpi_context, amount)?;
+
+    // Update balance
+    user_balance.owner = owner;
+    user_balance.balance = user_balance.balance
+        .checked_add(amount)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    user_balance.total_deposited = user_balance.total_deposited
+        .checked_add(amount)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    user_balance.bump = balance_bump;
+    user_balance.version = USER_BALANCE_VERSION;
+    Ok(())
+}
+
+/// Debit a user's balance and move the wager from their vault into its game vault
+/// Shared by transfer_to_global_vault and debit_for_game; the caller emits FundsLocked
+#[allow(clippy::too_many_arguments)]
+fn lock_wager<'info>(
+    game_state: &mut GameState,
+    user_balance: &mut UserBalance,
+    user_vault: &SystemAccount<'info>,
+    game_vault: &SystemAccount<'info>,
+    system_program: &Program<'info, System>,
+    game_type: GameType,
+    vault_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    // SECURITY: User must have sufficient balance
+    require!(
+        user_balance.balance >= amount,
+        SessionBettingError::InsufficientBalance
+    );
+
+    // Update balance BEFORE transfer (reentrancy protection)
+    user_balance.balance = user_balance.balance
+        .checked_sub(amount)
+        .ok_or(SessionBettingError::MathOverflow)?;
+
+    // The locked wager is owed back to players until credit_winnings pays it out
+    let reserved = &mut game_state.game_vault_reserved[game_type as usize];
+    *reserved = reserved
+        .checked_add(amount)
+        .ok_or(SessionBettingError::MathOverflow)?;
+
+    // Transfer from user's vault to the game vault
+    let owner = user_balance.owner;
+    let seeds = &[
+        b"vault",
+        owner.as_ref(),
+        &[vault_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_context = CpiContext::new_with_signer(
+        system_program.to_account_info(),
+        Transfer {
+            from: user_vault.to_account_info(),
+            to: game_vault.to_account_info(),
+        },
+        signer_seeds,
+    );
+    transfer(cpi_context, amount)
+}
+
+/// Credit a user's balance and move the payout from its game vault into their vault
+/// Shared by credit_winnings and credit_for_game; the caller emits WinningsCredited
+#[allow(clippy::too_many_arguments)]
+fn pay_winnings<'info>(
+    game_state: &mut GameState,
+    user_balance: &mut UserBalance,
+    user_vault: &SystemAccount<'info>,
+    game_vault: &SystemAccount<'info>,
+    system_program: &Program<'info, System>,
+    game_type: GameType,
+    game_vault_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    // SECURITY: Amount must be positive
+    require!(amount > 0, SessionBettingError::AmountTooSmall);
+
+    // SECURITY: Game vault must have sufficient balance to pay out
+    require!(
+        game_vault.lamports() >= amount,
+        SessionBettingError::InsufficientVaultBalance
+    );
+
+    // Payouts draw down locked wagers first, then house funds
+    let reserved = &mut game_state.game_vault_reserved[game_type as usize];
+    *reserved = reserved.saturating_sub(amount);
+
+    // SECURITY: Never pay out of wagers still reserved for other players of this game
+    require_game_vault_solvent(game_state, game_type, game_vault.lamports(), amount)?;
+
+    // Credit to user balance
+    user_balance.balance = user_balance.balance
+        .checked_add(amount)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    user_balance.total_winnings = user_balance.total_winnings
+        .checked_add(amount)
+        .ok_or(SessionBettingError::MathOverflow)?;
+
+    // Transfer from the game vault to user's vault
+    let seeds: &[&[u8]] = &[
+        b"global_vault",
+        &[game_type as u8],
+        &[game_vault_bump],
+    ];
+    let signer_seeds = &[seeds];
+
+    let cpi_context = CpiContext::new_with_signer(
+        system_program.to_account_info(),
+        Transfer {
+            from: game_vault.to_account_info(),
+            to: user_vault.to_account_info(),
+        },
+        signer_seeds,
+    );
+    transfer(cpi_context, amount)
+}
+
+/// Move the jackpot's slice of a claim fee out of protocol revenue into the jackpot
+/// Returns the lamports diverted so the caller can shrink the fee it routes onward
+fn fund_jackpot(game_state: &mut GameState, jackpot: &mut Jackpot, fee: u64) -> Result<u64> {
+    let share = bet_math::bps_of(fee, jackpot.contribution_bps)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    if share == 0 {
+        return Ok(0);
+    }
+
+    game_state.total_fees_collected = game_state.total_fees_collected
+        .checked_sub(share)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    jackpot.balance = jackpot.balance
+        .checked_add(share)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    jackpot.total_contributed = jackpot.total_contributed
+        .checked_add(share)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    Ok(share)
+}
+
+/// Stamp a lazily created UserStats account with its owner (no-op once initialized)
+fn init_user_stats(stats: &mut UserStats, owner: Pubkey, bump: u8) {
+    if stats.owner == Pubkey::default() {
+        stats.owner = owner;
+        stats.bump = bump;
+        stats.version = ACCOUNT_VERSION;
+    }
+}
+
+/// Count a newly placed position and its stake in the owner's lifetime stats
+fn record_bet_stats(stats: &mut UserStats, amount: u64) -> Result<()> {
+    stats.rounds_played = stats.rounds_played
+        .checked_add(1)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    stats.total_volume = stats.total_volume
+        .checked_add(amount)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    Ok(())
+}
+
+/// Wins the streak holds going into `round`, or 0 unless `round` directly follows the
+/// streak's last round in the same market
+/// SECURITY: The streak lives on round ids, not claim order, so leaving a loss unclaimed
+/// breaks it the same as claiming it, and a late claim for an earlier round never adds to it
+fn streak_before(stats: &UserStats, round: &BettingRound) -> u32 {
+    let follows = stats.streak_market == round.market
+        && stats.streak_round_id.checked_add(1) == Some(round.round_id);
+    if follows {
+        stats.current_streak
+    } else {
+        0
+    }
+}
+
+/// Count a claimed position as a win or loss and advance the win streak
+/// Refunds (draws, cancelled rounds) leave the record untouched; a forfeited hidden stake is a loss
+/// Claims for a round at or before the streak's last round in its market leave the streak alone
+fn record_claim_stats(stats: &mut UserStats, round: &BettingRound, claim: &PayoutPreview) -> Result<()> {
+    let is_late = stats.streak_market == round.market && round.round_id <= stats.streak_round_id;
+    if claim.won {
+        stats.rounds_won = stats.rounds_won
+            .checked_add(1)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        if !is_late {
+            stats.current_streak = streak_before(stats, round)
+                .checked_add(1)
+                .ok_or(SessionBettingError::MathOverflow)?;
+            stats.best_streak = stats.best_streak.max(stats.current_streak);
+            stats.streak_market = round.market;
+            stats.streak_round_id = round.round_id;
+        }
+    } else if claim.payout == 0 {
+        stats.rounds_lost = stats.rounds_lost
+            .checked_add(1)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        if !is_late || round.round_id == stats.streak_round_id {
+            stats.current_streak = 0;
+            stats.streak_market = round.market;
+            stats.streak_round_id = round.round_id;
+        }
+    }
+    Ok(())
+}
+
+/// Win-streak bonus: min(streak * per-win bps, max bps) of winnings
+fn streak_bonus_bps(game_state: &GameState, win_streak: u32) -> u64 {
+    game_state.streak_bonus_bps_per_win
+        .saturating_mul(win_streak as u64)
+        .min(game_state.max_streak_bonus_bps)
+}
+
+/// Early-bird bonus: the max bonus scaled by how much of the betting window
+/// (start_time..lock_time) was still left when the stake was placed
+fn early_bird_bonus_bps(game_state: &GameState, round: &BettingRound, position: &PlayerPosition) -> Result<u64> {
+    let window = round.lock_time.saturating_sub(round.start_time);
+    if game_state.max_early_bird_bonus_bps == 0 || window <= 0 {
+        return Ok(0);
+    }
+    let lead = round.lock_time
+        .saturating_sub(position.bet_timestamp)
+        .clamp(0, window);
+
+    bet_math::mul_div(game_state.max_early_bird_bonus_bps, lead as u64, window as u64)
+        .ok_or(SessionBettingError::MathOverflow.into())
+}
+
+/// Stake-weighted entry time after adding `added` lamports at `now` to a position
+fn blended_bet_timestamp(
+    round: &BettingRound,
+    bet_timestamp: i64,
+    amount: u64,
+    now: i64,
+    added: u64,
+) -> Result<i64> {
+    let total = amount
+        .checked_add(added)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    let old_lead = round.lock_time.saturating_sub(bet_timestamp).max(0) as u64;
+    let new_lead = round.lock_time.saturating_sub(now).max(0) as u64;
+
+    let lead = bet_math::mul_div(old_lead, amount, total)
+        .zip(bet_math::mul_div(new_lead, added, total))
+        .and_then(|(old, new)| old.checked_add(new))
+        .ok_or(SessionBettingError::MathOverflow)?;
+    Ok(round.lock_time.saturating_sub(lead as i64))
+}
+
+/// Move a bonus of `bonus_bps` of gross winnings from a winning claim's fee to its payout
+/// Capped at the fee, so bonuses are always funded by the protocol's share
+fn apply_fee_funded_bonus(claim: &mut PayoutPreview, bonus_bps: u64) -> Result<()> {
+    if bonus_bps == 0 {
+        return Ok(());
+    }
+
+    let winnings = claim.payout
+        .checked_add(claim.fee)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    let bonus = bet_math::bps_of(winnings, bonus_bps)
+        .ok_or(SessionBettingError::MathOverflow)?
+        .min(claim.fee);
+
+    claim.payout = claim.payout
+        .checked_add(bonus)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    claim.fee -= bonus;
+    Ok(())
+}
+
+/// Checks shared by settle_round and force_settle: the round is locked and has ended
+fn require_settleable(round: &BettingRound, now: i64) -> Result<()> {
+    // SECURITY: Round must be locked
+    require!(round.status == RoundStatus::Locked, SessionBettingError::RoundNotLocked);
+
+    // SECURITY: Must be after end_time
+    require!(now >= round.end_time, SessionBettingError::TooEarlyToSettle);
+    Ok(())
+}
+
+/// Winner of a locked round from its recorded prices and pools
+/// Returns (winner, price move in bps, whether the circuit breaker forced a Draw)
+fn settlement_outcome(
+    game_state: &GameState,
+    round: &BettingRound,
+    pool: &BettingPool,
+) -> Result<(WinnerSide, u64, bool)> {
+    // SECURITY: Circuit breaker - an extreme move is most likely a bad print
+    // (flash crash / feed glitch), so refund everyone instead of paying out on it
+    let move_bps = price_move_bps(round.start_price, round.end_price)?;
+    let breaker_tripped = game_state.max_price_move_bps > 0
+        && move_bps > game_state.max_price_move_bps;
+
+    // Rounds below the minimum pool settle as refunds, avoiding
+    // pathological fee rounding and dust payouts
+    let below_min_pool = pool.total_pool < game_state.min_pool_for_settlement;
+
+    // One-sided rounds have no counterparty: winners would only get their stake
+    // back minus the fee and losers would lose it to nobody, so refund everyone
+    // (fixed-odds rounds always have one - the house vault)
+    let one_sided = round.fixed_odds_bps == 0 && (pool.up_pool == 0 || pool.down_pool == 0);
+
+    let winner = if breaker_tripped || below_min_pool || one_sided || round.wide_confidence {
+        WinnerSide::Draw
+    } else if round.end_price > round.start_price {
+        WinnerSide::Up
+    } else if round.end_price < round.start_price {
+        WinnerSide::Down
+    } else {
+        WinnerSide::Draw
+    };
+    Ok((winner, move_bps, breaker_tripped))
+}
+
+/// Record a settleable round's outcome: winner, status, jackpot award and volume stats
+/// Shared by settle_round, force_settle and advance_round, which emit the audit events;
+/// returns (price move in bps, whether the circuit breaker forced a Draw)
+/// Without a jackpot account the pot is left untouched
+fn apply_settlement(
+    game_state: &mut GameState,
+    round: &mut BettingRound,
+    pool: &BettingPool,
+    jackpot: Option<&mut Jackpot>,
+) -> Result<(u64, bool)> {
+    let (winner, move_bps, breaker_tripped) = settlement_outcome(game_state, round, pool)?;
+    round.winner = winner;
+    round.status = RoundStatus::Settled;
+
+    // Progressive jackpot: a decisive round that hits a trigger takes the whole pot
+    // Fixed-odds rounds are paid by their house vault and never take it
+    if let (Some(jackpot), 0) = (jackpot, round.fixed_odds_bps) {
+        let jackpot_hit = (jackpot.trigger_move_bps > 0 && move_bps >= jackpot.trigger_move_bps)
+            || bet_math::is_every_nth_round(round.round_id, jackpot.trigger_every_n_rounds);
+        if jackpot_hit && winner != WinnerSide::Draw && jackpot.balance > 0 {
+            round.jackpot_payout = jackpot.balance;
+            jackpot.total_paid = jackpot.total_paid
+                .checked_add(jackpot.balance)
+                .ok_or(SessionBettingError::MathOverflow)?;
+            jackpot.balance = 0;
+        }
+    }
+
+    // Update stats
+    game_state.total_volume = game_state.total_volume
+        .checked_add(pool.total_pool)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    record_game_type_stats(game_state, GameType::Oracle, pool.total_pool, 0, 0)?;
+
+    Ok((move_bps, breaker_tripped))
+}
+
+/// RoundSettled audit event for a round apply_settlement just settled
+fn round_settled_event(round: &BettingRound, pool: &BettingPool, timestamp: i64) -> RoundSettled {
+    RoundSettled {
+        market: round.market,
+        round_id: round.round_id,
+        start_price: round.start_price,
+        end_price: round.end_price,
+        winner: round.winner,
+        up_pool: pool.up_pool,
+        down_pool: pool.down_pool,
+        up_bettors: pool.up_bettors,
+        down_bettors: pool.down_bettors,
+        timestamp,
+    }
+}
+
+/// Whether a round has reached an outcome positions can be claimed against
+fn round_is_final(round: &BettingRound) -> bool {
+    matches!(round.status, RoundStatus::Settled | RoundStatus::Cancelled)
+}
+
+/// Payout owed to a position in a settled or cancelled round
+/// Cancelled rounds refund every stake in full, including unrevealed commitments;
+/// winners of a jackpot round also receive their share of the jackpot
+fn round_claim(
+    round: &BettingRound,
+    position: &PlayerPosition,
+    pool: &BettingPool,
+    fee_bps: u64,
+) -> Result<PayoutPreview> {
+    if round.status == RoundStatus::Cancelled {
+        return Ok(PayoutPreview { payout: position.amount, fee: 0, won: false });
+    }
+    if round.fixed_odds_bps > 0 {
+        return fixed_odds_claim(round, position);
+    }
+    let mut claim = calculate_claim(position, round.winner, pool, fee_bps)?;
+
+    // Winners of a jackpot round split it pro rata by stake, fee-free
+    if claim.won && round.jackpot_payout > 0 {
+        let winning_pool = match round.winner {
+            WinnerSide::Up => pool.up_pool,
+            _ => pool.down_pool,
+        };
+        let share = bet_math::mul_div(round.jackpot_payout, position.amount, winning_pool)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        claim.payout = claim.payout
+            .checked_add(share)
+            .ok_or(SessionBettingError::MathOverflow)?;
+    }
+    Ok(claim)
+}
+
+/// Payout owed to a position in a settled fixed-odds round
+/// Winners are paid the round's quoted odds and a Draw refunds the stake; the house edge is in
+/// the odds, so no fee is taken
+fn fixed_odds_claim(round: &BettingRound, position: &PlayerPosition) -> Result<PayoutPreview> {
+    let won = matches!(
+        (position.side, round.winner),
+        (BetSide::Up, WinnerSide::Up) | (BetSide::Down, WinnerSide::Down)
+    );
+    let payout = if won {
+        bet_math::mul_div(position.amount, round.fixed_odds_bps, BPS_DENOMINATOR)
+            .ok_or(SessionBettingError::MathOverflow)?
+    } else if round.winner == WinnerSide::Draw {
+        position.amount
+    } else {
+        0
+    };
+    Ok(PayoutPreview { payout, fee: 0, won })
+}
+
+/// Debit a user's balance and pay it out of their vault to `destination`
+/// Shared by withdraw, withdraw_to and claim_and_withdraw; the caller emits the event
+fn withdraw_from_vault<'info>(
+    user_balance: &mut UserBalance,
+    vault: &SystemAccount<'info>,
+    user: &Signer<'info>,
+    destination: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    vault_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    // SECURITY: Check ownership (wallet must sign, not session)
+    require!(
+        user_balance.owner == user.key(),
+        SessionBettingError::NotBalanceOwner
+    );
+
+    // SECURITY: Check sufficient balance
+    require!(
+        user_balance.balance >= amount,
+        SessionBettingError::InsufficientBalance
+    );
+
+    // SECURITY: Update state BEFORE transfer (reentrancy protection)
+    user_balance.balance = user_balance.balance
+        .checked_sub(amount)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    user_balance.total_withdrawn = user_balance.total_withdrawn
+        .checked_add(amount)
+        .ok_or(SessionBettingError::MathOverflow)?;
+
+    // Transfer from vault to user (PDA signs)
+    let user_key = user.key();
+    let seeds = &[
+        b"vault",
+        user_key.as_ref(),
+        &[vault_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_context = CpiContext::new_with_signer(
+        system_program.to_account_info(),
+        Transfer {
+            from: vault.to_account_info(),
+            to: destination.clone(),
+        },
+        signer_seeds,
+    );
+    transfer(cpi_context, amount)
+}
+
+/// Settle a position into the owner's balance after the round is settled
+/// Shared by claim_winnings, claim_and_close, claim_and_withdraw and settle_and_credit;
+/// records the outcome in the owner's stats, funds the jackpot and returns the claim
+/// with the fee left for callers to route
+fn process_claim(
+    game_state: &mut GameState,
+    round: &BettingRound,
+    pool: &BettingPool,
+    position: &mut PlayerPosition,
+    user_balance: &mut UserBalance,
+    user_stats: &mut UserStats,
+    jackpot: &mut Jackpot,
+) -> Result<PayoutPreview> {
+    // SECURITY: Round must be settled or cancelled
+    require!(round_is_final(round), SessionBettingError::RoundNotSettled);
+
+    // SECURITY: Position not already claimed
+    require!(!position.claimed, SessionBettingError::AlreadyClaimed);
+
+    // SECURITY: Position belongs to user
+    require!(
+        position.player == user_balance.owner,
+        SessionBettingError::NotPositionOwner
+    );
+
+    let mut claim = round_claim(round, position, pool, game_state.fee_bps)?;
+    if claim.won {
+        let bonus_bps = streak_bonus_bps(game_state, streak_before(user_stats, round))
+            .saturating_add(early_bird_bonus_bps(game_state, round, position)?);
+        apply_fee_funded_bonus(&mut claim, bonus_bps)?;
+    }
+
+    // SECURITY: Mark as claimed BEFORE credit (reentrancy protection)
+    position.claimed = true;
+
+    record_claim_stats(user_stats, round, &claim)?;
+
+    // Credit to user balance
+    user_balance.balance = user_balance.balance
+        .checked_add(claim.payout)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    if claim.won {
+        user_balance.total_winnings = user_balance.total_winnings
+            .checked_add(claim.payout)
+            .ok_or(SessionBettingError::MathOverflow)?;
+    }
+
+    // Track fees
+    game_state.total_fees_collected = game_state.total_fees_collected
+        .checked_add(claim.fee)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    record_game_type_stats(game_state, GameType::Oracle, 0, claim.fee, claim.payout)?;
+
+    // Grow the progressive jackpot out of the protocol's fee
+    let jackpot_share = fund_jackpot(game_state, jackpot, claim.fee)?;
+    claim.fee = claim.fee
+        .checked_sub(jackpot_share)
+        .ok_or(SessionBettingError::MathOverflow)?;
+
+    Ok(claim)
+}
+
+/// Route the bettor's referrer share of a claim fee out of the global vault
+/// No-op unless every referral account is supplied; skipped (not failed) while the
+/// global vault can't cover the fee, so a referral never blocks a claim
+fn share_referral_fee<'info>(
+    accounts: &ReferralFeeAccounts<'info>,
+    bumps: &ReferralFeeAccountsBumps,
+    game_state: &mut GameState,
+    user: Pubkey,
+    fee: u64,
+) -> Result<()> {
+    let (Some(referral), Some(referrer_earnings)) = (&accounts.referral, &accounts.referrer_earnings) else {
+        return Ok(());
+    };
+    route_referral_fee(
+        accounts,
+        bumps,
+        (&referral.to_account_info(), &referrer_earnings.to_account_info()),
+        game_state,
+        user,
+        fee,
+    )
+}
+
+/// share_referral_fee with the bettor's (referral, referrer_earnings) passed separately,
+/// so settle_and_credit can route a batch of bettors through one set of referral accounts
+fn route_referral_fee<'info>(
+    accounts: &ReferralFeeAccounts<'info>,
+    bumps: &ReferralFeeAccountsBumps,
+    (referral, referrer_earnings): (&AccountInfo<'info>, &AccountInfo<'info>),
+    game_state: &mut GameState,
+    user: Pubkey,
+    fee: u64,
+) -> Result<()> {
+    let (
+        Some(crediter),
+        Some(config),
+        Some(referral_vault),
+        Some(global_vault),
+        Some(event_authority),
+        Some(referral_program),
+        Some(system_program),
+    ) = (
+        &accounts.referral_crediter,
+        &accounts.referral_config,
+        &accounts.referral_vault,
+        &accounts.global_vault,
+        &accounts.referral_event_authority,
+        &accounts.referral_program,
+        &accounts.system_program,
+    )
+    else {
+        return Ok(());
+    };
+
+    let spendable = global_vault.lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(0));
+    if fee == 0 || spendable < fee {
+        return Ok(());
+    }
+
+    let crediter_bump = bumps.referral_crediter.ok_or(SessionBettingError::Unauthorized)?;
+    let vault_bump = bumps.global_vault.ok_or(SessionBettingError::Unauthorized)?;
+    let crediter_seeds: &[&[u8]] = &[b"referral_crediter", &[crediter_bump]];
+    let vault_seeds: &[&[u8]] = &[b"global_vault", &[vault_bump]];
+    let signer_seeds = &[crediter_seeds, vault_seeds];
+
+    let cpi_context = CpiContext::new_with_signer(
+        referral_program.to_account_info(),
+        AccrueReferralFee {
+            config: config.to_account_info(),
+            referral: referral.clone(),
+            referrer_earnings: referrer_earnings.clone(),
+            referral_vault: referral_vault.to_account_info(),
+            fee_source: global_vault.to_account_info(),
+            crediter: crediter.to_account_info(),
+            system_program: system_program.to_account_info(),
+            event_authority: event_authority.to_account_info(),
+            program: referral_program.to_account_info(),
+        },
+        signer_seeds,
+    );
+    let share = referral::cpi::accrue_referral_fee(cpi_context, user, fee)?.get();
+
+    // The referrer's share is no longer protocol revenue
+    game_state.total_fees_collected = game_state.total_fees_collected
+        .checked_sub(share)
+        .ok_or(SessionBettingError::MathOverflow)?;
+
+    Ok(())
+}
+
+/// Route the insurance fund's share of a claim fee out of the global vault
+/// No-op unless every insurance account is supplied; skipped (not failed) while the
+/// global vault can't cover the fee, like share_referral_fee
+fn contribute_insurance_fee<'info>(
+    accounts: &InsuranceFeeAccounts<'info>,
+    bumps: &InsuranceFeeAccountsBumps,
+    game_state: &mut GameState,
+    fee: u64,
+) -> Result<()> {
+    let (
+        Some(crediter),
+        Some(config),
+        Some(insurance_vault),
+        Some(global_vault),
+        Some(event_authority),
+        Some(insurance_program),
+        Some(system_program),
+    ) = (
+        &accounts.insurance_crediter,
+        &accounts.insurance_config,
+        &accounts.insurance_vault,
+        &accounts.global_vault,
+        &accounts.insurance_event_authority,
+        &accounts.insurance_program,
+        &accounts.system_program,
+    )
+    else {
+        return Ok(());
+    };
+
+    let spendable = global_vault.lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(0));
+    if fee == 0 || spendable < fee {
+        return Ok(());
+    }
+
+    let crediter_bump = bumps.insurance_crediter.ok_or(SessionBettingError::Unauthorized)?;
+    let vault_bump = bumps.global_vault.ok_or(SessionBettingError::Unauthorized)?;
+    let crediter_seeds: &[&[u8]] = &[b"insurance_crediter", &[crediter_bump]];
+    let vault_seeds: &[&[u8]] = &[b"global_vault", &[vault_bump]];
+    let signer_seeds = &[crediter_seeds, vault_seeds];
+
+    let cpi_context = CpiContext::new_with_signer(
+        insurance_program.to_account_info(),
+        Contribute {
+            config: config.to_account_info(),
+            insurance_vault: insurance_vault.to_account_info(),
+            fee_source: global_vault.to_account_info(),
+            crediter: crediter.to_account_info(),
+            system_program: system_program.to_account_info(),
+            event_authority: event_authority.to_account_info(),
+            program: insurance_program.to_account_info(),
+        },
+        signer_seeds,
+    );
+    let contribution = insurance::cpi::contribute(cpi_context, fee)?.get();
+
+    // The fund's share is no longer protocol revenue
+    game_state.total_fees_collected = game_state.total_fees_collected
+        .checked_sub(contribution)
+        .ok_or(SessionBettingError::MathOverflow)?;
+
+    Ok(())
+}
+
+/// Set up a market's house on its first deposit
+fn init_house(house: &mut House, market: Pubkey, bump: u8) {
+    if house.market == Pubkey::default() {
+        house.market = market;
+        house.bump = bump;
+        house.version = ACCOUNT_VERSION;
+    }
+}
+
+/// Set up a liquidity provider's position on their first deposit
+fn init_lp_position(lp_position: &mut LpPosition, owner: Pubkey, market: Pubkey, bump: u8) {
+    if lp_position.owner == Pubkey::default() {
+        lp_position.owner = owner;
+        lp_position.market = market;
+        lp_position.bump = bump;
+        lp_position.version = ACCOUNT_VERSION;
+    }
+}
+
+/// House vault SOL that open fixed-odds rounds don't hold back (the rent floor never leaves)
+fn house_free_liquidity(house: &House, vault_lamports: u64) -> Result<u64> {
+    Ok(vault_lamports
+        .saturating_sub(Rent::get()?.minimum_balance(0))
+        .saturating_sub(house.reserved))
+}
+
+/// Worst-case payout of a fixed-odds round: every stake on the larger side paid at the round's
+/// odds, or every stake refunded on a Draw
+fn house_round_reserve(pool: &BettingPool, fixed_odds_bps: u64) -> Result<u64> {
+    let win_payout = bet_math::mul_div(pool.up_pool.max(pool.down_pool), fixed_odds_bps, BPS_DENOMINATOR)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    Ok(win_payout.max(pool.total_pool))
+}
+
+/// Hand back the part of a final fixed-odds round's reserve its outcome doesn't need
+/// Runs once per round, before its first claim (no payouts have left the reserve yet);
+/// returns the amount released to the LPs
+fn true_up_house_reserve(house: &mut House, pool: &mut BettingPool, round: &BettingRound) -> Result<u64> {
+    if pool.house_reserve_settled {
+        return Ok(0);
+    }
+    let owed = match (round.status, round.winner) {
+        (RoundStatus::Settled, WinnerSide::Up) => bet_math::mul_div(pool.up_pool, round.fixed_odds_bps, BPS_DENOMINATOR),
+        (RoundStatus::Settled, WinnerSide::Down) => bet_math::mul_div(pool.down_pool, round.fixed_odds_bps, BPS_DENOMINATOR),
+        // Cancelled or Draw: every stake is refunded
+        _ => Some(pool.total_pool),
+    }
+    .ok_or(SessionBettingError::MathOverflow)?;
+
+    let release = pool.house_reserved
+        .checked_sub(owed)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    release_house_reserve_amount(house, pool, release)?;
+    pool.house_reserve_settled = true;
+    Ok(release)
+}
+
+/// Return `amount` of a round's reserve to the house's free liquidity
+fn release_house_reserve_amount(house: &mut House, pool: &mut BettingPool, amount: u64) -> Result<()> {
+    pool.house_reserved = pool.house_reserved
+        .checked_sub(amount)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    house.reserved = house.reserved
+        .checked_sub(amount)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    Ok(())
+}
+
+/// Add stake to its side of the pool on behalf of the wallet's round record
+fn add_to_pool(pool: &mut BettingPool, player_round: &mut PlayerRound, side: BetSide, amount: u64) -> Result<()> {
+    match side {
+        BetSide::Up => {
+            pool.up_pool = pool.up_pool
+                .checked_add(amount)
+                .ok_or(SessionBettingError::MathOverflow)?;
+        }
+        BetSide::Down => {
+            pool.down_pool = pool.down_pool
+                .checked_add(amount)
+                .ok_or(SessionBettingError::MathOverflow)?;
+        }
+    }
+    pool.total_pool = pool.total_pool
+        .checked_add(amount)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    credit_player_side(pool, player_round, side, amount)
+}
+
+/// Take a cancelled position's stake back out of its side of the pool
+#[inline]
+fn remove_from_pool(pool: &mut BettingPool, player_round: &mut PlayerRound, side: BetSide, amount: u64) -> Result<()> {
+    match side {
+        BetSide::Up => {
+            pool.up_pool = pool.up_pool
+                .checked_sub(amount)
+                .ok_or(SessionBettingError::MathOverflow)?;
+        }
+        BetSide::Down => {
+            pool.down_pool = pool.down_pool
+                .checked_sub(amount)
+                .ok_or(SessionBettingError::MathOverflow)?;
+        }
+    }
+    pool.total_pool = pool.total_pool
+        .checked_sub(amount)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    debit_player_side(pool, player_round, side, amount)
+}
+
+/// Record a wallet's stake on a side; its first stake there makes it a new bettor on that side
+fn credit_player_side(pool: &mut BettingPool, player_round: &mut PlayerRound, side: BetSide, amount: u64) -> Result<()> {
+    let (stake, bettors) = match side {
+        BetSide::Up => (&mut player_round.up_stake, &mut pool.up_bettors),
+        BetSide::Down => (&mut player_round.down_stake, &mut pool.down_bettors),
+    };
+    if *stake == 0 && amount > 0 {
+        *bettors = bettors
+            .checked_add(1)
+            .ok_or(SessionBettingError::MathOverflow)?;
+    }
+    *stake = stake
+        .checked_add(amount)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    Ok(())
+}
+
+/// Release a wallet's stake on a side; the wallet stops counting as a bettor once none is left
+fn debit_player_side(pool: &mut BettingPool, player_round: &mut PlayerRound, side: BetSide, amount: u64) -> Result<()> {
+    let (stake, bettors) = match side {
+        BetSide::Up => (&mut player_round.up_stake, &mut pool.up_bettors),
+        BetSide::Down => (&mut player_round.down_stake, &mut pool.down_bettors),
+    };
+    let had_stake = *stake > 0;
+    *stake = stake
+        .checked_sub(amount)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    if had_stake && *stake == 0 {
+        *bettors = bettors
+            .checked_sub(1)
+            .ok_or(SessionBettingError::MathOverflow)?;
+    }
+    Ok(())
+}
+
+/// Stamp a lazily created PlayerRound with its wallet and round (no-op once initialized)
+fn init_player_round(player_round: &mut PlayerRound, player: Pubkey, round_id: u64, bump: u8) {
+    if player_round.player == Pubkey::default() {
+        player_round.player = player;
+        player_round.round_id = round_id;
+        player_round.bump = bump;
+        player_round.version = ACCOUNT_VERSION;
+    }
+}
+
+/// Everything a wallet has staked in a round, revealed or not
+fn player_round_stake(player_round: &PlayerRound) -> Result<u64> {
+    player_round.up_stake
+        .checked_add(player_round.down_stake)
+        .and_then(|total| total.checked_add(player_round.hidden_stake))
+        .ok_or(SessionBettingError::MathOverflow.into())
+}
+
+/// Commitment for a hidden bet: sha256(side || salt || owner)
+/// Binding the owner stops one player from replaying another's commitment
+#[inline]
+fn bet_commitment(side: BetSide, salt: &[u8; 32], owner: &Pubkey) -> [u8; 32] {
+    let side_byte = [side as u8];
+    hashv(&[&side_byte, salt, owner.as_ref()]).to_bytes()
+}
+
+/// Absolute price move between start and end, in basis points of the start price
+#[inline]
+fn price_move_bps(start_price: u64, end_price: u64) -> Result<u64> {
+    // SECURITY: Prevent division by zero
+    let Some(bps) = bet_math::abs_change_bps(start_price, end_price) else {
+        return Ok(0);
+    };
+
+    // Saturate: anything this large trips any configured breaker anyway
+    Ok(u64::try_from(bps).unwrap_or(u64::MAX))
+}
+
+/// Enforce the compliance eligibility mode for a bettor
+/// Must only be called from betting entrypoints, never claim/withdraw paths
+fn check_eligibility(game_state: &GameState, user_balance: &UserBalance) -> Result<()> {
+    let eligible = match game_state.eligibility_mode {
+        EligibilityMode::Open => true,
+        EligibilityMode::Allowlist => user_balance.eligibility == EligibilityStatus::Allowed,
+        EligibilityMode::Blocklist => user_balance.eligibility != EligibilityStatus::Blocked,
+    };
+    require!(eligible, SessionBettingError::NotEligible);
+    Ok(())
+}
+
+/// Enforce the authority's per-user cap and the bettor's own limits from
+/// set_personal_limits, counting the stake toward today's volume; `stake` is the
+/// position size max_bet applies to and `added` the lamports newly staked
+fn check_personal_limits(user_balance: &mut UserBalance, stake: u64, added: u64, now: i64) -> Result<()> {
+    // SECURITY: Authority-set restriction on this account
+    if user_balance.max_bet_override > 0 {
+        require!(
+            stake <= user_balance.max_bet_override,
+            SessionBettingError::AmountTooLarge
+        );
+    }
+
+    enforce_personal_limits(&mut user_balance.personal_limits, stake, added, now)
+}
+
+/// Enforce a bettor's own limits, counting `added` toward today's volume
+/// Shared by the SOL path (UserBalance) and the token path (TokenBalance)
+fn enforce_personal_limits(limits: &mut PersonalLimits, stake: u64, added: u64, now: i64) -> Result<()> {
+    // Queued loosening takes effect once its cooldown has passed
+    if limits.pending_at != 0 && now >= limits.pending_at {
+        limits.max_bet = limits.pending_max_bet;
+        limits.max_daily_volume = limits.pending_max_daily_volume;
+        limits.pending_at = 0;
+    }
+
+    if limits.max_bet > 0 {
+        require!(
+            stake <= limits.max_bet,
+            SessionBettingError::PersonalLimitExceeded
+        );
+    }
+
+    let today = now / SECONDS_PER_DAY;
+    if limits.volume_day != today {
+        limits.volume_day = today;
+        limits.daily_volume = 0;
+    }
+    limits.daily_volume = limits.daily_volume
+        .checked_add(added)
+        .ok_or(SessionBettingError::MathOverflow)?;
+    if limits.max_daily_volume > 0 {
+        require!(
+            limits.daily_volume <= limits.max_daily_volume,
+            SessionBettingError::PersonalLimitExceeded
+        );
+    }
+    Ok(())
+}
+
+/// Apply requested personal limits: tighter ones immediately, looser ones queued for
+/// LIMIT_LOOSEN_COOLDOWN_SECONDS; returns when the request takes full effect
+fn queue_personal_limits(limits: &mut PersonalLimits, max_bet: u64, max_daily_volume: u64, now: i64) -> Result<i64> {
+    // A pending loosening is replaced by whatever is requested now
+    limits.pending_at = 0;
+
+    let loosens = |current: u64, requested: u64| current > 0 && (requested == 0 || requested > current);
+    if loosens(limits.max_bet, max_bet) || loosens(limits.max_daily_volume, max_daily_volume) {
+        // SECURITY: Queue the change so a looser limit can't be set on impulse
+        if !loosens(limits.max_bet, max_bet) {
+            limits.max_bet = max_bet;
+        }
+        if !loosens(limits.max_daily_volume, max_daily_volume) {
+            limits.max_daily_volume = max_daily_volume;
+        }
+        let effective_at = now
+            .checked_add(LIMIT_LOOSEN_COOLDOWN_SECONDS)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        limits.pending_max_bet = max_bet;
+        limits.pending_max_daily_volume = max_daily_volume;
+        limits.pending_at = effective_at;
+        Ok(effective_at)
+    } else {
+        limits.max_bet = max_bet;
+        limits.max_daily_volume = max_daily_volume;
+        Ok(now)
+    }
+}
+
+/// Consult the shared compliance program (allowlist/blocklist/self-exclusion) for a bettor
+/// Must only be called from betting entrypoints, never claim/withdraw paths
+fn check_compliance<'info>(
+    compliance_program: &Program<'info, Compliance>,
+    compliance_config: &UncheckedAccount<'info>,
+    user_compliance: &UncheckedAccount<'info>,
+    user: Pubkey,
+) -> Result<()> {
+    let cpi_context = CpiContext::new(
+        compliance_program.to_account_info(),
+        AssertEligible {
+            config: compliance_config.to_account_info(),
+            user_compliance: user_compliance.to_account_info(),
+        },
+    );
+    compliance::cpi::assert_eligible(cpi_context, user)
+}
+
+/// Enforce the configured pool caps for a stake about to be added to a round
+/// amount joins the pool; player_stake is the player's total stake in the round after it
+/// Caps of 0 are disabled
+fn check_pool_caps(
+    game_state: &GameState,
+    pool: &BettingPool,
+    side: BetSide,
+    amount: u64,
+    player_stake: u64,
+) -> Result<()> {
+    if game_state.max_player_stake > 0 {
+        require!(
+            player_stake <= game_state.max_player_stake,
+            SessionBettingError::PlayerStakeCapExceeded
+        );
+    }
+
+    if game_state.max_total_pool > 0 {
+        let new_total = pool.total_pool
+            .checked_add(amount)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        require!(
+            new_total <= game_state.max_total_pool,
+            SessionBettingError::PoolCapExceeded
+        );
+    }
+
+    if game_state.max_side_pool > 0 {
+        let side_pool = match side {
+            BetSide::Up => pool.up_pool,
+            BetSide::Down => pool.down_pool,
+        };
+        let new_side = side_pool
+            .checked_add(amount)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        require!(
+            new_side <= game_state.max_side_pool,
+            SessionBettingError::PoolCapExceeded
+        );
+    }
+
+    Ok(())
+}
+
+/// Token counterpart of check_pool_caps, against game_state.token_limits
+fn check_token_pool_caps(limits: &TokenBetLimits, pool: &TokenPool, side: BetSide, amount: u64) -> Result<()> {
+    if limits.max_player_stake > 0 {
+        require!(
+            amount <= limits.max_player_stake,
+            SessionBettingError::PlayerStakeCapExceeded
+        );
+    }
+
+    if limits.max_total_pool > 0 {
+        let new_total = pool.total_pool
+            .checked_add(amount)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        require!(
+            new_total <= limits.max_total_pool,
+            SessionBettingError::PoolCapExceeded
+        );
+    }
+
+    if limits.max_side_pool > 0 {
+        let side_pool = match side {
+            BetSide::Up => pool.up_pool,
+            BetSide::Down => pool.down_pool,
+        };
+        let new_side = side_pool
+            .checked_add(amount)
+            .ok_or(SessionBettingError::MathOverflow)?;
+        require!(
+            new_side <= limits.max_side_pool,
+            SessionBettingError::PoolCapExceeded
+        );
+    }
+
+    Ok(())
+}
+
+/// Bounds for token bet limits, mirroring set_bet_limits and set_pool_caps
+fn validate_token_bet_limits(limits: &TokenBetLimits) -> Result<()> {
+    require!(
+        limits.min_bet > 0 && (limits.max_bet == 0 || limits.min_bet <= limits.max_bet),
+        SessionBettingError::InvalidBetLimits
+    );
+    if limits.max_total_pool > 0 {
+        require!(
+            limits.max_side_pool <= limits.max_total_pool,
+            SessionBettingError::InvalidPoolCaps
+        );
+    }
+    Ok(())
+}
+
+#[inline]
+fn calculate_winnings(
+    bet_amount: u64,
+    bet_side: BetSide,
+    winner: WinnerSide,
+    up_pool: u64,
+    down_pool: u64,
+) -> Result<u64> {
+    // Check if user won
+    let user_won = matches!((bet_side, winner), (BetSide::Up, WinnerSide::Up) | (BetSide::Down, WinnerSide::Down));
+
+    if !user_won {
+        return Ok(0);
+    }
+
+    // Calculate share of losing pool
+    let (winning_pool, losing_pool) = match winner {
+        WinnerSide::Up => (up_pool, down_pool),
+        WinnerSide::Down => (down_pool, up_pool),
+        _ => return Ok(0),
+    };
+
+    // Winnings = bet_amount + (bet_amount / winning_pool * losing_pool)
+    bet_math::parimutuel_winnings(bet_amount, winning_pool, losing_pool)
+        .ok_or(SessionBettingError::MathOverflow.into())
+}
+
+/// Parlay payout = stake * (PARLAY_LEG_MULTIPLIER_BPS / BPS_DENOMINATOR) ^ winning_legs
+#[inline]
+fn calculate_parlay_payout(stake: u64, winning_legs: usize) -> Result<u64> {
+    bet_math::compound_bps(stake, PARLAY_LEG_MULTIPLIER_BPS, winning_legs)
+        .ok_or(SessionBettingError::MathOverflow.into())
+}
+
+// ===================
+// Account Structs
+// ===================
+
+#[derive(Accounts)]
+#[instruction(price_feed_id: [u8; 32])]
+pub struct InitializeGame<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + GameState::INIT_SPACE,
+        seeds = [b"game"],
+        bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// First market, opened for the deployment's price feed
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Market::INIT_SPACE,
+        seeds = [b"market", price_feed_id.as_ref()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Jackpot::INIT_SPACE,
+        seeds = [b"jackpot"],
+        bump
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+
+    /// Global vault PDA for pooled game funds - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"global_vault"],
+        bump
+    )]
+    pub global_vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(feed_id: [u8; 32])]
+pub struct CreateMarket<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Market::INIT_SPACE,
+        seeds = [b"market", feed_id.as_ref()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeRoundHistory<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [b"market", market.feed_id.as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<RoundHistory>(),
+        seeds = [b"round_history", market.key().as_ref()],
+        bump
+    )]
+    pub round_history: AccountLoader<'info, RoundHistory>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetMarketActive<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market.feed_id.as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMarketFixedOdds<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market.feed_id.as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMarketCadence<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market.feed_id.as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetSwitchboardFeed<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market.feed_id.as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct StartRound<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump,
+        constraint = is_round_operator(&game_state, &authority.key()) @ SessionBettingError::Unauthorized
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market.feed_id.as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + BettingRound::INIT_SPACE,
+        seeds = [b"round", market.key().as_ref(), market.current_round.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub round: Account<'info, BettingRound>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + BettingPool::INIT_SPACE,
+        seeds = [b"pool", market.key().as_ref(), market.current_round.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, BettingPool>,
+
+    /// CHECK: Market's price account (Pyth push/pull or Chainlink) - validated in instruction
+    pub price_feed: AccountInfo<'info>,
+
+    /// Authority or operator; pays the round and pool rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless round start priced from the market's Pyth feed
+#[derive(Accounts)]
+pub struct StartRoundOracle<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market.feed_id.as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = caller,
+        space = 8 + BettingRound::INIT_SPACE,
+        seeds = [b"round", market.key().as_ref(), market.current_round.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub round: Account<'info, BettingRound>,
+
+    #[account(
+        init,
+        payer = caller,
+        space = 8 + BettingPool::INIT_SPACE,
+        seeds = [b"pool", market.key().as_ref(), market.current_round.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, BettingPool>,
+
+    /// CHECK: Pyth price account (push) or PriceUpdateV2 (pull) - validated in instruction
+    pub price_feed: AccountInfo<'info>,
+
+    /// Anyone can open the next round (pays its rent)
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct LockRound<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump,
+        constraint = is_round_operator(&game_state, &authority.key()) @ SessionBettingError::Unauthorized
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [b"market", market.feed_id.as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"round", market.key().as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump
+    )]
+    pub round: Account<'info, BettingRound>,
+
+    /// CHECK: Pyth price account (push) or PriceUpdateV2 (pull) - validated in instruction
+    pub price_feed: AccountInfo<'info>,
+
+    /// Authority or operator must sign to prevent griefing
+    pub authority: Signer<'info>,
+}
+
+/// Permissionless fallback for locking rounds when authority is offline
+/// SECURITY: Uses Pyth oracle price - no arbitrary price input allowed
+#[derive(Accounts)]
+pub struct LockRoundFallback<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [b"market", market.feed_id.as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"round", market.key().as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump
+    )]
+    pub round: Account<'info, BettingRound>,
+
+    /// CHECK: Pyth price account (push) or PriceUpdateV2 (pull) - validated in instruction
+    pub price_feed: AccountInfo<'info>,
+
+    /// Anyone can call this after fallback time
+    pub caller: Signer<'info>,
+}
+
+/// Permissionless TWAP sample crank
+#[derive(Accounts)]
+pub struct RecordPriceSample<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [b"market", market.feed_id.as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"round", market.key().as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump
+    )]
+    pub round: Account<'info, BettingRound>,
+
+    /// CHECK: Pyth price account (push) or PriceUpdateV2 (pull) - validated in instruction
+    pub price_feed: AccountInfo<'info>,
+
+    pub caller: Signer<'info>,
+}
+
+/// Secondary-oracle lock for when the market's Pyth feed has halted
+#[derive(Accounts)]
+pub struct LockRoundSwitchboard<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [b"market", market.feed_id.as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"round", market.key().as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump
+    )]
+    pub round: Account<'info, BettingRound>,
+
+    /// CHECK: Pyth price account (push) or PriceUpdateV2 (pull) - must prove staleness
+    pub price_feed: AccountInfo<'info>,
+
+    /// CHECK: Switchboard pull feed - pinned to the market, parsed in instruction
+    #[account(address = market.switchboard_feed)]
+    pub switchboard_feed: AccountInfo<'info>,
+
+    pub caller: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SettleRound<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump
+    )]
+    pub round: Account<'info, BettingRound>,
+
+    #[account(
+        seeds = [b"pool", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [b"jackpot"],
+        bump = jackpot.bump
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+
+    /// Market's round history - appended to when supplied
+    #[account(
+        mut,
+        seeds = [b"round_history", round.market.as_ref()],
+        bump
+    )]
+    pub round_history: Option<AccountLoader<'info, RoundHistory>>,
+
+    pub caller: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SettleAndCredit<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        constraint = is_round_operator(&game_state, &caller.key()) @ SessionBettingError::Unauthorized
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+        constraint = round.fixed_odds_bps == 0 @ SessionBettingError::FixedOddsRound
+    )]
+    pub round: Account<'info, BettingRound>,
+
+    #[account(
+        seeds = [b"pool", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [b"jackpot"],
+        bump = jackpot.bump
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+
+    /// Market's round history - appended to when this call settles the round
+    #[account(
+        mut,
+        seeds = [b"round_history", round.market.as_ref()],
+        bump
+    )]
+    pub round_history: Option<AccountLoader<'info, RoundHistory>>,
+
+    /// Authority or operator (the settlement crank)
+    pub caller: Signer<'info>,
+
+    /// Shared referral accounts; each bettor's referral and earnings come via remaining_accounts
+    pub referral_fees: ReferralFeeAccounts<'info>,
+
+    pub insurance_fees: InsuranceFeeAccounts<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AdvanceRound<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        constraint = is_round_operator(&game_state, &authority.key()) @ SessionBettingError::Unauthorized
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market.feed_id.as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Round being settled
+    #[account(
+        mut,
+        seeds = [b"round", market.key().as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump
+    )]
+    pub round: Account<'info, BettingRound>,
+
+    #[account(
+        seeds = [b"pool", market.key().as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, BettingPool>,
+
+    /// Round being opened
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + BettingRound::INIT_SPACE,
+        seeds = [b"round", market.key().as_ref(), market.current_round.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub next_round: Account<'info, BettingRound>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + BettingPool::INIT_SPACE,
+        seeds = [b"pool", market.key().as_ref(), market.current_round.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub next_pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [b"jackpot"],
+        bump = jackpot.bump
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+
+    /// Market's round history - appended to when supplied
+    #[account(
+        mut,
+        seeds = [b"round_history", market.key().as_ref()],
+        bump
+    )]
+    pub round_history: Option<AccountLoader<'info, RoundHistory>>,
+
+    /// CHECK: Market's price account (Pyth push/pull or Chainlink) - validated in instruction
+    pub price_feed: AccountInfo<'info>,
+
+    /// Authority or operator; pays the next round and pool rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ForceSettle<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump
+    )]
+    pub round: Account<'info, BettingRound>,
+
+    #[account(
+        seeds = [b"pool", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, BettingPool>,
+
+    /// Authority or operator once settleable; anyone once FORCE_SETTLE_DELAY_SECONDS have passed
+    pub caller: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelRound<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump
+    )]
+    pub round: Account<'info, BettingRound>,
+
+    /// Authority at any time; anyone once CANCEL_ROUND_DELAY_SECONDS have passed
+    pub caller: Signer<'info>,
+}
+
+/// Close a settled round and reclaim rent
+/// Anyone, after grace period
+#[derive(Accounts)]
+pub struct CloseRound<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+        close = authority
+    )]
+    pub round: Account<'info, BettingRound>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump,
+        close = authority
+    )]
+    pub pool: Account<'info, BettingPool>,
+
+    /// Protocol authority wallet - receives the remaining rent
+    #[account(mut)]
+    pub authority: SystemAccount<'info>,
+
+    /// Anyone can close once the grace period is over; receives the bounty
+    #[account(mut)]
+    pub caller: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPoolCaps<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetCircuitBreaker<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMaxConfBps<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetJackpotConfig<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"jackpot"],
+        bump = jackpot.bump
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeeBps<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetCancelFeeBps<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetOperator<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddGameProgram<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveGameProgram<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetCloseRoundBounty<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetSessionRateLimit<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetStreakBonus<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetEarlyBirdBonus<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetAdminTimelock<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetTokenBetLimits<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetBetLimits<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetRoundDurationBounds<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMinPoolForSettlement<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetRequireMultisig<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    pub authority: Signer<'info>,
+
+    /// CHECK: Squads multisig whose default vault is the authority (omit for a raw-key authority)
+    /// Verified by admin_guard; required when game_state.require_multisig is set
+    pub admin_multisig: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct SetOracleMode<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetEligibilityMode<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetUserEligibility<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"balance", user_balance.owner.as_ref()],
+        bump = user_balance.bump
+    )]
+    pub user_balance: Account<'info, UserBalance>,
+
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetUserMaxBet<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"balance", user_balance.owner.as_ref()],
+        bump = user_balance.bump
+    )]
+    pub user_balance: Account<'info, UserBalance>,
+
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetUserTokenMaxBet<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"token_balance", token_balance.owner.as_ref(), token_balance.mint.as_ref()],
+        bump = token_balance.bump
+    )]
+    pub token_balance: Account<'info, TokenBalance>,
+
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateGameState<'info> {
+    /// CHECK: Raw account so older layouts can be grown before deserializing;
+    /// owner and discriminator are checked in the instruction
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump
+    )]
+    pub game_state: UncheckedAccount<'info>,
+
+    /// Created for deployments that predate the jackpot
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + Jackpot::INIT_SPACE,
+        seeds = [b"jackpot"],
+        bump
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+
+    /// Global vault PDA; a v0 migration reserves the wagers it already holds
+    #[account(
+        seeds = [b"global_vault"],
+        bump
+    )]
+    pub global_vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateUserBalance<'info> {
+    /// CHECK: Raw account so older layouts can be grown before deserializing;
+    /// owner and discriminator are checked in the instruction
+    #[account(
+        mut,
+        seeds = [b"balance", owner.key().as_ref()],
+        bump
+    )]
+    pub user_balance: UncheckedAccount<'info>,
+
+    /// CHECK: Balance owner's wallet - only used to derive the PDA
+    pub owner: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AssertSolvency<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// Global vault PDA for pooled funds - uses SystemAccount for type safety
+    #[account(
+        seeds = [b"global_vault"],
+        bump
+    )]
+    pub global_vault: SystemAccount<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ProposeAdminChange<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteAdminChange<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// Target market - required only for a queued market change (SwitchboardFeed, ActivateMarket,
+    /// MarketCadence or MarketFixedOdds)
+    #[account(
+        mut,
+        seeds = [b"market", market.feed_id.as_ref()],
+        bump = market.bump
+    )]
+    pub market: Option<Account<'info, Market>>,
+
+    /// Jackpot - required only for a queued JackpotConfig change
+    #[account(
+        mut,
+        seeds = [b"jackpot"],
+        bump = jackpot.bump
+    )]
+    pub jackpot: Option<Account<'info, Jackpot>>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAdminChange<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Propose a new authority (two-step transfer)
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ProposeAuthorityTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    pub authority: Signer<'info>,
+
+    /// CHECK: Squads multisig whose default vault is the authority (omit for a raw-key authority)
+    /// Verified by admin_guard; required when game_state.require_multisig is set
+    pub admin_multisig: Option<UncheckedAccount<'info>>,
+}
+
+/// Accept authority transfer (must be signed by pending authority)
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptAuthorityTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// The new authority accepting the transfer
+    pub new_authority: Signer<'info>,
+}
+
+/// Cancel a pending authority transfer
+#[derive(Accounts)]
+pub struct CancelAuthorityTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Withdraw collected platform fees
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Global vault PDA for pooled funds
+    #[account(
+        mut,
+        seeds = [b"global_vault"],
+        bump
+    )]
+    pub global_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Squads multisig whose default vault is the authority (omit for a raw-key authority)
+    /// Verified by admin_guard; required when game_state.require_multisig is set
+    pub admin_multisig: Option<UncheckedAccount<'info>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ConfigureFeeStream<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    pub authority: Signer<'info>,
+
+    /// CHECK: Squads multisig whose default vault is the authority (omit for a raw-key authority)
+    /// Verified by admin_guard; required when game_state.require_multisig is set
+    pub admin_multisig: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeeSplit<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    pub authority: Signer<'info>,
+
+    /// CHECK: Squads multisig whose default vault is the authority (omit for a raw-key authority)
+    /// Verified by admin_guard; required when game_state.require_multisig is set
+    pub admin_multisig: Option<UncheckedAccount<'info>>,
+}
+
+/// Crank the fee stream (permissionless)
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CrankFeeStream<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// Global vault PDA for pooled funds
+    #[account(
+        mut,
+        seeds = [b"global_vault"],
+        bump
+    )]
+    pub global_vault: SystemAccount<'info>,
+
+    /// Treasury wallet configured on the stream
+    #[account(
+        mut,
+        address = game_state.fee_stream_recipient
+    )]
+    pub recipient: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateSession<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + SessionToken::INIT_SPACE,
+        seeds = [b"session", authority.key().as_ref(), session_signer.key().as_ref()],
+        bump
+    )]
+    pub session_token: Account<'info, SessionToken>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + SessionRegistry::INIT_SPACE,
+        seeds = [b"session_registry", authority.key().as_ref()],
+        bump
+    )]
+    pub session_registry: Account<'info, SessionRegistry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: The session signer public key (does not need to sign creation)
+    pub session_signer: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetSessionTokenLimit<'info> {
+    #[account(
+        mut,
+        seeds = [b"session", authority.key().as_ref(), session_token.session_signer.as_ref()],
+        bump = session_token.bump,
+        constraint = session_token.authority == authority.key() @ SessionBettingError::NotSessionOwner
+    )]
+    pub session_token: Account<'info, SessionToken>,
+
+    /// Wallet that created the session (session keys cannot raise their own limit)
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeSession<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"session", authority.key().as_ref(), session_token.session_signer.as_ref()],
+        bump = session_token.bump,
+        constraint = session_token.authority == authority.key() @ SessionBettingError::NotSessionOwner
+    )]
+    pub session_token: Account<'info, SessionToken>,
+
+    #[account(
+        mut,
+        seeds = [b"session_registry", authority.key().as_ref()],
+        bump = session_registry.bump,
+    )]
+    pub session_registry: Option<Account<'info, SessionRegistry>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeAllSessions<'info> {
+    #[account(
+        mut,
+        seeds = [b"session_registry", authority.key().as_ref()],
+        bump = session_registry.bump,
+        constraint = session_registry.owner == authority.key() @ SessionBettingError::NotSessionOwner
+    )]
+    pub session_registry: Account<'info, SessionRegistry>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExtendSession<'info> {
+    #[account(
+        mut,
+        seeds = [b"session", authority.key().as_ref(), session_token.session_signer.as_ref()],
+        bump = session_token.bump,
+        constraint = session_token.authority == authority.key() @ SessionBettingError::NotSessionOwner
+    )]
+    pub session_token: Account<'info, SessionToken>,
+
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(owner: Pubkey)]
+pub struct DepositFor<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + UserBalance::INIT_SPACE,
+        seeds = [b"balance", owner.as_ref()],
+        bump
+    )]
+    pub user_balance: Account<'info, UserBalance>,
+
+    /// Owner's vault PDA - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"vault", owner.as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Funds the deposit (and the balance account's rent on first deposit)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserBalance::INIT_SPACE,
+        seeds = [b"balance", user.key().as_ref()],
+        bump
+    )]
+    pub user_balance: Account<'info, UserBalance>,
+
+    /// Vault PDA to hold user's funds - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"vault", user.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositWithReferrer<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserBalance::INIT_SPACE,
+        seeds = [b"balance", user.key().as_ref()],
+        bump
+    )]
+    pub user_balance: Account<'info, UserBalance>,
+
+    /// Vault PDA to hold user's funds - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"vault", user.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// CHECK: Wallet's referral link, created and validated by the referral program
+    #[account(mut)]
+    pub referral: UncheckedAccount<'info>,
+
+    /// CHECK: Referrer's earnings, created and validated by the referral program
+    #[account(mut)]
+    pub referrer_earnings: UncheckedAccount<'info>,
+
+    /// CHECK: Referral's event-CPI authority PDA
+    #[account(
+        seeds = [b"__event_authority"],
+        seeds::program = referral::ID,
+        bump
+    )]
+    pub referral_event_authority: UncheckedAccount<'info>,
+
+    pub referral_program: Program<'info, Referral>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"balance", user.key().as_ref()],
+        bump = user_balance.bump,
+        constraint = user_balance.owner == user.key() @ SessionBettingError::NotBalanceOwner
+    )]
+    pub user_balance: Account<'info, UserBalance>,
+
+    /// Vault PDA that holds user's funds - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"vault", user.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseBalance<'info> {
+    #[account(
+        mut,
+        seeds = [b"balance", user.key().as_ref()],
+        bump = user_balance.bump,
+        constraint = user_balance.owner == user.key() @ SessionBettingError::NotBalanceOwner,
+        close = user
+    )]
+    pub user_balance: Account<'info, UserBalance>,
+
+    /// Vault PDA that holds user's funds - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"vault", user.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(destination: Pubkey)]
+pub struct WithdrawTo<'info> {
+    #[account(
+        mut,
+        seeds = [b"balance", user.key().as_ref()],
+        bump = user_balance.bump,
+        constraint = user_balance.owner == user.key() @ SessionBettingError::NotBalanceOwner
+    )]
+    pub user_balance: Account<'info, UserBalance>,
+
+    /// Vault PDA that holds user's funds - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"vault", user.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// CHECK: Receives the withdrawal; any account, pinned to the signed `destination` argument
+    #[account(mut, address = destination)]
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(side: BetSide, amount: u64, position_index: u8)]
+pub struct PlaceBet<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [b"round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+        constraint = round.fixed_odds_bps == 0 @ SessionBettingError::FixedOddsRound
+    )]
+    pub round: Account<'info, BettingRound>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [b"balance", user_balance.owner.as_ref()],
+        bump = user_balance.bump
+    )]
+    pub user_balance: Account<'info, UserBalance>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + PlayerPosition::INIT_SPACE,
+        seeds = [b"position", round.market.as_ref(), round.round_id.to_le_bytes().as_ref(), user_balance.owner.as_ref(), &[position_index]],
+        bump
+    )]
+    pub position: Account<'info, PlayerPosition>,
+
+    /// Wallet's stake across its positions in this round - created on first use
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + PlayerRound::INIT_SPACE,
+        seeds = [b"player_round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref(), user_balance.owner.as_ref()],
+        bump
+    )]
+    pub player_round: Account<'info, PlayerRound>,
+
+    /// Bettor's lifetime stats - created on first use
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + UserStats::INIT_SPACE,
+        seeds = [b"user_stats", user_balance.owner.as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    /// Session token for session key authentication (optional)
+    /// If provided, allows session_signer to act on behalf of authority
+    #[account(
+        mut,
+        seeds = [b"session", user_balance.owner.as_ref(), signer.key().as_ref()],
+        bump = session_token.bump,
+    )]
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    /// Owner's session registry - required whenever a session key signs
+    #[account(
+        seeds = [b"session_registry", user_balance.owner.as_ref()],
+        bump = session_registry.bump,
+    )]
+    pub session_registry: Option<Account<'info, SessionRegistry>>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Shared compliance config, validated by the compliance program
+    pub compliance_config: UncheckedAccount<'info>,
+
+    /// CHECK: Bettor's compliance record (may be uninitialized), validated by the compliance program
+    pub user_compliance: UncheckedAccount<'info>,
+
+    pub compliance_program: Program<'info, Compliance>,
+
+    /// CHECK: This program's registry entry (may be unregistered), read by registry::require_not_deprecated
+    #[account(
+        seeds = [b"program_entry", crate::ID.as_ref()],
+        bump,
+        seeds::program = registry::ID
+    )]
+    pub program_entry: UncheckedAccount<'info>,
+
+    /// CHECK: Program signer for lottery credit_tickets (optional, with the accounts below)
+    #[account(
+        seeds = [b"lottery_crediter"],
+        bump
+    )]
+    pub lottery_crediter: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Lottery config, validated by the lottery program
+    pub lottery_config: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Bettor's ticket account, validated by the lottery program
+    #[account(mut)]
+    pub player_tickets: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Lottery's event-CPI authority PDA
+    #[account(
+        seeds = [b"__event_authority"],
+        seeds::program = lottery::ID,
+        bump
+    )]
+    pub lottery_event_authority: Option<UncheckedAccount<'info>>,
+
+    pub lottery_program: Option<Program<'info, Lottery>>,
+
+    /// CHECK: Program signer for rewards credit_points (optional, with the accounts below)
+    #[account(
+        seeds = [b"rewards_crediter"],
+        bump
+    )]
+    pub rewards_crediter: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Rewards config, validated by the rewards program
+    pub rewards_config: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Current rewards epoch, validated by the rewards program
+    #[account(mut)]
+    pub rewards_epoch: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Bettor's lifetime points, validated by the rewards program
+    #[account(mut)]
+    pub user_points: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Bettor's points for the current epoch, validated by the rewards program
+    #[account(mut)]
+    pub user_epoch_points: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Rewards' event-CPI authority PDA
+    #[account(
+        seeds = [b"__event_authority"],
+        seeds::program = rewards::ID,
+        bump
+    )]
+    pub rewards_event_authority: Option<UncheckedAccount<'info>>,
+
+    pub rewards_program: Option<Program<'info, Rewards>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct IncreaseBet<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [b"round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+        constraint = round.fixed_odds_bps == 0 @ SessionBettingError::FixedOddsRound
+    )]
+    pub round: Account<'info, BettingRound>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [b"balance", user_balance.owner.as_ref()],
+        bump = user_balance.bump
+    )]
+    pub user_balance: Account<'info, UserBalance>,
+
+    #[account(
+        mut,
+        seeds = [b"position", round.market.as_ref(), round.round_id.to_le_bytes().as_ref(), position.bettor.as_ref(), &[position.index]],
+        bump = position.bump,
+        constraint = position.player == user_balance.owner @ SessionBettingError::NotPositionOwner
+    )]
+    pub position: Account<'info, PlayerPosition>,
+
+    /// Wallet's stake across its positions in this round
+    #[account(
+        mut,
+        seeds = [b"player_round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref(), position.player.as_ref()],
+        bump = player_round.bump
+    )]
+    pub player_round: Account<'info, PlayerRound>,
+
+    /// Session token for session key authentication (optional)
+    #[account(
+        mut,
+        seeds = [b"session", user_balance.owner.as_ref(), signer.key().as_ref()],
+        bump = session_token.bump,
+    )]
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    /// Owner's session registry - required whenever a session key signs
+    #[account(
+        seeds = [b"session_registry", user_balance.owner.as_ref()],
+        bump = session_registry.bump,
+    )]
+    pub session_registry: Option<Account<'info, SessionRegistry>>,
+
+    pub signer: Signer<'info>,
+
+    /// CHECK: Shared compliance config, validated by the compliance program
+    pub compliance_config: UncheckedAccount<'info>,
+
+    /// CHECK: Bettor's compliance record (may be uninitialized), validated by the compliance program
+    pub user_compliance: UncheckedAccount<'info>,
+
+    pub compliance_program: Program<'info, Compliance>,
+
+    /// CHECK: This program's registry entry (may be unregistered), read by registry::require_not_deprecated
+    #[account(
+        seeds = [b"program_entry", crate::ID.as_ref()],
+        bump,
+        seeds::program = registry::ID
+    )]
+    pub program_entry: UncheckedAccount<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelBet<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [b"round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+        constraint = round.fixed_odds_bps == 0 @ SessionBettingError::FixedOddsRound
+    )]
+    pub round: Account<'info, BettingRound>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [b"balance", user_balance.owner.as_ref()],
+        bump = user_balance.bump
+    )]
+    pub user_balance: Account<'info, UserBalance>,
+
+    #[account(
+        mut,
+        close = player,
+        seeds = [b"position", round.market.as_ref(), round.round_id.to_le_bytes().as_ref(), position.bettor.as_ref(), &[position.index]],
+        bump = position.bump,
+        constraint = position.player == user_balance.owner @ SessionBettingError::NotPositionOwner
+    )]
+    pub position: Account<'info, PlayerPosition>,
+
+    /// Wallet's stake across its positions in this round
+    #[account(
+        mut,
+        seeds = [b"player_round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref(), position.player.as_ref()],
+        bump = player_round.bump
+    )]
+    pub player_round: Account<'info, PlayerRound>,
+
+    /// Position owner wallet - receives the position rent
+    #[account(
+        mut,
+        address = position.player @ SessionBettingError::NotPositionOwner
+    )]
+    pub player: SystemAccount<'info>,
+
+    /// Session token for session key authentication (optional)
+    #[account(
+        seeds = [b"session", user_balance.owner.as_ref(), signer.key().as_ref()],
+        bump = session_token.bump,
+    )]
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    /// Owner's session registry - required whenever a session key signs
+    #[account(
+        seeds = [b"session_registry", user_balance.owner.as_ref()],
+        bump = session_registry.bump,
+    )]
+    pub session_registry: Option<Account<'info, SessionRegistry>>,
+
+    pub signer: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct EmergencyRefund<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [b"round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+        constraint = round.fixed_odds_bps == 0 @ SessionBettingError::FixedOddsRound
+    )]
+    pub round: Account<'info, BettingRound>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [b"balance", user_balance.owner.as_ref()],
+        bump = user_balance.bump
+    )]
+    pub user_balance: Account<'info, UserBalance>,
+
+    #[account(
+        mut,
+        close = player,
+        seeds = [b"position", round.market.as_ref(), round.round_id.to_le_bytes().as_ref(), position.bettor.as_ref(), &[position.index]],
+        bump = position.bump,
+        constraint = position.player == user_balance.owner @ SessionBettingError::NotPositionOwner
+    )]
+    pub position: Account<'info, PlayerPosition>,
+
+    /// Wallet's stake across its positions in this round
+    #[account(
+        mut,
+        seeds = [b"player_round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref(), position.player.as_ref()],
+        bump = player_round.bump
+    )]
+    pub player_round: Account<'info, PlayerRound>,
+
+    /// Position owner wallet - receives the position rent
+    #[account(
+        mut,
+        address = position.player @ SessionBettingError::NotPositionOwner
+    )]
+    pub player: SystemAccount<'info>,
+
+    /// Anyone can refund; funds can only ever reach position.player
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimWinnings<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [b"round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+        constraint = round.fixed_odds_bps == 0 @ SessionBettingError::FixedOddsRound
+    )]
+    pub round: Account<'info, BettingRound>,
+
+    #[account(
+        seeds = [b"pool", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [b"balance", user_balance.owner.as_ref()],
+        bump = user_balance.bump
+    )]
+    pub user_balance: Account<'info, UserBalance>,
+
+    /// Seeded by the original bettor; may have been transferred to a new player since
+    #[account(
+        mut,
+        seeds = [b"position", round.market.as_ref(), round.round_id.to_le_bytes().as_ref(), position.bettor.as_ref(), &[position.index]],
+        bump = position.bump,
+        constraint = position.player == user_balance.owner @ SessionBettingError::NotPositionOwner
+    )]
+    pub position: Account<'info, PlayerPosition>,
+
+    /// Owner's lifetime stats - created on first use
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + UserStats::INIT_SPACE,
+        seeds = [b"user_stats", user_balance.owner.as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    #[account(
+        mut,
+        seeds = [b"jackpot"],
+        bump = jackpot.bump
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+
+    /// Anyone can claim; funds can only ever reach position.player's balance
+    /// Pays for user_stats when it is created
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: This program's registry entry (may be unregistered), read by registry::require_not_deprecated
+    #[account(
+        seeds = [b"program_entry", crate::ID.as_ref()],
+        bump,
+        seeds::program = registry::ID
+    )]
+    pub program_entry: UncheckedAccount<'info>,
+
+    pub referral_fees: ReferralFeeAccounts<'info>,
+
+    pub insurance_fees: InsuranceFeeAccounts<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimAndClose<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [b"round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+        constraint = round.fixed_odds_bps == 0 @ SessionBettingError::FixedOddsRound
+    )]
+    pub round: Account<'info, BettingRound>,
+
+    #[account(
+        seeds = [b"pool", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [b"balance", user_balance.owner.as_ref()],
+        bump = user_balance.bump
+    )]
+    pub user_balance: Account<'info, UserBalance>,
+
+    #[account(
+        mut,
+        close = player,
+        seeds = [b"position", round.market.as_ref(), round.round_id.to_le_bytes().as_ref(), position.bettor.as_ref(), &[position.index]],
+        bump = position.bump,
+        constraint = position.player == user_balance.owner @ SessionBettingError::NotPositionOwner
+    )]
+    pub position: Account<'info, PlayerPosition>,
+
+    /// Position owner wallet - receives the position rent
+    #[account(
+        mut,
+        address = position.player @ SessionBettingError::NotPositionOwner
+    )]
+    pub player: SystemAccount<'info>,
+
+    /// Owner's lifetime stats - created on first use
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + UserStats::INIT_SPACE,
+        seeds = [b"user_stats", user_balance.owner.as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    #[account(
+        mut,
+        seeds = [b"jackpot"],
+        bump = jackpot.bump
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+
+    /// Anyone can claim; funds can only ever reach position.player
+    /// Pays for user_stats when it is created
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: This program's registry entry (may be unregistered), read by registry::require_not_deprecated
+    #[account(
+        seeds = [b"program_entry", crate::ID.as_ref()],
+        bump,
+        seeds::program = registry::ID
+    )]
+    pub program_entry: UncheckedAccount<'info>,
+
+    pub referral_fees: ReferralFeeAccounts<'info>,
+
+    pub insurance_fees: InsuranceFeeAccounts<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimAndWithdraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [b"round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+        constraint = round.fixed_odds_bps == 0 @ SessionBettingError::FixedOddsRound
+    )]
+    pub round: Account<'info, BettingRound>,
+
+    #[account(
+        seeds = [b"pool", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [b"balance", user.key().as_ref()],
+        bump = user_balance.bump,
+        constraint = user_balance.owner == user.key() @ SessionBettingError::NotBalanceOwner
+    )]
+    pub user_balance: Account<'info, UserBalance>,
+
+    /// Seeded by the original bettor; may have been transferred to a new player since
+    #[account(
+        mut,
+        seeds = [b"position", round.market.as_ref(), round.round_id.to_le_bytes().as_ref(), position.bettor.as_ref(), &[position.index]],
+        bump = position.bump,
+        constraint = position.player == user_balance.owner @ SessionBettingError::NotPositionOwner
+    )]
+    pub position: Account<'info, PlayerPosition>,
+
+    /// Vault PDA that holds user's funds - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"vault", user.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Owner's lifetime stats - created on first use
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserStats::INIT_SPACE,
+        seeds = [b"user_stats", user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    #[account(
+        mut,
+        seeds = [b"jackpot"],
+        bump = jackpot.bump
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: This program's registry entry (may be unregistered), read by registry::require_not_deprecated
+    #[account(
+        seeds = [b"program_entry", crate::ID.as_ref()],
+        bump,
+        seeds::program = registry::ID
+    )]
+    pub program_entry: UncheckedAccount<'info>,
+
+    pub referral_fees: ReferralFeeAccounts<'info>,
+
+    pub insurance_fees: InsuranceFeeAccounts<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClosePosition<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [b"round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump
+    )]
+    pub round: Account<'info, BettingRound>,
+
+    #[account(
+        seeds = [b"pool", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        close = player,
+        seeds = [b"position", round.market.as_ref(), round.round_id.to_le_bytes().as_ref(), position.bettor.as_ref(), &[position.index]],
+        bump = position.bump
+    )]
+    pub position: Account<'info, PlayerPosition>,
+
+    /// Position owner wallet - receives the position rent
+    #[account(
+        mut,
+        address = position.player @ SessionBettingError::NotPositionOwner
+    )]
+    pub player: SystemAccount<'info>,
+
+    /// Owner's lifetime stats - created on first use
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + UserStats::INIT_SPACE,
+        seeds = [b"user_stats", position.player.as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    /// Anyone can close; rent can only ever reach position.player
+    /// Pays for user_stats when it is created
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClosePlayerRound<'info> {
+    #[account(
+        seeds = [b"round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump
+    )]
+    pub round: Account<'info, BettingRound>,
+
+    #[account(
+        mut,
+        close = player,
+        seeds = [b"player_round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref(), player_round.player.as_ref()],
+        bump = player_round.bump
+    )]
+    pub player_round: Account<'info, PlayerRound>,
+
+    /// Record owner wallet - receives the rent
+    #[account(
+        mut,
+        address = player_round.player @ SessionBettingError::NotPositionOwner
+    )]
+    pub player: SystemAccount<'info>,
+
+    /// Anyone can close; rent can only ever reach player_round.player
+    pub signer: Signer<'info>,
+}
+
+/// Optional accounts that route a referrer's fee share through the referral program
+/// Supplied by the client when the bettor has registered a referrer
+#[derive(Accounts)]
+pub struct ReferralFeeAccounts<'info> {
+    /// CHECK: Program signer for referral accrue_referral_fee (optional, with the accounts below)
+    #[account(
+        seeds = [b"referral_crediter"],
+        bump
+    )]
+    pub referral_crediter: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Referral config, validated by the referral program
+    pub referral_config: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Bettor's referral link, validated by the referral program against the bettor
+    pub referral: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Referrer's earnings, validated by the referral program
+    #[account(mut)]
+    pub referrer_earnings: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Referral vault, validated by the referral program
+    #[account(mut)]
+    pub referral_vault: Option<UncheckedAccount<'info>>,
+
+    /// Global vault PDA - the referrer's share is paid out of collected fees
+    #[account(
+        mut,
+        seeds = [b"global_vault"],
+        bump
+    )]
+    pub global_vault: Option<SystemAccount<'info>>,
+
+    /// CHECK: Referral's event-CPI authority PDA
+    #[account(
+        seeds = [b"__event_authority"],
+        seeds::program = referral::ID,
+        bump
+    )]
+    pub referral_event_authority: Option<UncheckedAccount<'info>>,
+
+    pub referral_program: Option<Program<'info, Referral>>,
+
+    pub system_program: Option<Program<'info, System>>,
+}
+
+/// Optional accounts that route the insurance fund's share of a claim fee
+#[derive(Accounts)]
+pub struct InsuranceFeeAccounts<'info> {
+    /// CHECK: Program signer for insurance contribute (optional, with the accounts below)
+    #[account(
+        seeds = [b"insurance_crediter"],
+        bump
+    )]
+    pub insurance_crediter: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Insurance config, validated by the insurance program
+    #[account(mut)]
+    pub insurance_config: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Insurance vault, validated by the insurance program
+    #[account(mut)]
+    pub insurance_vault: Option<UncheckedAccount<'info>>,
+
+    /// Global vault PDA - the fund's share is paid out of collected fees
+    #[account(
+        mut,
+        seeds = [b"global_vault"],
+        bump
+    )]
+    pub global_vault: Option<SystemAccount<'info>>,
+
+    /// CHECK: Insurance's event-CPI authority PDA
+    #[account(
+        seeds = [b"__event_authority"],
+        seeds::program = insurance::ID,
+        bump
+    )]
+    pub insurance_event_authority: Option<UncheckedAccount<'info>>,
+
+    pub insurance_program: Option<Program<'info, Insurance>>,
+
+    pub system_program: Option<Program<'info, System>>,
+}
+
+#[derive(Accounts)]
+pub struct PreviewPayout<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [b"round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump
+    )]
+    pub round: Account<'info, BettingRound>,
+
+    #[account(
+        seeds = [b"pool", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, BettingPool>,
+
+    #[account(
+        seeds = [b"position", round.market.as_ref(), round.round_id.to_le_bytes().as_ref(), position.bettor.as_ref(), &[position.index]],
+        bump = position.bump
+    )]
+    pub position: Account<'info, PlayerPosition>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct TransferPosition<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [b"round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump
+    )]
+    pub round: Account<'info, BettingRound>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [b"position", round.market.as_ref(), round.round_id.to_le_bytes().as_ref(), position.bettor.as_ref(), &[position.index]],
+        bump = position.bump,
+        constraint = position.player == owner.key() @ SessionBettingError::NotPositionOwner
+    )]
+    pub position: Account<'info, PlayerPosition>,
+
+    /// New owner must already have a balance account so the claim has somewhere to pay
+    #[account(
+        seeds = [b"balance", new_owner_balance.owner.as_ref()],
+        bump = new_owner_balance.bump
+    )]
+    pub new_owner_balance: Account<'info, UserBalance>,
+
+    /// Current owner's stake across its positions in this round
+    #[account(
+        mut,
+        seeds = [b"player_round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref(), owner.key().as_ref()],
+        bump = from_player_round.bump
+    )]
+    pub from_player_round: Account<'info, PlayerRound>,
+
+    /// New owner's stake across its positions in this round - created on first use
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + PlayerRound::INIT_SPACE,
+        seeds = [b"player_round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref(), new_owner_balance.owner.as_ref()],
+        bump
+    )]
+    pub to_player_round: Account<'info, PlayerRound>,
+
+    /// Current owner wallet (session keys cannot transfer positions); pays for to_player_round
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// ===================
+// Commit-Reveal Account Structs
+// ===================
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(commitment: [u8; 32], amount: u64, position_index: u8)]
+pub struct CommitBet<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [b"round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+        constraint = round.fixed_odds_bps == 0 @ SessionBettingError::FixedOddsRound
+    )]
+    pub round: Account<'info, BettingRound>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [b"balance", user_balance.owner.as_ref()],
+        bump = user_balance.bump
+    )]
+    pub user_balance: Account<'info, UserBalance>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + PlayerPosition::INIT_SPACE,
+        seeds = [b"position", round.market.as_ref(), round.round_id.to_le_bytes().as_ref(), user_balance.owner.as_ref(), &[position_index]],
+        bump
+    )]
+    pub position: Account<'info, PlayerPosition>,
+
+    /// Wallet's stake across its positions in this round - created on first use
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + PlayerRound::INIT_SPACE,
+        seeds = [b"player_round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref(), user_balance.owner.as_ref()],
+        bump
+    )]
+    pub player_round: Account<'info, PlayerRound>,
+
+    /// Bettor's lifetime stats - created on first use
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + UserStats::INIT_SPACE,
+        seeds = [b"user_stats", user_balance.owner.as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    /// Session token for session key authentication (optional)
+    #[account(
+        mut,
+        seeds = [b"session", user_balance.owner.as_ref(), signer.key().as_ref()],
+        bump = session_token.bump,
+    )]
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    /// Owner's session registry - required whenever a session key signs
+    #[account(
+        seeds = [b"session_registry", user_balance.owner.as_ref()],
+        bump = session_registry.bump,
+    )]
+    pub session_registry: Option<Account<'info, SessionRegistry>>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Shared compliance config, validated by the compliance program
+    pub compliance_config: UncheckedAccount<'info>,
+
+    /// CHECK: Bettor's compliance record (may be uninitialized), validated by the compliance program
+    pub user_compliance: UncheckedAccount<'info>,
+
+    pub compliance_program: Program<'info, Compliance>,
+
+    /// CHECK: This program's registry entry (may be unregistered), read by registry::require_not_deprecated
+    #[account(
+        seeds = [b"program_entry", crate::ID.as_ref()],
+        bump,
+        seeds::program = registry::ID
+    )]
+    pub program_entry: UncheckedAccount<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RevealBet<'info> {
+    #[account(
+        seeds = [b"round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump
+    )]
+    pub round: Account<'info, BettingRound>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, BettingPool>,
+
+    #[account(
+        seeds = [b"balance", user_balance.owner.as_ref()],
+        bump = user_balance.bump
+    )]
+    pub user_balance: Account<'info, UserBalance>,
+
+    #[account(
+        mut,
+        seeds = [b"position", round.market.as_ref(), round.round_id.to_le_bytes().as_ref(), user_balance.owner.as_ref(), &[position.index]],
+        bump = position.bump,
+        constraint = position.player == user_balance.owner @ SessionBettingError::NotPositionOwner
+    )]
+    pub position: Account<'info, PlayerPosition>,
+
+    /// Wallet's stake across its positions in this round
+    #[account(
+        mut,
+        seeds = [b"player_round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref(), user_balance.owner.as_ref()],
+        bump = player_round.bump
+    )]
+    pub player_round: Account<'info, PlayerRound>,
+
+    /// Session token for session key authentication (optional)
+    #[account(
+        seeds = [b"session", user_balance.owner.as_ref(), signer.key().as_ref()],
+        bump = session_token.bump,
+    )]
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    /// Owner's session registry - required whenever a session key signs
+    #[account(
+        seeds = [b"session_registry", user_balance.owner.as_ref()],
+        bump = session_registry.bump,
+    )]
+    pub session_registry: Option<Account<'info, SessionRegistry>>,
+
+    pub signer: Signer<'info>,
+}
+
+// ===================
+// Copy-Trading Account Structs
+// ===================
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(leader: Pubkey)]
+pub struct CreateCopyConfig<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init,
+        payer = follower,
+        space = 8 + CopyConfig::INIT_SPACE,
+        seeds = [b"copy", follower.key().as_ref(), leader.as_ref()],
+        bump
+    )]
+    pub copy_config: Account<'info, CopyConfig>,
+
+    #[account(mut)]
+    pub follower: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseCopyConfig<'info> {
+    #[account(
+        mut,
+        close = follower,
+        seeds = [b"copy", follower.key().as_ref(), copy_config.leader.as_ref()],
+        bump = copy_config.bump,
+        has_one = follower
+    )]
+    pub copy_config: Account<'info, CopyConfig>,
+
+    #[account(mut)]
+    pub follower: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetCopyable<'info> {
+    #[account(
+        mut,
+        seeds = [b"balance", user.key().as_ref()],
+        bump = user_balance.bump,
+        constraint = user_balance.owner == user.key() @ SessionBettingError::NotBalanceOwner
+    )]
+    pub user_balance: Account<'info, UserBalance>,
+
+    pub user: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPersonalLimits<'info> {
+    #[account(
+        mut,
+        seeds = [b"balance", user.key().as_ref()],
+        bump = user_balance.bump,
+        constraint = user_balance.owner == user.key() @ SessionBettingError::NotBalanceOwner
+    )]
+    pub user_balance: Account<'info, UserBalance>,
+
+    pub user: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetTokenPersonalLimits<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"token_balance", user.key().as_ref(), game_state.token_mint.as_ref()],
+        bump = token_balance.bump
+    )]
+    pub token_balance: Account<'info, TokenBalance>,
+
+    pub user: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteCopyBet<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [b"round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+        constraint = round.fixed_odds_bps == 0 @ SessionBettingError::FixedOddsRound
+    )]
+    pub round: Account<'info, BettingRound>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, BettingPool>,
+
+    #[account(
+        seeds = [b"copy", copy_config.follower.as_ref(), copy_config.leader.as_ref()],
+        bump = copy_config.bump
+    )]
+    pub copy_config: Account<'info, CopyConfig>,
+
+    #[account(
+        seeds = [b"balance", copy_config.leader.as_ref()],
+        bump = leader_balance.bump
+    )]
+    pub leader_balance: Account<'info, UserBalance>,
+
+    #[account(
+        mut,
+        seeds = [b"position", round.market.as_ref(), round.round_id.to_le_bytes().as_ref(), copy_config.leader.as_ref(), &[leader_position.index]],
+        bump = leader_position.bump
+    )]
+    pub leader_position: Account<'info, PlayerPosition>,
+
+    #[account(
+        mut,
+        seeds = [b"balance", copy_config.follower.as_ref()],
+        bump = follower_balance.bump
+    )]
+    pub follower_balance: Account<'info, UserBalance>,
+
+    #[account(
+        init,
+        payer = caller,
+        space = 8 + PlayerPosition::INIT_SPACE,
+        seeds = [b"position", round.market.as_ref(), round.round_id.to_le_bytes().as_ref(), copy_config.follower.as_ref(), &[leader_position.index]],
+        bump
+    )]
+    pub follower_position: Account<'info, PlayerPosition>,
+
+    /// Follower's stake across its positions in this round - created on first use
+    #[account(
+        init_if_needed,
+        payer = caller,
+        space = 8 + PlayerRound::INIT_SPACE,
+        seeds = [b"player_round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref(), copy_config.follower.as_ref()],
+        bump
+    )]
+    pub player_round: Account<'info, PlayerRound>,
+
+    /// Follower's lifetime stats - created on first use
+    #[account(
+        init_if_needed,
+        payer = caller,
+        space = 8 + UserStats::INIT_SPACE,
+        seeds = [b"user_stats", follower_balance.owner.as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    /// Anyone can execute a copy bet; caller pays the position rent
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Shared compliance config, validated by the compliance program
+    pub compliance_config: UncheckedAccount<'info>,
+
+    /// CHECK: Bettor's compliance record (may be uninitialized), validated by the compliance program
+    pub user_compliance: UncheckedAccount<'info>,
+
+    pub compliance_program: Program<'info, Compliance>,
+
+    /// CHECK: This program's registry entry (may be unregistered), read by registry::require_not_deprecated
+    #[account(
+        seeds = [b"program_entry", crate::ID.as_ref()],
+        bump,
+        seeds::program = registry::ID
+    )]
+    pub program_entry: UncheckedAccount<'info>,
+}
+
+// ===================
+// Parlay Account Structs
+// ===================
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PlaceParlay<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"balance", user_balance.owner.as_ref()],
+        bump = user_balance.bump
+    )]
+    pub user_balance: Account<'info, UserBalance>,
+
+    /// User's vault PDA - stake is moved out of here
+    #[account(
+        mut,
+        seeds = [b"vault", user_balance.owner.as_ref()],
+        bump
+    )]
+    pub user_vault: SystemAccount<'info>,
+
+    /// Parlay vault PDA that backs parlay payouts
+    #[account(
+        mut,
+        seeds = [b"parlay_vault"],
+        bump
+    )]
+    pub parlay_vault: SystemAccount<'info>,
+
+    /// Market whose upcoming rounds are the parlay legs
+    #[account(
+        seeds = [b"market", market.feed_id.as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + Parlay::INIT_SPACE,
+        seeds = [
+            b"parlay",
+            user_balance.owner.as_ref(),
+            market.key().as_ref(),
+            market.current_round.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub parlay: Account<'info, Parlay>,
+
+    /// Session token for session key authentication (optional)
+    #[account(
+        mut,
+        seeds = [b"session", user_balance.owner.as_ref(), signer.key().as_ref()],
+        bump = session_token.bump,
+    )]
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    /// Owner's session registry - required whenever a session key signs
+    #[account(
+        seeds = [b"session_registry", user_balance.owner.as_ref()],
+        bump = session_registry.bump,
+    )]
+    pub session_registry: Option<Account<'info, SessionRegistry>>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Shared compliance config, validated by the compliance program
+    pub compliance_config: UncheckedAccount<'info>,
+
+    /// CHECK: Bettor's compliance record (may be uninitialized), validated by the compliance program
+    pub user_compliance: UncheckedAccount<'info>,
+
+    pub compliance_program: Program<'info, Compliance>,
+
+    /// CHECK: This program's registry entry (may be unregistered), read by registry::require_not_deprecated
+    #[account(
+        seeds = [b"program_entry", crate::ID.as_ref()],
+        bump,
+        seeds::program = registry::ID
+    )]
+    pub program_entry: UncheckedAccount<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SettleParlay<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"parlay",
+            parlay.owner.as_ref(),
+            parlay.market.as_ref(),
+            parlay.start_round.to_le_bytes().as_ref()
+        ],
+        bump = parlay.bump
+    )]
+    pub parlay: Account<'info, Parlay>,
+
+    /// Payout always goes to the parlay owner's balance
+    #[account(
+        mut,
+        seeds = [b"balance", parlay.owner.as_ref()],
+        bump = user_balance.bump
+    )]
+    pub user_balance: Account<'info, UserBalance>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", parlay.owner.as_ref()],
+        bump
+    )]
+    pub user_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"parlay_vault"],
+        bump
+    )]
+    pub parlay_vault: SystemAccount<'info>,
+
+    /// Anyone can settle a parlay
+    pub caller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RefundParlay<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"parlay",
+            parlay.owner.as_ref(),
+            parlay.market.as_ref(),
+            parlay.start_round.to_le_bytes().as_ref()
+        ],
+        bump = parlay.bump
+    )]
+    pub parlay: Account<'info, Parlay>,
+
+    /// Refund always goes to the parlay owner's balance
+    #[account(
+        mut,
+        seeds = [b"balance", parlay.owner.as_ref()],
+        bump = user_balance.bump
+    )]
+    pub user_balance: Account<'info, UserBalance>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", parlay.owner.as_ref()],
+        bump
+    )]
+    pub user_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"parlay_vault"],
+        bump
+    )]
+    pub parlay_vault: SystemAccount<'info>,
 
-            // SECURITY: Session must not be expired
-            let clock = Clock::get()?;
-            require!(
-                clock.unix_timestamp < session.valid_until,
-                SessionBettingError::SessionExpired
-            );
+    /// Anyone can refund an expired parlay
+    pub caller: Signer<'info>,
 
-            Ok(())
-        }
-        None => {
-            // No session and not authority - unauthorized
-            Err(SessionBettingError::Unauthorized.into())
-        }
-    }
+    pub system_program: Program<'info, System>,
 }
 
-#[inline]
-fn calculate_winnings(
-    bet_amount: u64,
-    bet_side: BetSide,
-    winner: WinnerSide,
-    up_pool: u64,
-    down_pool: u64,
-) -> Result<u64> {
-    // Check if user won
-    let user_won = matches!((bet_side, winner), (BetSide::Up, WinnerSide::Up) | (BetSide::Down, WinnerSide::Down));
+#[derive(Accounts)]
+pub struct CloseParlay<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [
+            b"parlay",
+            parlay.owner.as_ref(),
+            parlay.market.as_ref(),
+            parlay.start_round.to_le_bytes().as_ref()
+        ],
+        bump = parlay.bump
+    )]
+    pub parlay: Account<'info, Parlay>,
 
-    if !user_won {
-        return Ok(0);
-    }
+    /// Parlay owner wallet - receives the rent
+    #[account(
+        mut,
+        address = parlay.owner @ SessionBettingError::Unauthorized
+    )]
+    pub owner: SystemAccount<'info>,
 
-    // Calculate share of losing pool
-    let (winning_pool, losing_pool) = match winner {
-        WinnerSide::Up => (up_pool, down_pool),
-        WinnerSide::Down => (down_pool, up_pool),
-        _ => return Ok(0),
-    };
+    /// Anyone can close; rent can only ever reach parlay.owner
+    pub signer: Signer<'info>,
+}
 
-    // SECURITY: Prevent division by zero
-    if winning_pool == 0 {
-        return Ok(bet_amount);
-    }
+/// Fund the parlay vault (authority provides parlay liquidity)
+#[derive(Accounts)]
+pub struct FundParlayVault<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
 
-    // Winnings = bet_amount + (bet_amount / winning_pool * losing_pool)
-    // Using u128 for intermediate calculation to prevent overflow
-    let share = (bet_amount as u128)
-        .checked_mul(losing_pool as u128)
-        .ok_or(SessionBettingError::MathOverflow)?
-        .checked_div(winning_pool as u128)
-        .ok_or(SessionBettingError::MathOverflow)?;
+    #[account(mut)]
+    pub authority: Signer<'info>,
 
-    let winnings = (bet_amount as u128)
-        .checked_add(share)
-        .ok_or(SessionBettingError::MathOverflow)?;
+    #[account(
+        mut,
+        seeds = [b"parlay_vault"],
+        bump
+    )]
+    pub parlay_vault: SystemAccount<'info>,
 
-    // SECURITY: Ensure result fits in u64
-    if winnings > u64::MAX as u128 {
-        return Err(SessionBettingError::MathOverflow.into());
-    }
+    pub system_program: Program<'info, System>,
+}
+
+/// Withdraw excess parlay liquidity
+#[derive(Accounts)]
+pub struct WithdrawParlayVault<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"parlay_vault"],
+        bump
+    )]
+    pub parlay_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
 
-    Ok(winnings as u64)
+    /// CHECK: Squads multisig whose default vault is the authority (omit for a raw-key authority)
+    /// Verified by admin_guard; required when game_state.require_multisig is set
+    pub admin_multisig: Option<UncheckedAccount<'info>>,
 }
 
 // ===================
-// Account Structs
+// Token (USDC) Account Structs
 // ===================
 
 #[derive(Accounts)]
-pub struct InitializeGame<'info> {
+pub struct SetTokenMint<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    pub mint: Account<'info, Mint>,
+
+    /// Per-mint vault holding every user's token deposits; owned by its own PDA
     #[account(
         init,
         payer = authority,
-        space = 8 + GameState::INIT_SPACE,
+        seeds = [b"token_vault", mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = token_vault
+    )]
+    pub token_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositToken<'info> {
+    #[account(
         seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(address = game_state.token_mint @ SessionBettingError::TokenMintNotSet)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + TokenBalance::INIT_SPACE,
+        seeds = [b"token_balance", user.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub token_balance: Account<'info, TokenBalance>,
+
+    #[account(
+        mut,
+        seeds = [b"token_vault", mint.key().as_ref()],
+        bump
+    )]
+    pub token_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = user
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Withdraw tokens - REQUIRES wallet signature (no session key)
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawToken<'info> {
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"token_balance", user.key().as_ref(), mint.key().as_ref()],
+        bump = token_balance.bump,
+        constraint = token_balance.owner == user.key() @ SessionBettingError::NotBalanceOwner
+    )]
+    pub token_balance: Account<'info, TokenBalance>,
+
+    #[account(
+        mut,
+        seeds = [b"token_vault", mint.key().as_ref()],
         bump
     )]
+    pub token_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PlaceTokenBet<'info> {
+    #[account(
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
     pub game_state: Account<'info, GameState>,
 
-    /// Global vault PDA for pooled game funds - uses SystemAccount for type safety
+    #[account(
+        seeds = [b"round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+        constraint = round.fixed_odds_bps == 0 @ SessionBettingError::FixedOddsRound
+    )]
+    pub round: Account<'info, BettingRound>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + TokenPool::INIT_SPACE,
+        seeds = [b"token_pool", round.market.as_ref(), round.round_id.to_le_bytes().as_ref(), token_balance.mint.as_ref()],
+        bump
+    )]
+    pub token_pool: Account<'info, TokenPool>,
+
     #[account(
         mut,
-        seeds = [b"global_vault"],
+        seeds = [b"token_balance", token_balance.owner.as_ref(), game_state.token_mint.as_ref()],
+        bump = token_balance.bump
+    )]
+    pub token_balance: Account<'info, TokenBalance>,
+
+    /// Bettor's SOL balance record, read for the authority's eligibility status - created on
+    /// first use so token-only bettors can still be allowlisted or blocked
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + UserBalance::INIT_SPACE,
+        seeds = [b"balance", token_balance.owner.as_ref()],
         bump
     )]
-    pub global_vault: SystemAccount<'info>,
+    pub user_balance: Account<'info, UserBalance>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + TokenPosition::INIT_SPACE,
+        seeds = [
+            b"token_position",
+            round.market.as_ref(),
+            round.round_id.to_le_bytes().as_ref(),
+            token_balance.mint.as_ref(),
+            token_balance.owner.as_ref()
+        ],
+        bump
+    )]
+    pub token_position: Account<'info, TokenPosition>,
+
+    /// Session token for session key authentication (optional)
+    #[account(
+        mut,
+        seeds = [b"session", token_balance.owner.as_ref(), signer.key().as_ref()],
+        bump = session_token.bump,
+    )]
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    /// Owner's session registry - required whenever a session key signs
+    #[account(
+        seeds = [b"session_registry", token_balance.owner.as_ref()],
+        bump = session_registry.bump,
+    )]
+    pub session_registry: Option<Account<'info, SessionRegistry>>,
 
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub signer: Signer<'info>,
 
     pub system_program: Program<'info, System>,
+
+    /// CHECK: Shared compliance config, validated by the compliance program
+    pub compliance_config: UncheckedAccount<'info>,
+
+    /// CHECK: Bettor's compliance record (may be uninitialized), validated by the compliance program
+    pub user_compliance: UncheckedAccount<'info>,
+
+    pub compliance_program: Program<'info, Compliance>,
+
+    /// CHECK: This program's registry entry (may be unregistered), read by registry::require_not_deprecated
+    #[account(
+        seeds = [b"program_entry", crate::ID.as_ref()],
+        bump,
+        seeds::program = registry::ID
+    )]
+    pub program_entry: UncheckedAccount<'info>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-pub struct StartRound<'info> {
+pub struct ClaimTokenWinnings<'info> {
+    #[account(
+        mut,
+        seeds = [b"game"],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [b"round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump
+    )]
+    pub round: Account<'info, BettingRound>,
+
+    #[account(
+        seeds = [b"token_pool", round.market.as_ref(), round.round_id.to_le_bytes().as_ref(), token_position.mint.as_ref()],
+        bump = token_pool.bump
+    )]
+    pub token_pool: Account<'info, TokenPool>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"token_position",
+            round.market.as_ref(),
+            round.round_id.to_le_bytes().as_ref(),
+            token_position.mint.as_ref(),
+            token_position.player.as_ref()
+        ],
+        bump = token_position.bump
+    )]
+    pub token_position: Account<'info, TokenPosition>,
+
+    #[account(
+        mut,
+        seeds = [b"token_balance", token_position.player.as_ref(), token_position.mint.as_ref()],
+        bump = token_balance.bump
+    )]
+    pub token_balance: Account<'info, TokenBalance>,
+
+    /// Anyone can claim; funds can only ever reach token_position.player
+    pub signer: Signer<'info>,
+}
+
+/// Withdraw collected token fees
+#[derive(Accounts)]
+pub struct WithdrawTokenFees<'info> {
     #[account(
         mut,
         seeds = [b"game"],
@@ -980,176 +9695,239 @@ pub struct StartRound<'info> {
     )]
     pub game_state: Account<'info, GameState>,
 
+    #[account(address = game_state.token_mint @ SessionBettingError::TokenMintNotSet)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"token_vault", mint.key().as_ref()],
+        bump
+    )]
+    pub token_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    /// CHECK: Squads multisig whose default vault is the authority (omit for a raw-key authority)
+    /// Verified by admin_guard; required when game_state.require_multisig is set
+    pub admin_multisig: Option<UncheckedAccount<'info>>,
+}
+
+// ===================
+// Fixed-Odds House Account Structs
+// ===================
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositLiquidity<'info> {
+    #[account(
+        seeds = [b"market", market.feed_id.as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Market's house - created on the first deposit
+    #[account(
+        init_if_needed,
+        payer = provider,
+        space = 8 + House::INIT_SPACE,
+        seeds = [b"house", market.key().as_ref()],
+        bump
+    )]
+    pub house: Account<'info, House>,
+
+    /// House vault PDA holding LP liquidity and fixed-odds stakes - uses SystemAccount for type safety
     #[account(
-        init,
-        payer = authority,
-        space = 8 + BettingRound::INIT_SPACE,
-        seeds = [b"round", game_state.current_round.to_le_bytes().as_ref()],
+        mut,
+        seeds = [b"house_vault", market.key().as_ref()],
         bump
     )]
-    pub round: Account<'info, BettingRound>,
+    pub house_vault: SystemAccount<'info>,
 
+    /// Provider's shares - created on first use
     #[account(
-        init,
-        payer = authority,
-        space = 8 + BettingPool::INIT_SPACE,
-        seeds = [b"pool", game_state.current_round.to_le_bytes().as_ref()],
+        init_if_needed,
+        payer = provider,
+        space = 8 + LpPosition::INIT_SPACE,
+        seeds = [b"lp_position", market.key().as_ref(), provider.key().as_ref()],
         bump
     )]
-    pub pool: Account<'info, BettingPool>,
+    pub lp_position: Account<'info, LpPosition>,
 
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub provider: Signer<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-pub struct LockRound<'info> {
+pub struct WithdrawLiquidity<'info> {
     #[account(
-        seeds = [b"game"],
-        bump = game_state.bump,
-        has_one = authority
+        seeds = [b"market", market.feed_id.as_ref()],
+        bump = market.bump
     )]
-    pub game_state: Account<'info, GameState>,
+    pub market: Account<'info, Market>,
 
     #[account(
         mut,
-        seeds = [b"round", round.round_id.to_le_bytes().as_ref()],
-        bump = round.bump
+        seeds = [b"house", market.key().as_ref()],
+        bump = house.bump
     )]
-    pub round: Account<'info, BettingRound>,
-
-    /// CHECK: Pyth price feed account - validated in instruction
-    pub price_feed: AccountInfo<'info>,
+    pub house: Account<'info, House>,
 
-    /// Authority must sign to prevent griefing
-    pub authority: Signer<'info>,
-}
-
-/// Permissionless fallback for locking rounds when authority is offline
-/// SECURITY: Uses Pyth oracle price - no arbitrary price input allowed
-#[derive(Accounts)]
-pub struct LockRoundFallback<'info> {
     #[account(
-        seeds = [b"game"],
-        bump = game_state.bump
+        mut,
+        seeds = [b"house_vault", market.key().as_ref()],
+        bump
     )]
-    pub game_state: Account<'info, GameState>,
+    pub house_vault: SystemAccount<'info>,
 
     #[account(
         mut,
-        seeds = [b"round", round.round_id.to_le_bytes().as_ref()],
-        bump = round.bump
+        seeds = [b"lp_position", market.key().as_ref(), provider.key().as_ref()],
+        bump = lp_position.bump
     )]
-    pub round: Account<'info, BettingRound>,
+    pub lp_position: Account<'info, LpPosition>,
 
-    /// CHECK: Pyth price feed account - validated in instruction
-    pub price_feed: AccountInfo<'info>,
+    /// Must be the position owner - receives the withdrawn SOL
+    #[account(mut)]
+    pub provider: Signer<'info>,
 
-    /// Anyone can call this after fallback time
-    pub caller: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-pub struct SettleRound<'info> {
+#[instruction(side: BetSide, amount: u64, position_index: u8)]
+pub struct PlaceHouseBet<'info> {
     #[account(
-        mut,
         seeds = [b"game"],
         bump = game_state.bump
     )]
     pub game_state: Account<'info, GameState>,
 
+    #[account(address = round.market @ SessionBettingError::WrongMarket)]
+    pub market: Account<'info, Market>,
+
     #[account(
-        mut,
-        seeds = [b"round", round.round_id.to_le_bytes().as_ref()],
-        bump = round.bump
+        seeds = [b"round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+        constraint = round.fixed_odds_bps > 0 @ SessionBettingError::NotFixedOddsRound
     )]
     pub round: Account<'info, BettingRound>,
 
     #[account(
-        seeds = [b"pool", round.round_id.to_le_bytes().as_ref()],
+        mut,
+        seeds = [b"pool", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
         bump = pool.bump
     )]
     pub pool: Account<'info, BettingPool>,
 
-    pub caller: Signer<'info>,
-}
-
-/// Close a settled round and reclaim rent
-/// Authority only, after grace period
-#[derive(Accounts)]
-pub struct CloseRound<'info> {
     #[account(
-        seeds = [b"game"],
-        bump = game_state.bump,
-        has_one = authority
+        mut,
+        seeds = [b"house", round.market.as_ref()],
+        bump = house.bump
     )]
-    pub game_state: Account<'info, GameState>,
+    pub house: Account<'info, House>,
 
     #[account(
         mut,
-        seeds = [b"round", round.round_id.to_le_bytes().as_ref()],
-        bump = round.bump,
-        close = authority
+        seeds = [b"house_vault", round.market.as_ref()],
+        bump
     )]
-    pub round: Account<'info, BettingRound>,
+    pub house_vault: SystemAccount<'info>,
 
     #[account(
         mut,
-        seeds = [b"pool", round.round_id.to_le_bytes().as_ref()],
-        bump = pool.bump,
-        close = authority
+        seeds = [b"balance", user_balance.owner.as_ref()],
+        bump = user_balance.bump
     )]
-    pub pool: Account<'info, BettingPool>,
-
-    #[account(mut)]
-    pub authority: Signer<'info>,
-}
+    pub user_balance: Account<'info, UserBalance>,
 
-#[derive(Accounts)]
-pub struct SetPaused<'info> {
+    /// Bettor's vault - the stake leaves it for the house vault
     #[account(
         mut,
-        seeds = [b"game"],
-        bump = game_state.bump,
-        has_one = authority
+        seeds = [b"vault", user_balance.owner.as_ref()],
+        bump
     )]
-    pub game_state: Account<'info, GameState>,
+    pub user_vault: SystemAccount<'info>,
 
-    pub authority: Signer<'info>,
-}
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + PlayerPosition::INIT_SPACE,
+        seeds = [b"position", round.market.as_ref(), round.round_id.to_le_bytes().as_ref(), user_balance.owner.as_ref(), &[position_index]],
+        bump
+    )]
+    pub position: Account<'info, PlayerPosition>,
 
-#[derive(Accounts)]
-pub struct SetPriceFeed<'info> {
+    /// Wallet's stake across its positions in this round - created on first use
     #[account(
-        mut,
-        seeds = [b"game"],
-        bump = game_state.bump,
-        has_one = authority
+        init_if_needed,
+        payer = signer,
+        space = 8 + PlayerRound::INIT_SPACE,
+        seeds = [b"player_round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref(), user_balance.owner.as_ref()],
+        bump
     )]
-    pub game_state: Account<'info, GameState>,
+    pub player_round: Account<'info, PlayerRound>,
 
-    pub authority: Signer<'info>,
-}
+    /// Bettor's lifetime stats - created on first use
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + UserStats::INIT_SPACE,
+        seeds = [b"user_stats", user_balance.owner.as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
 
-/// Propose a new authority (two-step transfer)
-#[derive(Accounts)]
-pub struct ProposeAuthorityTransfer<'info> {
+    /// Session token for session key authentication (optional)
     #[account(
         mut,
-        seeds = [b"game"],
-        bump = game_state.bump,
-        has_one = authority
+        seeds = [b"session", user_balance.owner.as_ref(), signer.key().as_ref()],
+        bump = session_token.bump,
     )]
-    pub game_state: Account<'info, GameState>,
+    pub session_token: Option<Account<'info, SessionToken>>,
 
-    pub authority: Signer<'info>,
+    /// Owner's session registry - required whenever a session key signs
+    #[account(
+        seeds = [b"session_registry", user_balance.owner.as_ref()],
+        bump = session_registry.bump,
+    )]
+    pub session_registry: Option<Account<'info, SessionRegistry>>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Shared compliance config, validated by the compliance program
+    pub compliance_config: UncheckedAccount<'info>,
+
+    /// CHECK: Bettor's compliance record (may be uninitialized), validated by the compliance program
+    pub user_compliance: UncheckedAccount<'info>,
+
+    pub compliance_program: Program<'info, Compliance>,
+
+    /// CHECK: This program's registry entry (may be unregistered), read by registry::require_not_deprecated
+    #[account(
+        seeds = [b"program_entry", crate::ID.as_ref()],
+        bump,
+        seeds::program = registry::ID
+    )]
+    pub program_entry: UncheckedAccount<'info>,
 }
 
-/// Accept authority transfer (must be signed by pending authority)
 #[derive(Accounts)]
-pub struct AcceptAuthorityTransfer<'info> {
+pub struct ClaimHouseBet<'info> {
     #[account(
         mut,
         seeds = [b"game"],
@@ -1157,256 +9935,290 @@ pub struct AcceptAuthorityTransfer<'info> {
     )]
     pub game_state: Account<'info, GameState>,
 
-    /// The new authority accepting the transfer
-    pub new_authority: Signer<'info>,
-}
+    #[account(
+        seeds = [b"round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+        constraint = round.fixed_odds_bps > 0 @ SessionBettingError::NotFixedOddsRound
+    )]
+    pub round: Account<'info, BettingRound>,
 
-/// Cancel a pending authority transfer
-#[derive(Accounts)]
-pub struct CancelAuthorityTransfer<'info> {
     #[account(
         mut,
-        seeds = [b"game"],
-        bump = game_state.bump,
-        has_one = authority
+        seeds = [b"pool", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
     )]
-    pub game_state: Account<'info, GameState>,
+    pub pool: Account<'info, BettingPool>,
 
-    pub authority: Signer<'info>,
-}
+    #[account(
+        mut,
+        seeds = [b"house", round.market.as_ref()],
+        bump = house.bump
+    )]
+    pub house: Account<'info, House>,
 
-/// Withdraw collected platform fees
-#[derive(Accounts)]
-pub struct WithdrawFees<'info> {
     #[account(
         mut,
-        seeds = [b"game"],
-        bump = game_state.bump,
-        has_one = authority
+        seeds = [b"house_vault", round.market.as_ref()],
+        bump
     )]
-    pub game_state: Account<'info, GameState>,
+    pub house_vault: SystemAccount<'info>,
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"balance", user_balance.owner.as_ref()],
+        bump = user_balance.bump
+    )]
+    pub user_balance: Account<'info, UserBalance>,
 
-    /// Global vault PDA for pooled funds
+    /// Owner's vault - receives the payout
     #[account(
         mut,
-        seeds = [b"global_vault"],
+        seeds = [b"vault", user_balance.owner.as_ref()],
         bump
     )]
-    pub global_vault: SystemAccount<'info>,
+    pub user_vault: SystemAccount<'info>,
 
-    pub system_program: Program<'info, System>,
-}
+    /// Seeded by the original bettor; may have been transferred to a new player since
+    #[account(
+        mut,
+        seeds = [b"position", round.market.as_ref(), round.round_id.to_le_bytes().as_ref(), position.bettor.as_ref(), &[position.index]],
+        bump = position.bump,
+        constraint = position.player == user_balance.owner @ SessionBettingError::NotPositionOwner
+    )]
+    pub position: Account<'info, PlayerPosition>,
 
-#[derive(Accounts)]
-pub struct CreateSession<'info> {
+    /// Owner's lifetime stats - created on first use
     #[account(
-        init,
-        payer = authority,
-        space = 8 + SessionToken::INIT_SPACE,
-        seeds = [b"session", authority.key().as_ref(), session_signer.key().as_ref()],
+        init_if_needed,
+        payer = signer,
+        space = 8 + UserStats::INIT_SPACE,
+        seeds = [b"user_stats", user_balance.owner.as_ref()],
         bump
     )]
-    pub session_token: Account<'info, SessionToken>,
+    pub user_stats: Account<'info, UserStats>,
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"jackpot"],
+        bump = jackpot.bump
+    )]
+    pub jackpot: Account<'info, Jackpot>,
 
-    /// CHECK: The session signer public key (does not need to sign creation)
-    pub session_signer: AccountInfo<'info>,
+    /// Anyone can claim; funds can only ever reach position.player's vault
+    /// Pays for user_stats when it is created
+    #[account(mut)]
+    pub signer: Signer<'info>,
 
     pub system_program: Program<'info, System>,
+
+    /// CHECK: This program's registry entry (may be unregistered), read by registry::require_not_deprecated
+    #[account(
+        seeds = [b"program_entry", crate::ID.as_ref()],
+        bump,
+        seeds::program = registry::ID
+    )]
+    pub program_entry: UncheckedAccount<'info>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-pub struct RevokeSession<'info> {
+pub struct ReleaseHouseReserve<'info> {
+    #[account(
+        seeds = [b"round", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+        constraint = round.fixed_odds_bps > 0 @ SessionBettingError::NotFixedOddsRound
+    )]
+    pub round: Account<'info, BettingRound>,
+
     #[account(
         mut,
-        close = authority,
-        seeds = [b"session", authority.key().as_ref(), session_token.session_signer.as_ref()],
-        bump = session_token.bump,
-        constraint = session_token.authority == authority.key() @ SessionBettingError::NotSessionOwner
+        seeds = [b"pool", round.market.as_ref(), round.round_id.to_le_bytes().as_ref()],
+        bump = pool.bump
     )]
-    pub session_token: Account<'info, SessionToken>,
+    pub pool: Account<'info, BettingPool>,
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"house", round.market.as_ref()],
+        bump = house.bump
+    )]
+    pub house: Account<'info, House>,
 }
 
+// ===================
+// Game Settlement Account Structs (Authority Only)
+// ===================
+
+/// Transfer lamports from user vault to global vault (for losses)
+#[event_cpi]
 #[derive(Accounts)]
-pub struct Deposit<'info> {
+#[instruction(amount: u64, game_type: GameType)]
+pub struct TransferToGlobalVault<'info> {
     #[account(
+        mut,
         seeds = [b"game"],
-        bump = game_state.bump
+        bump = game_state.bump,
+        has_one = authority
     )]
     pub game_state: Account<'info, GameState>,
 
-    #[account(
-        init_if_needed,
-        payer = user,
-        space = 8 + UserBalance::INIT_SPACE,
-        seeds = [b"balance", user.key().as_ref()],
-        bump
-    )]
-    pub user_balance: Account<'info, UserBalance>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: User wallet (not signer - backend acts on their behalf)
+    pub owner: AccountInfo<'info>,
 
-    /// Vault PDA to hold user's funds - uses SystemAccount for type safety
     #[account(
         mut,
-        seeds = [b"vault", user.key().as_ref()],
-        bump
+        seeds = [b"balance", owner.key().as_ref()],
+        bump = user_balance.bump
     )]
-    pub vault: SystemAccount<'info>,
-
-    #[account(mut)]
-    pub user: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
-}
+    pub user_balance: Account<'info, UserBalance>,
 
-#[derive(Accounts)]
-pub struct Withdraw<'info> {
+    /// User's vault PDA - uses SystemAccount for type safety
     #[account(
         mut,
-        seeds = [b"balance", user.key().as_ref()],
-        bump = user_balance.bump,
-        constraint = user_balance.owner == user.key() @ SessionBettingError::NotBalanceOwner
+        seeds = [b"vault", owner.key().as_ref()],
+        bump
     )]
-    pub user_balance: Account<'info, UserBalance>,
+    pub user_vault: SystemAccount<'info>,
 
-    /// Vault PDA that holds user's funds - uses SystemAccount for type safety
+    /// Game vault PDA holding this game type's funds - uses SystemAccount for type safety
     #[account(
         mut,
-        seeds = [b"vault", user.key().as_ref()],
+        seeds = [b"global_vault", [game_type as u8].as_ref()],
         bump
     )]
-    pub vault: SystemAccount<'info>,
-
-    #[account(mut)]
-    pub user: Signer<'info>,
+    pub game_vault: SystemAccount<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
+/// Credit winnings from global vault to user vault
+#[event_cpi]
 #[derive(Accounts)]
-#[instruction(side: BetSide, amount: u64)]
-pub struct PlaceBet<'info> {
+#[instruction(amount: u64, game_type: GameType)]
+pub struct CreditWinnings<'info> {
     #[account(
+        mut,
         seeds = [b"game"],
-        bump = game_state.bump
+        bump = game_state.bump,
+        has_one = authority
     )]
     pub game_state: Account<'info, GameState>,
 
-    #[account(
-        seeds = [b"round", round.round_id.to_le_bytes().as_ref()],
-        bump = round.bump
-    )]
-    pub round: Account<'info, BettingRound>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
 
-    #[account(
-        mut,
-        seeds = [b"pool", round.round_id.to_le_bytes().as_ref()],
-        bump = pool.bump
-    )]
-    pub pool: Account<'info, BettingPool>,
+    /// CHECK: User wallet (not signer - backend credits on their behalf)
+    pub owner: AccountInfo<'info>,
 
     #[account(
         mut,
-        seeds = [b"balance", user_balance.owner.as_ref()],
+        seeds = [b"balance", owner.key().as_ref()],
         bump = user_balance.bump
     )]
     pub user_balance: Account<'info, UserBalance>,
 
+    /// User's vault PDA - uses SystemAccount for type safety
     #[account(
-        init,
-        payer = signer,
-        space = 8 + PlayerPosition::INIT_SPACE,
-        seeds = [b"position", round.round_id.to_le_bytes().as_ref(), user_balance.owner.as_ref()],
+        mut,
+        seeds = [b"vault", owner.key().as_ref()],
         bump
     )]
-    pub position: Account<'info, PlayerPosition>,
+    pub user_vault: SystemAccount<'info>,
 
-    /// Session token for session key authentication (optional)
-    /// If provided, allows session_signer to act on behalf of authority
+    /// Game vault PDA holding this game type's funds - uses SystemAccount for type safety
     #[account(
-        seeds = [b"session", user_balance.owner.as_ref(), signer.key().as_ref()],
-        bump = session_token.bump,
+        mut,
+        seeds = [b"global_vault", [game_type as u8].as_ref()],
+        bump
     )]
-    pub session_token: Option<Account<'info, SessionToken>>,
-
-    #[account(mut)]
-    pub signer: Signer<'info>,
+    pub game_vault: SystemAccount<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
+/// Lock a user's wager for an allowlisted game program (CPI)
+#[event_cpi]
 #[derive(Accounts)]
-pub struct ClaimWinnings<'info> {
+#[instruction(amount: u64, game_type: GameType)]
+pub struct DebitForGame<'info> {
     #[account(
         mut,
         seeds = [b"game"],
-        bump = game_state.bump
+        bump = game_state.bump,
+        constraint = is_game_program(&game_state, &game_program.key()) @ SessionBettingError::UnauthorizedGameProgram
     )]
     pub game_state: Account<'info, GameState>,
 
-    #[account(
-        seeds = [b"round", round.round_id.to_le_bytes().as_ref()],
-        bump = round.bump
-    )]
-    pub round: Account<'info, BettingRound>,
+    /// Allowlisted game program whose PDA signs this call
+    /// CHECK: Only used to derive game_signer; checked against game_state.game_programs
+    pub game_program: UncheckedAccount<'info>,
 
+    /// Game program's `game_signer` PDA, signed via invoke_signed
     #[account(
-        seeds = [b"pool", round.round_id.to_le_bytes().as_ref()],
-        bump = pool.bump
+        seeds = [b"game_signer"],
+        bump,
+        seeds::program = game_program.key()
     )]
-    pub pool: Account<'info, BettingPool>,
+    pub game_signer: Signer<'info>,
+
+    /// CHECK: User wallet (not signer - the game program acts on their behalf)
+    pub owner: AccountInfo<'info>,
 
     #[account(
         mut,
-        seeds = [b"balance", user_balance.owner.as_ref()],
+        seeds = [b"balance", owner.key().as_ref()],
         bump = user_balance.bump
     )]
     pub user_balance: Account<'info, UserBalance>,
 
+    /// User's vault PDA - uses SystemAccount for type safety
     #[account(
         mut,
-        seeds = [b"position", round.round_id.to_le_bytes().as_ref(), user_balance.owner.as_ref()],
-        bump = position.bump,
-        constraint = position.player == user_balance.owner @ SessionBettingError::NotPositionOwner
+        seeds = [b"vault", owner.key().as_ref()],
+        bump
     )]
-    pub position: Account<'info, PlayerPosition>,
+    pub user_vault: SystemAccount<'info>,
 
-    /// Session token for session key authentication (optional)
+    /// Game vault PDA holding this game type's funds - uses SystemAccount for type safety
     #[account(
-        seeds = [b"session", user_balance.owner.as_ref(), signer.key().as_ref()],
-        bump = session_token.bump,
+        mut,
+        seeds = [b"global_vault", [game_type as u8].as_ref()],
+        bump
     )]
-    pub session_token: Option<Account<'info, SessionToken>>,
+    pub game_vault: SystemAccount<'info>,
 
-    #[account(mut)]
-    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
-// ===================
-// Game Settlement Account Structs (Authority Only)
-// ===================
-
-/// Transfer lamports from user vault to global vault (for losses)
+/// Pay a user's winnings for an allowlisted game program (CPI)
+#[event_cpi]
 #[derive(Accounts)]
-pub struct TransferToGlobalVault<'info> {
+#[instruction(amount: u64, game_type: GameType)]
+pub struct CreditForGame<'info> {
     #[account(
+        mut,
         seeds = [b"game"],
         bump = game_state.bump,
-        has_one = authority
+        constraint = is_game_program(&game_state, &game_program.key()) @ SessionBettingError::UnauthorizedGameProgram
     )]
     pub game_state: Account<'info, GameState>,
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
+    /// Allowlisted game program whose PDA signs this call
+    /// CHECK: Only used to derive game_signer; checked against game_state.game_programs
+    pub game_program: UncheckedAccount<'info>,
 
-    /// CHECK: User wallet (not signer - backend acts on their behalf)
+    /// Game program's `game_signer` PDA, signed via invoke_signed
+    #[account(
+        seeds = [b"game_signer"],
+        bump,
+        seeds::program = game_program.key()
+    )]
+    pub game_signer: Signer<'info>,
+
+    /// CHECK: User wallet (not signer - the game program acts on their behalf)
     pub owner: AccountInfo<'info>,
 
     #[account(
@@ -1424,20 +10236,20 @@ pub struct TransferToGlobalVault<'info> {
     )]
     pub user_vault: SystemAccount<'info>,
 
-    /// Global vault PDA for pooled funds - uses SystemAccount for type safety
+    /// Game vault PDA holding this game type's funds - uses SystemAccount for type safety
     #[account(
         mut,
-        seeds = [b"global_vault"],
+        seeds = [b"global_vault", [game_type as u8].as_ref()],
         bump
     )]
-    pub global_vault: SystemAccount<'info>,
+    pub game_vault: SystemAccount<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
-/// Credit winnings from global vault to user vault
+/// Fund the global vault (authority deposits for payouts)
 #[derive(Accounts)]
-pub struct CreditWinnings<'info> {
+pub struct FundGlobalVault<'info> {
     #[account(
         seeds = [b"game"],
         bump = game_state.bump,
@@ -1448,46 +10260,54 @@ pub struct CreditWinnings<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
 
-    /// CHECK: User wallet (not signer - backend credits on their behalf)
-    pub owner: AccountInfo<'info>,
-
+    /// Global vault PDA for pooled funds - uses SystemAccount for type safety
     #[account(
         mut,
-        seeds = [b"balance", owner.key().as_ref()],
-        bump = user_balance.bump
+        seeds = [b"global_vault"],
+        bump
     )]
-    pub user_balance: Account<'info, UserBalance>,
+    pub global_vault: SystemAccount<'info>,
 
-    /// User's vault PDA - uses SystemAccount for type safety
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(game_type: GameType)]
+pub struct FundGameVault<'info> {
     #[account(
-        mut,
-        seeds = [b"vault", owner.key().as_ref()],
-        bump
+        seeds = [b"game"],
+        bump = game_state.bump,
+        has_one = authority
     )]
-    pub user_vault: SystemAccount<'info>,
+    pub game_state: Account<'info, GameState>,
 
-    /// Global vault PDA for pooled funds - uses SystemAccount for type safety
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Game vault PDA holding this game type's funds - uses SystemAccount for type safety
     #[account(
         mut,
-        seeds = [b"global_vault"],
+        seeds = [b"global_vault", [game_type as u8].as_ref()],
         bump
     )]
-    pub global_vault: SystemAccount<'info>,
+    pub game_vault: SystemAccount<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
-/// Fund the global vault (authority deposits for payouts)
+#[event_cpi]
 #[derive(Accounts)]
-pub struct FundGlobalVault<'info> {
+#[instruction(game_type: GameType)]
+pub struct MoveReservedToGameVault<'info> {
     #[account(
+        mut,
         seeds = [b"game"],
         bump = game_state.bump,
         has_one = authority
     )]
     pub game_state: Account<'info, GameState>,
 
-    #[account(mut)]
     pub authority: Signer<'info>,
 
     /// Global vault PDA for pooled funds - uses SystemAccount for type safety
@@ -1498,6 +10318,14 @@ pub struct FundGlobalVault<'info> {
     )]
     pub global_vault: SystemAccount<'info>,
 
+    /// Game vault PDA holding this game type's funds - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"global_vault", [game_type as u8].as_ref()],
+        bump
+    )]
+    pub game_vault: SystemAccount<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -1511,18 +10339,137 @@ pub struct GameState {
     pub authority: Pubkey,
     /// Pending authority for two-step transfer (security: prevents accidental lockout)
     pub pending_authority: Option<Pubkey>,
-    /// Pyth price feed ID for oracle price validation
+    /// Original single-market feed; rounds now read Market.feed_id. Kept, like every field up
+    /// to bump, so accounts in the original layout still decode
     pub price_feed_id: [u8; 32],
+    /// Original round counter; rounds now count on Market.current_round
     pub current_round: u64,
     pub total_volume: u64,
     pub total_fees_collected: u64,
     pub is_paused: bool,
     pub bump: u8,
+    /// Layout version (GAME_STATE_VERSION); every field before it is the original layout,
+    /// see migrate_game_state
+    pub version: u8,
+    /// Keeper key allowed to start, lock and cancel rounds (Pubkey::default() = none)
+    pub operator: Pubkey,
+    /// Wagers locked into the global vault before game vaults existed and not yet moved out
+    /// by move_reserved_to_game_vault; the vault must always hold total_fees_collected + reserved_payouts
+    pub reserved_payouts: u64,
+    /// Sum of max payouts of all unsettled parlays (parlay vault must cover this)
+    pub parlay_exposure: u64,
+    /// Max total pool per round (0 = no cap)
+    pub max_total_pool: u64,
+    /// Max pool per side per round (0 = no cap)
+    pub max_side_pool: u64,
+    /// Max stake per position per round (0 = no cap)
+    pub max_player_stake: u64,
+    /// Circuit breaker: max |end - start| move in bps before a round settles as Draw (0 = disabled)
+    pub max_price_move_bps: u64,
+    /// Widest Pyth confidence interval, in bps of price, accepted at start and lock (0 = disabled)
+    pub max_conf_bps: u64,
+    /// Rounds with a total pool below this settle as refunds (0 = disabled)
+    pub min_pool_for_settlement: u64,
+    /// Platform fee taken from winnings, in basis points (<= MAX_FEE_BPS)
+    pub fee_bps: u64,
+    /// Fee kept from stakes cancelled before lock, in basis points (<= MAX_FEE_BPS)
+    pub cancel_fee_bps: u64,
+    /// Lamports of reclaimed round rent paid to whoever calls close_round
+    pub close_round_bounty: u64,
+    /// Most bets, SOL or token, one session key may sign per clock minute (0 = unlimited); wallets are exempt
+    pub session_bets_per_minute: u64,
+    /// Bonus on winnings per consecutive prior win, in basis points (0 = disabled)
+    pub streak_bonus_bps_per_win: u64,
+    /// Largest streak bonus, in basis points of winnings (<= MAX_STREAK_BONUS_BPS)
+    pub max_streak_bonus_bps: u64,
+    /// Bonus on winnings for a bet placed at round start, decaying linearly to 0 at lock
+    /// (<= MAX_EARLY_BIRD_BONUS_BPS, 0 = disabled)
+    pub max_early_bird_bonus_bps: u64,
+    /// Smallest stake (and deposit) accepted, in lamports
+    pub min_bet: u64,
+    /// Largest single stake accepted, in lamports
+    pub max_bet: u64,
+    /// Shortest round start_round accepts, in seconds
+    pub min_round_duration: i64,
+    /// Longest round start_round accepts, in seconds
+    pub max_round_duration: i64,
+    /// Compliance gating applied to betting entrypoints
+    pub eligibility_mode: EligibilityMode,
+    /// Which Pyth account format price_feed accounts use
+    pub oracle_mode: OracleMode,
+    /// Fee withdrawals and authority changes must be signed by the Squads multisig vault
+    pub require_multisig: bool,
+    /// Treasury wallet receiving streamed fees
+    pub fee_stream_recipient: Pubkey,
+    /// Lamports per second streamed to the treasury (0 = streaming off, lump-sum withdrawals allowed)
+    pub fee_stream_rate: u64,
+    /// Last time the stream was cranked
+    pub fee_stream_last_at: i64,
+    /// SPL mint accepted by the token path (default = token betting disabled)
+    pub token_mint: Pubkey,
+    /// Fees collected in token_mint, withdrawable via withdraw_token_fees
+    pub token_fees_collected: u64,
+    /// When the current pause began (0 = not paused); drives emergency_refund
+    pub paused_at: i64,
+    /// Delay between propose_admin_change and execute_admin_change (0 = direct setters allowed)
+    pub admin_timelock_seconds: i64,
+    /// When pending_admin_change becomes executable
+    pub pending_admin_change_at: i64,
+    /// Volume, fee and payout counters indexed by GameType
+    pub game_type_stats: [GameTypeStats; GAME_TYPE_COUNT],
+    /// Wagers locked in each GameType's game vault, owed back to players
+    pub game_vault_reserved: [u64; GAME_TYPE_COUNT],
+    /// Stake limits and pool caps for the token path (set with token_mint)
+    pub token_limits: TokenBetLimits,
+    /// Always zero; new fixed-size fields are carved from the front of it so no field before
+    /// it ever moves. Variable-length fields stay last, behind it
+    pub reserved: [u8; GAME_STATE_RESERVED_BYTES],
+    /// Recipients withdraw_fees divides fees across (empty = all to the authority)
+    #[max_len(MAX_FEE_SPLIT_RECIPIENTS)]
+    pub fee_split: Vec<FeeSplitEntry>,
+    /// Fee, limit or price feed change waiting out the timelock (executable at pending_admin_change_at)
+    pub pending_admin_change: Option<AdminChange>,
+    /// Programs whose `game_signer` PDA may call debit_for_game / credit_for_game
+    #[max_len(MAX_GAME_PROGRAMS)]
+    pub game_programs: Vec<Pubkey>,
+}
+
+/// One asset's round series, keyed by its feed ID
+/// Rounds and pools live under [b"round" | b"pool", market, round_id] so markets run in parallel
+#[account]
+#[derive(InitSpace)]
+pub struct Market {
+    /// Layout version (ACCOUNT_VERSION)
+    pub version: u8,
+    /// Pyth price feed ID, or Chainlink feed account address, for oracle price validation
+    pub feed_id: [u8; 32],
+    pub current_round: u64,
+    /// When the latest round opened (rate-limits start_round_oracle)
+    pub last_round_started_at: i64,
+    /// Switchboard pull feed used while the primary feed is stale (default = none)
+    pub switchboard_feed: Pubkey,
+    /// Oracle network feed_id refers to
+    pub price_source: PriceSource,
+    /// Inactive markets start no new rounds
+    pub is_active: bool,
+    pub bump: u8,
+    /// Seconds from a round's start to its end (ROUND_DURATION_SECONDS by default)
+    pub round_duration: i64,
+    /// Seconds before end_time that betting closes (LOCK_BUFFER_SECONDS by default)
+    pub lock_buffer: i64,
+    /// Payout multiplier, stake included, its rounds quote against the house vault on either
+    /// side (0 = pari-mutuel pools; at most MAX_FIXED_ODDS_BPS)
+    pub fixed_odds_bps: u64,
+    /// Most the house vault can lose on one fixed-odds round (0 = only its free liquidity)
+    pub max_round_exposure: u64,
 }
 
 #[account]
 #[derive(InitSpace)]
 pub struct BettingRound {
+    /// Layout version (ACCOUNT_VERSION)
+    pub version: u8,
+    pub market: Pubkey,
     pub round_id: u64,
     pub start_time: i64,
     pub lock_time: i64,
@@ -1530,57 +10477,398 @@ pub struct BettingRound {
     /// Fallback time after which anyone can lock the round (decentralization)
     pub lock_time_fallback: i64,
     pub start_price: u64,
+    /// Oracle slot start_price was published in (audit trail)
+    pub start_publish_slot: u64,
     pub end_price: u64,
+    /// How end_price is derived from the oracle
+    pub settlement_mode: SettlementMode,
+    /// Sum of recorded TWAP samples (TWAP rounds only)
+    pub price_sample_sum: u128,
+    pub price_sample_count: u32,
+    /// When the latest TWAP sample was recorded (start_time before the first)
+    pub last_sample_at: i64,
+    /// Locked on a wide-confidence print after the retry window - settles as a Draw
+    pub wide_confidence: bool,
+    /// Jackpot won by this round, shared by winning positions pro rata (0 = none)
+    pub jackpot_payout: u64,
     pub status: RoundStatus,
     pub winner: WinnerSide,
     pub bump: u8,
+    /// Market's fixed odds when the round opened (0 = pari-mutuel)
+    pub fixed_odds_bps: u64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct BettingPool {
+    /// Layout version (ACCOUNT_VERSION)
+    pub version: u8,
+    pub round_id: u64,
+    pub up_pool: u64,
+    pub down_pool: u64,
+    pub total_pool: u64,
+    /// Number of unique bettors on the UP side
+    pub up_bettors: u32,
+    /// Number of unique bettors on the DOWN side
+    pub down_bettors: u32,
+    /// Stake from committed bets whose side has not been revealed yet
+    pub unrevealed_pool: u64,
+    pub bump: u8,
+    /// House vault liquidity held back for this round's payouts (fixed-odds rounds)
+    pub house_reserved: u64,
+    /// Whether house_reserved has been cut down to what the round's outcome owes
+    pub house_reserve_settled: bool,
+}
+
+/// Fixed-size ring buffer of a market's last ROUND_HISTORY_LEN settled rounds, so frontends
+/// can chart recent outcomes without an indexer
+/// Newest entry is at (total_recorded - 1) % ROUND_HISTORY_LEN
+#[account(zero_copy)]
+pub struct RoundHistory {
+    pub market: Pubkey,
+    /// Rounds recorded since creation (entries only hold the latest ROUND_HISTORY_LEN)
+    pub total_recorded: u64,
+    pub entries: [RoundSummary; ROUND_HISTORY_LEN],
+    pub bump: u8,
+    /// Layout version (ACCOUNT_VERSION), carved from the trailing padding
+    pub version: u8,
+    pub _padding: [u8; 6],
+}
+
+/// Compact outcome of one settled round
+#[zero_copy]
+pub struct RoundSummary {
+    pub round_id: u64,
+    pub start_price: u64,
+    pub end_price: u64,
+    pub up_pool: u64,
+    pub down_pool: u64,
+    pub settled_at: i64,
+    /// WinnerSide as u8 (0 None, 1 Up, 2 Down, 3 Draw)
+    pub winner: u8,
+    pub _padding: [u8; 7],
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct UserBalance {
+    pub owner: Pubkey,
+    pub balance: u64,
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+    pub total_winnings: u64,
+    pub bump: u8,
+    /// Layout version (USER_BALANCE_VERSION); every field before it is the original layout,
+    /// new fields go after it, see migrate_user_balance
+    pub version: u8,
+    /// Opted out of being copied by other players
+    pub copy_opt_out: bool,
+    /// Compliance registry entry, set by the authority
+    pub eligibility: EligibilityStatus,
+    /// Authority-set cap on a single position for this user (0 = none)
+    pub max_bet_override: u64,
+    /// Player-set limits from set_personal_limits, in lamports
+    pub personal_limits: PersonalLimits,
+}
+
+/// GameState as first deployed (v0), decoded by hand in migrate_game_state
+#[derive(AnchorDeserialize)]
+pub struct GameStateV0 {
+    pub authority: Pubkey,
+    pub pending_authority: Option<Pubkey>,
+    pub price_feed_id: [u8; 32],
+    pub current_round: u64,
+    pub total_volume: u64,
+    pub total_fees_collected: u64,
+    pub is_paused: bool,
+    pub bump: u8,
+}
+
+/// UserBalance as first deployed (v0), decoded by hand in migrate_user_balance
+#[derive(AnchorDeserialize)]
+pub struct UserBalanceV0 {
+    pub owner: Pubkey,
+    pub balance: u64,
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+    pub total_winnings: u64,
+    pub bump: u8,
+}
+
+/// Per-user, per-mint token balance (the token counterpart of UserBalance)
+#[account]
+#[derive(InitSpace)]
+pub struct TokenBalance {
+    /// Layout version (ACCOUNT_VERSION)
+    pub version: u8,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub balance: u64,
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+    pub total_winnings: u64,
+    /// Authority-set cap on a single token position for this user (0 = none)
+    pub max_bet_override: u64,
+    /// Player-set limits from set_token_personal_limits, in mint base units
+    pub personal_limits: PersonalLimits,
+    pub bump: u8,
+}
+
+/// A bettor's own responsible-gambling limits, in the denomination of the balance holding them
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, InitSpace)]
+pub struct PersonalLimits {
+    /// Cap on a single position (0 = none)
+    pub max_bet: u64,
+    /// Cap on stakes per UTC day (0 = none)
+    pub max_daily_volume: u64,
+    /// Looser limits queued by the setter, applied at pending_at
+    pub pending_max_bet: u64,
+    pub pending_max_daily_volume: u64,
+    /// When the queued limits take effect (0 = nothing queued)
+    pub pending_at: i64,
+    /// Staked during volume_day
+    pub daily_volume: u64,
+    /// Unix day (timestamp / SECONDS_PER_DAY) daily_volume belongs to
+    pub volume_day: i64,
+}
+
+impl PersonalLimits {
+    /// Whether any limit is in force or queued
+    pub fn is_set(&self) -> bool {
+        self.max_bet != 0 || self.max_daily_volume != 0 || self.pending_at != 0
+    }
+}
+
+/// Token stakes for one round, settled on the round's price move
+#[account]
+#[derive(InitSpace)]
+pub struct TokenPool {
+    /// Layout version (ACCOUNT_VERSION)
+    pub version: u8,
+    pub round_id: u64,
+    pub mint: Pubkey,
+    pub up_pool: u64,
+    pub down_pool: u64,
+    pub total_pool: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct TokenPosition {
+    /// Layout version (ACCOUNT_VERSION)
+    pub version: u8,
+    pub player: Pubkey,
+    pub round_id: u64,
+    pub mint: Pubkey,
+    pub side: BetSide,
+    pub amount: u64,
+    pub claimed: bool,
+    pub bump: u8,
+}
+
+/// One wallet's stake across all of its positions in a round
+/// Seeded by [b"player_round", market, round_id, player]; the per-player cap and the
+/// unique bettor counts are per wallet, so they are tracked here rather than per position
+#[account]
+#[derive(InitSpace)]
+pub struct PlayerRound {
+    /// Layout version (ACCOUNT_VERSION)
+    pub version: u8,
+    pub player: Pubkey,
+    pub round_id: u64,
+    /// Revealed stake on the UP side
+    pub up_stake: u64,
+    /// Revealed stake on the DOWN side
+    pub down_stake: u64,
+    /// Committed stake whose side has not been revealed yet
+    pub hidden_stake: u64,
+    /// Stake mirrored by execute_copy_bet, held to the follower's CopyConfig max_bet
+    pub copied_stake: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct PlayerPosition {
+    /// Layout version (ACCOUNT_VERSION)
+    pub version: u8,
+    /// Current owner; claims pay this player's balance
+    pub player: Pubkey,
+    /// Wallet that placed the bet; seeds the PDA and never changes
+    pub bettor: Pubkey,
+    pub round_id: u64,
+    /// Bettor-chosen slot in the PDA seeds, so one wallet can hold several positions per round
+    pub index: u8,
+    pub side: BetSide,
+    pub amount: u64,
+    /// When the stake was placed (stake-weighted across increase_bet top-ups)
+    pub bet_timestamp: i64,
+    /// sha256(side || salt || owner) for commit-reveal bets, None for plain bets
+    pub commitment: Option<[u8; 32]>,
+    /// False only for committed bets whose side has not been revealed yet
+    pub revealed: bool,
+    pub claimed: bool,
+    pub bump: u8,
+    /// Mirrored by execute_copy_bet at least once; such a position can no longer be cancelled
+    pub copied: bool,
+}
+
+/// Progressive jackpot grown from claim fees and won by a triggering round
+#[account]
+#[derive(InitSpace)]
+pub struct Jackpot {
+    /// Layout version (ACCOUNT_VERSION)
+    pub version: u8,
+    /// Current pot, carved out of protocol fees
+    pub balance: u64,
+    /// Share of each claim fee added to the pot, in basis points (<= MAX_JACKPOT_CONTRIBUTION_BPS)
+    pub contribution_bps: u64,
+    /// Price move, in bps of the start price, that wins the pot (0 = disabled)
+    pub trigger_move_bps: u64,
+    /// Every Nth round of a market wins the pot (0 = disabled)
+    pub trigger_every_n_rounds: u64,
+    pub total_contributed: u64,
+    pub total_paid: u64,
+    pub bump: u8,
 }
 
+/// A market's fixed-odds bankroll: LP shares of the SOL in its house_vault PDA, which takes the
+/// other side of every bet on the market's fixed-odds rounds
 #[account]
 #[derive(InitSpace)]
-pub struct BettingPool {
-    pub round_id: u64,
-    pub up_pool: u64,
-    pub down_pool: u64,
-    pub total_pool: u64,
+pub struct House {
+    /// Layout version (ACCOUNT_VERSION)
+    pub version: u8,
+    pub market: Pubkey,
+    /// LP shares outstanding
+    pub total_shares: u64,
+    /// Worst-case payouts held back for the market's fixed-odds rounds (see BettingPool::house_reserved)
+    pub reserved: u64,
     pub bump: u8,
 }
 
+/// A liquidity provider's shares of one market's house
 #[account]
 #[derive(InitSpace)]
-pub struct UserBalance {
+pub struct LpPosition {
+    /// Layout version (ACCOUNT_VERSION)
+    pub version: u8,
     pub owner: Pubkey,
-    pub balance: u64,
-    pub total_deposited: u64,
-    pub total_withdrawn: u64,
-    pub total_winnings: u64,
+    pub market: Pubkey,
+    pub shares: u64,
     pub bump: u8,
 }
 
+/// Lifetime betting record of one player, for on-chain leaderboards
+/// Created on first use by place_bet, commit_bet, execute_copy_bet and the claim and close paths
 #[account]
 #[derive(InitSpace)]
-pub struct PlayerPosition {
-    pub player: Pubkey,
-    pub round_id: u64,
-    pub side: BetSide,
+pub struct UserStats {
+    /// Layout version (ACCOUNT_VERSION)
+    pub version: u8,
+    pub owner: Pubkey,
+    /// Positions placed (each position in a round counts once)
+    pub rounds_played: u64,
+    /// Claimed positions that won
+    pub rounds_won: u64,
+    /// Claimed or closed positions that lost
+    pub rounds_lost: u64,
+    /// Wins in consecutive rounds of streak_market, ending at streak_round_id
+    pub current_streak: u32,
+    pub best_streak: u32,
+    /// Lifetime lamports staked
+    pub total_volume: u64,
+    pub bump: u8,
+    /// Market the current streak runs in
+    pub streak_market: Pubkey,
+    /// Last round counted toward the current streak (a win, or the loss that ended it)
+    pub streak_round_id: u64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct CopyConfig {
+    /// Layout version (ACCOUNT_VERSION)
+    pub version: u8,
+    /// Player whose balance funds the copied bets
+    pub follower: Pubkey,
+    /// Player being copied
+    pub leader: Pubkey,
+    /// Maximum stake per copied bet
+    pub max_bet: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Parlay {
+    /// Layout version (ACCOUNT_VERSION)
+    pub version: u8,
+    pub owner: Pubkey,
+    /// Market whose rounds are the legs
+    pub market: Pubkey,
+    /// First round covered; leg i is round start_round + i
+    pub start_round: u64,
+    #[max_len(MAX_PARLAY_LEGS)]
+    pub picks: Vec<BetSide>,
     pub amount: u64,
-    pub claimed: bool,
+    /// Payout if every leg wins (reserved against the parlay vault)
+    pub max_payout: u64,
+    pub settled: bool,
+    /// When the parlay was placed; starts the PARLAY_EXPIRY_SECONDS refund window
+    pub placed_at: i64,
     pub bump: u8,
 }
 
 #[account]
 #[derive(InitSpace)]
 pub struct SessionToken {
+    /// Layout version (ACCOUNT_VERSION)
+    pub version: u8,
     /// The wallet that created this session
     pub authority: Pubkey,
     /// The temporary signer authorized by this session
     pub session_signer: Pubkey,
     /// Unix timestamp when this session expires
     pub valid_until: i64,
+    /// Largest stake the session key may place in one instruction (0 = no cap)
+    pub max_bet_per_tx: u64,
+    /// ENTRYPOINT_* bits the session key may call
+    pub allowed_instructions: u64,
+    /// Total lamports the session key may stake over its lifetime (0 = no cap)
+    pub spend_limit: u64,
+    /// Lamports staked through this session key so far
+    pub spent: u64,
+    /// Owner's SessionRegistry generation at creation; stale after revoke_all_sessions
+    pub generation: u64,
+    /// Timestamp of the last session-signed bet
+    pub last_bet_ts: i64,
+    /// Session-signed bets in last_bet_ts's clock minute
+    pub bets_in_window: u32,
+    /// Total game_state.token_mint base units the session key may stake (0 = no token bets)
+    pub token_spend_limit: u64,
+    /// Token base units staked through this session key so far
+    pub token_spent: u64,
     /// PDA bump
     pub bump: u8,
 }
 
+/// Per-user list of issued session keys plus the generation that revokes them all
+#[account]
+#[derive(InitSpace)]
+pub struct SessionRegistry {
+    /// Layout version (ACCOUNT_VERSION)
+    pub version: u8,
+    pub owner: Pubkey,
+    /// Session signers issued since the last revoke_all_sessions
+    #[max_len(MAX_SESSIONS_PER_USER)]
+    pub signers: Vec<Pubkey>,
+    /// Bumped by revoke_all_sessions; sessions from an older generation are rejected
+    pub generation: u64,
+    pub bump: u8,
+}
+
 // ===================
 // Enums
 // ===================
@@ -1591,11 +10879,23 @@ pub enum BetSide {
     Down,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum SettlementMode {
+    /// Single aggregate print at lock
+    Spot,
+    /// Pyth's exponentially-weighted moving average price
+    Ema,
+    /// Average of record_price_sample cranks plus the lock reading
+    Twap,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
 pub enum RoundStatus {
     Open,
     Locked,
     Settled,
+    /// Abandoned before settlement; every position is refunded in full
+    Cancelled,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
@@ -1606,6 +10906,97 @@ pub enum WinnerSide {
     Draw,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum PriceSource {
+    /// Pyth feed (push or pull per GameState.oracle_mode)
+    Pyth,
+    /// Chainlink OCR2 data feed account
+    Chainlink,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum OracleMode {
+    /// Legacy push-oracle price accounts
+    Push,
+    /// Pyth receiver PriceUpdateV2 accounts
+    Pull,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum EligibilityMode {
+    Open,
+    Allowlist,
+    Blocklist,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum EligibilityStatus {
+    Unset,
+    Allowed,
+    Blocked,
+}
+
+/// Lifetime lamport counters for one GameType, for on-chain P&L breakdowns
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, InitSpace)]
+pub struct GameTypeStats {
+    /// Lamports wagered (settled Oracle pools, wagers locked by other games)
+    pub volume: u64,
+    /// Gross fees charged, before jackpot, referral and insurance shares
+    pub fees: u64,
+    /// Lamports paid back into user balances
+    pub payouts: u64,
+}
+
+/// One recipient's share of withdrawn fees
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct FeeSplitEntry {
+    pub recipient: Pubkey,
+    /// Share of each withdrawal, in basis points (all entries total BPS_DENOMINATOR)
+    pub bps: u64,
+}
+
+/// A sensitive admin change queued behind the timelock
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum AdminChange {
+    FeeBps { fee_bps: u64 },
+    CancelFeeBps { cancel_fee_bps: u64 },
+    BetLimits { min_bet: u64, max_bet: u64 },
+    SwitchboardFeed { market: Pubkey, switchboard_feed: Pubkey },
+    Timelock { seconds: i64 },
+    AddGameProgram { program_id: Pubkey },
+    ActivateMarket { market: Pubkey },
+    PoolCaps { max_total_pool: u64, max_side_pool: u64, max_player_stake: u64 },
+    CircuitBreaker { max_price_move_bps: u64 },
+    MaxConfBps { max_conf_bps: u64 },
+    MinPoolForSettlement { min_pool: u64 },
+    OracleMode { mode: OracleMode },
+    RoundDurationBounds { min_duration: i64, max_duration: i64 },
+    Operator { operator: Pubkey },
+    SessionRateLimit { session_bets_per_minute: u64 },
+    StreakBonus { streak_bonus_bps_per_win: u64, max_streak_bonus_bps: u64 },
+    EarlyBirdBonus { max_early_bird_bonus_bps: u64 },
+    JackpotConfig { contribution_bps: u64, trigger_move_bps: u64, trigger_every_n_rounds: u64 },
+    TokenBetLimits { limits: TokenBetLimits },
+    MarketCadence { market: Pubkey, round_duration: i64, lock_buffer: i64 },
+    MarketFixedOdds { market: Pubkey, fixed_odds_bps: u64, max_round_exposure: u64 },
+}
+
+/// Stake limits and pool caps for token bets, in token_mint base units (0 = no cap,
+/// except min_bet which is always enforced)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, InitSpace)]
+pub struct TokenBetLimits {
+    /// Smallest token stake accepted
+    pub min_bet: u64,
+    /// Largest single token stake accepted
+    pub max_bet: u64,
+    /// Max token pool per round
+    pub max_total_pool: u64,
+    /// Max token pool per side per round
+    pub max_side_pool: u64,
+    /// Max token stake per wallet per round
+    pub max_player_stake: u64,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
 pub enum GameType {
     Oracle,
@@ -1614,6 +11005,21 @@ pub enum GameType {
     Spectator,
 }
 
+// ===================
+// Return Types
+// ===================
+
+/// Result of a claim computation, returned by preview_payout
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct PayoutPreview {
+    /// Amount credited to the owner's balance
+    pub payout: u64,
+    /// Amount kept by the protocol
+    pub fee: u64,
+    /// Whether the payout counts as winnings (false for refunds)
+    pub won: bool,
+}
+
 // ===================
 // Events
 // ===================
@@ -1627,6 +11033,27 @@ pub struct WinningsCredited {
     pub game_id: [u8; 32],
 }
 
+/// Emitted when an account is grown to the current layout
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+/// Emitted when an admin change is queued behind the timelock
+#[event]
+pub struct AdminChangeProposed {
+    pub change: AdminChange,
+    pub execute_after: i64,
+}
+
+/// Emitted when a queued admin change is applied
+#[event]
+pub struct AdminChangeExecuted {
+    pub change: AdminChange,
+}
+
 /// Emitted when authority transfer is proposed
 #[event]
 pub struct AuthorityTransferProposed {
@@ -1649,6 +11076,21 @@ pub struct FeesWithdrawn {
     pub remaining_fees: u64,
 }
 
+/// Emitted when the authority configures or stops the fee stream
+#[event]
+pub struct FeeStreamConfigured {
+    pub recipient: Pubkey,
+    pub rate_per_second: u64,
+}
+
+/// Emitted when the fee stream is cranked
+#[event]
+pub struct FeesStreamed {
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub remaining_fees: u64,
+}
+
 /// Emitted when a bet is placed
 #[event]
 pub struct BetPlaced {
@@ -1659,18 +11101,213 @@ pub struct BetPlaced {
     pub timestamp: i64,
 }
 
+/// Emitted when a player adds stake to an existing position
+#[event]
+pub struct BetIncreased {
+    pub user: Pubkey,
+    pub round_id: u64,
+    pub side: BetSide,
+    /// Stake added by this call
+    pub amount: u64,
+    /// Position stake after the increase
+    pub total: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a bet is cancelled before lock
+#[event]
+pub struct BetCancelled {
+    pub user: Pubkey,
+    pub round_id: u64,
+    pub side: BetSide,
+    /// Stake returned to the balance
+    pub refund: u64,
+    /// Cancellation fee kept as platform fees
+    pub fee: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a live position changes owner
+#[event]
+pub struct PositionTransferred {
+    pub round_id: u64,
+    pub bettor: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+}
+
+/// Emitted when a hidden bet is committed (side revealed later via BetPlaced)
+#[event]
+pub struct BetCommitted {
+    pub user: Pubkey,
+    pub round_id: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a player changes their own betting limits
+#[event]
+pub struct PersonalLimitsUpdated {
+    pub user: Pubkey,
+    pub max_bet: u64,
+    pub max_daily_volume: u64,
+    /// When the new limits apply (later than now when they loosen)
+    pub effective_at: i64,
+}
+
+/// Emitted when a player changes their own token betting limits
+#[event]
+pub struct TokenPersonalLimitsUpdated {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub max_bet: u64,
+    pub max_daily_volume: u64,
+    /// When the new limits apply (later than now when they loosen)
+    pub effective_at: i64,
+}
+
+/// Emitted when a follower starts copying a leader
+#[event]
+pub struct CopyConfigCreated {
+    pub follower: Pubkey,
+    pub leader: Pubkey,
+    pub max_bet: u64,
+}
+
+/// Emitted when a leader's bet is mirrored for a follower
+#[event]
+pub struct CopyBetExecuted {
+    pub leader: Pubkey,
+    pub follower: Pubkey,
+    pub round_id: u64,
+    pub side: BetSide,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a parlay is placed
+#[event]
+pub struct ParlayPlaced {
+    pub user: Pubkey,
+    pub market: Pubkey,
+    pub start_round: u64,
+    pub legs: u8,
+    pub amount: u64,
+    pub max_payout: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a parlay is settled
+#[event]
+pub struct ParlaySettled {
+    pub user: Pubkey,
+    pub start_round: u64,
+    pub won: bool,
+    pub payout: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an expired parlay with a missing leg round is refunded
+#[event]
+pub struct ParlayRefunded {
+    pub user: Pubkey,
+    pub start_round: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a round is cancelled and its stakes become refundable
+#[event]
+pub struct RoundCancelled {
+    pub market: Pubkey,
+    pub round_id: u64,
+    pub cancelled_by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when SOL is added to a market's house vault
+#[event]
+pub struct LiquidityDeposited {
+    pub market: Pubkey,
+    pub provider: Pubkey,
+    pub amount: u64,
+    pub shares: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when house shares are burned for SOL
+#[event]
+pub struct LiquidityWithdrawn {
+    pub market: Pubkey,
+    pub provider: Pubkey,
+    pub amount: u64,
+    pub shares: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a finished fixed-odds round hands liquidity back to its house
+#[event]
+pub struct HouseReserveReleased {
+    pub market: Pubkey,
+    pub round_id: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a settling round wins the progressive jackpot
+#[event]
+pub struct JackpotTriggered {
+    pub market: Pubkey,
+    pub round_id: u64,
+    pub amount: u64,
+    pub winner: WinnerSide,
+    pub timestamp: i64,
+}
+
 /// Emitted when a round is settled
 #[event]
 pub struct RoundSettled {
+    pub market: Pubkey,
     pub round_id: u64,
     pub start_price: u64,
     pub end_price: u64,
     pub winner: WinnerSide,
     pub up_pool: u64,
     pub down_pool: u64,
+    pub up_bettors: u32,
+    pub down_bettors: u32,
+    pub timestamp: i64,
+}
+
+/// Emitted when the crank pushes a batch of a round's payouts into balances
+#[event]
+pub struct RoundCredited {
+    pub market: Pubkey,
+    pub round_id: u64,
+    pub positions: u32,
+    pub total_paid: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a stuck round is settled through force_settle instead of settle_round
+#[event]
+pub struct RoundForceSettled {
+    pub market: Pubkey,
+    pub round_id: u64,
+    pub settled_by: Pubkey,
     pub timestamp: i64,
 }
 
+/// Emitted when a round is forced to Draw by the extreme-move circuit breaker
+#[event]
+pub struct CircuitBreakerTripped {
+    pub round_id: u64,
+    pub start_price: u64,
+    pub end_price: u64,
+    pub move_bps: u64,
+    pub max_price_move_bps: u64,
+}
+
 /// Emitted when funds are withdrawn
 #[event]
 pub struct FundsWithdrawn {
@@ -1679,6 +11316,59 @@ pub struct FundsWithdrawn {
     pub timestamp: i64,
 }
 
+/// Emitted when a player withdraws everything and closes their balance account
+#[event]
+pub struct BalanceClosed {
+    pub user: Pubkey,
+    pub withdrawn: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when funds are withdrawn to an address other than the owner wallet
+#[event]
+pub struct FundsWithdrawnTo {
+    pub user: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when tokens are deposited
+#[event]
+pub struct TokenDeposited {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub new_balance: u64,
+}
+
+/// Emitted when tokens are withdrawn
+#[event]
+pub struct TokenWithdrawn {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when a token bet is placed
+#[event]
+pub struct TokenBetPlaced {
+    pub user: Pubkey,
+    pub round_id: u64,
+    pub mint: Pubkey,
+    pub side: BetSide,
+    pub amount: u64,
+}
+
+/// Emitted when a token position is claimed
+#[event]
+pub struct TokenWinningsClaimed {
+    pub user: Pubkey,
+    pub round_id: u64,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
 /// Emitted when funds are deposited
 #[event]
 pub struct FundsDeposited {
@@ -1688,6 +11378,24 @@ pub struct FundsDeposited {
     pub timestamp: i64,
 }
 
+/// Emitted when a wallet funds another wallet's balance
+#[event]
+pub struct FundsDepositedFor {
+    pub payer: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub new_balance: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a game type's vault receives funds from the authority or the global vault
+#[event]
+pub struct GameVaultFunded {
+    pub game_type: GameType,
+    pub amount: u64,
+    pub from_global_vault: bool,
+}
+
 /// Emitted when funds are locked for a game mode
 #[event]
 pub struct FundsLocked {
@@ -1697,6 +11405,45 @@ pub struct FundsLocked {
     pub timestamp: i64,
 }
 
+/// Emitted when pool caps are updated
+#[event]
+pub struct PoolCapsUpdated {
+    pub max_total_pool: u64,
+    pub max_side_pool: u64,
+    pub max_player_stake: u64,
+}
+
+/// Emitted when a user's compliance eligibility changes
+#[event]
+pub struct EligibilityUpdated {
+    pub user: Pubkey,
+    pub status: EligibilityStatus,
+}
+
+/// Emitted when the authority changes a user's position cap
+#[event]
+pub struct UserMaxBetUpdated {
+    pub user: Pubkey,
+    pub max_bet_override: u64,
+}
+
+/// Emitted when the authority caps a user's token position size
+#[event]
+pub struct UserTokenMaxBetUpdated {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub max_bet_override: u64,
+}
+
+/// Emitted when a stuck position is refunded after a long pause
+#[event]
+pub struct EmergencyRefunded {
+    pub user: Pubkey,
+    pub round_id: u64,
+    pub refund: u64,
+    pub timestamp: i64,
+}
+
 /// Emitted when game is paused or unpaused
 #[event]
 pub struct GamePaused {
@@ -1773,4 +11520,148 @@ pub enum SessionBettingError {
     InvalidAuthority,
     #[msg("No pending")]
     NoPendingAuthority,
+    #[msg("Bad legs")]
+    InvalidParlayLegs,
+    #[msg("Leg mismatch")]
+    ParlayLegMismatch,
+    #[msg("Low parlay vault")]
+    InsufficientParlayLiquidity,
+    #[msg("Bad caps")]
+    InvalidPoolCaps,
+    #[msg("Pool cap")]
+    PoolCapExceeded,
+    #[msg("Stake cap")]
+    PlayerStakeCapExceeded,
+    #[msg("Not committed")]
+    NotCommitted,
+    #[msg("Revealed")]
+    AlreadyRevealed,
+    #[msg("Reveal early")]
+    RevealTooEarly,
+    #[msg("Reveal closed")]
+    RevealWindowClosed,
+    #[msg("Bad reveal")]
+    InvalidReveal,
+    #[msg("Bad leader")]
+    InvalidCopyLeader,
+    #[msg("Not copyable")]
+    NotCopyable,
+    #[msg("Settled")]
+    RoundAlreadySettled,
+    #[msg("Not revealed")]
+    NotRevealed,
+    #[msg("Bad transfer")]
+    InvalidPositionTransfer,
+    #[msg("Not eligible")]
+    NotEligible,
+    #[msg("Multisig required")]
+    MultisigRequired,
+    #[msg("Fee stream active")]
+    FeeStreamActive,
+    #[msg("No fee stream")]
+    FeeStreamInactive,
+    #[msg("Mint set")]
+    TokenMintAlreadySet,
+    #[msg("Bad token mint")]
+    TokenMintNotSet,
+    #[msg("Market inactive")]
+    MarketInactive,
+    #[msg("Fee too high")]
+    InvalidFee,
+    #[msg("Bad bet limits")]
+    InvalidBetLimits,
+    #[msg("Bad round duration")]
+    InvalidRoundDuration,
+    #[msg("Too early to start")]
+    TooEarlyToStart,
+    #[msg("No Switchboard feed")]
+    SwitchboardNotConfigured,
+    #[msg("Primary feed not stale")]
+    PythNotStale,
+    #[msg("Price conf too wide")]
+    PriceConfidenceTooWide,
+    #[msg("Not TWAP")]
+    NotTwapRound,
+    #[msg("Sample too early")]
+    TooEarlyToSample,
+    #[msg("Mode unsupported")]
+    SettlementModeUnsupported,
+    #[msg("Position not claimed")]
+    PositionNotClaimed,
+    #[msg("Too early to cancel")]
+    CancelTooEarly,
+    #[msg("Not paused long enough")]
+    NotPausedLongEnough,
+    #[msg("Not first deposit")]
+    NotFirstDeposit,
+    #[msg("Bad streak bonus")]
+    InvalidStreakBonus,
+    #[msg("Bad early-bird bonus")]
+    InvalidEarlyBirdBonus,
+    #[msg("Bad jackpot config")]
+    InvalidJackpotConfig,
+    #[msg("Personal limit exceeded")]
+    PersonalLimitExceeded,
+    #[msg("Outside session scope")]
+    SessionScopeExceeded,
+    #[msg("Session spend limit")]
+    SessionSpendLimitExceeded,
+    #[msg("Too many sessions")]
+    TooManySessions,
+    #[msg("Session revoked")]
+    SessionRevoked,
+    #[msg("Session rate limited")]
+    SessionRateLimited,
+    #[msg("Timelock active")]
+    TimelockActive,
+    #[msg("Bad timelock")]
+    InvalidTimelock,
+    #[msg("No pending change")]
+    NoPendingAdminChange,
+    #[msg("Timelock not elapsed")]
+    TimelockNotElapsed,
+    #[msg("Wrong market")]
+    WrongMarket,
+    #[msg("Bad fee split")]
+    InvalidFeeSplit,
+    #[msg("Fee split mismatch")]
+    FeeSplitMismatch,
+    #[msg("Vault insolvent")]
+    VaultInsolvent,
+    #[msg("Bad credit accounts")]
+    CreditAccountMismatch,
+    #[msg("Bad address")]
+    InvalidAddress,
+    #[msg("Restrictions active")]
+    BalanceRestricted,
+    #[msg("Unauthorized game program")]
+    UnauthorizedGameProgram,
+    #[msg("Too many game programs")]
+    TooManyGamePrograms,
+    #[msg("Not latest round")]
+    RoundNotLatest,
+    #[msg("Parlay not expired")]
+    ParlayNotExpired,
+    #[msg("Parlay can be settled")]
+    ParlaySettleable,
+    #[msg("Parlay leg lost")]
+    ParlayLegLost,
+    #[msg("Parlay not settled")]
+    ParlayNotSettled,
+    #[msg("Position copied")]
+    PositionCopied,
+    #[msg("Fixed-odds round")]
+    FixedOddsRound,
+    #[msg("Not a fixed-odds round")]
+    NotFixedOddsRound,
+    #[msg("Invalid fixed odds")]
+    InvalidFixedOdds,
+    #[msg("House exposure exceeded")]
+    HouseExposureExceeded,
+    #[msg("Insufficient house liquidity")]
+    HouseLiquidityInsufficient,
+    #[msg("House reserve outstanding")]
+    HouseReserveOutstanding,
+    #[msg("Claim expired")]
+    ClaimExpired,
 }
@@ -0,0 +1,1402 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use compliance::cpi::accounts::AssertEligible;
+use compliance::program::Compliance;
+use insurance::cpi::accounts::CoverShortfall;
+use insurance::program::Insurance;
+use session_betting::SessionToken;
+
+declare_id!("685gd3zcPK2W48u8D3x2fTc9pcwXZY715vfkyzzRAyAx");
+
+// ===================
+// Constants
+// ===================
+
+/// Minimum bet amount: 0.01 SOL (10,000,000 lamports)
+pub const MIN_BET: u64 = 10_000_000;
+
+/// Maximum bet amount: 100 SOL
+pub const MAX_BET: u64 = 100_000_000_000;
+
+/// Platform fee: 5% (500 basis points)
+pub const PLATFORM_FEE_BPS: u64 = 500;
+
+/// Basis points denominator
+pub use bet_math::BPS_DENOMINATOR;
+
+/// Maximum length of a market description
+pub const MAX_DESCRIPTION_LEN: usize = 128;
+
+/// Time after an attestation during which bettors can dispute it (in seconds)
+pub const DISPUTE_WINDOW_SECONDS: i64 = 60 * 60;
+
+/// Time after close_time the streamer has to attest before the market can be voided (in seconds)
+pub const ATTESTATION_WINDOW_SECONDS: i64 = 24 * 60 * 60;
+
+/// Bond a disputer posts, refunded only if the attestation is overturned: 0.1 SOL
+pub const DISPUTE_BOND: u64 = 100_000_000;
+
+/// Registry deprecation bits for this program's entrypoints (see registry::ProgramEntry)
+pub const ENTRYPOINT_PLACE_BET: u64 = 1 << 0;
+
+// ===================
+// Program
+// ===================
+
+#[program]
+pub mod spectator {
+    use super::*;
+
+    /// Initialize the spectator program config
+    pub fn initialize_spectator(ctx: Context<InitializeSpectator>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.authority = ctx.accounts.authority.key();
+        config.market_count = 0;
+        config.require_multisig = false;
+        config.bump = ctx.bumps.config;
+        Ok(())
+    }
+
+    /// Require the Squads multisig path for fee withdrawals
+    /// AUTHORITY ONLY - once enabled, only the multisig can change it again
+    pub fn set_require_multisig(ctx: Context<SetRequireMultisig>, require_multisig: bool) -> Result<()> {
+        // SECURITY: Treasury paths must come from the Squads vault when required
+        require!(
+            admin_guard::admin_path_ok(
+                &ctx.accounts.authority.key(),
+                ctx.accounts.admin_multisig.as_deref(),
+                ctx.accounts.config.require_multisig,
+            ),
+            SpectatorError::MultisigRequired
+        );
+
+        ctx.accounts.config.require_multisig = require_multisig;
+        Ok(())
+    }
+
+    // =====================
+    // Streamer Instructions
+    // =====================
+
+    /// Register (or re-enable) a streamer allowed to open markets
+    /// AUTHORITY ONLY
+    pub fn register_streamer(ctx: Context<RegisterStreamer>, wallet: Pubkey) -> Result<()> {
+        let streamer = &mut ctx.accounts.streamer;
+        streamer.wallet = wallet;
+        streamer.active = true;
+        streamer.bump = ctx.bumps.streamer;
+
+        emit_cpi!(StreamerRegistered { wallet });
+        Ok(())
+    }
+
+    /// Disable a streamer - existing markets still resolve normally
+    /// AUTHORITY ONLY
+    pub fn deactivate_streamer(ctx: Context<DeactivateStreamer>) -> Result<()> {
+        ctx.accounts.streamer.active = false;
+        Ok(())
+    }
+
+    /// Open a binary market on an outcome of the streamer's stream
+    /// REQUIRES streamer wallet signature - streamer pays rent and seeds the market vault
+    pub fn open_market(
+        ctx: Context<OpenMarket>,
+        description: String,
+        close_time: i64,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+
+        // SECURITY: Only active streamers can open markets
+        require!(ctx.accounts.streamer.active, SpectatorError::StreamerInactive);
+        require!(
+            !description.is_empty() && description.len() <= MAX_DESCRIPTION_LEN,
+            SpectatorError::InvalidDescription
+        );
+        require!(close_time > clock.unix_timestamp, SpectatorError::InvalidCloseTime);
+
+        // Keep the market vault rent-exempt so the last claims and fee withdrawal never strand it
+        let rent = Rent::get()?.minimum_balance(0);
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.wallet.to_account_info(),
+                to: ctx.accounts.market_vault.to_account_info(),
+            },
+        );
+        transfer(cpi_context, rent)?;
+
+        let config = &mut ctx.accounts.config;
+        let market = &mut ctx.accounts.market;
+        market.market_id = config.market_count;
+        market.streamer = ctx.accounts.wallet.key();
+        market.description = description;
+        market.close_time = close_time;
+        market.yes_pool = 0;
+        market.no_pool = 0;
+        market.outcome = MarketOutcome::Pending;
+        market.status = MarketStatus::Open;
+        market.dispute_deadline = 0;
+        market.disputer = None;
+        market.fees_collected = 0;
+        market.fees_withdrawn = 0;
+        market.bump = ctx.bumps.market;
+
+        let odds = &mut ctx.accounts.odds;
+        odds.market = market.key();
+        odds.bump = ctx.bumps.odds;
+        update_odds(odds, market, false, clock.unix_timestamp)?;
+
+        config.market_count = config.market_count
+            .checked_add(1)
+            .ok_or(SpectatorError::MathOverflow)?;
+
+        emit_cpi!(MarketOpened {
+            market_id: market.market_id,
+            streamer: market.streamer,
+            close_time,
+        });
+        Ok(())
+    }
+
+    /// Attest the market outcome and open the dispute window
+    /// REQUIRES streamer wallet signature
+    pub fn attest_outcome(ctx: Context<AttestOutcome>, outcome: MarketOutcome) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let clock = Clock::get()?;
+
+        require!(market.status == MarketStatus::Open, SpectatorError::MarketNotOpen);
+        require!(clock.unix_timestamp >= market.close_time, SpectatorError::MarketNotClosed);
+        require!(outcome != MarketOutcome::Pending, SpectatorError::InvalidOutcome);
+
+        market.outcome = outcome;
+        market.status = MarketStatus::Attested;
+        market.dispute_deadline = clock.unix_timestamp
+            .checked_add(DISPUTE_WINDOW_SECONDS)
+            .ok_or(SpectatorError::MathOverflow)?;
+
+        // Freeze the final odds now that betting has closed
+        update_odds(&mut ctx.accounts.odds, market, true, clock.unix_timestamp)?;
+
+        emit_cpi!(OutcomeAttested {
+            market_id: market.market_id,
+            outcome,
+            dispute_deadline: market.dispute_deadline,
+        });
+        Ok(())
+    }
+
+    /// Void a market the streamer never attested - every bettor is refunded
+    /// Permissionless - anyone can call after the attestation window
+    pub fn void_unattested_market(ctx: Context<VoidUnattestedMarket>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let clock = Clock::get()?;
+
+        require!(market.status == MarketStatus::Open, SpectatorError::MarketNotOpen);
+
+        let attestation_deadline = market.close_time
+            .checked_add(ATTESTATION_WINDOW_SECONDS)
+            .ok_or(SpectatorError::MathOverflow)?;
+        require!(
+            clock.unix_timestamp >= attestation_deadline,
+            SpectatorError::TooEarly
+        );
+
+        market.outcome = MarketOutcome::Void;
+        market.status = MarketStatus::Resolved;
+
+        emit_cpi!(MarketResolved {
+            market_id: market.market_id,
+            outcome: MarketOutcome::Void,
+        });
+        Ok(())
+    }
+
+    // =====================
+    // Dispute Instructions
+    // =====================
+
+    /// Dispute an attested outcome by posting a bond
+    /// REQUIRES wallet signature - only bettors in the market can dispute
+    pub fn dispute_outcome(ctx: Context<DisputeOutcome>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let clock = Clock::get()?;
+
+        require!(market.status == MarketStatus::Attested, SpectatorError::MarketNotAttested);
+        require!(
+            clock.unix_timestamp < market.dispute_deadline,
+            SpectatorError::DisputeWindowClosed
+        );
+
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.disputer.to_account_info(),
+                to: ctx.accounts.market_vault.to_account_info(),
+            },
+        );
+        transfer(cpi_context, DISPUTE_BOND)?;
+
+        market.status = MarketStatus::Disputed;
+        market.disputer = Some(ctx.accounts.disputer.key());
+
+        emit_cpi!(OutcomeDisputed {
+            market_id: market.market_id,
+            disputer: ctx.accounts.disputer.key(),
+        });
+        Ok(())
+    }
+
+    /// Resolve a disputed market with the final outcome
+    /// AUTHORITY ONLY - bond is refunded if the attestation is overturned, otherwise kept as a fee
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, outcome: MarketOutcome) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        require!(market.status == MarketStatus::Disputed, SpectatorError::MarketNotDisputed);
+        require!(outcome != MarketOutcome::Pending, SpectatorError::InvalidOutcome);
+
+        let overturned = outcome != market.outcome;
+        market.outcome = outcome;
+        market.status = MarketStatus::Resolved;
+
+        if overturned {
+            let market_key = market.key();
+            let seeds: &[&[u8]] = &[b"market_vault", market_key.as_ref(), &[ctx.bumps.market_vault]];
+            let signer_seeds = &[seeds];
+
+            let cpi_context = CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.market_vault.to_account_info(),
+                    to: ctx.accounts.disputer.to_account_info(),
+                },
+                signer_seeds,
+            );
+            transfer(cpi_context, DISPUTE_BOND)?;
+        } else {
+            market.fees_collected = market.fees_collected
+                .checked_add(DISPUTE_BOND)
+                .ok_or(SpectatorError::MathOverflow)?;
+        }
+
+        emit_cpi!(MarketResolved {
+            market_id: market.market_id,
+            outcome,
+        });
+        Ok(())
+    }
+
+    /// Finalize an attested market once the dispute window has passed
+    /// Permissionless - anyone can call
+    pub fn finalize_market(ctx: Context<FinalizeMarket>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let clock = Clock::get()?;
+
+        require!(market.status == MarketStatus::Attested, SpectatorError::MarketNotAttested);
+        require!(
+            clock.unix_timestamp >= market.dispute_deadline,
+            SpectatorError::TooEarly
+        );
+
+        market.status = MarketStatus::Resolved;
+
+        emit_cpi!(MarketResolved {
+            market_id: market.market_id,
+            outcome: market.outcome,
+        });
+        Ok(())
+    }
+
+    // =====================
+    // Viewer Balance Instructions
+    // =====================
+
+    /// Deposit SOL into the viewer's balance
+    /// REQUIRES wallet signature
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        require!(amount >= MIN_BET, SpectatorError::AmountTooSmall);
+
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        );
+        transfer(cpi_context, amount)?;
+
+        let viewer_balance = &mut ctx.accounts.viewer_balance;
+        viewer_balance.owner = ctx.accounts.user.key();
+        viewer_balance.balance = viewer_balance.balance
+            .checked_add(amount)
+            .ok_or(SpectatorError::MathOverflow)?;
+        viewer_balance.bump = ctx.bumps.viewer_balance;
+        Ok(())
+    }
+
+    /// Withdraw SOL from the viewer's balance
+    /// CRITICAL SECURITY: REQUIRES wallet signature - NEVER session key
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let viewer_balance = &mut ctx.accounts.viewer_balance;
+
+        require!(
+            viewer_balance.balance >= amount,
+            SpectatorError::InsufficientBalance
+        );
+
+        // SECURITY: Update state BEFORE transfer
+        viewer_balance.balance = viewer_balance.balance
+            .checked_sub(amount)
+            .ok_or(SpectatorError::MathOverflow)?;
+
+        let user_key = ctx.accounts.user.key();
+        let seeds: &[&[u8]] = &[b"viewer_vault", user_key.as_ref(), &[ctx.bumps.vault]];
+        let signer_seeds = &[seeds];
+
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.user.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(cpi_context, amount)?;
+        Ok(())
+    }
+
+    // =====================
+    // Betting Instructions
+    // =====================
+
+    /// Place a bet on an open market
+    /// Can be signed by wallet OR a session_betting session key
+    pub fn place_bet(ctx: Context<PlaceBet>, side: Side, amount: u64) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let viewer_balance = &mut ctx.accounts.viewer_balance;
+        let clock = Clock::get()?;
+
+        // SECURITY: Verify session or wallet authority
+        verify_session_or_authority(
+            &ctx.accounts.session_token,
+            &ctx.accounts.signer,
+            &viewer_balance.owner,
+        )?;
+
+        require!(market.status == MarketStatus::Open, SpectatorError::MarketNotOpen);
+        require!(clock.unix_timestamp < market.close_time, SpectatorError::MarketClosed);
+
+        // SECURITY: Streamers decide the outcome, so they can't bet on it
+        require!(
+            viewer_balance.owner != market.streamer,
+            SpectatorError::StreamerCannotBet
+        );
+
+        // SECURITY: Shared compliance policy (bet paths only)
+        let cpi_context = CpiContext::new(
+            ctx.accounts.compliance_program.to_account_info(),
+            AssertEligible {
+                config: ctx.accounts.compliance_config.to_account_info(),
+                user_compliance: ctx.accounts.user_compliance.to_account_info(),
+            },
+        );
+        compliance::cpi::assert_eligible(cpi_context, viewer_balance.owner)?;
+
+        // Refuse entrypoints deprecated in the program registry
+        registry::require_not_deprecated(&ctx.accounts.program_entry, ENTRYPOINT_PLACE_BET)?;
+
+        require!(amount >= MIN_BET, SpectatorError::AmountTooSmall);
+        require!(amount <= MAX_BET, SpectatorError::AmountTooLarge);
+        require!(
+            viewer_balance.balance >= amount,
+            SpectatorError::InsufficientBalance
+        );
+
+        // SECURITY: Update state BEFORE transfer
+        viewer_balance.balance = viewer_balance.balance
+            .checked_sub(amount)
+            .ok_or(SpectatorError::MathOverflow)?;
+
+        match side {
+            Side::Yes => {
+                market.yes_pool = market.yes_pool
+                    .checked_add(amount)
+                    .ok_or(SpectatorError::MathOverflow)?;
+            }
+            Side::No => {
+                market.no_pool = market.no_pool
+                    .checked_add(amount)
+                    .ok_or(SpectatorError::MathOverflow)?;
+            }
+        }
+
+        // Stakes move into the market vault so winners can be paid from it
+        let owner_key = viewer_balance.owner;
+        let seeds: &[&[u8]] = &[b"viewer_vault", owner_key.as_ref(), &[ctx.bumps.vault]];
+        let signer_seeds = &[seeds];
+
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.market_vault.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(cpi_context, amount)?;
+
+        update_odds(&mut ctx.accounts.odds, market, false, clock.unix_timestamp)?;
+
+        let position = &mut ctx.accounts.position;
+        position.market = market.key();
+        position.owner = owner_key;
+        position.side = side;
+        position.amount = amount;
+        position.claimed = false;
+        position.bump = ctx.bumps.position;
+
+        emit_cpi!(SpectatorBetPlaced {
+            market_id: market.market_id,
+            owner: owner_key,
+            side,
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Claim winnings (or a refund on Void) into the viewer's balance
+    /// Permissionless - funds can only go to the position owner's balance
+    pub fn claim_winnings(ctx: Context<ClaimWinnings>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let position = &mut ctx.accounts.position;
+
+        require!(market.status == MarketStatus::Resolved, SpectatorError::MarketNotResolved);
+        require!(!position.claimed, SpectatorError::AlreadyClaimed);
+
+        let (payout, fee) = calculate_payout(position, market)?;
+
+        // SECURITY: Mark claimed BEFORE transfer
+        position.claimed = true;
+        market.fees_collected = market.fees_collected
+            .checked_add(fee)
+            .ok_or(SpectatorError::MathOverflow)?;
+
+        if payout > 0 {
+            let viewer_balance = &mut ctx.accounts.viewer_balance;
+            viewer_balance.balance = viewer_balance.balance
+                .checked_add(payout)
+                .ok_or(SpectatorError::MathOverflow)?;
+
+            // Rounding or a bug must never leave a winner unpaid
+            cover_escrow_shortfall(
+                &ctx.accounts.insurance,
+                &ctx.bumps.insurance,
+                ctx.accounts.market_vault.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                payout,
+            )?;
+
+            let market_key = market.key();
+            let seeds: &[&[u8]] = &[b"market_vault", market_key.as_ref(), &[ctx.bumps.market_vault]];
+            let signer_seeds = &[seeds];
+
+            let cpi_context = CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.market_vault.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
+                signer_seeds,
+            );
+            transfer(cpi_context, payout)?;
+        }
+
+        emit_cpi!(SpectatorWinningsClaimed {
+            market_id: market.market_id,
+            owner: position.owner,
+            amount: payout,
+        });
+        Ok(())
+    }
+
+    /// Withdraw a market's accumulated fees to the authority
+    /// AUTHORITY ONLY
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>) -> Result<()> {
+        // SECURITY: Treasury paths must come from the Squads vault when required
+        require!(
+            admin_guard::admin_path_ok(
+                &ctx.accounts.authority.key(),
+                ctx.accounts.admin_multisig.as_deref(),
+                ctx.accounts.config.require_multisig,
+            ),
+            SpectatorError::MultisigRequired
+        );
+
+        let market = &mut ctx.accounts.market;
+
+        let amount = market.fees_collected
+            .checked_sub(market.fees_withdrawn)
+            .ok_or(SpectatorError::MathOverflow)?;
+        require!(amount > 0, SpectatorError::NoFeesToWithdraw);
+
+        market.fees_withdrawn = market.fees_collected;
+
+        let market_key = market.key();
+        let seeds: &[&[u8]] = &[b"market_vault", market_key.as_ref(), &[ctx.bumps.market_vault]];
+        let signer_seeds = &[seeds];
+
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.market_vault.to_account_info(),
+                to: ctx.accounts.authority.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(cpi_context, amount)?;
+        Ok(())
+    }
+}
+
+// ===================
+// Helper Functions
+// ===================
+
+/// Top up `escrow` from the insurance fund so it can pay `amount`
+/// No-op while the escrow already holds enough; fails if it is short and no insurance accounts were supplied
+fn cover_escrow_shortfall<'info>(
+    accounts: &InsuranceTopUpAccounts<'info>,
+    bumps: &InsuranceTopUpAccountsBumps,
+    escrow: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let shortfall = amount.saturating_sub(escrow.lamports());
+    if shortfall == 0 {
+        return Ok(());
+    }
+
+    let (
+        Some(crediter),
+        Some(config),
+        Some(insurance_vault),
+        Some(event_authority),
+        Some(insurance_program),
+    ) = (
+        &accounts.insurance_crediter,
+        &accounts.insurance_config,
+        &accounts.insurance_vault,
+        &accounts.insurance_event_authority,
+        &accounts.insurance_program,
+    )
+    else {
+        return err!(SpectatorError::EscrowShortfall);
+    };
+
+    let crediter_bump = bumps.insurance_crediter.ok_or(SpectatorError::Unauthorized)?;
+    let seeds: &[&[u8]] = &[b"insurance_crediter", &[crediter_bump]];
+    let signer_seeds = &[seeds];
+
+    let cpi_context = CpiContext::new_with_signer(
+        insurance_program.to_account_info(),
+        CoverShortfall {
+            config: config.to_account_info(),
+            insurance_vault: insurance_vault.to_account_info(),
+            escrow,
+            crediter: crediter.to_account_info(),
+            system_program,
+            event_authority: event_authority.to_account_info(),
+            program: insurance_program.to_account_info(),
+        },
+        signer_seeds,
+    );
+    insurance::cpi::cover_shortfall(cpi_context, shortfall)
+}
+
+/// Verify signer is the viewer's wallet or holds a valid session_betting session for it
+fn verify_session_or_authority(
+    session_token: &Option<Account<SessionToken>>,
+    signer: &Signer,
+    expected_authority: &Pubkey,
+) -> Result<()> {
+    if signer.key() == *expected_authority {
+        return Ok(());
+    }
+
+    match session_token {
+        Some(session) => {
+            // SECURITY: Session must be for this viewer
+            require!(
+                session.authority == *expected_authority,
+                SpectatorError::SessionAuthorityMismatch
+            );
+
+            // SECURITY: Signer must be the session signer
+            require!(
+                session.session_signer == signer.key(),
+                SpectatorError::InvalidSessionSigner
+            );
+
+            // SECURITY: Session must not be expired
+            let clock = Clock::get()?;
+            require!(
+                clock.unix_timestamp < session.valid_until,
+                SpectatorError::SessionExpired
+            );
+
+            Ok(())
+        }
+        None => Err(SpectatorError::Unauthorized.into()),
+    }
+}
+
+/// Payout and fee for a position in a resolved market
+/// Parimutuel: winners split the losing pool pro-rata, fee is taken from winnings
+/// Implied net payout per unit stake on one side, in bps (10,000 = stake back)
+/// Uses the same parimutuel and fee math as calculate_payout
+fn implied_multiplier_bps(side_pool: u64, other_pool: u64) -> Result<u64> {
+    let gross = bet_math::parimutuel_winnings(BPS_DENOMINATOR, side_pool, other_pool)
+        .ok_or(SpectatorError::MathOverflow)?;
+    let (net, _) = bet_math::split_fee(gross, PLATFORM_FEE_BPS)
+        .ok_or(SpectatorError::MathOverflow)?;
+    Ok(net)
+}
+
+/// Refresh a market's odds account from its pools
+fn update_odds(odds: &mut SpectatorOdds, market: &Market, locked: bool, now: i64) -> Result<()> {
+    odds.yes_pool = market.yes_pool;
+    odds.no_pool = market.no_pool;
+    odds.yes_multiplier_bps = implied_multiplier_bps(market.yes_pool, market.no_pool)?;
+    odds.no_multiplier_bps = implied_multiplier_bps(market.no_pool, market.yes_pool)?;
+    odds.locked = locked;
+    odds.updated_at = now;
+    Ok(())
+}
+
+fn calculate_payout(position: &SpectatorPosition, market: &Market) -> Result<(u64, u64)> {
+    let (winning_pool, losing_pool) = match (market.outcome, position.side) {
+        (MarketOutcome::Void, _) => return Ok((position.amount, 0)),
+        (MarketOutcome::Yes, Side::Yes) => (market.yes_pool, market.no_pool),
+        (MarketOutcome::No, Side::No) => (market.no_pool, market.yes_pool),
+        _ => return Ok((0, 0)),
+    };
+
+    let winnings = bet_math::parimutuel_winnings(position.amount, winning_pool, losing_pool)
+        .ok_or(SpectatorError::MathOverflow)?;
+
+    bet_math::split_fee(winnings, PLATFORM_FEE_BPS).ok_or(SpectatorError::MathOverflow.into())
+}
+
+// ===================
+// Account Structs
+// ===================
+
+#[derive(Accounts)]
+pub struct InitializeSpectator<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + SpectatorConfig::INIT_SPACE,
+        seeds = [b"spectator_config"],
+        bump
+    )]
+    pub config: Account<'info, SpectatorConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetRequireMultisig<'info> {
+    #[account(
+        mut,
+        seeds = [b"spectator_config"],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, SpectatorConfig>,
+
+    pub authority: Signer<'info>,
+
+    /// CHECK: Squads multisig whose default vault is the authority (omit for a raw-key authority)
+    /// Verified by admin_guard; required when config.require_multisig is set
+    pub admin_multisig: Option<UncheckedAccount<'info>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct RegisterStreamer<'info> {
+    #[account(
+        seeds = [b"spectator_config"],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, SpectatorConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + Streamer::INIT_SPACE,
+        seeds = [b"streamer", wallet.as_ref()],
+        bump
+    )]
+    pub streamer: Account<'info, Streamer>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DeactivateStreamer<'info> {
+    #[account(
+        seeds = [b"spectator_config"],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, SpectatorConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"streamer", streamer.wallet.as_ref()],
+        bump = streamer.bump
+    )]
+    pub streamer: Account<'info, Streamer>,
+
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct OpenMarket<'info> {
+    #[account(
+        mut,
+        seeds = [b"spectator_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, SpectatorConfig>,
+
+    #[account(
+        seeds = [b"streamer", wallet.key().as_ref()],
+        bump = streamer.bump
+    )]
+    pub streamer: Account<'info, Streamer>,
+
+    #[account(
+        init,
+        payer = wallet,
+        space = 8 + Market::INIT_SPACE,
+        seeds = [b"market", config.market_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Market vault PDA holding stakes - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump
+    )]
+    pub market_vault: SystemAccount<'info>,
+
+    #[account(
+        init,
+        payer = wallet,
+        space = 8 + SpectatorOdds::INIT_SPACE,
+        seeds = [b"spectator_odds", market.key().as_ref()],
+        bump
+    )]
+    pub odds: Account<'info, SpectatorOdds>,
+
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AttestOutcome<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        has_one = streamer
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"spectator_odds", market.key().as_ref()],
+        bump = odds.bump
+    )]
+    pub odds: Account<'info, SpectatorOdds>,
+
+    pub streamer: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct VoidUnattestedMarket<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Anyone can void an unattested market (permissionless)
+    pub caller: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DisputeOutcome<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Disputer must hold a position in this market
+    #[account(
+        seeds = [b"spectator_position", market.key().as_ref(), disputer.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, SpectatorPosition>,
+
+    /// Market vault PDA holding stakes - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump
+    )]
+    pub market_vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub disputer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(
+        seeds = [b"spectator_config"],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, SpectatorConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Market vault PDA holding stakes - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump
+    )]
+    pub market_vault: SystemAccount<'info>,
+
+    /// Disputer wallet - receives the bond back if the attestation is overturned
+    #[account(
+        mut,
+        constraint = market.disputer == Some(disputer.key()) @ SpectatorError::InvalidDisputer
+    )]
+    pub disputer: SystemAccount<'info>,
+
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FinalizeMarket<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Anyone can finalize a market (permissionless)
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + ViewerBalance::INIT_SPACE,
+        seeds = [b"viewer_balance", user.key().as_ref()],
+        bump
+    )]
+    pub viewer_balance: Account<'info, ViewerBalance>,
+
+    /// Viewer vault PDA - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"viewer_vault", user.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"viewer_balance", user.key().as_ref()],
+        bump = viewer_balance.bump,
+        constraint = viewer_balance.owner == user.key() @ SpectatorError::Unauthorized
+    )]
+    pub viewer_balance: Account<'info, ViewerBalance>,
+
+    /// Viewer vault PDA - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"viewer_vault", user.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// CRITICAL: Must be the wallet owner, NOT a session key
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PlaceBet<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Market vault PDA holding stakes - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump
+    )]
+    pub market_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"spectator_odds", market.key().as_ref()],
+        bump = odds.bump
+    )]
+    pub odds: Account<'info, SpectatorOdds>,
+
+    #[account(
+        mut,
+        seeds = [b"viewer_balance", viewer_balance.owner.as_ref()],
+        bump = viewer_balance.bump
+    )]
+    pub viewer_balance: Account<'info, ViewerBalance>,
+
+    /// Viewer vault PDA - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"viewer_vault", viewer_balance.owner.as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + SpectatorPosition::INIT_SPACE,
+        seeds = [b"spectator_position", market.key().as_ref(), viewer_balance.owner.as_ref()],
+        bump
+    )]
+    pub position: Account<'info, SpectatorPosition>,
+
+    /// session_betting session token (optional)
+    /// If provided, allows session_signer to bet on behalf of the viewer
+    #[account(
+        seeds = [b"session", viewer_balance.owner.as_ref(), signer.key().as_ref()],
+        bump = session_token.bump,
+        seeds::program = session_betting::ID
+    )]
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Shared compliance config, validated by the compliance program
+    pub compliance_config: UncheckedAccount<'info>,
+
+    /// CHECK: Viewer's compliance record (may be uninitialized), validated by the compliance program
+    pub user_compliance: UncheckedAccount<'info>,
+
+    pub compliance_program: Program<'info, Compliance>,
+
+    /// CHECK: This program's registry entry (may be unregistered), read by registry::require_not_deprecated
+    #[account(
+        seeds = [b"program_entry", crate::ID.as_ref()],
+        bump,
+        seeds::program = registry::ID
+    )]
+    pub program_entry: UncheckedAccount<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimWinnings<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Market vault PDA holding stakes - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump
+    )]
+    pub market_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"spectator_position", market.key().as_ref(), position.owner.as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, SpectatorPosition>,
+
+    #[account(
+        mut,
+        seeds = [b"viewer_balance", position.owner.as_ref()],
+        bump = viewer_balance.bump
+    )]
+    pub viewer_balance: Account<'info, ViewerBalance>,
+
+    /// Viewer vault PDA - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"viewer_vault", position.owner.as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Anyone can claim on behalf of the owner (permissionless)
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub insurance: InsuranceTopUpAccounts<'info>,
+}
+
+/// Optional accounts that let a claim draw an escrow shortfall from the insurance fund
+#[derive(Accounts)]
+pub struct InsuranceTopUpAccounts<'info> {
+    /// CHECK: Program signer for insurance cover_shortfall (optional, with the accounts below)
+    #[account(
+        seeds = [b"insurance_crediter"],
+        bump
+    )]
+    pub insurance_crediter: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Insurance config, validated by the insurance program
+    #[account(mut)]
+    pub insurance_config: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Insurance vault, validated by the insurance program
+    #[account(mut)]
+    pub insurance_vault: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Insurance's event-CPI authority PDA
+    #[account(
+        seeds = [b"__event_authority"],
+        seeds::program = insurance::ID,
+        bump
+    )]
+    pub insurance_event_authority: Option<UncheckedAccount<'info>>,
+
+    pub insurance_program: Option<Program<'info, Insurance>>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    #[account(
+        seeds = [b"spectator_config"],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, SpectatorConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Market vault PDA holding stakes - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump
+    )]
+    pub market_vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Squads multisig whose default vault is the authority (omit for a raw-key authority)
+    /// Verified by admin_guard; required when config.require_multisig is set
+    pub admin_multisig: Option<UncheckedAccount<'info>>,
+}
+
+// ===================
+// State
+// ===================
+
+#[account]
+#[derive(InitSpace)]
+pub struct SpectatorConfig {
+    pub authority: Pubkey,
+    pub market_count: u64,
+    /// Fee withdrawals must be signed by the Squads multisig vault
+    pub require_multisig: bool,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Streamer {
+    pub wallet: Pubkey,
+    /// Only active streamers can open new markets
+    pub active: bool,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Market {
+    pub market_id: u64,
+    pub streamer: Pubkey,
+    #[max_len(MAX_DESCRIPTION_LEN)]
+    pub description: String,
+    /// Betting closes at this time
+    pub close_time: i64,
+    pub yes_pool: u64,
+    pub no_pool: u64,
+    pub outcome: MarketOutcome,
+    pub status: MarketStatus,
+    /// Attestation becomes final after this time unless disputed
+    pub dispute_deadline: i64,
+    pub disputer: Option<Pubkey>,
+    /// Claim fees plus forfeited dispute bonds
+    pub fees_collected: u64,
+    pub fees_withdrawn: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ViewerBalance {
+    pub owner: Pubkey,
+    pub balance: u64,
+    pub bump: u8,
+}
+
+/// Live odds for one market, refreshed on every bet and frozen at attestation
+/// Overlays and bots read this instead of recomputing from the pools
+#[account]
+#[derive(InitSpace)]
+pub struct SpectatorOdds {
+    pub market: Pubkey,
+    pub yes_pool: u64,
+    pub no_pool: u64,
+    /// Implied net payout per unit stake on YES, in bps (10,000 = stake back)
+    pub yes_multiplier_bps: u64,
+    /// Implied net payout per unit stake on NO, in bps (10,000 = stake back)
+    pub no_multiplier_bps: u64,
+    /// True once betting has closed and the odds are final
+    pub locked: bool,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct SpectatorPosition {
+    pub market: Pubkey,
+    pub owner: Pubkey,
+    pub side: Side,
+    pub amount: u64,
+    pub claimed: bool,
+    pub bump: u8,
+}
+
+// ===================
+// Enums
+// ===================
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum Side {
+    Yes,
+    No,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum MarketOutcome {
+    Pending,
+    Yes,
+    No,
+    /// Refund every bettor
+    Void,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum MarketStatus {
+    Open,
+    Attested,
+    Disputed,
+    Resolved,
+}
+
+// ===================
+// Events
+// ===================
+
+/// Emitted when the authority registers a streamer
+#[event]
+pub struct StreamerRegistered {
+    pub wallet: Pubkey,
+}
+
+/// Emitted when a streamer opens a market
+#[event]
+pub struct MarketOpened {
+    pub market_id: u64,
+    pub streamer: Pubkey,
+    pub close_time: i64,
+}
+
+/// Emitted when a viewer bets on a market
+#[event]
+pub struct SpectatorBetPlaced {
+    pub market_id: u64,
+    pub owner: Pubkey,
+    pub side: Side,
+    pub amount: u64,
+}
+
+/// Emitted when a streamer attests an outcome
+#[event]
+pub struct OutcomeAttested {
+    pub market_id: u64,
+    pub outcome: MarketOutcome,
+    pub dispute_deadline: i64,
+}
+
+/// Emitted when a bettor disputes an attestation
+#[event]
+pub struct OutcomeDisputed {
+    pub market_id: u64,
+    pub disputer: Pubkey,
+}
+
+/// Emitted when a market's outcome becomes final
+#[event]
+pub struct MarketResolved {
+    pub market_id: u64,
+    pub outcome: MarketOutcome,
+}
+
+/// Emitted when a position is paid out
+#[event]
+pub struct SpectatorWinningsClaimed {
+    pub market_id: u64,
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+// ===================
+// Errors
+// ===================
+
+#[error_code]
+pub enum SpectatorError {
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Streamer inactive")]
+    StreamerInactive,
+    #[msg("Bad description")]
+    InvalidDescription,
+    #[msg("Bad close time")]
+    InvalidCloseTime,
+    #[msg("Bad outcome")]
+    InvalidOutcome,
+    #[msg("Market not open")]
+    MarketNotOpen,
+    #[msg("Market closed")]
+    MarketClosed,
+    #[msg("Market not closed")]
+    MarketNotClosed,
+    #[msg("Not attested")]
+    MarketNotAttested,
+    #[msg("Not disputed")]
+    MarketNotDisputed,
+    #[msg("Not resolved")]
+    MarketNotResolved,
+    #[msg("Dispute closed")]
+    DisputeWindowClosed,
+    #[msg("Bad disputer")]
+    InvalidDisputer,
+    #[msg("Too early")]
+    TooEarly,
+    #[msg("Streamer can't bet")]
+    StreamerCannotBet,
+    #[msg("Amount too small")]
+    AmountTooSmall,
+    #[msg("Amount too large")]
+    AmountTooLarge,
+    #[msg("Insufficient balance")]
+    InsufficientBalance,
+    #[msg("Already claimed")]
+    AlreadyClaimed,
+    #[msg("No fees")]
+    NoFeesToWithdraw,
+    #[msg("Session mismatch")]
+    SessionAuthorityMismatch,
+    #[msg("Bad session signer")]
+    InvalidSessionSigner,
+    #[msg("Session expired")]
+    SessionExpired,
+    #[msg("Overflow")]
+    MathOverflow,
+    #[msg("Multisig required")]
+    MultisigRequired,
+    #[msg("Escrow short")]
+    EscrowShortfall,
+}
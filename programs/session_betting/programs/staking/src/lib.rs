@@ -0,0 +1,612 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+declare_id!("FQyoPWefUjd1ydsSZjk8e5GLCSRj7yY3WYdsy3anM58j");
+
+// ===================
+// Constants
+// ===================
+
+/// Minimum stake amount: 0.01 SOL (10,000,000 lamports)
+pub const MIN_STAKE: u64 = 10_000_000;
+
+/// Fixed-point scale for fees-per-staked-lamport accounting
+pub const FEE_SHARE_PRECISION: u128 = 1_000_000_000_000;
+
+/// Bounds for epoch length (in seconds)
+pub const MIN_EPOCH_DURATION_SECONDS: i64 = 60 * 60;
+pub const MAX_EPOCH_DURATION_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+/// Upper bound for the unstake cooldown (in seconds)
+pub const MAX_COOLDOWN_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+// ===================
+// Program
+// ===================
+
+#[program]
+pub mod staking {
+    use super::*;
+
+    /// Initialize the staking pool
+    /// Seeds both vaults with their rent-exempt minimum so withdrawals never strand them
+    pub fn initialize_staking(
+        ctx: Context<InitializeStaking>,
+        epoch_duration: i64,
+        cooldown_seconds: i64,
+    ) -> Result<()> {
+        require!(
+            (MIN_EPOCH_DURATION_SECONDS..=MAX_EPOCH_DURATION_SECONDS).contains(&epoch_duration),
+            StakingError::InvalidEpochDuration
+        );
+        require!(
+            (0..=MAX_COOLDOWN_SECONDS).contains(&cooldown_seconds),
+            StakingError::InvalidCooldown
+        );
+
+        let rent = Rent::get()?.minimum_balance(0);
+        for vault in [&ctx.accounts.stake_vault, &ctx.accounts.fee_vault] {
+            let cpi_context = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: vault.to_account_info(),
+                },
+            );
+            transfer(cpi_context, rent)?;
+        }
+
+        let clock = Clock::get()?;
+        let pool = &mut ctx.accounts.pool;
+        pool.authority = ctx.accounts.authority.key();
+        pool.total_staked = 0;
+        pool.fees_per_share = 0;
+        pool.pending_fees = 0;
+        pool.total_fees_distributed = 0;
+        pool.current_epoch = 0;
+        pool.epoch_start = clock.unix_timestamp;
+        pool.epoch_duration = epoch_duration;
+        pool.cooldown_seconds = cooldown_seconds;
+        pool.bump = ctx.bumps.pool;
+        Ok(())
+    }
+
+    /// Route protocol fees into the current epoch
+    /// Anyone can deposit (game treasuries forward their withdrawn fees here)
+    pub fn deposit_fees(ctx: Context<DepositFees>, amount: u64) -> Result<()> {
+        require!(amount > 0, StakingError::AmountTooSmall);
+
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.depositor.to_account_info(),
+                to: ctx.accounts.fee_vault.to_account_info(),
+            },
+        );
+        transfer(cpi_context, amount)?;
+
+        let pool = &mut ctx.accounts.pool;
+        pool.pending_fees = pool.pending_fees
+            .checked_add(amount)
+            .ok_or(StakingError::MathOverflow)?;
+
+        emit_cpi!(FeesDeposited {
+            depositor: ctx.accounts.depositor.key(),
+            epoch: pool.current_epoch,
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Close the current epoch and distribute its fees pro-rata to active stake
+    /// Permissionless - anyone can call once the epoch has elapsed
+    pub fn advance_epoch(ctx: Context<AdvanceEpoch>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+        let clock = Clock::get()?;
+
+        let epoch_end = pool.epoch_start
+            .checked_add(pool.epoch_duration)
+            .ok_or(StakingError::MathOverflow)?;
+        require!(clock.unix_timestamp >= epoch_end, StakingError::EpochNotOver);
+
+        // With nobody staked, fees roll into the next epoch
+        let distributed = if pool.total_staked > 0 {
+            let increment = (pool.pending_fees as u128)
+                .checked_mul(FEE_SHARE_PRECISION)
+                .ok_or(StakingError::MathOverflow)?
+                .checked_div(pool.total_staked as u128)
+                .ok_or(StakingError::MathOverflow)?;
+            pool.fees_per_share = pool.fees_per_share
+                .checked_add(increment)
+                .ok_or(StakingError::MathOverflow)?;
+            pool.total_fees_distributed = pool.total_fees_distributed
+                .checked_add(pool.pending_fees)
+                .ok_or(StakingError::MathOverflow)?;
+            std::mem::take(&mut pool.pending_fees)
+        } else {
+            0
+        };
+
+        emit_cpi!(EpochAdvanced {
+            epoch: pool.current_epoch,
+            distributed,
+            total_staked: pool.total_staked,
+        });
+
+        pool.current_epoch = pool.current_epoch
+            .checked_add(1)
+            .ok_or(StakingError::MathOverflow)?;
+        pool.epoch_start = clock.unix_timestamp;
+        Ok(())
+    }
+
+    /// Stake SOL to earn a share of protocol fees from the next epoch close
+    /// REQUIRES wallet signature
+    pub fn stake(ctx: Context<Stake>, amount: u64) -> Result<()> {
+        require!(amount >= MIN_STAKE, StakingError::AmountTooSmall);
+
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.owner.to_account_info(),
+                to: ctx.accounts.stake_vault.to_account_info(),
+            },
+        );
+        transfer(cpi_context, amount)?;
+
+        let pool = &mut ctx.accounts.pool;
+        let stake_account = &mut ctx.accounts.stake_account;
+        stake_account.owner = ctx.accounts.owner.key();
+        stake_account.bump = ctx.bumps.stake_account;
+
+        settle_fees(pool, stake_account)?;
+        stake_account.amount = stake_account.amount
+            .checked_add(amount)
+            .ok_or(StakingError::MathOverflow)?;
+        pool.total_staked = pool.total_staked
+            .checked_add(amount)
+            .ok_or(StakingError::MathOverflow)?;
+        reset_fee_debt(pool, stake_account)?;
+
+        emit_cpi!(Staked {
+            owner: stake_account.owner,
+            amount,
+            total_staked: pool.total_staked,
+        });
+        Ok(())
+    }
+
+    /// Start the cooldown for part of a stake - it stops earning immediately
+    /// REQUIRES wallet signature
+    pub fn request_unstake(ctx: Context<RequestUnstake>, amount: u64) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+        let stake_account = &mut ctx.accounts.stake_account;
+        let clock = Clock::get()?;
+
+        require!(amount > 0, StakingError::AmountTooSmall);
+        require!(stake_account.amount >= amount, StakingError::InsufficientStake);
+
+        settle_fees(pool, stake_account)?;
+        stake_account.amount = stake_account.amount
+            .checked_sub(amount)
+            .ok_or(StakingError::MathOverflow)?;
+        pool.total_staked = pool.total_staked
+            .checked_sub(amount)
+            .ok_or(StakingError::MathOverflow)?;
+        reset_fee_debt(pool, stake_account)?;
+
+        // A new request restarts the cooldown for everything already unstaking
+        stake_account.unstaking_amount = stake_account.unstaking_amount
+            .checked_add(amount)
+            .ok_or(StakingError::MathOverflow)?;
+        stake_account.unlock_time = clock.unix_timestamp
+            .checked_add(pool.cooldown_seconds)
+            .ok_or(StakingError::MathOverflow)?;
+
+        emit_cpi!(UnstakeRequested {
+            owner: stake_account.owner,
+            amount,
+            unlock_time: stake_account.unlock_time,
+        });
+        Ok(())
+    }
+
+    /// Withdraw stake whose cooldown has elapsed
+    /// CRITICAL SECURITY: REQUIRES wallet signature
+    pub fn complete_unstake(ctx: Context<CompleteUnstake>) -> Result<()> {
+        let stake_account = &mut ctx.accounts.stake_account;
+        let clock = Clock::get()?;
+
+        let amount = stake_account.unstaking_amount;
+        require!(amount > 0, StakingError::NothingUnstaking);
+        require!(
+            clock.unix_timestamp >= stake_account.unlock_time,
+            StakingError::CooldownActive
+        );
+
+        // SECURITY: Update state BEFORE transfer
+        stake_account.unstaking_amount = 0;
+
+        let seeds: &[&[u8]] = &[b"stake_vault", &[ctx.bumps.stake_vault]];
+        let signer_seeds = &[seeds];
+
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.stake_vault.to_account_info(),
+                to: ctx.accounts.owner.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(cpi_context, amount)?;
+
+        emit_cpi!(Unstaked {
+            owner: stake_account.owner,
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Claim accrued fee share to the staker's wallet
+    /// REQUIRES wallet signature
+    pub fn claim_fees(ctx: Context<ClaimFees>) -> Result<()> {
+        let pool = &ctx.accounts.pool;
+        let stake_account = &mut ctx.accounts.stake_account;
+
+        settle_fees(pool, stake_account)?;
+        reset_fee_debt(pool, stake_account)?;
+
+        let amount = stake_account.unclaimed_fees;
+        require!(amount > 0, StakingError::NothingToClaim);
+
+        // SECURITY: Update state BEFORE transfer
+        stake_account.unclaimed_fees = 0;
+
+        let seeds: &[&[u8]] = &[b"fee_vault", &[ctx.bumps.fee_vault]];
+        let signer_seeds = &[seeds];
+
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.fee_vault.to_account_info(),
+                to: ctx.accounts.owner.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(cpi_context, amount)?;
+
+        emit_cpi!(FeesClaimed {
+            owner: stake_account.owner,
+            amount,
+        });
+        Ok(())
+    }
+}
+
+// ===================
+// Helper Functions
+// ===================
+
+/// Move fees earned since the last checkpoint into unclaimed_fees
+fn settle_fees(pool: &StakingPool, stake_account: &mut StakeAccount) -> Result<()> {
+    let accrued = accrued_fees(pool, stake_account.amount)?;
+    let earned = accrued
+        .checked_sub(stake_account.fee_debt)
+        .ok_or(StakingError::MathOverflow)?;
+
+    stake_account.unclaimed_fees = stake_account.unclaimed_fees
+        .checked_add(u64::try_from(earned).map_err(|_| StakingError::MathOverflow)?)
+        .ok_or(StakingError::MathOverflow)?;
+    Ok(())
+}
+
+/// Checkpoint the stake against the current fees_per_share
+fn reset_fee_debt(pool: &StakingPool, stake_account: &mut StakeAccount) -> Result<()> {
+    stake_account.fee_debt = accrued_fees(pool, stake_account.amount)?;
+    Ok(())
+}
+
+fn accrued_fees(pool: &StakingPool, amount: u64) -> Result<u128> {
+    Ok((amount as u128)
+        .checked_mul(pool.fees_per_share)
+        .ok_or(StakingError::MathOverflow)?
+        / FEE_SHARE_PRECISION)
+}
+
+// ===================
+// Account Structs
+// ===================
+
+#[derive(Accounts)]
+pub struct InitializeStaking<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + StakingPool::INIT_SPACE,
+        seeds = [b"staking_pool"],
+        bump
+    )]
+    pub pool: Account<'info, StakingPool>,
+
+    /// Stake vault PDA holding staked SOL - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"stake_vault"],
+        bump
+    )]
+    pub stake_vault: SystemAccount<'info>,
+
+    /// Fee vault PDA holding deposited fees - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositFees<'info> {
+    #[account(
+        mut,
+        seeds = [b"staking_pool"],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, StakingPool>,
+
+    /// Fee vault PDA holding deposited fees - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AdvanceEpoch<'info> {
+    #[account(
+        mut,
+        seeds = [b"staking_pool"],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, StakingPool>,
+
+    /// Anyone can advance the epoch (permissionless)
+    pub caller: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Stake<'info> {
+    #[account(
+        mut,
+        seeds = [b"staking_pool"],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, StakingPool>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + StakeAccount::INIT_SPACE,
+        seeds = [b"stake", owner.key().as_ref()],
+        bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    /// Stake vault PDA holding staked SOL - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"stake_vault"],
+        bump
+    )]
+    pub stake_vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RequestUnstake<'info> {
+    #[account(
+        mut,
+        seeds = [b"staking_pool"],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, StakingPool>,
+
+    #[account(
+        mut,
+        seeds = [b"stake", owner.key().as_ref()],
+        bump = stake_account.bump,
+        has_one = owner
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    pub owner: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CompleteUnstake<'info> {
+    #[account(
+        mut,
+        seeds = [b"stake", owner.key().as_ref()],
+        bump = stake_account.bump,
+        has_one = owner
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    /// Stake vault PDA holding staked SOL - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"stake_vault"],
+        bump
+    )]
+    pub stake_vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimFees<'info> {
+    #[account(
+        seeds = [b"staking_pool"],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, StakingPool>,
+
+    #[account(
+        mut,
+        seeds = [b"stake", owner.key().as_ref()],
+        bump = stake_account.bump,
+        has_one = owner
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    /// Fee vault PDA holding deposited fees - uses SystemAccount for type safety
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// ===================
+// State
+// ===================
+
+#[account]
+#[derive(InitSpace)]
+pub struct StakingPool {
+    pub authority: Pubkey,
+    /// Stake currently earning fees (excludes stake in cooldown)
+    pub total_staked: u64,
+    /// Cumulative fees per staked lamport, scaled by FEE_SHARE_PRECISION
+    pub fees_per_share: u128,
+    /// Fees deposited this epoch, distributed at advance_epoch
+    pub pending_fees: u64,
+    pub total_fees_distributed: u64,
+    pub current_epoch: u64,
+    pub epoch_start: i64,
+    pub epoch_duration: i64,
+    pub cooldown_seconds: i64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct StakeAccount {
+    pub owner: Pubkey,
+    /// Active stake earning fees
+    pub amount: u64,
+    /// amount * fees_per_share at the last checkpoint
+    pub fee_debt: u128,
+    pub unclaimed_fees: u64,
+    /// Stake in cooldown, withdrawable at unlock_time
+    pub unstaking_amount: u64,
+    pub unlock_time: i64,
+    pub bump: u8,
+}
+
+// ===================
+// Events
+// ===================
+
+/// Emitted when protocol fees are deposited
+#[event]
+pub struct FeesDeposited {
+    pub depositor: Pubkey,
+    pub epoch: u64,
+    pub amount: u64,
+}
+
+/// Emitted when an epoch closes and its fees are distributed
+#[event]
+pub struct EpochAdvanced {
+    pub epoch: u64,
+    pub distributed: u64,
+    pub total_staked: u64,
+}
+
+/// Emitted when a user stakes
+#[event]
+pub struct Staked {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub total_staked: u64,
+}
+
+/// Emitted when a user starts an unstake cooldown
+#[event]
+pub struct UnstakeRequested {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub unlock_time: i64,
+}
+
+/// Emitted when cooled-down stake is withdrawn
+#[event]
+pub struct Unstaked {
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when a staker claims their fee share
+#[event]
+pub struct FeesClaimed {
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+// ===================
+// Errors
+// ===================
+
+#[error_code]
+pub enum StakingError {
+    #[msg("Bad epoch duration")]
+    InvalidEpochDuration,
+    #[msg("Bad cooldown")]
+    InvalidCooldown,
+    #[msg("Amount too small")]
+    AmountTooSmall,
+    #[msg("Epoch not over")]
+    EpochNotOver,
+    #[msg("Insufficient stake")]
+    InsufficientStake,
+    #[msg("Nothing unstaking")]
+    NothingUnstaking,
+    #[msg("Cooldown active")]
+    CooldownActive,
+    #[msg("Nothing to claim")]
+    NothingToClaim,
+    #[msg("Overflow")]
+    MathOverflow,
+}
@@ -0,0 +1,155 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Achievements } from "../target/types/achievements";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+
+const TOKEN_2022_PROGRAM_ID = new PublicKey(
+  "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"
+);
+const ASSOCIATED_TOKEN_PROGRAM_ID = new PublicKey(
+  "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+);
+
+describe("achievements", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Achievements as Program<Achievements>;
+
+  const authority = provider.wallet.publicKey;
+  // Stands in for a game program's `achievements_crediter` PDA
+  const crediter = Keypair.generate();
+  const player = Keypair.generate().publicKey;
+
+  const ACHIEVEMENT_ID = 1;
+
+  // PDAs
+  let configPda: PublicKey;
+  let achievementPda: PublicKey;
+  let playerAchievementPda: PublicKey;
+  let mintPda: PublicKey;
+  let playerTokenAccount: PublicKey;
+
+  const awardAccounts = () => ({
+    config: configPda,
+    achievement: achievementPda,
+    playerAchievement: playerAchievementPda,
+    mint: mintPda,
+    playerTokenAccount: playerTokenAccount,
+    recipient: player,
+    payer: authority,
+    tokenProgram: TOKEN_2022_PROGRAM_ID,
+    associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+    systemProgram: SystemProgram.programId,
+  });
+
+  before(async () => {
+    const idBytes = Buffer.alloc(4);
+    idBytes.writeUInt32LE(ACHIEVEMENT_ID);
+
+    [configPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("achievements_config")],
+      program.programId
+    );
+    [achievementPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("achievement"), idBytes],
+      program.programId
+    );
+    [playerAchievementPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("player_achievement"), idBytes, player.toBuffer()],
+      program.programId
+    );
+    [mintPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("badge_mint"), idBytes, player.toBuffer()],
+      program.programId
+    );
+    [playerTokenAccount] = PublicKey.findProgramAddressSync(
+      [player.toBuffer(), TOKEN_2022_PROGRAM_ID.toBuffer(), mintPda.toBuffer()],
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+  });
+
+  it("Initializes the registry and defines an achievement", async () => {
+    await program.methods
+      .initializeAchievements()
+      .accounts({
+        config: configPda,
+        authority: authority,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .setCrediters([crediter.publicKey])
+      .accounts({ config: configPda, authority: authority })
+      .rpc();
+
+    await program.methods
+      .defineAchievement(
+        ACHIEVEMENT_ID,
+        "First Blood",
+        "CCWIN",
+        "https://example.com/first-blood.json"
+      )
+      .accounts({
+        config: configPda,
+        achievement: achievementPda,
+        authority: authority,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const achievement = await program.account.achievement.fetch(
+      achievementPda
+    );
+    expect(achievement.name).to.equal("First Blood");
+    expect(achievement.active).to.be.true;
+  });
+
+  it("Rejects awards from a non-allowlisted crediter", async () => {
+    const rogue = Keypair.generate();
+
+    try {
+      await program.methods
+        .awardAchievement(player, ACHIEVEMENT_ID)
+        .accounts({ ...awardAccounts(), crediter: rogue.publicKey })
+        .signers([rogue])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("UnauthorizedCrediter");
+    }
+  });
+
+  it("Mints a soulbound badge to the player", async () => {
+    await program.methods
+      .awardAchievement(player, ACHIEVEMENT_ID)
+      .accounts({ ...awardAccounts(), crediter: crediter.publicKey })
+      .signers([crediter])
+      .rpc();
+
+    const record = await program.account.playerAchievement.fetch(
+      playerAchievementPda
+    );
+    expect(record.mint.toBase58()).to.equal(mintPda.toBase58());
+
+    const balance = await provider.connection.getTokenAccountBalance(
+      playerTokenAccount
+    );
+    expect(balance.value.amount).to.equal("1");
+  });
+
+  it("Rejects awarding the same achievement twice", async () => {
+    try {
+      await program.methods
+        .awardAchievement(player, ACHIEVEMENT_ID)
+        .accounts({ ...awardAccounts(), crediter: crediter.publicKey })
+        .signers([crediter])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("already in use");
+    }
+  });
+});
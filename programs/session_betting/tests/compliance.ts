@@ -0,0 +1,128 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { Compliance } from "../target/types/compliance";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+} from "@solana/web3.js";
+import { expect } from "chai";
+
+describe("compliance", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Compliance as Program<Compliance>;
+
+  const authority = provider.wallet.publicKey;
+  const bettor = Keypair.generate();
+
+  // PDAs
+  let configPda: PublicKey;
+  let bettorCompliancePda: PublicKey;
+  let authorityCompliancePda: PublicKey;
+
+  const now = () => Math.floor(Date.now() / 1000);
+
+  before(async () => {
+    [configPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("compliance_config")],
+      program.programId
+    );
+    [bettorCompliancePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_compliance"), bettor.publicKey.toBuffer()],
+      program.programId
+    );
+    [authorityCompliancePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_compliance"), authority.toBuffer()],
+      program.programId
+    );
+
+    const sig = await provider.connection.requestAirdrop(
+      bettor.publicKey,
+      LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    await program.methods
+      .initializeCompliance({ open: {} })
+      .accounts({
+        config: configPda,
+        authority: authority,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  });
+
+  it("Treats wallets without a record as eligible in Open mode", async () => {
+    await program.methods
+      .assertEligible(bettor.publicKey)
+      .accounts({ config: configPda, userCompliance: bettorCompliancePda })
+      .rpc();
+  });
+
+  it("Blocks self-excluded wallets and never shortens an exclusion", async () => {
+    const until = now() + 30 * 24 * 60 * 60;
+    await program.methods
+      .selfExclude(new BN(until))
+      .accounts({ user: bettor.publicKey })
+      .signers([bettor])
+      .rpc();
+
+    try {
+      await program.methods
+        .assertEligible(bettor.publicKey)
+        .accounts({ config: configPda, userCompliance: bettorCompliancePda })
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("SelfExcluded");
+    }
+
+    try {
+      await program.methods
+        .selfExclude(new BN(until - 60))
+        .accounts({ user: bettor.publicKey })
+        .signers([bettor])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("InvalidExclusion");
+    }
+  });
+
+  it("Rejects blocklisted wallets in Blocklist mode", async () => {
+    await program.methods
+      .setMode({ blocklist: {} })
+      .accounts({ config: configPda, authority: authority })
+      .rpc();
+    await program.methods
+      .setUserStatus(authority, { blocked: {} })
+      .accounts({ config: configPda, authority: authority })
+      .rpc();
+
+    try {
+      await program.methods
+        .assertEligible(authority)
+        .accounts({ config: configPda, userCompliance: authorityCompliancePda })
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("NotEligible");
+    }
+  });
+
+  it("Rejects mode changes from non-authority", async () => {
+    try {
+      await program.methods
+        .setMode({ open: {} })
+        .accounts({ config: configPda, authority: bettor.publicKey })
+        .signers([bettor])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("ConstraintHasOne");
+    }
+  });
+});
@@ -0,0 +1,140 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { Draft } from "../target/types/draft";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+} from "@solana/web3.js";
+import { expect } from "chai";
+
+describe("draft", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Draft as Program<Draft>;
+
+  const authority = provider.wallet.publicKey;
+  let player: Keypair;
+
+  // PDAs
+  let configPda: PublicKey;
+  let contestPda: PublicKey;
+  let escrowPda: PublicKey;
+  let entryPda: PublicKey;
+
+  const ENTRY_FEE = 0.05 * LAMPORTS_PER_SOL;
+  const FEED_IDS = [1, 2, 3].map((n) => Array(32).fill(n));
+
+  before(async () => {
+    player = Keypair.generate();
+
+    const sig = await provider.connection.requestAirdrop(
+      player.publicKey,
+      LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    [configPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("draft_config")],
+      program.programId
+    );
+  });
+
+  it("Initializes the draft config", async () => {
+    await program.methods
+      .initializeDraft()
+      .accounts({
+        config: configPda,
+        authority: authority,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const config = await program.account.draftConfig.fetch(configPda);
+    expect(config.authority.toString()).to.equal(authority.toString());
+    expect(config.contestCount.toNumber()).to.equal(0);
+  });
+
+  it("Creates a contest", async () => {
+    const contestId = new BN(0);
+    const idBytes = contestId.toArrayLike(Buffer, "le", 8);
+    [contestPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("contest"), idBytes],
+      program.programId
+    );
+    [escrowPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("contest_escrow"), idBytes],
+      program.programId
+    );
+    [entryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("entry"), idBytes, player.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const now = Math.floor(Date.now() / 1000);
+    await program.methods
+      .createContest(
+        new BN(ENTRY_FEE),
+        2,
+        10,
+        new BN(now + 600),
+        new BN(now + 3600),
+        FEED_IDS
+      )
+      .accounts({
+        config: configPda,
+        contest: contestPda,
+        authority: authority,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const contest = await program.account.contest.fetch(contestPda);
+    expect(contest.rosterSize).to.equal(2);
+    expect(contest.feedIds.length).to.equal(3);
+    expect(contest.status).to.deep.equal({ open: {} });
+  });
+
+  it("Rejects a roster with duplicate picks", async () => {
+    try {
+      await program.methods
+        .enterContest(Buffer.from([1, 1]))
+        .accounts({
+          contest: contestPda,
+          entry: entryPda,
+          escrow: escrowPda,
+          player: player.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([player])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("InvalidRoster");
+    }
+  });
+
+  it("Enters a contest and escrows the fee", async () => {
+    await program.methods
+      .enterContest(Buffer.from([0, 2]))
+      .accounts({
+        contest: contestPda,
+        entry: entryPda,
+        escrow: escrowPda,
+        player: player.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([player])
+      .rpc();
+
+    const contest = await program.account.contest.fetch(contestPda);
+    expect(contest.entryCount).to.equal(1);
+    expect(contest.prizePool.toNumber()).to.equal(ENTRY_FEE);
+
+    const entry = await program.account.contestEntry.fetch(entryPda);
+    expect(Array.from(entry.picks)).to.deep.equal([0, 2]);
+    expect(await provider.connection.getBalance(escrowPda)).to.equal(ENTRY_FEE);
+  });
+});
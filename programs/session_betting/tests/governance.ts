@@ -0,0 +1,129 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { Governance } from "../target/types/governance";
+import { SessionBetting } from "../target/types/session_betting";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+
+describe("governance", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Governance as Program<Governance>;
+  const sessionBetting = anchor.workspace
+    .SessionBetting as Program<SessionBetting>;
+
+  const authority = provider.wallet.publicKey;
+  const member2 = Keypair.generate();
+
+  // PDAs
+  let governancePda: PublicKey;
+  let proposalPda: PublicKey;
+  let gameStatePda: PublicKey;
+
+  const TIMELOCK = 60 * 60;
+
+  before(async () => {
+    [governancePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("governance")],
+      program.programId
+    );
+    [proposalPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("proposal"), new BN(0).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    [gameStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("game")],
+      sessionBetting.programId
+    );
+  });
+
+  it("Initializes a 2-of-2 council", async () => {
+    await program.methods
+      .initializeGovernance([authority, member2.publicKey], 2, new BN(TIMELOCK))
+      .accounts({
+        governance: governancePda,
+        payer: authority,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const governance = await program.account.governance.fetch(governancePda);
+    expect(governance.members.length).to.equal(2);
+    expect(governance.threshold).to.equal(2);
+  });
+
+  it("Approves a proposal and holds it for the timelock", async () => {
+    await program.methods
+      .createProposal({ setCircuitBreaker: { maxPriceMoveBps: new BN(500) } })
+      .accounts({
+        governance: governancePda,
+        proposal: proposalPda,
+        proposer: authority,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    let proposal = await program.account.proposal.fetch(proposalPda);
+    expect(proposal.approvedAt.toNumber()).to.equal(0);
+
+    await program.methods
+      .approveProposal()
+      .accounts({
+        governance: governancePda,
+        proposal: proposalPda,
+        member: member2.publicKey,
+      })
+      .signers([member2])
+      .rpc();
+
+    proposal = await program.account.proposal.fetch(proposalPda);
+    expect(proposal.approvedAt.toNumber()).to.be.greaterThan(0);
+
+    try {
+      await program.methods
+        .executeProposal()
+        .accounts({
+          governance: governancePda,
+          proposal: proposalPda,
+          gameState: gameStatePda,
+          sessionBettingProgram: sessionBetting.programId,
+          caller: authority,
+        })
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("TimelockActive");
+    }
+  });
+
+  it("Rejects proposals from non-members", async () => {
+    const outsider = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      outsider.publicKey,
+      1_000_000_000
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    const [nextProposalPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("proposal"), new BN(1).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .createProposal({ setPaused: { paused: true } })
+        .accounts({
+          governance: governancePda,
+          proposal: nextProposalPda,
+          proposer: outsider.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([outsider])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("NotCouncilMember");
+    }
+  });
+});
@@ -0,0 +1,117 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { Insurance } from "../target/types/insurance";
+import { SessionBetting } from "../target/types/session_betting";
+import { Spectator } from "../target/types/spectator";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+} from "@solana/web3.js";
+import { expect } from "chai";
+
+describe("insurance", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Insurance as Program<Insurance>;
+  const sessionBetting = anchor.workspace
+    .SessionBetting as Program<SessionBetting>;
+  const spectator = anchor.workspace.Spectator as Program<Spectator>;
+
+  const authority = provider.wallet.publicKey;
+
+  // PDAs
+  let configPda: PublicKey;
+  let insuranceVaultPda: PublicKey;
+  let sessionBettingCrediter: PublicKey;
+  let spectatorCrediter: PublicKey;
+
+  const CONTRIBUTION_BPS = 500;
+
+  before(async () => {
+    [configPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("insurance_config")],
+      program.programId
+    );
+    [insuranceVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("insurance_vault")],
+      program.programId
+    );
+    [sessionBettingCrediter] = PublicKey.findProgramAddressSync(
+      [Buffer.from("insurance_crediter")],
+      sessionBetting.programId
+    );
+    [spectatorCrediter] = PublicKey.findProgramAddressSync(
+      [Buffer.from("insurance_crediter")],
+      spectator.programId
+    );
+  });
+
+  it("Initializes the fund and allowlists the game programs", async () => {
+    await program.methods
+      .initializeInsurance(new BN(CONTRIBUTION_BPS))
+      .accounts({
+        config: configPda,
+        insuranceVault: insuranceVaultPda,
+        authority: authority,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .setCrediters([sessionBettingCrediter, spectatorCrediter])
+      .accounts({ config: configPda, authority: authority })
+      .rpc();
+
+    const config = await program.account.insuranceConfig.fetch(configPda);
+    expect(config.contributionBps.toNumber()).to.equal(CONTRIBUTION_BPS);
+    expect(config.crediters.length).to.equal(2);
+  });
+
+  it("Tracks authority replenishment", async () => {
+    const amount = new BN(LAMPORTS_PER_SOL / 10);
+    const before = await provider.connection.getBalance(insuranceVaultPda);
+
+    await program.methods
+      .replenish(amount)
+      .accounts({ authority: authority })
+      .rpc();
+
+    const config = await program.account.insuranceConfig.fetch(configPda);
+    expect(config.totalReplenished.toString()).to.equal(amount.toString());
+    expect(await provider.connection.getBalance(insuranceVaultPda)).to.equal(
+      before + amount.toNumber()
+    );
+  });
+
+  it("Rejects shortfall requests from non-allowlisted crediters", async () => {
+    const crediter = Keypair.generate();
+    try {
+      await program.methods
+        .coverShortfall(new BN(1_000))
+        .accounts({
+          escrow: Keypair.generate().publicKey,
+          crediter: crediter.publicKey,
+        })
+        .signers([crediter])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("UnauthorizedCrediter");
+    }
+  });
+
+  it("Rejects contribution above the cap", async () => {
+    try {
+      await program.methods
+        .setContributionBps(new BN(2_001))
+        .accounts({ config: configPda, authority: authority })
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("InvalidContribution");
+    }
+  });
+});
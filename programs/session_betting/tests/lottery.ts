@@ -0,0 +1,118 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { Lottery } from "../target/types/lottery";
+import { SessionBetting } from "../target/types/session_betting";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+} from "@solana/web3.js";
+import { expect } from "chai";
+
+describe("lottery", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Lottery as Program<Lottery>;
+  const sessionBetting = anchor.workspace
+    .SessionBetting as Program<SessionBetting>;
+
+  const authority = provider.wallet.publicKey;
+
+  // PDAs
+  let configPda: PublicKey;
+  let drawPda: PublicKey;
+  let prizeVaultPda: PublicKey;
+  let sessionBettingCrediter: PublicKey;
+
+  const LAMPORTS_PER_TICKET = 0.1 * LAMPORTS_PER_SOL;
+  const PRIZE_AMOUNT = 0.5 * LAMPORTS_PER_SOL;
+
+  before(async () => {
+    [configPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lottery_config")],
+      program.programId
+    );
+    [drawPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("draw"), new BN(0).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    [prizeVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("prize_vault")],
+      program.programId
+    );
+    [sessionBettingCrediter] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lottery_crediter")],
+      sessionBetting.programId
+    );
+  });
+
+  it("Initializes the lottery and opens draw 0", async () => {
+    await program.methods
+      .initializeLottery(new BN(LAMPORTS_PER_TICKET))
+      .accounts({
+        config: configPda,
+        draw: drawPda,
+        prizeVault: prizeVaultPda,
+        authority: authority,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const draw = await program.account.draw.fetch(drawPda);
+    expect(draw.status).to.deep.equal({ open: {} });
+  });
+
+  it("Allowlists the session_betting crediter", async () => {
+    await program.methods
+      .setCrediters([sessionBettingCrediter])
+      .accounts({ config: configPda, authority: authority })
+      .rpc();
+
+    const config = await program.account.lotteryConfig.fetch(configPda);
+    expect(config.crediters[0].toString()).to.equal(
+      sessionBettingCrediter.toString()
+    );
+  });
+
+  it("Rejects tickets from a non-allowlisted crediter", async () => {
+    const rogue = Keypair.generate();
+    const [playerTicketsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("player_tickets"), rogue.publicKey.toBuffer()],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .creditTickets(rogue.publicKey, new BN(LAMPORTS_PER_SOL))
+        .accounts({
+          config: configPda,
+          playerTickets: playerTicketsPda,
+          crediter: rogue.publicKey,
+          payer: authority,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([rogue])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("UnauthorizedCrediter");
+    }
+  });
+
+  it("Funds the open draw", async () => {
+    await program.methods
+      .fundDraw(new BN(PRIZE_AMOUNT))
+      .accounts({
+        draw: drawPda,
+        prizeVault: prizeVaultPda,
+        funder: authority,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const draw = await program.account.draw.fetch(drawPda);
+    expect(draw.prize.toNumber()).to.equal(PRIZE_AMOUNT);
+  });
+});
@@ -0,0 +1,131 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { Referral } from "../target/types/referral";
+import { SessionBetting } from "../target/types/session_betting";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+} from "@solana/web3.js";
+import { expect } from "chai";
+
+describe("referral", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Referral as Program<Referral>;
+  const sessionBetting = anchor.workspace
+    .SessionBetting as Program<SessionBetting>;
+
+  const authority = provider.wallet.publicKey;
+  const referrer = Keypair.generate();
+
+  // PDAs
+  let configPda: PublicKey;
+  let referralVaultPda: PublicKey;
+  let referralPda: PublicKey;
+  let earningsPda: PublicKey;
+  let sessionBettingCrediter: PublicKey;
+
+  const REFERRAL_FEE_BPS = 1_000;
+
+  before(async () => {
+    [configPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("referral_config")],
+      program.programId
+    );
+    [referralVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("referral_vault")],
+      program.programId
+    );
+    [referralPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("referral"), authority.toBuffer()],
+      program.programId
+    );
+    [earningsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("referrer_earnings"), referrer.publicKey.toBuffer()],
+      program.programId
+    );
+    [sessionBettingCrediter] = PublicKey.findProgramAddressSync(
+      [Buffer.from("referral_crediter")],
+      sessionBetting.programId
+    );
+
+    const sig = await provider.connection.requestAirdrop(
+      referrer.publicKey,
+      LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+  });
+
+  it("Initializes the registry and allowlists session_betting", async () => {
+    await program.methods
+      .initializeReferrals(new BN(REFERRAL_FEE_BPS))
+      .accounts({
+        config: configPda,
+        referralVault: referralVaultPda,
+        authority: authority,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .setCrediters([sessionBettingCrediter])
+      .accounts({ config: configPda, authority: authority })
+      .rpc();
+
+    const config = await program.account.referralConfig.fetch(configPda);
+    expect(config.referralFeeBps.toNumber()).to.equal(REFERRAL_FEE_BPS);
+    expect(config.crediters.length).to.equal(1);
+  });
+
+  it("Rejects self-referral", async () => {
+    try {
+      await program.methods
+        .registerReferrer(authority)
+        .accounts({ user: authority })
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("SelfReferral");
+    }
+  });
+
+  it("Registers a referrer once per wallet", async () => {
+    await program.methods
+      .registerReferrer(referrer.publicKey)
+      .accounts({ user: authority })
+      .rpc();
+
+    const referral = await program.account.referral.fetch(referralPda);
+    expect(referral.referrer.toBase58()).to.equal(
+      referrer.publicKey.toBase58()
+    );
+    const earnings = await program.account.referrerEarnings.fetch(earningsPda);
+    expect(earnings.referralCount.toNumber()).to.equal(1);
+
+    try {
+      await program.methods
+        .registerReferrer(Keypair.generate().publicKey)
+        .accounts({ user: authority })
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("already in use");
+    }
+  });
+
+  it("Rejects claims with no earnings", async () => {
+    try {
+      await program.methods
+        .claimReferralEarnings()
+        .accounts({ referrer: referrer.publicKey })
+        .signers([referrer])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("NothingToClaim");
+    }
+  });
+});
@@ -0,0 +1,113 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { Registry } from "../target/types/registry";
+import { SessionBetting } from "../target/types/session_betting";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+
+describe("registry", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Registry as Program<Registry>;
+  const sessionBetting = anchor.workspace
+    .SessionBetting as Program<SessionBetting>;
+
+  const authority = provider.wallet.publicKey;
+
+  // PDAs
+  let configPda: PublicKey;
+  let entryPda: PublicKey;
+
+  const version = (major: number, minor: number, patch: number) => ({
+    major,
+    minor,
+    patch,
+  });
+
+  // session_betting ENTRYPOINT_CLAIM_WINNINGS
+  const ENTRYPOINT_CLAIM_WINNINGS = 1 << 1;
+
+  before(async () => {
+    [configPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("registry_config")],
+      program.programId
+    );
+    [entryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("program_entry"), sessionBetting.programId.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initializeRegistry()
+      .accounts({
+        config: configPda,
+        authority: authority,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  });
+
+  it("Registers session_betting at its deployed version", async () => {
+    await program.methods
+      .registerProgram(sessionBetting.programId, version(1, 0, 0))
+      .accounts({ config: configPda, authority: authority })
+      .rpc();
+
+    const entry = await program.account.programEntry.fetch(entryPda);
+    expect(entry.version.major).to.equal(1);
+    expect(entry.migration).to.deep.equal({ stable: {} });
+    expect(entry.deprecatedEntrypoints.toNumber()).to.equal(0);
+  });
+
+  it("Records migrations and rejects version rollback", async () => {
+    await program.methods
+      .setVersion(version(1, 1, 0), { migrating: {} })
+      .accounts({ config: configPda, programEntry: entryPda, authority })
+      .rpc();
+
+    const entry = await program.account.programEntry.fetch(entryPda);
+    expect(entry.version.minor).to.equal(1);
+    expect(entry.migration).to.deep.equal({ migrating: {} });
+
+    try {
+      await program.methods
+        .setVersion(version(1, 0, 9), { migrated: {} })
+        .accounts({ config: configPda, programEntry: entryPda, authority })
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("VersionRollback");
+    }
+  });
+
+  it("Stores deprecated entrypoint flags", async () => {
+    await program.methods
+      .setDeprecatedEntrypoints(new BN(ENTRYPOINT_CLAIM_WINNINGS))
+      .accounts({ config: configPda, programEntry: entryPda, authority })
+      .rpc();
+
+    const entry = await program.account.programEntry.fetch(entryPda);
+    expect(entry.deprecatedEntrypoints.toNumber()).to.equal(
+      ENTRYPOINT_CLAIM_WINNINGS
+    );
+  });
+
+  it("Rejects updates from non-authority", async () => {
+    const attacker = Keypair.generate();
+    try {
+      await program.methods
+        .setDeprecatedEntrypoints(new BN(0))
+        .accounts({
+          config: configPda,
+          programEntry: entryPda,
+          authority: attacker.publicKey,
+        })
+        .signers([attacker])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("ConstraintHasOne");
+    }
+  });
+});
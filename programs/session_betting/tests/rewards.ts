@@ -0,0 +1,122 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { Rewards } from "../target/types/rewards";
+import { SessionBetting } from "../target/types/session_betting";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+} from "@solana/web3.js";
+import { expect } from "chai";
+
+describe("rewards", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Rewards as Program<Rewards>;
+  const sessionBetting = anchor.workspace
+    .SessionBetting as Program<SessionBetting>;
+
+  const authority = provider.wallet.publicKey;
+
+  // PDAs
+  let configPda: PublicKey;
+  let epochPda: PublicKey;
+  let rewardsVaultPda: PublicKey;
+  let sessionBettingCrediter: PublicKey;
+
+  const POINTS_PER_SOL = 100;
+  const EPOCH_DURATION = 60 * 60;
+  const REWARD_AMOUNT = 0.5 * LAMPORTS_PER_SOL;
+
+  before(async () => {
+    [configPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("rewards_config")],
+      program.programId
+    );
+    [epochPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("epoch"), new BN(0).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    [rewardsVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("rewards_vault")],
+      program.programId
+    );
+    [sessionBettingCrediter] = PublicKey.findProgramAddressSync(
+      [Buffer.from("rewards_crediter")],
+      sessionBetting.programId
+    );
+  });
+
+  it("Initializes the ledger and allowlists session_betting", async () => {
+    await program.methods
+      .initializeRewards(new BN(POINTS_PER_SOL), new BN(EPOCH_DURATION))
+      .accounts({
+        config: configPda,
+        epoch: epochPda,
+        rewardsVault: rewardsVaultPda,
+        authority: authority,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .setCrediters([sessionBettingCrediter])
+      .accounts({ config: configPda, authority: authority })
+      .rpc();
+
+    const config = await program.account.rewardsConfig.fetch(configPda);
+    expect(config.pointsPerSol.toNumber()).to.equal(POINTS_PER_SOL);
+    expect(config.crediters.length).to.equal(1);
+  });
+
+  it("Rejects points from a non-allowlisted crediter", async () => {
+    const rogue = Keypair.generate();
+    const epochBytes = new BN(0).toArrayLike(Buffer, "le", 8);
+    const [userPointsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_points"), rogue.publicKey.toBuffer()],
+      program.programId
+    );
+    const [userEpochPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_epoch"), epochBytes, rogue.publicKey.toBuffer()],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .creditPoints(rogue.publicKey, new BN(LAMPORTS_PER_SOL))
+        .accounts({
+          config: configPda,
+          epoch: epochPda,
+          userPoints: userPointsPda,
+          userEpochPoints: userEpochPda,
+          crediter: rogue.publicKey,
+          payer: authority,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([rogue])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("UnauthorizedCrediter");
+    }
+  });
+
+  it("Funds the current epoch", async () => {
+    await program.methods
+      .fundEpoch(new BN(REWARD_AMOUNT))
+      .accounts({
+        config: configPda,
+        epoch: epochPda,
+        rewardsVault: rewardsVaultPda,
+        funder: authority,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const epoch = await program.account.epoch.fetch(epochPda);
+    expect(epoch.rewardPool.toNumber()).to.equal(REWARD_AMOUNT);
+    expect(epoch.finalized).to.be.false;
+  });
+});
@@ -8,6 +8,7 @@ import {
   SystemProgram,
 } from "@solana/web3.js";
 import { expect } from "chai";
+import { createHash, randomBytes } from "crypto";
 
 describe("session_betting", () => {
   const provider = anchor.AnchorProvider.env();
@@ -22,6 +23,7 @@ describe("session_betting", () => {
 
   // PDAs
   let gameStatePda: PublicKey;
+  let marketPda: PublicKey;
   let globalVaultPda: PublicKey;
   let roundPda: PublicKey;
   let poolPda: PublicKey;
@@ -29,13 +31,19 @@ describe("session_betting", () => {
   let vaultPda: PublicKey;
   let positionPda: PublicKey;
   let sessionPda: PublicKey;
+  let sessionRegistryPda: PublicKey;
 
   // Test constants
   const DEPOSIT_AMOUNT = 0.5 * LAMPORTS_PER_SOL;
   const BET_AMOUNT = 0.1 * LAMPORTS_PER_SOL;
-  const START_PRICE = new BN(50000_00000000); // $50,000 with 8 decimals
   const END_PRICE_UP = new BN(51000_00000000); // $51,000 - price went up
   const END_PRICE_DOWN = new BN(49000_00000000); // $49,000 - price went down
+  const ROUND_DURATION = 30; // seconds
+  const SESSION_SCOPE_ALL = new BN(0x1ff); // every ENTRYPOINT_* bit
+
+  // Pyth push price accounts, cloned from devnet by the test validator
+  const PYTH_BTC_USD = new PublicKey("HovQMDrbAgAYPCmHVSrezcSmkMtXSSUsLDFANExrZh2J");
+  const PYTH_ETH_USD = new PublicKey("EdVCmQ9FSPcVe5YySXDPCRmc8aDQLKJ9xvYBMZPie1Vw");
 
   // Default Pyth price feed ID for BTC/USD (32 bytes)
   const DEFAULT_PRICE_FEED_ID = [
@@ -63,6 +71,11 @@ describe("session_betting", () => {
       program.programId
     );
 
+    [marketPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("market"), Buffer.from(DEFAULT_PRICE_FEED_ID)],
+      program.programId
+    );
+
     [globalVaultPda] = PublicKey.findProgramAddressSync(
       [Buffer.from("global_vault")],
       program.programId
@@ -82,6 +95,10 @@ describe("session_betting", () => {
       [Buffer.from("session"), user.publicKey.toBuffer(), sessionKeypair.publicKey.toBuffer()],
       program.programId
     );
+    [sessionRegistryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("session_registry"), user.publicKey.toBuffer()],
+      program.programId
+    );
   });
 
   describe("Admin Functions", () => {
@@ -90,6 +107,7 @@ describe("session_betting", () => {
         .initializeGame(DEFAULT_PRICE_FEED_ID)
         .accounts({
           gameState: gameStatePda,
+          market: marketPda,
           globalVault: globalVaultPda,
           authority: authority,
           systemProgram: SystemProgram.programId,
@@ -98,39 +116,260 @@ describe("session_betting", () => {
 
       const gameState = await program.account.gameState.fetch(gameStatePda);
       expect(gameState.authority.toString()).to.equal(authority.toString());
-      expect(gameState.currentRound.toNumber()).to.equal(0);
       expect(gameState.isPaused).to.be.false;
-      expect(gameState.priceFeedId).to.deep.equal(DEFAULT_PRICE_FEED_ID);
+
+      const market = await program.account.market.fetch(marketPda);
+      expect(market.currentRound.toNumber()).to.equal(0);
+      expect(market.feedId).to.deep.equal(DEFAULT_PRICE_FEED_ID);
+      expect(market.isActive).to.be.true;
     });
 
-    it("Starts a new round", async () => {
+    it("Global vault starts solvent with nothing reserved", async () => {
+      const surplus = await program.methods
+        .assertSolvency()
+        .accounts({ gameState: gameStatePda, globalVault: globalVaultPda })
+        .view();
+      expect(surplus.toNumber()).to.equal(
+        await provider.connection.getBalance(globalVaultPda)
+      );
+
+      const gameState = await program.account.gameState.fetch(gameStatePda);
+      expect(gameState.reservedPayouts.toNumber()).to.equal(0);
+    });
+
+    it("Migrating an up-to-date GameState keeps its version", async () => {
+      await program.methods
+        .migrateGameState()
+        .accounts({ gameState: gameStatePda, authority: authority })
+        .rpc();
+
+      const gameState = await program.account.gameState.fetch(gameStatePda);
+      expect(gameState.version).to.equal(7);
+      expect(gameState.gameTypeStats.length).to.equal(4);
+      expect(gameState.authority.toString()).to.equal(authority.toString());
+      expect(gameState.reserved.every((b: number) => b === 0)).to.be.true;
+
+      // The original layout is still a prefix: authority right after the discriminator
+      const info = await provider.connection.getAccountInfo(gameStatePda);
+      expect(new PublicKey(info!.data.subarray(8, 40)).toString()).to.equal(authority.toString());
+    });
+
+    it("Stamps a layout version into accounts it creates", async () => {
+      const market = await program.account.market.fetch(marketPda);
+      expect(market.version).to.equal(1);
+    });
+
+    it("Migrates a UserBalance still in the original layout", async () => {
+      // Loaded by Anchor.toml from tests/fixtures/legacy_user_balance.json: owner..bump
+      // byte-for-byte as first deployed, with no version byte
+      const legacyOwner = new PublicKey("5oDnDLB6PoYp4nR3oRSUkiguMXtgNfVnx9ho6N8eLKaj");
+      const [legacyBalancePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("balance"), legacyOwner.toBuffer()],
+        program.programId
+      );
+      const before = await provider.connection.getAccountInfo(legacyBalancePda);
+      expect(before!.data.length).to.equal(8 + 65);
+
+      await program.methods
+        .migrateUserBalance()
+        .accounts({ owner: legacyOwner, payer: authority })
+        .rpc();
+
+      const balance = await program.account.userBalance.fetch(legacyBalancePda);
+      expect(balance.owner.toString()).to.equal(legacyOwner.toString());
+      expect(balance.balance.toNumber()).to.equal(600_000_000);
+      expect(balance.totalDeposited.toNumber()).to.equal(750_000_000);
+      expect(balance.totalWithdrawn.toNumber()).to.equal(250_000_000);
+      expect(balance.totalWinnings.toNumber()).to.equal(100_000_000);
+      expect(balance.bump).to.equal(254);
+      expect(balance.version).to.equal(1);
+      expect(balance.copyOptOut).to.be.false;
+      expect(balance.maxBetOverride.toNumber()).to.equal(0);
+      expect(balance.personalLimits.maxBet.toNumber()).to.equal(0);
+    });
+
+    it("Authority can fund a game type's own vault", async () => {
+      const amount = 0.01 * LAMPORTS_PER_SOL;
+      // GameType::Battle
+      const [battleVaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("global_vault"), Buffer.from([1])],
+        program.programId
+      );
+
+      await program.methods
+        .fundGameVault({ battle: {} }, new BN(amount))
+        .accounts({ gameState: gameStatePda, gameVault: battleVaultPda, authority })
+        .rpc();
+
+      expect(await provider.connection.getBalance(battleVaultPda)).to.equal(amount);
       const gameState = await program.account.gameState.fetch(gameStatePda);
-      const roundId = gameState.currentRound;
+      expect(gameState.gameVaultReserved[1].toNumber()).to.equal(0);
+    });
+
+    it("Creates the market's round history buffer", async () => {
+      const [roundHistoryPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("round_history"), marketPda.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .initializeRoundHistory()
+        .accounts({
+          gameState: gameStatePda,
+          market: marketPda,
+          roundHistory: roundHistoryPda,
+          authority: authority,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const history = await program.account.roundHistory.fetch(roundHistoryPda);
+      expect(history.market.toString()).to.equal(marketPda.toString());
+      expect(history.totalRecorded.toNumber()).to.equal(0);
+      expect(history.entries.length).to.equal(50);
+    });
+
+    it("Opens a second market with its own round counter", async () => {
+      const ethFeedId = Array.from(
+        Buffer.from("ff61491a931112ddf1bd8147cd1b641375f79f5825126d665480874634fd0ace", "hex")
+      );
+      const [ethMarketPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), Buffer.from(ethFeedId)],
+        program.programId
+      );
+      const [ethRoundPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("round"), ethMarketPda.toBuffer(), new BN(0).toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [ethPoolPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("pool"), ethMarketPda.toBuffer(), new BN(0).toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      await program.methods
+        .createMarket(ethFeedId, { pyth: {} })
+        .accounts({
+          gameState: gameStatePda,
+          market: ethMarketPda,
+          authority: authority,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      await program.methods
+        .startRound({ spot: {} })
+        .accounts({
+          gameState: gameStatePda,
+          market: ethMarketPda,
+          round: ethRoundPda,
+          pool: ethPoolPda,
+          priceFeed: PYTH_ETH_USD,
+          authority: authority,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const ethMarket = await program.account.market.fetch(ethMarketPda);
+      expect(ethMarket.currentRound.toNumber()).to.equal(1);
+      expect(ethMarket.roundDuration.toNumber()).to.equal(ROUND_DURATION);
+      const round = await program.account.bettingRound.fetch(ethRoundPda);
+      expect(round.market.toString()).to.equal(ethMarketPda.toString());
+      expect(round.endTime.sub(round.lockTime).toNumber()).to.equal(ethMarket.lockBuffer.toNumber());
+
+      // The default market's counter is untouched
+      const market = await program.account.market.fetch(marketPda);
+      expect(market.currentRound.toNumber()).to.equal(0);
+    });
+
+    it("Starts a new round", async () => {
+      const market = await program.account.market.fetch(marketPda);
+      const roundId = market.currentRound;
 
       [roundPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("round"), roundId.toArrayLike(Buffer, "le", 8)],
+        [Buffer.from("round"), marketPda.toBuffer(), roundId.toArrayLike(Buffer, "le", 8)],
         program.programId
       );
 
       [poolPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("pool"), roundId.toArrayLike(Buffer, "le", 8)],
+        [Buffer.from("pool"), marketPda.toBuffer(), roundId.toArrayLike(Buffer, "le", 8)],
         program.programId
       );
 
       await program.methods
-        .startRound(START_PRICE)
+        .startRound({ spot: {} })
         .accounts({
           gameState: gameStatePda,
+          market: marketPda,
           round: roundPda,
           pool: poolPda,
+          priceFeed: PYTH_BTC_USD,
           authority: authority,
           systemProgram: SystemProgram.programId,
         })
         .rpc();
 
       const round = await program.account.bettingRound.fetch(roundPda);
-      expect(round.startPrice.toString()).to.equal(START_PRICE.toString());
+      // Start price and its slot come from the Pyth account, not an argument
+      expect(round.startPrice.toNumber()).to.be.greaterThan(0);
+      expect(round.startPublishSlot.toNumber()).to.be.greaterThan(0);
       expect(round.status).to.deep.equal({ open: {} });
+      expect(round.settlementMode).to.deep.equal({ spot: {} });
+    });
+
+    it("Only TWAP rounds accept price samples", async () => {
+      try {
+        await program.methods
+          .recordPriceSample()
+          .accounts({
+            gameState: gameStatePda,
+            market: marketPda,
+            round: roundPda,
+            priceFeed: Keypair.generate().publicKey,
+          })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.toString()).to.include("NotTwapRound");
+      }
+    });
+
+    it("Permissionless oracle start is rate-limited per market", async () => {
+      const caller = Keypair.generate();
+      const sig = await provider.connection.requestAirdrop(
+        caller.publicKey,
+        0.1 * LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig);
+
+      const market = await program.account.market.fetch(marketPda);
+      const [nextRoundPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("round"), marketPda.toBuffer(), market.currentRound.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [nextPoolPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("pool"), marketPda.toBuffer(), market.currentRound.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      // A round just opened on this market, so the next one is not due yet
+      try {
+        await program.methods
+          .startRoundOracle({ spot: {} })
+          .accounts({
+            gameState: gameStatePda,
+            market: marketPda,
+            round: nextRoundPda,
+            pool: nextPoolPda,
+            priceFeed: Keypair.generate().publicKey,
+            caller: caller.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([caller])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.toString()).to.include("TooEarlyToStart");
+      }
     });
 
     it("Can pause and unpause the game", async () => {
@@ -156,163 +395,851 @@ describe("session_betting", () => {
       gameState = await program.account.gameState.fetch(gameStatePda);
       expect(gameState.isPaused).to.be.false;
     });
-  });
-
-  describe("User Balance Functions", () => {
-    it("User can deposit SOL", async () => {
-      const balanceBefore = await provider.connection.getBalance(user.publicKey);
 
+    it("Can configure pool caps", async () => {
       await program.methods
-        .deposit(new BN(DEPOSIT_AMOUNT))
+        .setPoolCaps(new BN(0), new BN(0), new BN(LAMPORTS_PER_SOL))
         .accounts({
-          userBalance: userBalancePda,
-          vault: vaultPda,
-          user: user.publicKey,
-          systemProgram: SystemProgram.programId,
+          gameState: gameStatePda,
+          authority: authority,
         })
-        .signers([user])
         .rpc();
 
-      const userBalance = await program.account.userBalance.fetch(userBalancePda);
-      expect(userBalance.balance.toNumber()).to.equal(DEPOSIT_AMOUNT);
-      expect(userBalance.owner.toString()).to.equal(user.publicKey.toString());
-
-      const balanceAfter = await provider.connection.getBalance(user.publicKey);
-      expect(balanceAfter).to.be.lessThan(balanceBefore - DEPOSIT_AMOUNT + 10000); // Account for rent
+      const gameState = await program.account.gameState.fetch(gameStatePda);
+      expect(gameState.maxTotalPool.toNumber()).to.equal(0);
+      expect(gameState.maxSidePool.toNumber()).to.equal(0);
+      expect(gameState.maxPlayerStake.toNumber()).to.equal(LAMPORTS_PER_SOL);
     });
 
-    it("User can withdraw SOL", async () => {
-      const WITHDRAW_AMOUNT = 0.1 * LAMPORTS_PER_SOL;
-      const balanceBefore = await provider.connection.getBalance(user.publicKey);
+    it("Fee is configurable up to the 10% cap", async () => {
+      try {
+        await program.methods
+          .setFeeBps(new BN(1_001))
+          .accounts({ gameState: gameStatePda, authority: authority })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.toString()).to.include("InvalidFee");
+      }
 
       await program.methods
-        .withdraw(new BN(WITHDRAW_AMOUNT))
-        .accounts({
-          userBalance: userBalancePda,
-          vault: vaultPda,
-          user: user.publicKey,
-          systemProgram: SystemProgram.programId,
-        })
-        .signers([user])
+        .setFeeBps(new BN(500))
+        .accounts({ gameState: gameStatePda, authority: authority })
         .rpc();
 
-      const userBalance = await program.account.userBalance.fetch(userBalancePda);
-      expect(userBalance.balance.toNumber()).to.equal(DEPOSIT_AMOUNT - WITHDRAW_AMOUNT);
-
-      const balanceAfter = await provider.connection.getBalance(user.publicKey);
-      expect(balanceAfter).to.be.greaterThan(balanceBefore);
+      const gameState = await program.account.gameState.fetch(gameStatePda);
+      expect(gameState.feeBps.toNumber()).to.equal(500);
     });
 
-    it("Fails to withdraw more than balance", async () => {
-      const TOO_MUCH = 100 * LAMPORTS_PER_SOL;
+    it("Confidence limit defaults to 1% and is configurable", async () => {
+      let gameState = await program.account.gameState.fetch(gameStatePda);
+      expect(gameState.maxConfBps.toNumber()).to.equal(100);
+
+      await program.methods
+        .setMaxConfBps(new BN(50))
+        .accounts({ gameState: gameStatePda, authority: authority })
+        .rpc();
 
+      gameState = await program.account.gameState.fetch(gameStatePda);
+      expect(gameState.maxConfBps.toNumber()).to.equal(50);
+    });
+
+    it("Cancellation fee is capped like the platform fee", async () => {
       try {
         await program.methods
-          .withdraw(new BN(TOO_MUCH))
-          .accounts({
-            userBalance: userBalancePda,
-            vault: vaultPda,
-            user: user.publicKey,
-            systemProgram: SystemProgram.programId,
-          })
-          .signers([user])
+          .setCancelFeeBps(new BN(1_001))
+          .accounts({ gameState: gameStatePda, authority: authority })
           .rpc();
-        expect.fail("Should have thrown error");
+        expect.fail("Should have thrown");
       } catch (e: any) {
-        expect(e.message).to.include("InsufficientBalance");
+        expect(e.toString()).to.include("InvalidFee");
       }
+
+      const gameState = await program.account.gameState.fetch(gameStatePda);
+      expect(gameState.cancelFeeBps.toNumber()).to.equal(100);
     });
-  });
 
-  describe("Session Key Functions", () => {
-    it("User can create a session", async () => {
-      const validUntil = Math.floor(Date.now() / 1000) + 3600; // 1 hour from now
+    it("Close round bounty defaults and is configurable", async () => {
+      let gameState = await program.account.gameState.fetch(gameStatePda);
+      expect(gameState.closeRoundBounty.toNumber()).to.equal(500_000);
 
       await program.methods
-        .createSession(new BN(validUntil))
-        .accounts({
-          sessionToken: sessionPda,
-          authority: user.publicKey,
-          sessionSigner: sessionKeypair.publicKey,
-          systemProgram: SystemProgram.programId,
-        })
-        .signers([user])
+        .setCloseRoundBounty(new BN(250_000))
+        .accounts({ gameState: gameStatePda, authority: authority })
         .rpc();
 
-      const session = await program.account.sessionToken.fetch(sessionPda);
-      expect(session.authority.toString()).to.equal(user.publicKey.toString());
-      expect(session.sessionSigner.toString()).to.equal(sessionKeypair.publicKey.toString());
-      expect(session.validUntil.toNumber()).to.equal(validUntil);
+      gameState = await program.account.gameState.fetch(gameStatePda);
+      expect(gameState.closeRoundBounty.toNumber()).to.equal(250_000);
     });
 
-    it("Can revoke a session", async () => {
-      // First create a new session to revoke
-      const tempSession = Keypair.generate();
-      const [tempSessionPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("session"), user.publicKey.toBuffer(), tempSession.publicKey.toBuffer()],
-        program.programId
+    it("Authority can assign a round operator", async () => {
+      const keeper = Keypair.generate().publicKey;
+      await program.methods
+        .setOperator(keeper)
+        .accounts({ gameState: gameStatePda, authority: authority })
+        .rpc();
+
+      let gameState = await program.account.gameState.fetch(gameStatePda);
+      expect(gameState.operator.toString()).to.equal(keeper.toString());
+
+      await program.methods
+        .setOperator(PublicKey.default)
+        .accounts({ gameState: gameStatePda, authority: authority })
+        .rpc();
+      gameState = await program.account.gameState.fetch(gameStatePda);
+      expect(gameState.operator.toString()).to.equal(PublicKey.default.toString());
+    });
+
+    it("Authority can allowlist game programs for balance CPIs", async () => {
+      const gameProgram = Keypair.generate().publicKey;
+
+      await program.methods
+        .addGameProgram(gameProgram)
+        .accounts({ gameState: gameStatePda, authority: authority })
+        .rpc();
+      let gameState = await program.account.gameState.fetch(gameStatePda);
+      expect(gameState.gamePrograms.map((p) => p.toString())).to.include(
+        gameProgram.toString()
       );
 
       await program.methods
-        .createSession(new BN(Math.floor(Date.now() / 1000) + 3600))
-        .accounts({
-          sessionToken: tempSessionPda,
-          authority: user.publicKey,
-          sessionSigner: tempSession.publicKey,
-          systemProgram: SystemProgram.programId,
-        })
-        .signers([user])
+        .removeGameProgram(gameProgram)
+        .accounts({ gameState: gameStatePda, authority: authority })
         .rpc();
+      gameState = await program.account.gameState.fetch(gameStatePda);
+      expect(gameState.gamePrograms.length).to.equal(0);
+    });
+
+    it("Session rate limit defaults and is configurable", async () => {
+      let gameState = await program.account.gameState.fetch(gameStatePda);
+      expect(gameState.sessionBetsPerMinute.toNumber()).to.equal(30);
 
-      // Now revoke it
       await program.methods
-        .revokeSession()
-        .accounts({
-          sessionToken: tempSessionPda,
-          authority: user.publicKey,
-        })
-        .signers([user])
+        .setSessionRateLimit(new BN(10))
+        .accounts({ gameState: gameStatePda, authority: authority })
         .rpc();
 
-      // Verify it's closed
+      gameState = await program.account.gameState.fetch(gameStatePda);
+      expect(gameState.sessionBetsPerMinute.toNumber()).to.equal(10);
+    });
+
+    it("Streak bonus schedule is capped at 10%", async () => {
       try {
-        await program.account.sessionToken.fetch(tempSessionPda);
-        expect.fail("Account should be closed");
+        await program.methods
+          .setStreakBonus(new BN(100), new BN(1_001))
+          .accounts({ gameState: gameStatePda, authority: authority })
+          .rpc();
+        expect.fail("Should have thrown");
       } catch (e: any) {
-        expect(e.message).to.include("Account does not exist");
+        expect(e.toString()).to.include("InvalidStreakBonus");
       }
+
+      const gameState = await program.account.gameState.fetch(gameStatePda);
+      expect(gameState.streakBonusBpsPerWin.toNumber()).to.equal(100);
+      expect(gameState.maxStreakBonusBps.toNumber()).to.equal(1_000);
     });
-  });
 
-  describe("Betting Functions", () => {
-    let roundId: BN;
+    it("Early-bird bonus is capped at 10%", async () => {
+      try {
+        await program.methods
+          .setEarlyBirdBonus(new BN(1_001))
+          .accounts({ gameState: gameStatePda, authority: authority })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.toString()).to.include("InvalidEarlyBirdBonus");
+      }
 
-    before(async () => {
-      // Start a new round for betting tests
       const gameState = await program.account.gameState.fetch(gameStatePda);
-      roundId = gameState.currentRound;
+      expect(gameState.maxEarlyBirdBonusBps.toNumber()).to.equal(100);
+    });
 
-      [roundPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("round"), roundId.toArrayLike(Buffer, "le", 8)],
+    it("Jackpot is funded from fees and its contribution is capped", async () => {
+      const [jackpotPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("jackpot")],
         program.programId
       );
+      let jackpot = await program.account.jackpot.fetch(jackpotPda);
+      expect(jackpot.contributionBps.toNumber()).to.equal(1_000);
+      expect(jackpot.triggerEveryNRounds.toNumber()).to.equal(100);
 
-      [poolPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("pool"), roundId.toArrayLike(Buffer, "le", 8)],
-        program.programId
-      );
+      try {
+        await program.methods
+          .setJackpotConfig(new BN(5_001), new BN(0), new BN(100))
+          .accounts({ gameState: gameStatePda, jackpot: jackpotPda, authority })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.toString()).to.include("InvalidJackpotConfig");
+      }
+
+      await program.methods
+        .setJackpotConfig(new BN(500), new BN(200), new BN(50))
+        .accounts({ gameState: gameStatePda, jackpot: jackpotPda, authority })
+        .rpc();
+
+      jackpot = await program.account.jackpot.fetch(jackpotPda);
+      expect(jackpot.triggerMoveBps.toNumber()).to.equal(200);
+      expect(jackpot.triggerEveryNRounds.toNumber()).to.equal(50);
+    });
+
+    it("Bet limits are configurable and must be ordered", async () => {
+      try {
+        await program.methods
+          .setBetLimits(new BN(BET_AMOUNT), new BN(BET_AMOUNT - 1))
+          .accounts({ gameState: gameStatePda, authority: authority })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.toString()).to.include("InvalidBetLimits");
+      }
+
+      await program.methods
+        .setBetLimits(new BN(0.01 * LAMPORTS_PER_SOL), new BN(100 * LAMPORTS_PER_SOL))
+        .accounts({ gameState: gameStatePda, authority: authority })
+        .rpc();
+
+      const gameState = await program.account.gameState.fetch(gameStatePda);
+      expect(gameState.minBet.toNumber()).to.equal(0.01 * LAMPORTS_PER_SOL);
+      expect(gameState.maxBet.toNumber()).to.equal(100 * LAMPORTS_PER_SOL);
+    });
+
+    it("Rounds follow the market cadence within the configured bounds", async () => {
+      try {
+        await program.methods
+          .setMarketCadence(new BN(ROUND_DURATION), new BN(ROUND_DURATION))
+          .accounts({ gameState: gameStatePda, market: marketPda, authority })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.toString()).to.include("InvalidRoundDuration");
+      }
+
+      // 10 minute rounds are a valid cadence but exceed the 5 minute duration bound
+      await program.methods
+        .setMarketCadence(new BN(10 * 60), new BN(30))
+        .accounts({ gameState: gameStatePda, market: marketPda, authority })
+        .rpc();
+
+      const market = await program.account.market.fetch(marketPda);
+      expect(market.roundDuration.toNumber()).to.equal(10 * 60);
+      expect(market.lockBuffer.toNumber()).to.equal(30);
+      const [badRoundPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("round"), marketPda.toBuffer(), market.currentRound.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [badPoolPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("pool"), marketPda.toBuffer(), market.currentRound.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .startRound({ spot: {} })
+          .accounts({
+            gameState: gameStatePda,
+            market: marketPda,
+            round: badRoundPda,
+            pool: badPoolPda,
+            priceFeed: PYTH_BTC_USD,
+            authority: authority,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.toString()).to.include("InvalidRoundDuration");
+      }
+
+      await program.methods
+        .setMarketCadence(new BN(ROUND_DURATION), new BN(5))
+        .accounts({ gameState: gameStatePda, market: marketPda, authority })
+        .rpc();
+    });
+
+    it("Fixed-odds markets are backed by LP liquidity", async () => {
+      const ethFeedId = Buffer.from(
+        "ff61491a931112ddf1bd8147cd1b641375f79f5825126d665480874634fd0ace",
+        "hex"
+      );
+      const [ethMarketPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), ethFeedId],
+        program.programId
+      );
+      const [housePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("house"), ethMarketPda.toBuffer()],
+        program.programId
+      );
+      const [houseVaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("house_vault"), ethMarketPda.toBuffer()],
+        program.programId
+      );
+      const [lpPositionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("lp_position"), ethMarketPda.toBuffer(), authority.toBuffer()],
+        program.programId
+      );
+
+      // Odds better than even money would hand the house's edge to bettors
+      try {
+        await program.methods
+          .setMarketFixedOdds(new BN(25_000), new BN(0))
+          .accounts({ gameState: gameStatePda, market: ethMarketPda, authority })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.toString()).to.include("InvalidFixedOdds");
+      }
+
+      await program.methods
+        .setMarketFixedOdds(new BN(19_000), new BN(LAMPORTS_PER_SOL))
+        .accounts({ gameState: gameStatePda, market: ethMarketPda, authority })
+        .rpc();
+      const market = await program.account.market.fetch(ethMarketPda);
+      expect(market.fixedOddsBps.toNumber()).to.equal(19_000);
+      expect(market.maxRoundExposure.toNumber()).to.equal(LAMPORTS_PER_SOL);
+
+      // The first deposit also funds the vault's rent floor, which never earns shares
+      const rent = await provider.connection.getMinimumBalanceForRentExemption(0);
+      await program.methods
+        .depositLiquidity(new BN(LAMPORTS_PER_SOL))
+        .accounts({
+          market: ethMarketPda,
+          house: housePda,
+          houseVault: houseVaultPda,
+          lpPosition: lpPositionPda,
+          provider: authority,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      let lp = await program.account.lpPosition.fetch(lpPositionPda);
+      expect(lp.shares.toNumber()).to.equal(LAMPORTS_PER_SOL - rent);
+
+      const half = lp.shares.divn(2);
+      await program.methods
+        .withdrawLiquidity(half)
+        .accounts({
+          market: ethMarketPda,
+          house: housePda,
+          houseVault: houseVaultPda,
+          lpPosition: lpPositionPda,
+          provider: authority,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      lp = await program.account.lpPosition.fetch(lpPositionPda);
+      const house = await program.account.house.fetch(housePda);
+      expect(house.totalShares.toString()).to.equal(lp.shares.toString());
+      expect(house.reserved.toNumber()).to.equal(0);
+      const vaultBalance = await provider.connection.getBalance(houseVaultPda);
+      expect(vaultBalance - rent).to.equal(lp.shares.toNumber());
+    });
+
+    it("Fee stream replaces lump-sum withdrawals until stopped", async () => {
+      const treasury = Keypair.generate().publicKey;
+      await program.methods
+        .configureFeeStream(treasury, new BN(1_000))
+        .accounts({
+          gameState: gameStatePda,
+          authority: authority,
+          adminMultisig: null,
+        })
+        .rpc();
+
+      let gameState = await program.account.gameState.fetch(gameStatePda);
+      expect(gameState.feeStreamRate.toNumber()).to.equal(1_000);
+      expect(gameState.feeStreamRecipient.toBase58()).to.equal(
+        treasury.toBase58()
+      );
+
+      try {
+        await program.methods
+          .withdrawFees(new BN(1))
+          .accounts({
+            gameState: gameStatePda,
+            authority: authority,
+            globalVault: globalVaultPda,
+            adminMultisig: null,
+          })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.toString()).to.include("FeeStreamActive");
+      }
+
+      await program.methods
+        .configureFeeStream(treasury, new BN(0))
+        .accounts({
+          gameState: gameStatePda,
+          authority: authority,
+          adminMultisig: null,
+        })
+        .rpc();
+
+      gameState = await program.account.gameState.fetch(gameStatePda);
+      expect(gameState.feeStreamRate.toNumber()).to.equal(0);
+    });
+
+    it("Fee split must total 100%", async () => {
+      const treasury = Keypair.generate().publicKey;
+      const insurance = Keypair.generate().publicKey;
+      const buyback = Keypair.generate().publicKey;
+
+      try {
+        await program.methods
+          .setFeeSplit([
+            { recipient: treasury, bps: new BN(7_000) },
+            { recipient: insurance, bps: new BN(2_000) },
+          ])
+          .accounts({ gameState: gameStatePda, authority: authority, adminMultisig: null })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.toString()).to.include("InvalidFeeSplit");
+      }
+
+      await program.methods
+        .setFeeSplit([
+          { recipient: treasury, bps: new BN(7_000) },
+          { recipient: insurance, bps: new BN(2_000) },
+          { recipient: buyback, bps: new BN(1_000) },
+        ])
+        .accounts({ gameState: gameStatePda, authority: authority, adminMultisig: null })
+        .rpc();
+
+      let gameState = await program.account.gameState.fetch(gameStatePda);
+      expect(gameState.feeSplit.length).to.equal(3);
+      expect(gameState.feeSplit[0].recipient.toString()).to.equal(treasury.toString());
+
+      // Back to paying the authority directly
+      await program.methods
+        .setFeeSplit([])
+        .accounts({ gameState: gameStatePda, authority: authority, adminMultisig: null })
+        .rpc();
+      gameState = await program.account.gameState.fetch(gameStatePda);
+      expect(gameState.feeSplit.length).to.equal(0);
+    });
+
+    it("Timelocked admin changes must wait out the delay", async () => {
+      await program.methods
+        .setAdminTimelock(new BN(24 * 60 * 60))
+        .accounts({ gameState: gameStatePda, authority: authority })
+        .rpc();
+
+      try {
+        await program.methods
+          .setFeeBps(new BN(400))
+          .accounts({ gameState: gameStatePda, authority: authority })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.toString()).to.include("TimelockActive");
+      }
+
+      // Pool caps, oracle and bonus settings are timelocked too
+      try {
+        await program.methods
+          .setPoolCaps(new BN(0), new BN(0), new BN(0))
+          .accounts({ gameState: gameStatePda, authority: authority })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.toString()).to.include("TimelockActive");
+      }
+
+      await program.methods
+        .proposeAdminChange({ feeBps: { feeBps: new BN(400) } })
+        .accounts({ gameState: gameStatePda, authority: authority })
+        .rpc();
+
+      try {
+        await program.methods
+          .executeAdminChange()
+          .accounts({ gameState: gameStatePda, market: null, jackpot: null, authority: authority })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.toString()).to.include("TimelockNotElapsed");
+      }
+
+      await program.methods
+        .cancelAdminChange()
+        .accounts({ gameState: gameStatePda, authority: authority })
+        .rpc();
+
+      const gameState = await program.account.gameState.fetch(gameStatePda);
+      expect(gameState.feeBps.toNumber()).to.equal(500);
+      expect(gameState.pendingAdminChange).to.be.null;
+    });
+  });
+
+  describe("User Balance Functions", () => {
+    it("User can deposit SOL", async () => {
+      const balanceBefore = await provider.connection.getBalance(user.publicKey);
+
+      await program.methods
+        .deposit(new BN(DEPOSIT_AMOUNT))
+        .accounts({
+          userBalance: userBalancePda,
+          vault: vaultPda,
+          user: user.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+
+      const userBalance = await program.account.userBalance.fetch(userBalancePda);
+      expect(userBalance.balance.toNumber()).to.equal(DEPOSIT_AMOUNT);
+      expect(userBalance.owner.toString()).to.equal(user.publicKey.toString());
+
+      const balanceAfter = await provider.connection.getBalance(user.publicKey);
+      expect(balanceAfter).to.be.lessThan(balanceBefore - DEPOSIT_AMOUNT + 10000); // Account for rent
+    });
+
+    it("User can withdraw SOL", async () => {
+      const WITHDRAW_AMOUNT = 0.1 * LAMPORTS_PER_SOL;
+      const balanceBefore = await provider.connection.getBalance(user.publicKey);
+
+      await program.methods
+        .withdraw(new BN(WITHDRAW_AMOUNT))
+        .accounts({
+          userBalance: userBalancePda,
+          vault: vaultPda,
+          user: user.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+
+      const userBalance = await program.account.userBalance.fetch(userBalancePda);
+      expect(userBalance.balance.toNumber()).to.equal(DEPOSIT_AMOUNT - WITHDRAW_AMOUNT);
+
+      const balanceAfter = await provider.connection.getBalance(user.publicKey);
+      expect(balanceAfter).to.be.greaterThan(balanceBefore);
+    });
+
+    it("User can withdraw SOL to another address", async () => {
+      const WITHDRAW_AMOUNT = 0.01 * LAMPORTS_PER_SOL;
+      const destination = Keypair.generate().publicKey;
+      const before = await program.account.userBalance.fetch(userBalancePda);
+
+      await program.methods
+        .withdrawTo(destination, new BN(WITHDRAW_AMOUNT))
+        .accounts({
+          userBalance: userBalancePda,
+          vault: vaultPda,
+          recipient: destination,
+          user: user.publicKey,
+        })
+        .signers([user])
+        .rpc();
+
+      const after = await program.account.userBalance.fetch(userBalancePda);
+      expect(before.balance.sub(after.balance).toNumber()).to.equal(WITHDRAW_AMOUNT);
+      expect(await provider.connection.getBalance(destination)).to.equal(WITHDRAW_AMOUNT);
+    });
+
+    it("Fails to withdraw more than balance", async () => {
+      const TOO_MUCH = 100 * LAMPORTS_PER_SOL;
+
+      try {
+        await program.methods
+          .withdraw(new BN(TOO_MUCH))
+          .accounts({
+            userBalance: userBalancePda,
+            vault: vaultPda,
+            user: user.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([user])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (e: any) {
+        expect(e.message).to.include("InsufficientBalance");
+      }
+    });
+
+    it("Referrers can only be attached on the first deposit", async () => {
+      const referralProgramId = anchor.workspace.Referral.programId;
+      const referrer = Keypair.generate().publicKey;
+      const [referralPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("referral"), user.publicKey.toBuffer()],
+        referralProgramId
+      );
+      const [earningsPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("referrer_earnings"), referrer.toBuffer()],
+        referralProgramId
+      );
+
+      try {
+        await program.methods
+          .depositWithReferrer(new BN(DEPOSIT_AMOUNT), referrer)
+          .accounts({
+            userBalance: userBalancePda,
+            vault: vaultPda,
+            referral: referralPda,
+            referrerEarnings: earningsPda,
+            user: user.publicKey,
+          })
+          .signers([user])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.toString()).to.include("NotFirstDeposit");
+      }
+    });
+
+    it("Anyone can fund another wallet's balance", async () => {
+      const recipient = Keypair.generate().publicKey;
+      const [recipientBalancePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("balance"), recipient.toBuffer()],
+        program.programId
+      );
+      const [recipientVaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), recipient.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .depositFor(recipient, new BN(BET_AMOUNT))
+        .accounts({
+          userBalance: recipientBalancePda,
+          vault: recipientVaultPda,
+          payer: authority,
+        })
+        .rpc();
+
+      const balance = await program.account.userBalance.fetch(recipientBalancePda);
+      expect(balance.owner.toString()).to.equal(recipient.toString());
+      expect(balance.balance.toNumber()).to.equal(BET_AMOUNT);
+      expect(await provider.connection.getBalance(recipientVaultPda)).to.equal(BET_AMOUNT);
+    });
+
+    it("Closing a balance withdraws everything and refunds rent", async () => {
+      const leaver = Keypair.generate();
+      const sig = await provider.connection.requestAirdrop(
+        leaver.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig);
+      const [leaverBalancePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("balance"), leaver.publicKey.toBuffer()],
+        program.programId
+      );
+      const [leaverVaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), leaver.publicKey.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .deposit(new BN(BET_AMOUNT))
+        .accounts({
+          userBalance: leaverBalancePda,
+          vault: leaverVaultPda,
+          user: leaver.publicKey,
+        })
+        .signers([leaver])
+        .rpc();
+
+      await program.methods
+        .closeBalance()
+        .accounts({
+          userBalance: leaverBalancePda,
+          vault: leaverVaultPda,
+          user: leaver.publicKey,
+        })
+        .signers([leaver])
+        .rpc();
+
+      expect(await provider.connection.getAccountInfo(leaverBalancePda)).to.be.null;
+      expect(await provider.connection.getBalance(leaverVaultPda)).to.equal(0);
+    });
+
+    it("Tightens personal limits now but queues loosening", async () => {
+      // Well above max_bet so later bets from this user are unaffected
+      const personalMax = 100 * LAMPORTS_PER_SOL;
+      await program.methods
+        .setPersonalLimits(new BN(personalMax), new BN(0))
+        .accounts({ userBalance: userBalancePda, user: user.publicKey })
+        .signers([user])
+        .rpc();
+
+      await program.methods
+        .setPersonalLimits(new BN(0), new BN(0))
+        .accounts({ userBalance: userBalancePda, user: user.publicKey })
+        .signers([user])
+        .rpc();
+
+      const balance = await program.account.userBalance.fetch(userBalancePda);
+      expect(balance.personalLimits.maxBet.toNumber()).to.equal(personalMax);
+      expect(balance.personalLimits.pendingMaxBet.toNumber()).to.equal(0);
+      expect(balance.personalLimits.pendingAt.toNumber()).to.be.greaterThan(0);
+    });
+
+    it("Authority can cap a single user's bets", async () => {
+      await program.methods
+        .setUserMaxBet(new BN(BET_AMOUNT))
+        .accounts({ gameState: gameStatePda, userBalance: userBalancePda, authority })
+        .rpc();
+      let balance = await program.account.userBalance.fetch(userBalancePda);
+      expect(balance.maxBetOverride.toNumber()).to.equal(BET_AMOUNT);
+
+      await program.methods
+        .setUserMaxBet(new BN(0))
+        .accounts({ gameState: gameStatePda, userBalance: userBalancePda, authority })
+        .rpc();
+      balance = await program.account.userBalance.fetch(userBalancePda);
+      expect(balance.maxBetOverride.toNumber()).to.equal(0);
+    });
+  });
+
+  describe("Session Key Functions", () => {
+    it("User can create a session", async () => {
+      const validUntil = Math.floor(Date.now() / 1000) + 3600; // 1 hour from now
+
+      await program.methods
+        .createSession(new BN(validUntil), new BN(0), SESSION_SCOPE_ALL, new BN(0))
+        .accounts({
+          sessionToken: sessionPda,
+          authority: user.publicKey,
+          sessionSigner: sessionKeypair.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+
+      const session = await program.account.sessionToken.fetch(sessionPda);
+      expect(session.authority.toString()).to.equal(user.publicKey.toString());
+      expect(session.sessionSigner.toString()).to.equal(sessionKeypair.publicKey.toString());
+      expect(session.validUntil.toNumber()).to.equal(validUntil);
+      expect(session.maxBetPerTx.toNumber()).to.equal(0);
+      expect(session.allowedInstructions.toNumber()).to.equal(0x1ff);
+      expect(session.spent.toNumber()).to.equal(0);
+    });
+
+    it("User can extend a session", async () => {
+      const newValidUntil = Math.floor(Date.now() / 1000) + 7200;
+
+      await program.methods
+        .extendSession(new BN(newValidUntil))
+        .accounts({ sessionToken: sessionPda, authority: user.publicKey })
+        .signers([user])
+        .rpc();
+
+      const session = await program.account.sessionToken.fetch(sessionPda);
+      expect(session.validUntil.toNumber()).to.equal(newValidUntil);
+    });
+
+    it("Can revoke a session", async () => {
+      // First create a new session to revoke
+      const tempSession = Keypair.generate();
+      const [tempSessionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("session"), user.publicKey.toBuffer(), tempSession.publicKey.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .createSession(new BN(Math.floor(Date.now() / 1000) + 3600), new BN(0), SESSION_SCOPE_ALL, new BN(0))
+        .accounts({
+          sessionToken: tempSessionPda,
+          authority: user.publicKey,
+          sessionSigner: tempSession.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+
+      // Now revoke it
+      await program.methods
+        .revokeSession()
+        .accounts({
+          sessionToken: tempSessionPda,
+          sessionRegistry: sessionRegistryPda,
+          authority: user.publicKey,
+        })
+        .signers([user])
+        .rpc();
+
+      // Verify it's closed
+      try {
+        await program.account.sessionToken.fetch(tempSessionPda);
+        expect.fail("Account should be closed");
+      } catch (e: any) {
+        expect(e.message).to.include("Account does not exist");
+      }
+      const registry = await program.account.sessionRegistry.fetch(sessionRegistryPda);
+      expect(registry.signers.map((k) => k.toString())).to.not.include(
+        tempSession.publicKey.toString()
+      );
+    });
+  });
+
+  describe("Copy-Trading Functions", () => {
+    it("User can follow a leader with a size cap", async () => {
+      const leader = Keypair.generate();
+      const [copyConfigPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("copy"), user.publicKey.toBuffer(), leader.publicKey.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .createCopyConfig(leader.publicKey, new BN(BET_AMOUNT))
+        .accounts({
+          copyConfig: copyConfigPda,
+          follower: user.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+
+      const copyConfig = await program.account.copyConfig.fetch(copyConfigPda);
+      expect(copyConfig.follower.toString()).to.equal(user.publicKey.toString());
+      expect(copyConfig.leader.toString()).to.equal(leader.publicKey.toString());
+      expect(copyConfig.maxBet.toNumber()).to.equal(BET_AMOUNT);
+
+      await program.methods
+        .closeCopyConfig()
+        .accounts({
+          copyConfig: copyConfigPda,
+          follower: user.publicKey,
+        })
+        .signers([user])
+        .rpc();
+    });
+  });
+
+  describe("Betting Functions", () => {
+    let roundId: BN;
+
+    before(async () => {
+      // Start a new round for betting tests
+      const market = await program.account.market.fetch(marketPda);
+      roundId = market.currentRound;
+
+      [roundPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("round"), marketPda.toBuffer(), roundId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      [poolPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("pool"), marketPda.toBuffer(), roundId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
 
       [positionPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("position"), roundId.toArrayLike(Buffer, "le", 8), user.publicKey.toBuffer()],
+        [Buffer.from("position"), marketPda.toBuffer(), roundId.toArrayLike(Buffer, "le", 8), user.publicKey.toBuffer(), Buffer.from([0])],
         program.programId
       );
 
       await program.methods
-        .startRound(START_PRICE)
+        .startRound({ spot: {} })
         .accounts({
           gameState: gameStatePda,
+          market: marketPda,
           round: roundPda,
           pool: poolPda,
+          priceFeed: PYTH_BTC_USD,
           authority: authority,
           systemProgram: SystemProgram.programId,
         })
@@ -321,15 +1248,20 @@ describe("session_betting", () => {
 
     it("User can place bet with wallet signature", async () => {
       const userBalanceBefore = await program.account.userBalance.fetch(userBalancePda);
+      const [userStatsPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_stats"), user.publicKey.toBuffer()],
+        program.programId
+      );
 
       await program.methods
-        .placeBet({ up: {} }, new BN(BET_AMOUNT))
+        .placeBet({ up: {} }, new BN(BET_AMOUNT), 0)
         .accounts({
           gameState: gameStatePda,
           round: roundPda,
           pool: poolPda,
           userBalance: userBalancePda,
           position: positionPda,
+          userStats: userStatsPda,
           sessionToken: null,
           signer: user.publicKey,
           systemProgram: SystemProgram.programId,
@@ -345,10 +1277,101 @@ describe("session_betting", () => {
       const pool = await program.account.bettingPool.fetch(poolPda);
       expect(pool.upPool.toNumber()).to.equal(BET_AMOUNT);
       expect(pool.totalPool.toNumber()).to.equal(BET_AMOUNT);
+      expect(pool.upBettors).to.equal(1);
+      expect(pool.downBettors).to.equal(0);
 
       const position = await program.account.playerPosition.fetch(positionPda);
       expect(position.side).to.deep.equal({ up: {} });
       expect(position.amount.toNumber()).to.equal(BET_AMOUNT);
+      expect(position.betTimestamp.toNumber()).to.be.greaterThan(0);
+
+      const stats = await program.account.userStats.fetch(userStatsPda);
+      expect(stats.owner.toString()).to.equal(user.publicKey.toString());
+      expect(stats.roundsPlayed.toNumber()).to.equal(1);
+      expect(stats.totalVolume.toNumber()).to.equal(BET_AMOUNT);
+      // Placing a bet never moves the streak; only resolving a round does
+      expect(stats.currentStreak).to.equal(0);
+      expect(stats.streakMarket.toString()).to.equal(PublicKey.default.toString());
+    });
+
+    it("Increasing a bet is capped by max bet on the combined stake", async () => {
+      const gameState = await program.account.gameState.fetch(gameStatePda);
+      try {
+        await program.methods
+          .increaseBet(gameState.maxBet)
+          .accounts({
+            gameState: gameStatePda,
+            round: roundPda,
+            pool: poolPda,
+            userBalance: userBalancePda,
+            position: positionPda,
+            sessionToken: null,
+            signer: user.publicKey,
+          })
+          .signers([user])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.toString()).to.include("AmountTooLarge");
+      }
+    });
+
+    it("Previews payout with the same math as claim", async () => {
+      const preview = await program.methods
+        .previewPayout()
+        .accounts({
+          gameState: gameStatePda,
+          round: roundPda,
+          pool: poolPda,
+          position: positionPda,
+        })
+        .view();
+
+      // Only the UP side has bets: a one-sided round refunds the full stake
+      expect(preview.payout.toNumber()).to.equal(BET_AMOUNT);
+      expect(preview.fee.toNumber()).to.equal(0);
+      expect(preview.won).to.be.false;
+    });
+
+    it("Claim and withdraw requires a settled round", async () => {
+      try {
+        await program.methods
+          .claimAndWithdraw(new BN(BET_AMOUNT))
+          .accounts({
+            gameState: gameStatePda,
+            round: roundPda,
+            pool: poolPda,
+            userBalance: userBalancePda,
+            position: positionPda,
+            vault: vaultPda,
+            user: user.publicKey,
+          })
+          .signers([user])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.toString()).to.include("RoundNotSettled");
+      }
+    });
+
+    it("Open positions cannot be closed", async () => {
+      try {
+        await program.methods
+          .closePosition()
+          .accounts({
+            gameState: gameStatePda,
+            round: roundPda,
+            pool: poolPda,
+            position: positionPda,
+            player: user.publicKey,
+            signer: user.publicKey,
+          })
+          .signers([user])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.toString()).to.include("RoundNotSettled");
+      }
     });
 
     it("User can place bet with session key", async () => {
@@ -382,7 +1405,7 @@ describe("session_betting", () => {
         program.programId
       );
       const [position2Pda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("position"), roundId.toArrayLike(Buffer, "le", 8), user2.publicKey.toBuffer()],
+        [Buffer.from("position"), marketPda.toBuffer(), roundId.toArrayLike(Buffer, "le", 8), user2.publicKey.toBuffer(), Buffer.from([0])],
         program.programId
       );
 
@@ -400,7 +1423,7 @@ describe("session_betting", () => {
 
       // Create session
       await program.methods
-        .createSession(new BN(Math.floor(Date.now() / 1000) + 3600))
+        .createSession(new BN(Math.floor(Date.now() / 1000) + 3600), new BN(0), SESSION_SCOPE_ALL, new BN(0))
         .accounts({
           sessionToken: session2Pda,
           authority: user2.publicKey,
@@ -410,80 +1433,398 @@ describe("session_betting", () => {
         .signers([user2])
         .rpc();
 
-      // Place bet with session key (session key signs, not user wallet)
+      // Place bet with session key (session key signs, not user wallet)
+      await program.methods
+        .placeBet({ down: {} }, new BN(BET_AMOUNT), 0)
+        .accounts({
+          gameState: gameStatePda,
+          round: roundPda,
+          pool: poolPda,
+          userBalance: balance2Pda,
+          position: position2Pda,
+          sessionToken: session2Pda,
+          sessionRegistry: PublicKey.findProgramAddressSync(
+            [Buffer.from("session_registry"), user2.publicKey.toBuffer()],
+            program.programId
+          )[0],
+          signer: session2.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([session2]) // Session key signs, not user wallet!
+        .rpc();
+
+      const position = await program.account.playerPosition.fetch(position2Pda);
+      expect(position.side).to.deep.equal({ down: {} });
+      expect(position.player.toString()).to.equal(user2.publicKey.toString());
+
+      const pool = await program.account.bettingPool.fetch(poolPda);
+      expect(pool.downPool.toNumber()).to.equal(BET_AMOUNT);
+      expect(pool.downBettors).to.equal(1);
+    });
+
+    it("Fails bet amount below minimum", async () => {
+      const user3 = Keypair.generate();
+      const sig = await provider.connection.requestAirdrop(
+        user3.publicKey,
+        2 * LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig);
+
+      const [balance3Pda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("balance"), user3.publicKey.toBuffer()],
+        program.programId
+      );
+      const [vault3Pda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), user3.publicKey.toBuffer()],
+        program.programId
+      );
+      const [position3Pda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("position"), marketPda.toBuffer(), roundId.toArrayLike(Buffer, "le", 8), user3.publicKey.toBuffer(), Buffer.from([0])],
+        program.programId
+      );
+
+      await program.methods
+        .deposit(new BN(0.1 * LAMPORTS_PER_SOL))
+        .accounts({
+          userBalance: balance3Pda,
+          vault: vault3Pda,
+          user: user3.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user3])
+        .rpc();
+
+      try {
+        await program.methods
+          .placeBet({ up: {} }, new BN(1000), 0) // Way below minimum
+          .accounts({
+            gameState: gameStatePda,
+            round: roundPda,
+            pool: poolPda,
+            userBalance: balance3Pda,
+            position: position3Pda,
+            sessionToken: null,
+            signer: user3.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([user3])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (e: any) {
+        expect(e.message).to.include("AmountTooSmall");
+      }
+    });
+
+    it("User can commit a hidden bet", async () => {
+      const user4 = Keypair.generate();
+      const sig = await provider.connection.requestAirdrop(
+        user4.publicKey,
+        2 * LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig);
+
+      const [balance4Pda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("balance"), user4.publicKey.toBuffer()],
+        program.programId
+      );
+      const [vault4Pda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), user4.publicKey.toBuffer()],
+        program.programId
+      );
+      const [position4Pda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("position"), marketPda.toBuffer(), roundId.toArrayLike(Buffer, "le", 8), user4.publicKey.toBuffer(), Buffer.from([0])],
+        program.programId
+      );
+
+      await program.methods
+        .deposit(new BN(DEPOSIT_AMOUNT))
+        .accounts({
+          userBalance: balance4Pda,
+          vault: vault4Pda,
+          user: user4.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user4])
+        .rpc();
+
+      // commitment = sha256(side || salt || owner), side 0 = Up, 1 = Down
+      const salt = randomBytes(32);
+      const commitment = createHash("sha256")
+        .update(Buffer.concat([Buffer.from([1]), salt, user4.publicKey.toBuffer()]))
+        .digest();
+
+      await program.methods
+        .commitBet([...commitment], new BN(BET_AMOUNT), 0)
+        .accounts({
+          gameState: gameStatePda,
+          round: roundPda,
+          pool: poolPda,
+          userBalance: balance4Pda,
+          position: position4Pda,
+          sessionToken: null,
+          signer: user4.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user4])
+        .rpc();
+
+      const position = await program.account.playerPosition.fetch(position4Pda);
+      expect(position.revealed).to.be.false;
+      expect(position.commitment).to.deep.equal([...commitment]);
+
+      // Hidden stake does not show up on either side until revealed
+      const pool = await program.account.bettingPool.fetch(poolPda);
+      expect(pool.unrevealedPool.toNumber()).to.equal(BET_AMOUNT);
+      expect(pool.downPool.toNumber()).to.equal(BET_AMOUNT);
+
+      // Reveals are accepted only while the round is still open
+      await program.methods
+        .revealBet({ down: {} }, [...salt])
+        .accounts({
+          round: roundPda,
+          pool: poolPda,
+          userBalance: balance4Pda,
+          position: position4Pda,
+          sessionToken: null,
+          sessionRegistry: null,
+          signer: user4.publicKey,
+        })
+        .signers([user4])
+        .rpc();
+
+      const revealed = await program.account.playerPosition.fetch(position4Pda);
+      expect(revealed.revealed).to.be.true;
+      const revealedPool = await program.account.bettingPool.fetch(poolPda);
+      expect(revealedPool.unrevealedPool.toNumber()).to.equal(0);
+      expect(revealedPool.downPool.toNumber()).to.equal(2 * BET_AMOUNT);
+    });
+
+    it("A wallet can hold several positions in one round", async () => {
+      const gameState = await program.account.gameState.fetch(gameStatePda);
+      const [secondPositionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("position"), marketPda.toBuffer(), roundId.toArrayLike(Buffer, "le", 8), user.publicKey.toBuffer(), Buffer.from([1])],
+        program.programId
+      );
+
+      await program.methods
+        .placeBet({ down: {} }, gameState.minBet, 1)
+        .accounts({
+          gameState: gameStatePda,
+          round: roundPda,
+          pool: poolPda,
+          userBalance: userBalancePda,
+          position: secondPositionPda,
+          sessionToken: null,
+          signer: user.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+
+      const first = await program.account.playerPosition.fetch(positionPda);
+      const second = await program.account.playerPosition.fetch(secondPositionPda);
+      expect(first.index).to.equal(0);
+      expect(second.index).to.equal(1);
+      expect(second.side).to.deep.equal({ down: {} });
+    });
+
+    it("A wallet's extra positions on a side do not count as new bettors", async () => {
+      const gameState = await program.account.gameState.fetch(gameStatePda);
+      const [thirdPositionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("position"), marketPda.toBuffer(), roundId.toArrayLike(Buffer, "le", 8), user.publicKey.toBuffer(), Buffer.from([2])],
+        program.programId
+      );
+      const [playerRoundPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("player_round"), marketPda.toBuffer(), roundId.toArrayLike(Buffer, "le", 8), user.publicKey.toBuffer()],
+        program.programId
+      );
+      const poolBefore = await program.account.bettingPool.fetch(poolPda);
+
+      await program.methods
+        .placeBet({ down: {} }, gameState.minBet, 2)
+        .accounts({
+          gameState: gameStatePda,
+          round: roundPda,
+          pool: poolPda,
+          userBalance: userBalancePda,
+          position: thirdPositionPda,
+          sessionToken: null,
+          signer: user.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+
+      const poolAfter = await program.account.bettingPool.fetch(poolPda);
+      expect(poolAfter.downBettors).to.equal(poolBefore.downBettors);
+
+      const playerRound = await program.account.playerRound.fetch(playerRoundPda);
+      expect(playerRound.downStake.toString()).to.equal(gameState.minBet.muln(2).toString());
+    });
+
+    it("A leader cannot cancel a bet that has been copied", async () => {
+      const follower = Keypair.generate();
+      const sig = await provider.connection.requestAirdrop(follower.publicKey, LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+      const [followerBalancePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("balance"), follower.publicKey.toBuffer()],
+        program.programId
+      );
+      const [followerVaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), follower.publicKey.toBuffer()],
+        program.programId
+      );
+      const [copyConfigPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("copy"), follower.publicKey.toBuffer(), user.publicKey.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .deposit(new BN(DEPOSIT_AMOUNT))
+        .accounts({ userBalance: followerBalancePda, vault: followerVaultPda, user: follower.publicKey })
+        .signers([follower])
+        .rpc();
+      await program.methods
+        .createCopyConfig(user.publicKey, new BN(BET_AMOUNT))
+        .accounts({ copyConfig: copyConfigPda, follower: follower.publicKey, systemProgram: SystemProgram.programId })
+        .signers([follower])
+        .rpc();
+      await program.methods
+        .executeCopyBet()
+        .accounts({
+          round: roundPda,
+          pool: poolPda,
+          copyConfig: copyConfigPda,
+          leaderPosition: positionPda,
+          caller: authority,
+        })
+        .rpc();
+
+      const position = await program.account.playerPosition.fetch(positionPda);
+      expect(position.copied).to.be.true;
+
+      try {
+        await program.methods
+          .cancelBet()
+          .accounts({
+            round: roundPda,
+            pool: poolPda,
+            userBalance: userBalancePda,
+            position: positionPda,
+            player: user.publicKey,
+            sessionToken: null,
+            sessionRegistry: null,
+            signer: user.publicKey,
+          })
+          .signers([user])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.toString()).to.include("PositionCopied");
+      }
+    });
+  });
+
+  describe("Parlay Functions", () => {
+    let parlayVaultPda: PublicKey;
+
+    before(async () => {
+      [parlayVaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("parlay_vault")],
+        program.programId
+      );
+
       await program.methods
-        .placeBet({ down: {} }, new BN(BET_AMOUNT))
+        .fundParlayVault(new BN(2 * LAMPORTS_PER_SOL))
         .accounts({
           gameState: gameStatePda,
-          round: roundPda,
-          pool: poolPda,
-          userBalance: balance2Pda,
-          position: position2Pda,
-          sessionToken: session2Pda,
-          signer: session2.publicKey,
+          authority: authority,
+          parlayVault: parlayVaultPda,
           systemProgram: SystemProgram.programId,
         })
-        .signers([session2]) // Session key signs, not user wallet!
         .rpc();
-
-      const position = await program.account.playerPosition.fetch(position2Pda);
-      expect(position.side).to.deep.equal({ down: {} });
-      expect(position.player.toString()).to.equal(user2.publicKey.toString());
-
-      const pool = await program.account.bettingPool.fetch(poolPda);
-      expect(pool.downPool.toNumber()).to.equal(BET_AMOUNT);
     });
 
-    it("Fails bet amount below minimum", async () => {
-      const user3 = Keypair.generate();
-      const sig = await provider.connection.requestAirdrop(
-        user3.publicKey,
-        2 * LAMPORTS_PER_SOL
-      );
-      await provider.connection.confirmTransaction(sig);
-
-      const [balance3Pda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("balance"), user3.publicKey.toBuffer()],
-        program.programId
-      );
-      const [vault3Pda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("vault"), user3.publicKey.toBuffer()],
-        program.programId
-      );
-      const [position3Pda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("position"), roundId.toArrayLike(Buffer, "le", 8), user3.publicKey.toBuffer()],
+    it("User can place a parlay on the next rounds", async () => {
+      const market = await program.account.market.fetch(marketPda);
+      const [parlayPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("parlay"),
+          user.publicKey.toBuffer(),
+          marketPda.toBuffer(),
+          market.currentRound.toArrayLike(Buffer, "le", 8),
+        ],
         program.programId
       );
 
       await program.methods
-        .deposit(new BN(0.1 * LAMPORTS_PER_SOL))
+        .placeParlay([{ up: {} }, { down: {} }], new BN(BET_AMOUNT))
         .accounts({
-          userBalance: balance3Pda,
-          vault: vault3Pda,
-          user: user3.publicKey,
+          gameState: gameStatePda,
+          userBalance: userBalancePda,
+          userVault: vaultPda,
+          parlayVault: parlayVaultPda,
+          market: marketPda,
+          parlay: parlayPda,
+          sessionToken: null,
+          signer: user.publicKey,
           systemProgram: SystemProgram.programId,
         })
-        .signers([user3])
+        .signers([user])
         .rpc();
 
+      const parlay = await program.account.parlay.fetch(parlayPda);
+      expect(parlay.startRound.toString()).to.equal(market.currentRound.toString());
+      expect(parlay.picks.length).to.equal(2);
+      expect(parlay.settled).to.be.false;
+
+      // 1.9x per leg: 0.1 SOL * 1.9 * 1.9 = 0.361 SOL
+      expect(parlay.maxPayout.toNumber()).to.equal(0.361 * LAMPORTS_PER_SOL);
+
+      const gameStateAfter = await program.account.gameState.fetch(gameStatePda);
+      expect(gameStateAfter.parlayExposure.toNumber()).to.equal(parlay.maxPayout.toNumber());
+    });
+
+    it("An open parlay can be neither refunded early nor closed", async () => {
+      const [open] = (await program.account.parlay.all()).filter(
+        (p) => p.account.owner.equals(user.publicKey) && !p.account.settled
+      );
+      const legs = open.account.picks.map((_, i) => ({
+        pubkey: PublicKey.findProgramAddressSync(
+          [Buffer.from("round"), marketPda.toBuffer(), open.account.startRound.addn(i).toArrayLike(Buffer, "le", 8)],
+          program.programId
+        )[0],
+        isWritable: false,
+        isSigner: false,
+      }));
+
       try {
         await program.methods
-          .placeBet({ up: {} }, new BN(1000)) // Way below minimum
+          .refundParlay()
           .accounts({
             gameState: gameStatePda,
-            round: roundPda,
-            pool: poolPda,
-            userBalance: balance3Pda,
-            position: position3Pda,
-            sessionToken: null,
-            signer: user3.publicKey,
+            parlay: open.publicKey,
+            userBalance: userBalancePda,
+            userVault: vaultPda,
+            parlayVault: parlayVaultPda,
+            caller: authority,
             systemProgram: SystemProgram.programId,
           })
-          .signers([user3])
+          .remainingAccounts(legs)
           .rpc();
-        expect.fail("Should have thrown error");
+        expect.fail("Should have thrown");
       } catch (e: any) {
-        expect(e.message).to.include("AmountTooSmall");
+        expect(e.toString()).to.include("ParlayNotExpired");
+      }
+
+      try {
+        await program.methods
+          .closeParlay()
+          .accounts({ parlay: open.publicKey, owner: user.publicKey, signer: authority })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.toString()).to.include("ParlayNotSettled");
       }
     });
   });
@@ -524,25 +1865,27 @@ describe("session_betting", () => {
       );
       await provider.connection.confirmTransaction(sig);
 
-      const gameState = await program.account.gameState.fetch(gameStatePda);
-      const roundId = gameState.currentRound;
+      const market = await program.account.market.fetch(marketPda);
+      const roundId = market.currentRound;
 
       const [attackRoundPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("round"), roundId.toArrayLike(Buffer, "le", 8)],
+        [Buffer.from("round"), marketPda.toBuffer(), roundId.toArrayLike(Buffer, "le", 8)],
         program.programId
       );
       const [attackPoolPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("pool"), roundId.toArrayLike(Buffer, "le", 8)],
+        [Buffer.from("pool"), marketPda.toBuffer(), roundId.toArrayLike(Buffer, "le", 8)],
         program.programId
       );
 
       try {
         await program.methods
-          .startRound(START_PRICE)
+          .startRound({ spot: {} })
           .accounts({
             gameState: gameStatePda,
+            market: marketPda,
             round: attackRoundPda,
             pool: attackPoolPda,
+            priceFeed: PYTH_BTC_USD,
             authority: attacker.publicKey,
             systemProgram: SystemProgram.programId,
           })
@@ -557,6 +1900,142 @@ describe("session_betting", () => {
       }
     });
 
+    it("Non-authority cannot cancel a round before the delay", async () => {
+      const attacker = Keypair.generate();
+      const sig = await provider.connection.requestAirdrop(
+        attacker.publicKey,
+        0.1 * LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig);
+
+      try {
+        await program.methods
+          .cancelRound()
+          .accounts({
+            gameState: gameStatePda,
+            round: roundPda,
+            caller: attacker.publicKey,
+          })
+          .signers([attacker])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.toString()).to.include("CancelTooEarly");
+      }
+    });
+
+    it("Cannot force-settle a round that is not locked", async () => {
+      try {
+        await program.methods
+          .forceSettle()
+          .accounts({
+            gameState: gameStatePda,
+            round: roundPda,
+            pool: poolPda,
+            caller: authority,
+          })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.toString()).to.include("RoundNotLocked");
+      }
+    });
+
+    it("Cannot advance past a round that is not locked", async () => {
+      const market = await program.account.market.fetch(marketPda);
+      const nextId = market.currentRound;
+      const [nextRoundPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("round"), marketPda.toBuffer(), nextId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [nextPoolPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("pool"), marketPda.toBuffer(), nextId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .advanceRound({ spot: {} })
+          .accounts({
+            gameState: gameStatePda,
+            market: marketPda,
+            round: roundPda,
+            pool: poolPda,
+            nextRound: nextRoundPda,
+            nextPool: nextPoolPda,
+            roundHistory: null,
+            priceFeed: PYTH_BTC_USD,
+            authority: authority,
+          })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.toString()).to.include("RoundNotLocked");
+      }
+    });
+
+    it("Non-operator cannot push settlement credits", async () => {
+      const attacker = Keypair.generate();
+      const sig = await provider.connection.requestAirdrop(
+        attacker.publicKey,
+        0.1 * LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig);
+      const [userStatsPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_stats"), user.publicKey.toBuffer()],
+        program.programId
+      );
+      const [referralPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("referral"), user.publicKey.toBuffer()],
+        anchor.workspace.Referral.programId
+      );
+
+      try {
+        await program.methods
+          .settleAndCredit()
+          .accounts({
+            gameState: gameStatePda,
+            round: roundPda,
+            pool: poolPda,
+            roundHistory: null,
+            caller: attacker.publicKey,
+          })
+          .remainingAccounts([
+            { pubkey: positionPda, isWritable: true, isSigner: false },
+            { pubkey: userBalancePda, isWritable: true, isSigner: false },
+            { pubkey: userStatsPda, isWritable: true, isSigner: false },
+            { pubkey: referralPda, isWritable: false, isSigner: false },
+            { pubkey: referralPda, isWritable: true, isSigner: false },
+          ])
+          .signers([attacker])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.toString()).to.include("Unauthorized");
+      }
+    });
+
+    it("Emergency refunds require a long pause", async () => {
+      try {
+        await program.methods
+          .emergencyRefund()
+          .accounts({
+            gameState: gameStatePda,
+            round: roundPda,
+            pool: poolPda,
+            userBalance: userBalancePda,
+            position: positionPda,
+            player: user.publicKey,
+            signer: user.publicKey,
+          })
+          .signers([user])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.toString()).to.include("NotPausedLongEnough");
+      }
+    });
+
     it("Session expired cannot be used for betting", async () => {
       // Create a session that expires in the past
       const expiredSession = Keypair.generate();
@@ -569,7 +2048,7 @@ describe("session_betting", () => {
 
       try {
         await program.methods
-          .createSession(new BN(pastTimestamp))
+          .createSession(new BN(pastTimestamp), new BN(0), SESSION_SCOPE_ALL, new BN(0))
           .accounts({
             sessionToken: expiredSessionPda,
             authority: user.publicKey,
@@ -620,26 +2099,26 @@ describe("session_betting", () => {
         .signers([user2])
         .rpc();
 
-      const gameState = await program.account.gameState.fetch(gameStatePda);
-      const roundId = gameState.currentRound.subn(1); // Use current active round
+      const market = await program.account.market.fetch(marketPda);
+      const roundId = market.currentRound.subn(1); // Use current active round
 
       const [roundPdaTest] = PublicKey.findProgramAddressSync(
-        [Buffer.from("round"), roundId.toArrayLike(Buffer, "le", 8)],
+        [Buffer.from("round"), marketPda.toBuffer(), roundId.toArrayLike(Buffer, "le", 8)],
         program.programId
       );
       const [poolPdaTest] = PublicKey.findProgramAddressSync(
-        [Buffer.from("pool"), roundId.toArrayLike(Buffer, "le", 8)],
+        [Buffer.from("pool"), marketPda.toBuffer(), roundId.toArrayLike(Buffer, "le", 8)],
         program.programId
       );
       const [position2Pda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("position"), roundId.toArrayLike(Buffer, "le", 8), user2.publicKey.toBuffer()],
+        [Buffer.from("position"), marketPda.toBuffer(), roundId.toArrayLike(Buffer, "le", 8), user2.publicKey.toBuffer(), Buffer.from([0])],
         program.programId
       );
 
       // Try to use user1's session to bet from user2's balance
       try {
         await program.methods
-          .placeBet({ up: {} }, new BN(BET_AMOUNT))
+          .placeBet({ up: {} }, new BN(BET_AMOUNT), 0)
           .accounts({
             gameState: gameStatePda,
             round: roundPdaTest,
@@ -663,5 +2142,172 @@ describe("session_betting", () => {
         );
       }
     });
+
+    it("Session keys cannot act outside their scope", async () => {
+      const scopedSession = Keypair.generate();
+      const [scopedSessionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("session"), user.publicKey.toBuffer(), scopedSession.publicKey.toBuffer()],
+        program.programId
+      );
+      const sig = await provider.connection.requestAirdrop(
+        scopedSession.publicKey,
+        0.1 * LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig);
+
+      // Only ENTRYPOINT_CANCEL_BET
+      await program.methods
+        .createSession(new BN(Math.floor(Date.now() / 1000) + 3600), new BN(0), new BN(1 << 6), new BN(0))
+        .accounts({
+          sessionToken: scopedSessionPda,
+          authority: user.publicKey,
+          sessionSigner: scopedSession.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+
+      const market = await program.account.market.fetch(marketPda);
+      const roundId = market.currentRound.subn(1);
+      const [roundPdaTest] = PublicKey.findProgramAddressSync(
+        [Buffer.from("round"), marketPda.toBuffer(), roundId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [poolPdaTest] = PublicKey.findProgramAddressSync(
+        [Buffer.from("pool"), marketPda.toBuffer(), roundId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [scopedPositionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("position"), marketPda.toBuffer(), roundId.toArrayLike(Buffer, "le", 8), user.publicKey.toBuffer(), Buffer.from([7])],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .placeBet({ up: {} }, new BN(BET_AMOUNT), 7)
+          .accounts({
+            gameState: gameStatePda,
+            round: roundPdaTest,
+            pool: poolPdaTest,
+            userBalance: userBalancePda,
+            position: scopedPositionPda,
+            sessionToken: scopedSessionPda,
+            sessionRegistry: sessionRegistryPda,
+            signer: scopedSession.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([scopedSession])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.toString()).to.include("SessionScopeExceeded");
+      }
+    });
+
+    it("Session keys cannot stake past their spend limit", async () => {
+      const cappedSession = Keypair.generate();
+      const [cappedSessionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("session"), user.publicKey.toBuffer(), cappedSession.publicKey.toBuffer()],
+        program.programId
+      );
+      const sig = await provider.connection.requestAirdrop(
+        cappedSession.publicKey,
+        0.1 * LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig);
+
+      await program.methods
+        .createSession(
+          new BN(Math.floor(Date.now() / 1000) + 3600),
+          new BN(0),
+          SESSION_SCOPE_ALL,
+          new BN(BET_AMOUNT - 1)
+        )
+        .accounts({
+          sessionToken: cappedSessionPda,
+          authority: user.publicKey,
+          sessionSigner: cappedSession.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+
+      const market = await program.account.market.fetch(marketPda);
+      const roundId = market.currentRound.subn(1);
+      const [roundPdaTest] = PublicKey.findProgramAddressSync(
+        [Buffer.from("round"), marketPda.toBuffer(), roundId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [poolPdaTest] = PublicKey.findProgramAddressSync(
+        [Buffer.from("pool"), marketPda.toBuffer(), roundId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [cappedPositionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("position"), marketPda.toBuffer(), roundId.toArrayLike(Buffer, "le", 8), user.publicKey.toBuffer(), Buffer.from([8])],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .placeBet({ up: {} }, new BN(BET_AMOUNT), 8)
+          .accounts({
+            gameState: gameStatePda,
+            round: roundPdaTest,
+            pool: poolPdaTest,
+            userBalance: userBalancePda,
+            position: cappedPositionPda,
+            sessionToken: cappedSessionPda,
+            sessionRegistry: sessionRegistryPda,
+            signer: cappedSession.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([cappedSession])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.toString()).to.include("SessionSpendLimitExceeded");
+      }
+    });
+
+    it("Only the wallet can let a session key bet tokens", async () => {
+      const before = await program.account.sessionToken.fetch(sessionPda);
+      expect(before.tokenSpendLimit.toNumber()).to.equal(0);
+
+      try {
+        await program.methods
+          .setSessionTokenLimit(new BN(1_000_000))
+          .accounts({ sessionToken: sessionPda, authority: sessionKeypair.publicKey })
+          .signers([sessionKeypair])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.toString()).to.not.include("Should have thrown");
+      }
+
+      await program.methods
+        .setSessionTokenLimit(new BN(1_000_000))
+        .accounts({ sessionToken: sessionPda, authority: user.publicKey })
+        .signers([user])
+        .rpc();
+
+      const after = await program.account.sessionToken.fetch(sessionPda);
+      expect(after.tokenSpendLimit.toNumber()).to.equal(1_000_000);
+      expect(after.tokenSpent.toNumber()).to.equal(0);
+    });
+
+    it("Revoking all sessions invalidates every session key", async () => {
+      await program.methods
+        .revokeAllSessions()
+        .accounts({ sessionRegistry: sessionRegistryPda, authority: user.publicKey })
+        .signers([user])
+        .rpc();
+
+      const registry = await program.account.sessionRegistry.fetch(sessionRegistryPda);
+      expect(registry.generation.toNumber()).to.equal(1);
+      expect(registry.signers.length).to.equal(0);
+
+      const session = await program.account.sessionToken.fetch(sessionPda);
+      expect(session.generation.toNumber()).to.equal(0);
+    });
   });
 });
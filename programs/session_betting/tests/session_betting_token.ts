@@ -0,0 +1,190 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { SessionBetting } from "../target/types/session_betting";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+} from "@solana/web3.js";
+import {
+  TOKEN_PROGRAM_ID,
+  createMint,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { expect } from "chai";
+
+describe("session_betting token path", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.SessionBetting as Program<SessionBetting>;
+  const payer = (provider.wallet as anchor.Wallet).payer;
+
+  const authority = provider.wallet.publicKey;
+  let user: Keypair;
+  let mint: PublicKey;
+  let userTokenAccount: PublicKey;
+
+  // PDAs
+  let gameStatePda: PublicKey;
+  let tokenVaultPda: PublicKey;
+  let tokenBalancePda: PublicKey;
+
+  // USDC-style 6 decimals
+  const DEPOSIT_AMOUNT = 50_000_000;
+  const WITHDRAW_AMOUNT = 20_000_000;
+
+  before(async () => {
+    user = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      user.publicKey,
+      LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    mint = await createMint(provider.connection, payer, authority, null, 6);
+    userTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        user.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      payer,
+      mint,
+      userTokenAccount,
+      payer,
+      DEPOSIT_AMOUNT
+    );
+
+    [gameStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("game")],
+      program.programId
+    );
+    [tokenVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("token_vault"), mint.toBuffer()],
+      program.programId
+    );
+    [tokenBalancePda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("token_balance"),
+        user.publicKey.toBuffer(),
+        mint.toBuffer(),
+      ],
+      program.programId
+    );
+  });
+
+  it("Enables the token mint and creates its vault", async () => {
+    await program.methods
+      .setTokenMint()
+      .accounts({
+        gameState: gameStatePda,
+        mint,
+        tokenVault: tokenVaultPda,
+        authority,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const gameState = await program.account.gameState.fetch(gameStatePda);
+    expect(gameState.tokenMint.toString()).to.equal(mint.toString());
+    expect(gameState.tokenFeesCollected.toNumber()).to.equal(0);
+  });
+
+  it("Deposits and withdraws tokens through the vault", async () => {
+    await program.methods
+      .depositToken(new BN(DEPOSIT_AMOUNT))
+      .accounts({
+        gameState: gameStatePda,
+        mint,
+        tokenBalance: tokenBalancePda,
+        tokenVault: tokenVaultPda,
+        userTokenAccount,
+        user: user.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user])
+      .rpc();
+
+    await program.methods
+      .withdrawToken(new BN(WITHDRAW_AMOUNT))
+      .accounts({
+        mint,
+        tokenBalance: tokenBalancePda,
+        tokenVault: tokenVaultPda,
+        userTokenAccount,
+        user: user.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    const balance = await program.account.tokenBalance.fetch(tokenBalancePda);
+    expect(balance.balance.toNumber()).to.equal(
+      DEPOSIT_AMOUNT - WITHDRAW_AMOUNT
+    );
+    expect(balance.totalDeposited.toNumber()).to.equal(DEPOSIT_AMOUNT);
+
+    const vault = await getAccount(provider.connection, tokenVaultPda);
+    expect(Number(vault.amount)).to.equal(DEPOSIT_AMOUNT - WITHDRAW_AMOUNT);
+  });
+
+  it("Rejects withdrawing more than the token balance", async () => {
+    try {
+      await program.methods
+        .withdrawToken(new BN(DEPOSIT_AMOUNT))
+        .accounts({
+          mint,
+          tokenBalance: tokenBalancePda,
+          tokenVault: tokenVaultPda,
+          userTokenAccount,
+          user: user.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("InsufficientBalance");
+    }
+  });
+
+  it("Rejects changing the token mint once set", async () => {
+    const otherMint = await createMint(
+      provider.connection,
+      payer,
+      authority,
+      null,
+      6
+    );
+    const [otherVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("token_vault"), otherMint.toBuffer()],
+      program.programId
+    );
+    try {
+      await program.methods
+        .setTokenMint()
+        .accounts({
+          gameState: gameStatePda,
+          mint: otherMint,
+          tokenVault: otherVault,
+          authority,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("TokenMintAlreadySet");
+    }
+  });
+});
@@ -0,0 +1,190 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { Spectator } from "../target/types/spectator";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+} from "@solana/web3.js";
+import { expect } from "chai";
+
+describe("spectator", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Spectator as Program<Spectator>;
+
+  const authority = provider.wallet.publicKey;
+  let streamer: Keypair;
+  let viewer: Keypair;
+
+  // PDAs
+  let configPda: PublicKey;
+  let streamerPda: PublicKey;
+  let marketPda: PublicKey;
+  let marketVaultPda: PublicKey;
+  let oddsPda: PublicKey;
+  let viewerBalancePda: PublicKey;
+  let viewerVaultPda: PublicKey;
+
+  const DEPOSIT_AMOUNT = 0.5 * LAMPORTS_PER_SOL;
+  const BET_AMOUNT = 0.1 * LAMPORTS_PER_SOL;
+
+  before(async () => {
+    streamer = Keypair.generate();
+    viewer = Keypair.generate();
+
+    for (const kp of [streamer, viewer]) {
+      const sig = await provider.connection.requestAirdrop(
+        kp.publicKey,
+        2 * LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig);
+    }
+
+    [configPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("spectator_config")],
+      program.programId
+    );
+    [streamerPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("streamer"), streamer.publicKey.toBuffer()],
+      program.programId
+    );
+    [marketPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("market"), new BN(0).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    [marketVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("market_vault"), marketPda.toBuffer()],
+      program.programId
+    );
+    [oddsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("spectator_odds"), marketPda.toBuffer()],
+      program.programId
+    );
+    [viewerBalancePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("viewer_balance"), viewer.publicKey.toBuffer()],
+      program.programId
+    );
+    [viewerVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("viewer_vault"), viewer.publicKey.toBuffer()],
+      program.programId
+    );
+  });
+
+  it("Initializes and registers a streamer", async () => {
+    await program.methods
+      .initializeSpectator()
+      .accounts({
+        config: configPda,
+        authority: authority,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .registerStreamer(streamer.publicKey)
+      .accounts({
+        config: configPda,
+        streamer: streamerPda,
+        authority: authority,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const account = await program.account.streamer.fetch(streamerPda);
+    expect(account.active).to.be.true;
+  });
+
+  it("Streamer opens a market", async () => {
+    const closeTime = Math.floor(Date.now() / 1000) + 600;
+    await program.methods
+      .openMarket("Boss defeated on first try?", new BN(closeTime))
+      .accounts({
+        config: configPda,
+        streamer: streamerPda,
+        market: marketPda,
+        marketVault: marketVaultPda,
+        odds: oddsPda,
+        wallet: streamer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([streamer])
+      .rpc();
+
+    const market = await program.account.market.fetch(marketPda);
+    expect(market.streamer.toString()).to.equal(streamer.publicKey.toString());
+    expect(market.status).to.deep.equal({ open: {} });
+
+    // Empty pools: either side just gets its stake back, less the 5% fee
+    const odds = await program.account.spectatorOdds.fetch(oddsPda);
+    expect(odds.yesMultiplierBps.toNumber()).to.equal(9_500);
+    expect(odds.locked).to.be.false;
+  });
+
+  it("Viewer deposits and bets", async () => {
+    await program.methods
+      .deposit(new BN(DEPOSIT_AMOUNT))
+      .accounts({
+        viewerBalance: viewerBalancePda,
+        vault: viewerVaultPda,
+        user: viewer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([viewer])
+      .rpc();
+
+    const [positionPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("spectator_position"),
+        marketPda.toBuffer(),
+        viewer.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+
+    await program.methods
+      .placeBet({ yes: {} }, new BN(BET_AMOUNT))
+      .accounts({
+        market: marketPda,
+        marketVault: marketVaultPda,
+        odds: oddsPda,
+        viewerBalance: viewerBalancePda,
+        vault: viewerVaultPda,
+        position: positionPda,
+        sessionToken: null,
+        signer: viewer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([viewer])
+      .rpc();
+
+    const market = await program.account.market.fetch(marketPda);
+    expect(market.yesPool.toNumber()).to.equal(BET_AMOUNT);
+
+    const balance = await program.account.viewerBalance.fetch(viewerBalancePda);
+    expect(balance.balance.toNumber()).to.equal(DEPOSIT_AMOUNT - BET_AMOUNT);
+
+    const odds = await program.account.spectatorOdds.fetch(oddsPda);
+    expect(odds.yesPool.toNumber()).to.equal(BET_AMOUNT);
+    expect(odds.updatedAt.toNumber()).to.be.greaterThan(0);
+  });
+
+  it("Rejects attestation before close time", async () => {
+    try {
+      await program.methods
+        .attestOutcome({ yes: {} })
+        .accounts({
+          market: marketPda,
+          odds: oddsPda,
+          streamer: streamer.publicKey,
+        })
+        .signers([streamer])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("MarketNotClosed");
+    }
+  });
+});
@@ -0,0 +1,146 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { Staking } from "../target/types/staking";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+} from "@solana/web3.js";
+import { expect } from "chai";
+
+describe("staking", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Staking as Program<Staking>;
+
+  const authority = provider.wallet.publicKey;
+  let staker: Keypair;
+
+  // PDAs
+  let poolPda: PublicKey;
+  let stakeVaultPda: PublicKey;
+  let feeVaultPda: PublicKey;
+  let stakeAccountPda: PublicKey;
+
+  const EPOCH_DURATION = 60 * 60;
+  const COOLDOWN = 24 * 60 * 60;
+  const STAKE_AMOUNT = 1 * LAMPORTS_PER_SOL;
+  const FEE_AMOUNT = 0.2 * LAMPORTS_PER_SOL;
+
+  before(async () => {
+    staker = Keypair.generate();
+
+    const sig = await provider.connection.requestAirdrop(
+      staker.publicKey,
+      2 * LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    [poolPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("staking_pool")],
+      program.programId
+    );
+    [stakeVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stake_vault")],
+      program.programId
+    );
+    [feeVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_vault")],
+      program.programId
+    );
+    [stakeAccountPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stake"), staker.publicKey.toBuffer()],
+      program.programId
+    );
+  });
+
+  it("Initializes the staking pool", async () => {
+    await program.methods
+      .initializeStaking(new BN(EPOCH_DURATION), new BN(COOLDOWN))
+      .accounts({
+        pool: poolPda,
+        stakeVault: stakeVaultPda,
+        feeVault: feeVaultPda,
+        authority: authority,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const pool = await program.account.stakingPool.fetch(poolPda);
+    expect(pool.currentEpoch.toNumber()).to.equal(0);
+    expect(pool.cooldownSeconds.toNumber()).to.equal(COOLDOWN);
+  });
+
+  it("Stakes SOL and accepts fee deposits", async () => {
+    await program.methods
+      .stake(new BN(STAKE_AMOUNT))
+      .accounts({
+        pool: poolPda,
+        stakeAccount: stakeAccountPda,
+        stakeVault: stakeVaultPda,
+        owner: staker.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([staker])
+      .rpc();
+
+    await program.methods
+      .depositFees(new BN(FEE_AMOUNT))
+      .accounts({
+        pool: poolPda,
+        feeVault: feeVaultPda,
+        depositor: authority,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const pool = await program.account.stakingPool.fetch(poolPda);
+    expect(pool.totalStaked.toNumber()).to.equal(STAKE_AMOUNT);
+    expect(pool.pendingFees.toNumber()).to.equal(FEE_AMOUNT);
+  });
+
+  it("Rejects advancing the epoch early", async () => {
+    try {
+      await program.methods
+        .advanceEpoch()
+        .accounts({ pool: poolPda, caller: authority })
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("EpochNotOver");
+    }
+  });
+
+  it("Unstake waits for the cooldown", async () => {
+    await program.methods
+      .requestUnstake(new BN(STAKE_AMOUNT))
+      .accounts({
+        pool: poolPda,
+        stakeAccount: stakeAccountPda,
+        owner: staker.publicKey,
+      })
+      .signers([staker])
+      .rpc();
+
+    try {
+      await program.methods
+        .completeUnstake()
+        .accounts({
+          stakeAccount: stakeAccountPda,
+          stakeVault: stakeVaultPda,
+          owner: staker.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([staker])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (e: any) {
+      expect(e.toString()).to.include("CooldownActive");
+    }
+
+    const pool = await program.account.stakingPool.fetch(poolPda);
+    expect(pool.totalStaked.toNumber()).to.equal(0);
+  });
+});